@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(feature = "std"), no_main)]
+
+// Same save file types as the rkyv program, which reads the archive with `access_unchecked`.
+#[path = "../../rkyv/src/generate.rs"]
+mod generate;
+#[path = "../../rkyv/src/types.rs"]
+mod types;
+
+use core::hint::black_box;
+
+use types::Players;
+
+openvm::entry!(main);
+
+fn main() {
+    // The save file is hinted straight into an aligned buffer and validated in place, without
+    // copying it into a vec or deserializing it.
+    let archived = openvm::io::read_archived::<Players>().expect("invalid save file");
+    black_box(archived);
+}
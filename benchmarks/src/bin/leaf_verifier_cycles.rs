@@ -0,0 +1,77 @@
+use clap::Parser;
+use eyre::Result;
+use openvm_benchmarks::utils::BenchmarkCli;
+use openvm_circuit::arch::{instructions::exe::VmExe, VmConfig, VmExecutor};
+use openvm_native_compiler::conversion::CompilerOptions;
+use openvm_rv32im_circuit::Rv32ImConfig;
+use openvm_rv32im_transpiler::{
+    Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
+};
+use openvm_sdk::{
+    commit::commit_app_exe,
+    keygen::AppProvingKey,
+    prover::{AppProver, DEFAULT_NUM_CHILDREN_LEAF},
+    verifier::leaf::{types::LeafVmVerifierInput, LeafVmVerifierConfig},
+    StdIn, C,
+};
+use openvm_stark_sdk::{bench::run_with_metric_collection, p3_baby_bear::BabyBear};
+use openvm_transpiler::{transpiler::Transpiler, FromElf};
+
+/// Compares the number of cycles of the leaf verifier program with and without the DSL
+/// optimization passes, when verifying a proof of the fibonacci benchmark. Both counts are also
+/// recorded as the `leaf_verifier_cycles` gauge, labeled by `optimizations`.
+fn main() -> Result<()> {
+    let args = BenchmarkCli::parse();
+
+    let elf = args.build_bench_program("fibonacci")?;
+    let exe = VmExe::from_elf(
+        elf,
+        Transpiler::<BabyBear>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension),
+    )?;
+    let mut stdin = StdIn::default();
+    stdin.write(&100_000u64);
+
+    let app_config = args.app_config(Rv32ImConfig::default());
+    let app_pk = AppProvingKey::keygen(app_config.clone());
+    let committed_exe = commit_app_exe(app_config.app_fri_params.fri_params, exe);
+    let app_vk = app_pk.get_app_vk();
+    let app_proof = AppProver::new(app_pk.app_vm_pk, committed_exe).generate_app_proof(stdin);
+    let leaf_inputs =
+        LeafVmVerifierInput::chunk_continuation_vm_proof(&app_proof, DEFAULT_NUM_CHILDREN_LEAF);
+
+    let leaf_vm_config = args.agg_config().agg_stark_config.leaf_vm_config();
+    let executor = VmExecutor::<BabyBear, _>::new(leaf_vm_config);
+    let cycles = |compiler_options: CompilerOptions| -> Result<usize> {
+        let program = LeafVmVerifierConfig {
+            app_fri_params: app_config.app_fri_params.fri_params,
+            app_system_config: app_config.app_vm_config.system().clone(),
+            compiler_options,
+        }
+        .build_program(&app_vk.app_vm_vk);
+        let mut total = 0;
+        for input in &leaf_inputs {
+            let (_, cycles) =
+                executor.execute_with_cycle_count(program.clone(), input.write_to_stream::<C>())?;
+            total += cycles;
+        }
+        Ok(total)
+    };
+
+    run_with_metric_collection("OUTPUT_PATH", || -> Result<()> {
+        let options = app_config.compiler_options;
+        let baseline = cycles(options)?;
+        let optimized = cycles(options.with_optimizations())?;
+        metrics::gauge!("leaf_verifier_cycles", "optimizations" => "off").set(baseline as f64);
+        metrics::gauge!("leaf_verifier_cycles", "optimizations" => "on").set(optimized as f64);
+        println!("leaf verifier cycles without optimizations: {baseline}");
+        println!("leaf verifier cycles with optimizations:    {optimized}");
+        println!(
+            "reduction: {:.2}%",
+            100.0 * (baseline as f64 - optimized as f64) / baseline as f64
+        );
+        Ok(())
+    })
+}
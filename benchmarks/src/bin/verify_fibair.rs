@@ -53,6 +53,7 @@ fn main() -> Result<()> {
             app_vm_config,
             leaf_fri_params: app_fri_params.into(),
             compiler_options,
+            segmentation_strategy: None,
         };
         let (program, input_stream) = build_verification_program(vdata, compiler_options);
         let sdk = Sdk;
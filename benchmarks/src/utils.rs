@@ -96,6 +96,7 @@ impl BenchmarkCli {
                 enable_cycle_tracker: self.profiling,
                 ..Default::default()
             },
+            segmentation_strategy: None,
         }
     }
 
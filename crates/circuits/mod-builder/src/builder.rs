@@ -28,6 +28,9 @@ pub struct ExprBuilderConfig {
     pub modulus: BigUint,
     pub num_limbs: usize,
     pub limb_bits: usize,
+    /// Whether the modulus is prime. Division is only supported for prime moduli, since
+    /// otherwise an inverse may not exist or be unique.
+    pub is_prime: bool,
 }
 
 impl ExprBuilderConfig {
@@ -43,6 +46,8 @@ pub struct ExprBuilder {
     // Same value, but we need BigInt for computing the quotient.
     pub prime_bigint: BigInt,
     pub prime_limbs: Vec<usize>,
+    /// Whether `prime` is actually prime. If not, division is not allowed.
+    pub is_prime: bool,
 
     pub num_input: usize,
     pub num_flags: usize,
@@ -93,6 +98,7 @@ impl ExprBuilder {
             prime: config.modulus.clone(),
             prime_bigint,
             prime_limbs: big_uint_to_limbs(&config.modulus, config.limb_bits),
+            is_prime: config.is_prime,
             num_input: 0,
             num_flags: 0,
             limb_bits: config.limb_bits,
@@ -206,6 +212,16 @@ impl ExprBuilder {
         }
     }
 
+    /// Number of columns of the sub-row of the expression. Before finalizing, this does not
+    /// include the default flag of a single-op chip with setup.
+    pub fn width(&self) -> usize {
+        self.num_limbs * (self.num_input + self.num_variables)
+            + self.q_limbs.iter().sum::<usize>()
+            + self.carry_limbs.iter().sum::<usize>()
+            + self.num_flags
+            + 1 // is_valid
+    }
+
     pub fn set_constraint(&mut self, index: usize, constraint: SymbolicExpr) {
         let (q_limbs, carry_limbs) =
             constraint.constraint_limbs(&self.prime, self.limb_bits, self.num_limbs);
@@ -278,11 +294,7 @@ impl<F: Field> PartitionedBaseAir<F> for FieldExpr {}
 impl<F: Field> BaseAir<F> for FieldExpr {
     fn width(&self) -> usize {
         assert!(self.builder.is_finalized());
-        self.num_limbs * (self.builder.num_input + self.builder.num_variables)
-            + self.builder.q_limbs.iter().sum::<usize>()
-            + self.builder.carry_limbs.iter().sum::<usize>()
-            + self.builder.num_flags
-            + 1 // is_valid
+        self.builder.width()
     }
 }
 
@@ -257,9 +257,14 @@ impl FieldVariable {
 
     // expr cannot have division, so auto-save a new variable.
     // Note that division by zero will panic.
+    // Division is only supported when the modulus is prime.
     pub fn div(&mut self, other: &mut FieldVariable) -> FieldVariable {
         assert!(Rc::ptr_eq(&self.builder, &other.builder));
         let builder = self.builder.borrow();
+        assert!(
+            builder.is_prime,
+            "division is not supported for a composite modulus"
+        );
         let prime = builder.prime.clone();
         let limb_bits = builder.limb_bits;
         let num_limbs = builder.num_limbs;
@@ -0,0 +1,325 @@
+//! Optimization pass over the variables of an [ExprBuilder].
+//!
+//! Variables are introduced by auto-saves and by explicit `save` calls, and each of them costs
+//! `num_limbs` columns plus the quotient and carry limbs of its constraint. The pass
+//! 1. merges variables with the same compute expression and the same constraint, and
+//! 2. inlines intermediate variables into the constraints using them, whenever this reduces the
+//!    width without exceeding the maximum constraint degree or the range checker bits.
+
+use std::{cmp::Ordering, collections::HashSet};
+
+use itertools::Itertools;
+
+use super::{ExprBuilder, SymbolicExpr};
+
+/// Cost model of [ExprBuilder::optimize]: the width is minimized subject to these bounds.
+#[derive(Clone, Copy, Debug)]
+pub struct OptimizerConfig {
+    /// Maximum degree of a constraint in the trace columns.
+    pub max_constraint_degree: usize,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            max_constraint_degree: 3,
+        }
+    }
+}
+
+/// Result of [ExprBuilder::optimize]. Widths are as returned by [ExprBuilder::width].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptimizerReport {
+    pub num_variables_before: usize,
+    pub num_variables_after: usize,
+    pub width_before: usize,
+    pub width_after: usize,
+    /// Maximum degree of the constraints after optimization.
+    pub max_constraint_degree: usize,
+}
+
+/// Replaces each `Var(i)` in `expr` by `f(i)`.
+fn substitute(expr: &SymbolicExpr, f: &impl Fn(usize) -> SymbolicExpr) -> SymbolicExpr {
+    let sub = |e: &SymbolicExpr| Box::new(substitute(e, f));
+    match expr {
+        SymbolicExpr::Var(i) => f(*i),
+        SymbolicExpr::Input(_) | SymbolicExpr::Const(_, _, _) => expr.clone(),
+        SymbolicExpr::Add(lhs, rhs) => SymbolicExpr::Add(sub(lhs), sub(rhs)),
+        SymbolicExpr::Sub(lhs, rhs) => SymbolicExpr::Sub(sub(lhs), sub(rhs)),
+        SymbolicExpr::Mul(lhs, rhs) => SymbolicExpr::Mul(sub(lhs), sub(rhs)),
+        SymbolicExpr::Div(lhs, rhs) => SymbolicExpr::Div(sub(lhs), sub(rhs)),
+        SymbolicExpr::IntAdd(lhs, s) => SymbolicExpr::IntAdd(sub(lhs), *s),
+        SymbolicExpr::IntMul(lhs, s) => SymbolicExpr::IntMul(sub(lhs), *s),
+        SymbolicExpr::Select(flag, lhs, rhs) => SymbolicExpr::Select(*flag, sub(lhs), sub(rhs)),
+    }
+}
+
+fn contains_var(expr: &SymbolicExpr, var: usize) -> bool {
+    match expr {
+        SymbolicExpr::Var(i) => *i == var,
+        SymbolicExpr::Input(_) | SymbolicExpr::Const(_, _, _) => false,
+        SymbolicExpr::Add(lhs, rhs)
+        | SymbolicExpr::Sub(lhs, rhs)
+        | SymbolicExpr::Mul(lhs, rhs)
+        | SymbolicExpr::Div(lhs, rhs)
+        | SymbolicExpr::Select(_, lhs, rhs) => contains_var(lhs, var) || contains_var(rhs, var),
+        SymbolicExpr::IntAdd(lhs, _) | SymbolicExpr::IntMul(lhs, _) => contains_var(lhs, var),
+    }
+}
+
+fn contains_div(expr: &SymbolicExpr) -> bool {
+    match expr {
+        SymbolicExpr::Div(_, _) => true,
+        SymbolicExpr::Input(_) | SymbolicExpr::Var(_) | SymbolicExpr::Const(_, _, _) => false,
+        SymbolicExpr::Add(lhs, rhs)
+        | SymbolicExpr::Sub(lhs, rhs)
+        | SymbolicExpr::Mul(lhs, rhs)
+        | SymbolicExpr::Select(_, lhs, rhs) => contains_div(lhs) || contains_div(rhs),
+        SymbolicExpr::IntAdd(lhs, _) | SymbolicExpr::IntMul(lhs, _) => contains_div(lhs),
+    }
+}
+
+/// Whether both branches of each `Select` have the same number of limbs, as required by
+/// [SymbolicExpr::expr_limbs].
+fn selects_balanced(expr: &SymbolicExpr, num_limbs: usize) -> bool {
+    match expr {
+        SymbolicExpr::Input(_) | SymbolicExpr::Var(_) | SymbolicExpr::Const(_, _, _) => true,
+        SymbolicExpr::Add(lhs, rhs)
+        | SymbolicExpr::Sub(lhs, rhs)
+        | SymbolicExpr::Mul(lhs, rhs)
+        | SymbolicExpr::Div(lhs, rhs) => {
+            selects_balanced(lhs, num_limbs) && selects_balanced(rhs, num_limbs)
+        }
+        SymbolicExpr::IntAdd(lhs, _) | SymbolicExpr::IntMul(lhs, _) => {
+            selects_balanced(lhs, num_limbs)
+        }
+        SymbolicExpr::Select(_, lhs, rhs) => {
+            selects_balanced(lhs, num_limbs)
+                && selects_balanced(rhs, num_limbs)
+                && lhs.expr_limbs(num_limbs) == rhs.expr_limbs(num_limbs)
+        }
+    }
+}
+
+fn keep<T: Clone>(values: &[T], alive: &[bool]) -> Vec<T> {
+    values
+        .iter()
+        .zip_eq(alive)
+        .filter_map(|(x, &a)| a.then(|| x.clone()))
+        .collect()
+}
+
+/// Orders the operands of commutative operations, so that expressions equal up to commutativity
+/// are equal.
+fn canonicalize(expr: &SymbolicExpr) -> SymbolicExpr {
+    let sorted = |lhs: &SymbolicExpr, rhs: &SymbolicExpr| {
+        let (lhs, rhs) = (canonicalize(lhs), canonicalize(rhs));
+        match format!("{:?}", lhs).cmp(&format!("{:?}", rhs)) {
+            Ordering::Greater => (Box::new(rhs), Box::new(lhs)),
+            _ => (Box::new(lhs), Box::new(rhs)),
+        }
+    };
+    let canon = |e: &SymbolicExpr| Box::new(canonicalize(e));
+    match expr {
+        SymbolicExpr::Input(_) | SymbolicExpr::Var(_) | SymbolicExpr::Const(_, _, _) => {
+            expr.clone()
+        }
+        SymbolicExpr::Add(lhs, rhs) => {
+            let (lhs, rhs) = sorted(lhs, rhs);
+            SymbolicExpr::Add(lhs, rhs)
+        }
+        SymbolicExpr::Mul(lhs, rhs) => {
+            let (lhs, rhs) = sorted(lhs, rhs);
+            SymbolicExpr::Mul(lhs, rhs)
+        }
+        SymbolicExpr::Sub(lhs, rhs) => SymbolicExpr::Sub(canon(lhs), canon(rhs)),
+        SymbolicExpr::Div(lhs, rhs) => SymbolicExpr::Div(canon(lhs), canon(rhs)),
+        SymbolicExpr::IntAdd(lhs, s) => SymbolicExpr::IntAdd(canon(lhs), *s),
+        SymbolicExpr::IntMul(lhs, s) => SymbolicExpr::IntMul(canon(lhs), *s),
+        SymbolicExpr::Select(flag, lhs, rhs) => SymbolicExpr::Select(*flag, canon(lhs), canon(rhs)),
+    }
+}
+
+impl ExprBuilder {
+    /// Optimizes the variables of the expression, see the [module docs](self).
+    ///
+    /// This must be called once the expression is complete and before it is finalized, since
+    /// variables are renumbered: `FieldVariable`s of this builder must not be used afterwards.
+    pub fn optimize(&mut self, config: OptimizerConfig) -> OptimizerReport {
+        assert!(
+            !self.is_finalized(),
+            "optimize must be called before finalize"
+        );
+        let num_variables_before = self.num_variables;
+        let width_before = self.width();
+
+        let mut alive = vec![true; self.num_variables];
+        self.merge_common_variables(&mut alive);
+        self.inline_variables(&mut alive, config);
+        self.remove_variables(&alive);
+
+        let report = OptimizerReport {
+            num_variables_before,
+            num_variables_after: self.num_variables,
+            width_before,
+            width_after: self.width(),
+            max_constraint_degree: self
+                .constraints
+                .iter()
+                .map(|c| c.degree())
+                .max()
+                .unwrap_or(0),
+        };
+        tracing::debug!("expression optimized: {:?}", report);
+        report
+    }
+
+    /// Replaces `Var(from)` by `to` in the constraints and computes of the other alive variables.
+    fn replace_variable(&mut self, from: usize, to: &SymbolicExpr, alive: &[bool]) {
+        let f = |i| {
+            if i == from {
+                to.clone()
+            } else {
+                SymbolicExpr::Var(i)
+            }
+        };
+        for i in (0..self.num_variables).filter(|&i| i != from && alive[i]) {
+            if contains_var(&self.constraints[i], from) {
+                let constraint = substitute(&self.constraints[i], &f);
+                self.set_constraint(i, constraint);
+            }
+            if contains_var(&self.computes[i], from) {
+                self.computes[i] = substitute(&self.computes[i], &f);
+            }
+        }
+    }
+
+    /// Common subexpression elimination: a variable with the same compute expression and the same
+    /// constraint as an earlier one is replaced by it.
+    fn merge_common_variables(&mut self, alive: &mut [bool]) {
+        // The constraint of a variable with the variable itself replaced by a placeholder.
+        let own_constraint = |builder: &Self, i: usize| {
+            canonicalize(&substitute(&builder.constraints[i], &|j| {
+                SymbolicExpr::Var(if j == i { usize::MAX } else { j })
+            }))
+        };
+        // (compute, constraint, variable) of the variables kept so far
+        let mut keys: Vec<(SymbolicExpr, SymbolicExpr, usize)> = vec![];
+        for i in 0..self.num_variables {
+            let (compute, constraint) = (canonicalize(&self.computes[i]), own_constraint(self, i));
+            match keys
+                .iter()
+                .find(|(c, k, _)| *c == compute && *k == constraint)
+            {
+                Some(&(_, _, j)) => {
+                    alive[i] = false;
+                    self.replace_variable(i, &SymbolicExpr::Var(j), alive);
+                    for output in self.output_indices.iter_mut() {
+                        if *output == i {
+                            *output = j;
+                        }
+                    }
+                }
+                None => keys.push((compute, constraint, i)),
+            }
+        }
+    }
+
+    /// Inlines a variable whose constraint is `compute - Var(i)` into the other constraints, as long
+    /// as this reduces the width, greedily by largest reduction.
+    fn inline_variables(&mut self, alive: &mut [bool], config: OptimizerConfig) {
+        let outputs: HashSet<usize> = self.output_indices.iter().copied().collect();
+        loop {
+            let mut best: Option<(usize, usize)> = None; // (gain, variable)
+            for i in 0..self.num_variables {
+                if !alive[i] || outputs.contains(&i) || contains_div(&self.computes[i]) {
+                    continue;
+                }
+                let saved = SymbolicExpr::Sub(
+                    Box::new(self.computes[i].clone()),
+                    Box::new(SymbolicExpr::Var(i)),
+                );
+                if self.constraints[i] != saved {
+                    continue;
+                }
+                if let Some(gain) = self.inline_gain(i, alive, config) {
+                    if gain > 0 && best.map_or(true, |(best_gain, _)| gain > best_gain) {
+                        best = Some((gain, i));
+                    }
+                }
+            }
+            let Some((_, i)) = best else {
+                break;
+            };
+            alive[i] = false;
+            let compute = self.computes[i].clone();
+            self.replace_variable(i, &compute, alive);
+        }
+    }
+
+    /// Returns the number of columns saved by inlining variable `i`, or `None` if some constraint
+    /// using it would exceed the bounds.
+    fn inline_gain(&self, i: usize, alive: &[bool], config: OptimizerConfig) -> Option<usize> {
+        let mut removed = self.num_limbs + self.q_limbs[i] + self.carry_limbs[i];
+        let mut added = 0;
+        for k in (0..self.num_variables).filter(|&k| k != i && alive[k]) {
+            if !contains_var(&self.constraints[k], i) {
+                continue;
+            }
+            let constraint = substitute(&self.constraints[k], &|j| {
+                if j == i {
+                    self.computes[i].clone()
+                } else {
+                    SymbolicExpr::Var(j)
+                }
+            });
+            if constraint.degree() > config.max_constraint_degree
+                || !selects_balanced(&constraint, self.num_limbs)
+                || constraint.constraint_carry_bits_with_pq(
+                    &self.prime,
+                    self.limb_bits,
+                    self.num_limbs,
+                ) > self.range_checker_bits
+            {
+                return None;
+            }
+            let (q_limbs, carry_limbs) =
+                constraint.constraint_limbs(&self.prime, self.limb_bits, self.num_limbs);
+            removed += self.q_limbs[k] + self.carry_limbs[k];
+            added += q_limbs + carry_limbs;
+        }
+        removed.checked_sub(added)
+    }
+
+    /// Removes the variables that are not alive and renumbers the others, keeping their order so
+    /// that computes only use earlier variables.
+    fn remove_variables(&mut self, alive: &[bool]) {
+        let mut count = 0;
+        let new_index: Vec<usize> = alive
+            .iter()
+            .map(|&a| {
+                let index = count;
+                count += a as usize;
+                index
+            })
+            .collect();
+        let f = |i: usize| {
+            assert!(alive[i], "removed variable {i} is still used");
+            SymbolicExpr::Var(new_index[i])
+        };
+        self.constraints = keep(&self.constraints, alive)
+            .iter()
+            .map(|c| substitute(c, &f))
+            .collect();
+        self.computes = keep(&self.computes, alive)
+            .iter()
+            .map(|c| substitute(c, &f))
+            .collect();
+        self.q_limbs = keep(&self.q_limbs, alive);
+        self.carry_limbs = keep(&self.carry_limbs, alive);
+        for output in self.output_indices.iter_mut() {
+            *output = new_index[*output];
+        }
+        self.num_variables = count;
+    }
+}
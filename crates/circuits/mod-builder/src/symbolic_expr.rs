@@ -323,6 +323,24 @@ impl SymbolicExpr {
         }
     }
 
+    /// Returns the degree of the expression as a polynomial in the trace columns, where flags are
+    /// columns too.
+    pub fn degree(&self) -> usize {
+        match self {
+            SymbolicExpr::Input(_) | SymbolicExpr::Var(_) => 1,
+            SymbolicExpr::Const(_, _, _) => 0,
+            SymbolicExpr::Add(lhs, rhs) | SymbolicExpr::Sub(lhs, rhs) => {
+                max(lhs.degree(), rhs.degree())
+            }
+            SymbolicExpr::Mul(lhs, rhs) => lhs.degree() + rhs.degree(),
+            SymbolicExpr::IntAdd(lhs, _) | SymbolicExpr::IntMul(lhs, _) => lhs.degree(),
+            SymbolicExpr::Select(_, lhs, rhs) => max(lhs.degree(), rhs.degree()) + 1,
+            SymbolicExpr::Div(_, _) => {
+                unreachable!("should not have division when calling degree")
+            }
+        }
+    }
+
     /// Let `q` be such that `self.expr = q * p`.
     /// Returns (q_limbs, carry_limbs) where q_limbs is the number of limbs in q
     /// and carry_limbs is the number of limbs in the carry of the constraint self.expr - q * p = 0.
@@ -22,6 +22,13 @@ pub use bn254::*;
 pub const LIMB_BITS: usize = 8;
 
 pub fn setup(prime: &BigUint) -> (Arc<VariableRangeCheckerChip>, Rc<RefCell<ExprBuilder>>) {
+    setup_with_modulus(prime, true)
+}
+
+pub fn setup_with_modulus(
+    modulus: &BigUint,
+    is_prime: bool,
+) -> (Arc<VariableRangeCheckerChip>, Rc<RefCell<ExprBuilder>>) {
     let range_bus = 1;
     let range_decomp = 17; // double needs 17, rests need 16.
     let range_checker = Arc::new(VariableRangeCheckerChip::new(VariableRangeCheckerBus::new(
@@ -29,9 +36,10 @@ pub fn setup(prime: &BigUint) -> (Arc<VariableRangeCheckerChip>, Rc<RefCell<Expr
         range_decomp,
     )));
     let config = ExprBuilderConfig {
-        modulus: prime.clone(),
+        modulus: modulus.clone(),
         limb_bits: LIMB_BITS,
         num_limbs: 32,
+        is_prime,
     };
     let builder = ExprBuilder::new(config, range_checker.range_max_bits());
     (range_checker, Rc::new(RefCell::new(builder)))
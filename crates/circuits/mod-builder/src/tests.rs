@@ -1,7 +1,9 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 use num_bigint::BigUint;
-use openvm_circuit_primitives::{bigint::utils::*, TraceSubRowGenerator};
+use openvm_circuit_primitives::{
+    bigint::utils::*, var_range::VariableRangeCheckerChip, TraceSubRowGenerator,
+};
 use openvm_stark_backend::{
     p3_air::BaseAir, p3_field::FieldAlgebra, p3_matrix::dense::RowMajorMatrix,
 };
@@ -10,7 +12,10 @@ use openvm_stark_sdk::{
     p3_baby_bear::BabyBear,
 };
 
-use crate::{test_utils::*, ExprBuilder, FieldExpr, FieldExprCols, FieldVariable, SymbolicExpr};
+use crate::{
+    test_utils::*, ExprBuilder, FieldExpr, FieldExprCols, FieldVariable, OptimizerConfig,
+    SymbolicExpr,
+};
 
 const LIMB_BITS: usize = 8;
 
@@ -85,6 +90,58 @@ fn test_div() {
     .expect("Verification failed");
 }
 
+/// `(2^127 - 1) * (2^128 - 159)`, a product of two primes like an RSA modulus.
+fn composite_modulus() -> BigUint {
+    let one = BigUint::from(1u32);
+    ((&one << 127) - &one) * ((&one << 128) - BigUint::from(159u32))
+}
+
+#[test]
+fn test_mul_composite_modulus() {
+    let modulus = composite_modulus();
+    let (range_checker, builder) = setup_with_modulus(&modulus, false);
+
+    let x1 = ExprBuilder::new_input(builder.clone());
+    let x2 = ExprBuilder::new_input(builder.clone());
+    let mut x3 = x1 * x2;
+    x3.save();
+    let builder = builder.borrow().clone();
+
+    let expr = FieldExpr::new(builder, range_checker.bus(), false);
+    let width = BaseAir::<BabyBear>::width(&expr);
+
+    let x = generate_random_biguint(&modulus);
+    let y = generate_random_biguint(&modulus);
+    let expected = (&x * &y) % modulus;
+    let inputs = vec![x, y];
+
+    let mut row = BabyBear::zero_vec(width);
+    expr.generate_subrow((&range_checker, inputs, vec![]), &mut row);
+    let FieldExprCols { vars, .. } = expr.load_vars(&row);
+    assert_eq!(vars.len(), 1);
+    let generated = evaluate_biguint(&vars[0], LIMB_BITS);
+    assert_eq!(generated, expected);
+
+    let trace = RowMajorMatrix::new(row, width);
+    let range_trace = range_checker.generate_trace();
+
+    BabyBearBlake3Engine::run_simple_test_no_pis_fast(
+        any_rap_arc_vec![expr, range_checker.air],
+        vec![trace, range_trace],
+    )
+    .expect("Verification failed");
+}
+
+#[test]
+#[should_panic(expected = "division is not supported for a composite modulus")]
+fn test_div_composite_modulus() {
+    let (_, builder) = setup_with_modulus(&composite_modulus(), false);
+
+    let x1 = ExprBuilder::new_input(builder.clone());
+    let x2 = ExprBuilder::new_input(builder.clone());
+    let _x3 = x1 / x2;
+}
+
 #[test]
 fn test_auto_carry_mul() {
     let prime = secp256k1_coord_prime();
@@ -387,3 +444,109 @@ fn test_symbolic_limbs_mul() {
     let expected_carry = 63;
     test_symbolic_limbs(expr, expected_q, expected_carry);
 }
+
+/// Generates a row of `expr`, which has no flags, checks that the only output is `expected` and
+/// verifies the trace.
+fn test_single_output(
+    expr: FieldExpr,
+    range_checker: Arc<VariableRangeCheckerChip>,
+    inputs: Vec<BigUint>,
+    expected: BigUint,
+) {
+    let width = BaseAir::<BabyBear>::width(&expr);
+    let mut row = BabyBear::zero_vec(width);
+    expr.generate_subrow((&range_checker, inputs, vec![]), &mut row);
+    let FieldExprCols { vars, .. } = expr.load_vars(&row);
+    let output = &vars[expr.builder.output_indices[0]];
+    assert_eq!(evaluate_biguint(output, LIMB_BITS), expected);
+
+    let trace = RowMajorMatrix::new(row, width);
+    let range_trace = range_checker.generate_trace();
+
+    BabyBearBlake3Engine::run_simple_test_no_pis_fast(
+        any_rap_arc_vec![expr, range_checker.air],
+        vec![trace, range_trace],
+    )
+    .expect("Verification failed");
+}
+
+#[test]
+fn test_optimize_inline() {
+    let prime = secp256k1_coord_prime();
+    let (range_checker, builder) = setup(&prime);
+
+    let x1 = ExprBuilder::new_input(builder.clone());
+    let x2 = ExprBuilder::new_input(builder.clone());
+    let mut x3 = x1.clone() * x2;
+    x3.save();
+    let mut x4 = x3 + x1;
+    x4.save_output();
+    let mut builder = builder.borrow().clone();
+    assert_eq!(builder.num_variables, 2);
+
+    let report = builder.optimize(OptimizerConfig::default());
+    assert_eq!(report.num_variables_before, 2);
+    assert_eq!(report.num_variables_after, 1);
+    assert_eq!(report.width_after, builder.width());
+    assert!(report.width_after < report.width_before);
+    assert_eq!(report.max_constraint_degree, 2);
+    assert_eq!(builder.output_indices, vec![0]);
+
+    let x = generate_random_biguint(&prime);
+    let y = generate_random_biguint(&prime);
+    let expected = (&x * &y + &x) % &prime;
+    let expr = FieldExpr::new(builder, range_checker.bus(), false);
+    test_single_output(expr, range_checker, vec![x, y], expected);
+}
+
+#[test]
+fn test_optimize_degree_bound() {
+    let prime = secp256k1_coord_prime();
+    let (_, builder) = setup(&prime);
+
+    let x1 = ExprBuilder::new_input(builder.clone());
+    let x2 = ExprBuilder::new_input(builder.clone());
+    let mut x3 = x1.clone() * x2;
+    x3.save();
+    let mut x4 = x3 + x1;
+    x4.save_output();
+    let mut builder = builder.borrow().clone();
+
+    // Inlining x3 would give a constraint of degree 2.
+    let report = builder.optimize(OptimizerConfig {
+        max_constraint_degree: 1,
+    });
+    assert_eq!(report.num_variables_after, 2);
+    assert_eq!(report.width_after, report.width_before);
+}
+
+#[test]
+fn test_optimize_common_subexpression() {
+    let prime = secp256k1_coord_prime();
+    let (range_checker, builder) = setup(&prime);
+
+    let x1 = ExprBuilder::new_input(builder.clone());
+    let x2 = ExprBuilder::new_input(builder.clone());
+    let mut x3 = x1.clone() * x2.clone();
+    x3.save();
+    let mut x4 = x2 * x1;
+    x4.save();
+    let mut x5 = x3 - x4.int_mul(2);
+    x5.save_output();
+    let mut builder = builder.borrow().clone();
+    assert_eq!(builder.num_variables, 3);
+
+    // Forbid inlining, so that only the common subexpression is removed.
+    let report = builder.optimize(OptimizerConfig {
+        max_constraint_degree: 1,
+    });
+    assert_eq!(report.num_variables_after, 2);
+    assert_eq!(report.max_constraint_degree, 2);
+    assert_eq!(builder.output_indices, vec![1]);
+
+    let x = generate_random_biguint(&prime);
+    let y = generate_random_biguint(&prime);
+    let expected = (&prime - (&x * &y) % &prime) % &prime;
+    let expr = FieldExpr::new(builder, range_checker.bus(), false);
+    test_single_output(expr, range_checker, vec![x, y], expected);
+}
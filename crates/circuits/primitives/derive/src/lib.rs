@@ -398,3 +398,28 @@ pub fn bytes_stateful_derive(input: TokenStream) -> TokenStream {
         _ => unimplemented!(),
     }
 }
+
+/// Implements `FixedTable` for a unit struct from the `const` array given by `#[table(...)]`.
+#[proc_macro_derive(FixedTable, attributes(table))]
+pub fn fixed_table_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let table = ast
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("table"))
+        .expect("FixedTable requires a #[table(TABLE)] attribute")
+        .parse_args::<syn::Expr>()
+        .expect("Expected #[table(TABLE)] where TABLE is a const array");
+
+    quote! {
+        impl #impl_generics ::openvm_circuit_primitives::fixed_table::FixedTable for #name #ty_generics #where_clause {
+            fn rows() -> Vec<Vec<u32>> {
+                ::openvm_circuit_primitives::fixed_table::fixed_table_rows(&#table)
+            }
+        }
+    }
+    .into()
+}
@@ -0,0 +1,61 @@
+use openvm_stark_backend::{
+    interaction::{BusIndex, InteractionBuilder, LookupBus},
+    p3_field::FieldAlgebra,
+};
+
+/// Bus of a [FixedTableLookupChip](super::FixedTableLookupChip). Its messages are the rows of
+/// the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedTableLookupBus {
+    pub inner: LookupBus,
+}
+
+impl FixedTableLookupBus {
+    pub const fn new(index: BusIndex) -> Self {
+        Self {
+            inner: LookupBus::new(index),
+        }
+    }
+
+    #[must_use]
+    pub fn send<T>(&self, row: Vec<impl Into<T>>) -> FixedTableLookupBusInteraction<T> {
+        self.push(row, true)
+    }
+
+    #[must_use]
+    pub fn receive<T>(&self, row: Vec<impl Into<T>>) -> FixedTableLookupBusInteraction<T> {
+        self.push(row, false)
+    }
+
+    pub fn push<T>(
+        &self,
+        row: Vec<impl Into<T>>,
+        is_lookup: bool,
+    ) -> FixedTableLookupBusInteraction<T> {
+        FixedTableLookupBusInteraction {
+            row: row.into_iter().map(|t| t.into()).collect(),
+            bus: self.inner,
+            is_lookup,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FixedTableLookupBusInteraction<T> {
+    pub row: Vec<T>,
+    pub bus: LookupBus,
+    pub is_lookup: bool,
+}
+
+impl<T: FieldAlgebra> FixedTableLookupBusInteraction<T> {
+    pub fn eval<AB>(self, builder: &mut AB, count: impl Into<AB::Expr>)
+    where
+        AB: InteractionBuilder<Expr = T>,
+    {
+        if self.is_lookup {
+            self.bus.lookup_key(builder, self.row, count);
+        } else {
+            self.bus.add_key_with_lookups(builder, self.row, count);
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! Lookup into a table fixed at compile time, such as an S-box or a character class table.
+//!
+//! The table is given by a type implementing [FixedTable], usually with
+//! `#[derive(FixedTable)]` on a unit struct pointing to a `const` array. Since each table has its
+//! own type, the chip of a table can be shared between extensions with
+//! `VmInventoryBuilder::find_chip::<SharedFixedTableLookupChip<MyTable>>()`.
+
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    mem::size_of,
+    sync::{atomic::AtomicU32, Arc},
+};
+
+pub use openvm_circuit_primitives_derive::FixedTable;
+use openvm_stark_backend::{
+    config::{StarkGenericConfig, Val},
+    interaction::InteractionBuilder,
+    p3_air::{Air, BaseAir, PairBuilder},
+    p3_field::{Field, PrimeField32},
+    p3_matrix::{dense::RowMajorMatrix, Matrix},
+    prover::types::AirProofInput,
+    rap::{get_air_name, BaseAirWithPublicValues, PartitionedBaseAir},
+    AirRef, Chip, ChipUsageGetter,
+};
+
+use crate::SubAir;
+
+mod bus;
+
+#[cfg(test)]
+mod tests;
+
+pub use bus::*;
+
+/// A table whose rows are known at compile time. All rows must have the same width.
+///
+/// It can be derived from a `const` array `TABLE` with
+/// ```ignore
+/// #[derive(FixedTable)]
+/// #[table(TABLE)]
+/// pub struct MyTable;
+/// ```
+/// where the rows of the table are given by [FixedTableRow].
+pub trait FixedTable: Send + Sync + 'static {
+    fn rows() -> Vec<Vec<u32>>;
+}
+
+/// Element of a `const` array that [FixedTable] can be derived from.
+pub trait FixedTableRow {
+    /// The row of the `index`-th element of the array.
+    fn to_row(&self, index: usize) -> Vec<u32>;
+}
+
+macro_rules! impl_fixed_table_row {
+    ($($t:ty),*) => {
+        $(
+            /// A scalar `value` at `index` is the row `[index, value]`, so a table `[u8; 256]`
+            /// maps each byte to a value.
+            impl FixedTableRow for $t {
+                fn to_row(&self, index: usize) -> Vec<u32> {
+                    vec![index as u32, *self as u32]
+                }
+            }
+
+            /// An array is a row as is.
+            impl<const N: usize> FixedTableRow for [$t; N] {
+                fn to_row(&self, _index: usize) -> Vec<u32> {
+                    self.iter().map(|&x| x as u32).collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_table_row!(bool, u8, u16, u32);
+
+/// Rows of a table given by a `const` array, used by `#[derive(FixedTable)]`.
+pub fn fixed_table_rows<R: FixedTableRow>(table: &[R]) -> Vec<Vec<u32>> {
+    table
+        .iter()
+        .enumerate()
+        .map(|(i, row)| row.to_row(i))
+        .collect()
+}
+
+#[derive(Default, Copy, Clone)]
+pub struct FixedTableLookupCols<T> {
+    /// Number of lookups of each row of the table
+    pub mult: T,
+}
+
+#[derive(Default, Clone)]
+pub struct FixedTableLookupPreprocessedCols<T> {
+    /// The row of the table
+    pub row: Vec<T>,
+}
+
+pub const NUM_FIXED_TABLE_LOOKUP_COLS: usize = size_of::<FixedTableLookupCols<u8>>();
+
+pub struct FixedTableLookupAir<T> {
+    pub bus: FixedTableLookupBus,
+    /// Width of the rows of the table.
+    pub width: usize,
+    /// Height of the trace: the number of rows of the table, padded to a power of two.
+    pub height: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for FixedTableLookupAir<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FixedTableLookupAir<T> {}
+
+impl<T: FixedTable> FixedTableLookupAir<T> {
+    /// Checks that the table is a valid table over the field `F`.
+    pub fn new<F: PrimeField32>(bus: FixedTableLookupBus) -> Self {
+        let rows = T::rows();
+        assert!(!rows.is_empty(), "the table must not be empty");
+        let width = rows[0].len();
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "all rows of the table must have the same width"
+        );
+        assert!(
+            rows.iter().flatten().all(|&x| x < F::ORDER_U32),
+            "all values of the table must be smaller than the field modulus"
+        );
+        Self {
+            bus,
+            width,
+            height: rows.len().next_power_of_two(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field, T: FixedTable> BaseAirWithPublicValues<F> for FixedTableLookupAir<T> {}
+impl<F: Field, T: FixedTable> PartitionedBaseAir<F> for FixedTableLookupAir<T> {}
+
+impl<F: Field, T: FixedTable> BaseAir<F> for FixedTableLookupAir<T> {
+    fn width(&self) -> usize {
+        NUM_FIXED_TABLE_LOOKUP_COLS
+    }
+
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<F>> {
+        let mut rows = T::rows();
+        // The padding rows repeat the last row, with multiplicity 0.
+        let last = rows.last().unwrap().clone();
+        rows.resize(self.height, last);
+        Some(RowMajorMatrix::new(
+            rows.into_iter()
+                .flatten()
+                .map(F::from_canonical_u32)
+                .collect(),
+            self.width,
+        ))
+    }
+}
+
+impl<AB: InteractionBuilder + PairBuilder, T: FixedTable> Air<AB> for FixedTableLookupAir<T> {
+    fn eval(&self, builder: &mut AB) {
+        let preprocessed = builder.preprocessed();
+        let prep_local = preprocessed.row_slice(0);
+        let prep_local = FixedTableLookupPreprocessedCols {
+            row: (*prep_local).to_vec(),
+        };
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local = FixedTableLookupCols { mult: (*local)[0] };
+
+        self.bus.receive(prep_local.row).eval(builder, local.mult);
+    }
+}
+
+/// SubAir for AIRs looking up rows of a [FixedTableLookupChip]. The trace generation of the
+/// looked up rows is done with [FixedTableLookupChip::request].
+#[derive(Clone, Copy, Debug)]
+pub struct FixedTableLookupSubAir {
+    pub bus: FixedTableLookupBus,
+    /// Width of the rows of the table.
+    pub width: usize,
+}
+
+impl<AB: InteractionBuilder> SubAir<AB> for FixedTableLookupSubAir {
+    /// (row, count)
+    type AirContext<'a>
+        = (Vec<AB::Expr>, AB::Expr)
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(&'a self, builder: &'a mut AB, (row, count): (Vec<AB::Expr>, AB::Expr))
+    where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        assert_eq!(row.len(), self.width, "wrong width of a looked up row");
+        self.bus.send(row).eval(builder, count);
+    }
+}
+
+pub struct FixedTableLookupChip<T> {
+    pub air: FixedTableLookupAir<T>,
+    rows: Vec<Vec<u32>>,
+    /// Index of each row in `rows`
+    indices: HashMap<Vec<u32>, usize>,
+    count: Vec<AtomicU32>,
+}
+
+pub struct SharedFixedTableLookupChip<T>(Arc<FixedTableLookupChip<T>>);
+
+impl<T> Clone for SharedFixedTableLookupChip<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: FixedTable> FixedTableLookupChip<T> {
+    pub fn new<F: PrimeField32>(bus: FixedTableLookupBus) -> Self {
+        let air = FixedTableLookupAir::new::<F>(bus);
+        let rows = T::rows();
+        let mut indices = HashMap::with_capacity(rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            indices.entry(row.clone()).or_insert(i);
+        }
+        let count = (0..rows.len()).map(|_| AtomicU32::new(0)).collect();
+        Self {
+            air,
+            rows,
+            indices,
+            count,
+        }
+    }
+
+    pub fn bus(&self) -> FixedTableLookupBus {
+        self.air.bus
+    }
+
+    pub fn sub_air(&self) -> FixedTableLookupSubAir {
+        FixedTableLookupSubAir {
+            bus: self.air.bus,
+            width: self.air.width,
+        }
+    }
+
+    pub fn rows(&self) -> &[Vec<u32>] {
+        &self.rows
+    }
+
+    /// Requests a lookup of `row`, which must be a row of the table.
+    pub fn request(&self, row: &[u32]) {
+        let index = *self
+            .indices
+            .get(row)
+            .unwrap_or_else(|| panic!("{row:?} is not a row of the table"));
+        self.count[index].fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Requests a lookup of the `index`-th row and returns it.
+    pub fn request_index(&self, index: usize) -> &[u32] {
+        self.count[index].fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        &self.rows[index]
+    }
+
+    pub fn clear(&self) {
+        for val in &self.count {
+            val.store(0, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    pub fn generate_trace<F: Field>(&self) -> RowMajorMatrix<F> {
+        let mut rows = self
+            .count
+            .iter()
+            .map(|c| F::from_canonical_u32(c.load(std::sync::atomic::Ordering::SeqCst)))
+            .collect::<Vec<_>>();
+        rows.resize(self.air.height, F::ZERO);
+        RowMajorMatrix::new(rows, NUM_FIXED_TABLE_LOOKUP_COLS)
+    }
+}
+
+impl<T: FixedTable> SharedFixedTableLookupChip<T> {
+    pub fn new<F: PrimeField32>(bus: FixedTableLookupBus) -> Self {
+        Self(Arc::new(FixedTableLookupChip::new::<F>(bus)))
+    }
+
+    pub fn bus(&self) -> FixedTableLookupBus {
+        self.0.bus()
+    }
+
+    pub fn sub_air(&self) -> FixedTableLookupSubAir {
+        self.0.sub_air()
+    }
+
+    pub fn rows(&self) -> &[Vec<u32>] {
+        self.0.rows()
+    }
+
+    pub fn request(&self, row: &[u32]) {
+        self.0.request(row);
+    }
+
+    pub fn request_index(&self, index: usize) -> &[u32] {
+        self.0.request_index(index)
+    }
+
+    pub fn clear(&self) {
+        self.0.clear();
+    }
+
+    pub fn generate_trace<F: Field>(&self) -> RowMajorMatrix<F> {
+        self.0.generate_trace()
+    }
+}
+
+impl<SC: StarkGenericConfig, T: FixedTable> Chip<SC> for FixedTableLookupChip<T>
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        Arc::new(self.air)
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        let trace = self.generate_trace::<Val<SC>>();
+        AirProofInput::simple_no_pis(trace)
+    }
+}
+
+impl<SC: StarkGenericConfig, T: FixedTable> Chip<SC> for SharedFixedTableLookupChip<T>
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        self.0.air()
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        self.0.generate_air_proof_input()
+    }
+}
+
+impl<T: FixedTable> ChipUsageGetter for FixedTableLookupChip<T> {
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+    fn constant_trace_height(&self) -> Option<usize> {
+        Some(self.air.height)
+    }
+    fn current_trace_height(&self) -> usize {
+        self.air.height
+    }
+    fn trace_width(&self) -> usize {
+        NUM_FIXED_TABLE_LOOKUP_COLS
+    }
+}
+
+impl<T: FixedTable> ChipUsageGetter for SharedFixedTableLookupChip<T> {
+    fn air_name(&self) -> String {
+        self.0.air_name()
+    }
+
+    fn constant_trace_height(&self) -> Option<usize> {
+        self.0.constant_trace_height()
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.0.current_trace_height()
+    }
+
+    fn trace_width(&self) -> usize {
+        self.0.trace_width()
+    }
+}
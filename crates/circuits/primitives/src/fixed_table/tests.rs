@@ -0,0 +1,192 @@
+use std::{iter, sync::Arc};
+
+use openvm_stark_backend::{
+    interaction::InteractionBuilder,
+    p3_air::{Air, BaseAir},
+    p3_field::{Field, FieldAlgebra},
+    p3_matrix::{dense::RowMajorMatrix, Matrix},
+    rap::{BaseAirWithPublicValues, PartitionedBaseAir},
+    utils::disable_debug_builder,
+    verifier::VerificationError,
+    AirRef, ChipUsageGetter,
+};
+use openvm_stark_sdk::{
+    config::baby_bear_blake3::BabyBearBlake3Engine, engine::StarkFriEngine, p3_baby_bear::BabyBear,
+    utils::create_seeded_rng,
+};
+use rand::Rng;
+
+use crate::{
+    fixed_table::{
+        FixedTable, FixedTableLookupBus, FixedTableLookupChip, FixedTableLookupSubAir,
+        SharedFixedTableLookupChip,
+    },
+    SubAir,
+};
+
+const BYTE_TO_NIBBLES: [[u8; 3]; 256] = {
+    let mut table = [[0; 3]; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = [i as u8, (i >> 4) as u8, (i & 0xf) as u8];
+        i += 1;
+    }
+    table
+};
+
+#[derive(FixedTable)]
+#[table(BYTE_TO_NIBBLES)]
+struct ByteToNibbles;
+
+const SQUARES: [u16; 12] = [0, 1, 4, 9, 16, 25, 36, 49, 64, 81, 100, 121];
+
+#[derive(FixedTable)]
+#[table(SQUARES)]
+struct Squares;
+
+const TOO_LARGE: [u32; 2] = [0, u32::MAX];
+
+#[derive(FixedTable)]
+#[table(TOO_LARGE)]
+struct TooLarge;
+
+// We only create an Air for testing purposes
+
+/// Looks up the row in the columns `1..` with the count in column `0`.
+#[derive(Clone, Copy)]
+struct FixedTableLookupTestAir(FixedTableLookupSubAir);
+
+impl<F: Field> BaseAirWithPublicValues<F> for FixedTableLookupTestAir {}
+impl<F: Field> PartitionedBaseAir<F> for FixedTableLookupTestAir {}
+impl<F: Field> BaseAir<F> for FixedTableLookupTestAir {
+    fn width(&self) -> usize {
+        self.0.width + 1
+    }
+}
+
+impl<AB: InteractionBuilder> Air<AB> for FixedTableLookupTestAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let row = local[1..].iter().map(|&x| x.into()).collect();
+        self.0.eval(builder, (row, local[0].into()));
+    }
+}
+
+#[test]
+fn test_fixed_table_rows() {
+    assert_eq!(ByteToNibbles::rows().len(), 256);
+    assert_eq!(ByteToNibbles::rows()[0xab], vec![0xab, 0xa, 0xb]);
+    assert_eq!(Squares::rows().len(), 12);
+    assert_eq!(Squares::rows()[7], vec![7, 49]);
+
+    let chip = FixedTableLookupChip::<Squares>::new::<BabyBear>(FixedTableLookupBus::new(0));
+    assert_eq!(chip.air.width, 2);
+    assert_eq!(chip.air.height, 16);
+    let preprocessed = BaseAir::<BabyBear>::preprocessed_trace(&chip.air).unwrap();
+    assert_eq!(preprocessed.values.len(), 16 * 2);
+}
+
+/// Looks up random rows of `T` from several AIRs using the [FixedTableLookupSubAir].
+fn run_lookups<T: FixedTable>() {
+    let mut rng = create_seeded_rng();
+    let bus_index = 0;
+    let chip = FixedTableLookupChip::<T>::new::<BabyBear>(FixedTableLookupBus::new(bus_index));
+    let width = chip.air.width;
+    let num_rows = chip.rows().len();
+
+    let num_lists = 4;
+    let list_len = 64;
+    let mut traces: Vec<RowMajorMatrix<BabyBear>> = (0..num_lists)
+        .map(|_| {
+            RowMajorMatrix::new(
+                (0..list_len)
+                    .flat_map(|_| {
+                        let row = chip.request_index(rng.gen_range(0..num_rows));
+                        iter::once(1).chain(row.iter().copied())
+                    })
+                    .map(FieldAlgebra::from_canonical_u32)
+                    .collect(),
+                width + 1,
+            )
+        })
+        .collect();
+    // Rows can also be requested by value.
+    let row = chip.rows()[num_rows - 1].clone();
+    chip.request(&row);
+    traces.push(RowMajorMatrix::new(
+        iter::once(1)
+            .chain(row)
+            .map(FieldAlgebra::from_canonical_u32)
+            .collect(),
+        width + 1,
+    ));
+    traces.push(chip.generate_trace());
+
+    // One AIR per list, one for the row requested by value, and the table.
+    let mut airs: Vec<AirRef<_>> = (0..=num_lists)
+        .map(|_| Arc::new(FixedTableLookupTestAir(chip.sub_air())) as AirRef<_>)
+        .collect();
+    airs.push(Arc::new(chip.air));
+
+    BabyBearBlake3Engine::run_simple_test_no_pis_fast(airs, traces).expect("Verification failed");
+}
+
+#[test]
+fn test_shared_fixed_table_height() {
+    // The table of 12 rows is padded to the next power of two.
+    let chip = SharedFixedTableLookupChip::<Squares>::new::<BabyBear>(FixedTableLookupBus::new(0));
+    assert_eq!(chip.constant_trace_height(), Some(16));
+    assert_eq!(chip.current_trace_height(), 16);
+}
+
+#[test]
+fn test_fixed_table_lookup_chip() {
+    run_lookups::<ByteToNibbles>();
+}
+
+#[test]
+fn test_fixed_table_lookup_chip_padded() {
+    run_lookups::<Squares>();
+}
+
+#[test]
+fn negative_test_fixed_table_lookup_chip() {
+    let bus_index = 0;
+    let chip =
+        FixedTableLookupChip::<ByteToNibbles>::new::<BabyBear>(FixedTableLookupBus::new(bus_index));
+    let airs: Vec<AirRef<_>> = vec![
+        Arc::new(FixedTableLookupTestAir(chip.sub_air())),
+        Arc::new(chip.air),
+    ];
+    // The nibbles are swapped, so the row is not in the table.
+    let trace = RowMajorMatrix::new(
+        [1, 0xab, 0xb, 0xa]
+            .into_iter()
+            .map(FieldAlgebra::from_canonical_u32)
+            .collect(),
+        4,
+    );
+    chip.request(&[0xab, 0xa, 0xb]);
+
+    disable_debug_builder();
+    assert_eq!(
+        BabyBearBlake3Engine::run_simple_test_no_pis_fast(airs, vec![trace, chip.generate_trace()])
+            .err(),
+        Some(VerificationError::ChallengePhaseError),
+        "Expected constraint to fail"
+    );
+}
+
+#[test]
+#[should_panic(expected = "is not a row of the table")]
+fn test_fixed_table_request_missing_row() {
+    let chip = FixedTableLookupChip::<Squares>::new::<BabyBear>(FixedTableLookupBus::new(0));
+    chip.request(&[3, 10]);
+}
+
+#[test]
+#[should_panic(expected = "must be smaller than the field modulus")]
+fn test_fixed_table_value_too_large() {
+    FixedTableLookupChip::<TooLarge>::new::<BabyBear>(FixedTableLookupBus::new(0));
+}
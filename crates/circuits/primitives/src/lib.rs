@@ -2,6 +2,7 @@
 //! The primitives are separated into two types: standalone [Air](openvm_stark_backend::p3_air::Air)s and [SubAir]s.
 //!
 //! The following modules contain standalone [Air](openvm_stark_backend::p3_air::Air)s:
+//! - [fixed_table]
 //! - [range]
 //! - [range_gate]
 //! - [range_tuple]
@@ -19,6 +20,8 @@
 //! - [is_less_than_array]
 //! - [is_zero]
 
+extern crate self as openvm_circuit_primitives;
+
 /// Derive macros
 pub use openvm_circuit_primitives_derive::*;
 
@@ -26,6 +29,7 @@ pub mod assert_less_than;
 pub mod bigint;
 pub mod bitwise_op_lookup;
 pub mod encoder;
+pub mod fixed_table;
 pub mod is_equal;
 pub mod is_equal_array;
 pub mod is_less_than;
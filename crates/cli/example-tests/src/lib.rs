@@ -0,0 +1,34 @@
+#![cfg_attr(all(test, target_os = "zkvm"), feature(custom_test_frameworks))]
+#![cfg_attr(all(test, target_os = "zkvm"), test_runner(openvm::testing::runner))]
+
+pub fn fibonacci(n: u32) -> u32 {
+    let (mut a, mut b) = (0u32, 1u32);
+    for _ in 0..n {
+        let sum = a.checked_add(b).expect("fibonacci overflow");
+        a = b;
+        b = sum;
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fibonacci() {
+        assert_eq!(fibonacci(10), 55);
+    }
+
+    #[test]
+    #[should_panic(expected = "fibonacci overflow")]
+    fn test_fibonacci_overflow() {
+        fibonacci(100);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_fibonacci_large() {
+        assert_eq!(fibonacci(45), 1134903170);
+    }
+}
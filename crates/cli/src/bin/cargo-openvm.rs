@@ -1,5 +1,8 @@
 use cargo_openvm::{
-    commands::{BuildCmd, EvmProvingSetupCmd, KeygenCmd, ProveCmd, RunCmd, VerifyCmd},
+    commands::{
+        BuildCmd, CommitCmd, DisasmCmd, EstimateCmd, EvmProvingSetupCmd, InitCmd, InspectCmd,
+        KeygenCmd, NewCmd, ProveCmd, RunCmd, TestCmd, VerifyCmd,
+    },
     OPENVM_VERSION_MESSAGE,
 };
 use clap::{Parser, Subcommand};
@@ -24,10 +27,17 @@ pub struct VmCli {
 #[derive(Subcommand)]
 pub enum VmCliCommands {
     Build(BuildCmd),
+    Commit(CommitCmd),
+    Disasm(DisasmCmd),
+    Estimate(EstimateCmd),
+    Init(InitCmd),
+    Inspect(InspectCmd),
     Keygen(KeygenCmd),
+    New(NewCmd),
     Prove(ProveCmd),
     Run(RunCmd),
     Setup(EvmProvingSetupCmd),
+    Test(TestCmd),
     Verify(VerifyCmd),
 }
 
@@ -38,10 +48,17 @@ async fn main() -> Result<()> {
     setup_tracing_with_log_level(Level::WARN);
     match command {
         VmCliCommands::Build(cmd) => cmd.run(),
+        VmCliCommands::Commit(cmd) => cmd.run(),
+        VmCliCommands::Disasm(cmd) => cmd.run(),
+        VmCliCommands::Estimate(cmd) => cmd.run(),
+        VmCliCommands::Init(cmd) => cmd.run(),
+        VmCliCommands::Inspect(cmd) => cmd.run(),
+        VmCliCommands::New(cmd) => cmd.run(),
         VmCliCommands::Run(cmd) => cmd.run(),
         VmCliCommands::Keygen(cmd) => cmd.run(),
         VmCliCommands::Prove(cmd) => cmd.run(),
         VmCliCommands::Setup(cmd) => cmd.run().await,
+        VmCliCommands::Test(cmd) => cmd.run(),
         VmCliCommands::Verify(cmd) => cmd.run(),
     }
 }
@@ -68,6 +68,12 @@ pub struct BuildArgs {
 
     #[arg(long, default_value = "release", help = "Build profile")]
     pub profile: String,
+
+    #[arg(
+        long,
+        help = "Size of the guest stack in bytes, must be a multiple of 16 [default: 2 MiB]"
+    )]
+    pub stack_size: Option<u32>,
 }
 
 #[derive(Clone, Default, clap::Args)]
@@ -102,6 +108,7 @@ pub(crate) fn build(build_args: &BuildArgs) -> Result<Option<PathBuf>> {
         .with_features(build_args.features.clone())
         .with_profile(build_args.profile.clone());
     guest_options.target_dir = build_args.target_dir.clone();
+    guest_options.stack_size = build_args.stack_size;
 
     let pkg = get_package(&build_args.manifest_dir);
     // We support builds of libraries with 0 or >1 executables.
@@ -168,6 +175,7 @@ mod tests {
             exe_output: PathBuf::from(DEFAULT_APP_EXE_PATH),
             profile: "dev".to_string(),
             target_dir: Some(target_dir.to_path_buf()),
+            stack_size: None,
         };
         build(&build_args)?;
         assert!(
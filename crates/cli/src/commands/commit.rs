@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use eyre::Result;
+use openvm_sdk::{
+    commit::AppExecutionCommit,
+    config::SdkVmConfig,
+    fs::{read_app_pk_from_file, read_exe_from_file, write_json_to_file, AppExecutionCommitJson},
+    keygen::AppProvingKey,
+    Sdk,
+};
+
+use crate::default::{DEFAULT_APP_EXE_PATH, DEFAULT_APP_PK_PATH};
+
+#[derive(Parser)]
+#[command(
+    name = "commit",
+    about = "Compute the commitments of an OpenVM program that proofs are checked against, without proving"
+)]
+pub struct CommitCmd {
+    #[arg(long, action, help = "Path to app proving key", default_value = DEFAULT_APP_PK_PATH)]
+    app_pk: PathBuf,
+
+    #[arg(long, action, help = "Path to OpenVM executable", default_value = DEFAULT_APP_EXE_PATH)]
+    exe: PathBuf,
+
+    #[arg(long, action, help = "Print the commitments as JSON")]
+    json: bool,
+
+    #[arg(long, action, help = "Path to write the commitments to as JSON")]
+    output: Option<PathBuf>,
+}
+
+impl CommitCmd {
+    pub fn run(&self) -> Result<()> {
+        let app_pk: AppProvingKey<SdkVmConfig> = read_app_pk_from_file(&self.app_pk)?;
+        let app_exe = read_exe_from_file(&self.exe)?;
+        let committed_exe = Sdk.commit_app_exe(app_pk.app_fri_params(), app_exe)?;
+        let commits = AppExecutionCommit::compute(
+            &app_pk.app_vm_pk.vm_config,
+            &committed_exe,
+            &app_pk.leaf_committed_exe,
+        );
+        let commits_json = AppExecutionCommitJson::from(&commits);
+
+        if let Some(output) = &self.output {
+            write_json_to_file(output, &commits_json)?;
+        }
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&commits_json)?);
+        } else {
+            println!("exe commit: {}", commits_json.exe_commit_bn254);
+            println!(
+                "leaf verifier commit: {}",
+                commits_json.leaf_vm_verifier_commit_bn254
+            );
+        }
+        Ok(())
+    }
+}
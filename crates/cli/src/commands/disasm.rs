@@ -0,0 +1,42 @@
+use std::{fs::write, path::PathBuf};
+
+use clap::Parser;
+use eyre::Result;
+use openvm_assembler::disassemble;
+use openvm_circuit::arch::VmConfig;
+use openvm_sdk::{fs::read_exe_from_file, F};
+
+use crate::{
+    default::{DEFAULT_APP_CONFIG_PATH, DEFAULT_APP_EXE_PATH},
+    util::read_config_toml_or_default,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "disasm",
+    about = "Print an OpenVM executable as assembly, with opcode names from the app config"
+)]
+pub struct DisasmCmd {
+    #[arg(help = "Path to OpenVM executable", default_value = DEFAULT_APP_EXE_PATH)]
+    exe: PathBuf,
+
+    #[arg(long, action, help = "Path to app config TOML file", default_value = DEFAULT_APP_CONFIG_PATH)]
+    config: PathBuf,
+
+    #[arg(long, action, help = "Path to write the assembly to instead of stdout")]
+    output: Option<PathBuf>,
+}
+
+impl DisasmCmd {
+    pub fn run(&self) -> Result<()> {
+        let exe = read_exe_from_file(&self.exe)?;
+        let app_config = read_config_toml_or_default(&self.config)?;
+        let names = VmConfig::<F>::opcode_names(&app_config.app_vm_config);
+        let listing = disassemble(&exe, &names);
+        match &self.output {
+            Some(output) => write(output, listing)?,
+            None => print!("{listing}"),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::{fs::write, path::PathBuf};
+
+use clap::Parser;
+use eyre::Result;
+use openvm_sdk::{estimate::ExecutionEstimate, fs::read_exe_from_file, Sdk};
+use prettytable::{row, Table};
+
+use crate::{
+    default::{DEFAULT_APP_CONFIG_PATH, DEFAULT_APP_EXE_PATH},
+    util::{
+        read_config_toml_or_default, read_to_stdin, Input, KeyedInput, SegmentationArgs, VfsArgs,
+    },
+};
+
+#[derive(Parser)]
+#[command(
+    name = "estimate",
+    about = "Execute an OpenVM program and estimate the cost of proving it, without proving"
+)]
+pub struct EstimateCmd {
+    #[arg(long, action, help = "Path to OpenVM executable", default_value = DEFAULT_APP_EXE_PATH)]
+    exe: PathBuf,
+
+    #[arg(long, action, help = "Path to app config TOML file", default_value = DEFAULT_APP_CONFIG_PATH)]
+    config: PathBuf,
+
+    #[arg(long, value_parser, help = "Input to OpenVM program")]
+    input: Option<Input>,
+
+    #[arg(
+        long = "keyed-input",
+        value_parser,
+        help = "Keyed hint as <hex_key>=<hex_value | path>, can be repeated"
+    )]
+    keyed_inputs: Vec<KeyedInput>,
+
+    #[clap(flatten)]
+    segmentation: SegmentationArgs,
+
+    #[clap(flatten)]
+    vfs: VfsArgs,
+
+    #[arg(long, action, help = "Print the report as JSON instead of tables")]
+    json: bool,
+
+    #[arg(long, action, help = "Path to write the JSON report to")]
+    output: Option<PathBuf>,
+}
+
+impl EstimateCmd {
+    pub fn run(&self) -> Result<()> {
+        let exe = read_exe_from_file(&self.exe)?;
+        let mut app_config = read_config_toml_or_default(&self.config)?;
+        self.segmentation.apply(&mut app_config);
+        let estimate = Sdk.estimate(
+            exe,
+            &app_config,
+            read_to_stdin(&self.input, &self.keyed_inputs, &self.vfs, &self.exe)?,
+        )?;
+
+        if let Some(output) = &self.output {
+            write(output, serde_json::to_string_pretty(&estimate)?)?;
+        }
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&estimate)?);
+        } else {
+            print_estimate(&estimate);
+        }
+        Ok(())
+    }
+}
+
+fn print_estimate(estimate: &ExecutionEstimate) {
+    for (segment_idx, segment) in estimate.segments.iter().enumerate() {
+        println!("Segment {segment_idx}:");
+        let mut table = Table::new();
+        table.set_titles(row!["AIR", "Height", "Width", "Cells"]);
+        for air in &segment.airs {
+            table.add_row(row![air.name, r->air.height, r->air.width, r->air.cells]);
+        }
+        table.printstd();
+    }
+
+    println!("Summary:");
+    let mut table = Table::new();
+    table.set_titles(row![
+        "Segment",
+        "Padded main cells",
+        "Est. memory (MiB)",
+        "Est. proving time (s)",
+        "Est. proof size (KiB)"
+    ]);
+    for (segment_idx, segment) in estimate.segments.iter().enumerate() {
+        table.add_row(row![
+            r->segment_idx,
+            r->segment.cost.main_cells,
+            r->segment.cost.memory_bytes >> 20,
+            r->format!("{:.1}", segment.cost.proving_time_ms as f64 / 1000.0),
+            r->segment.proof_size_bytes >> 10
+        ]);
+    }
+    table.add_row(row![
+        "Total",
+        "",
+        r->estimate.max_memory_bytes() >> 20,
+        r->format!("{:.1}", estimate.total_proving_time_ms() as f64 / 1000.0),
+        r->estimate.total_proof_size_bytes() >> 10
+    ]);
+    table.printstd();
+    println!(
+        "Memory is the peak over all segments; proving time assumes segments are proven sequentially."
+    );
+}
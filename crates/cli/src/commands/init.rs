@@ -0,0 +1,650 @@
+use std::{
+    fmt::Write as _,
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+use clap::{Parser, ValueEnum};
+use eyre::Result;
+
+const OPENVM_GIT_URL: &str = "https://github.com/openvm-org/openvm.git";
+/// Release tag of this version of the CLI, which git dependencies are pinned to.
+const OPENVM_GIT_TAG: &str = concat!("v", env!("CARGO_PKG_VERSION"));
+
+#[derive(Parser)]
+#[command(
+    name = "init",
+    about = "Create a new OpenVM guest package in an existing directory"
+)]
+pub struct InitCmd {
+    #[arg(default_value = ".", help = "Directory to create the package in")]
+    path: PathBuf,
+
+    #[clap(flatten)]
+    init_args: InitArgs,
+}
+
+impl InitCmd {
+    pub fn run(&self) -> Result<()> {
+        init_package(&self.path, &self.init_args)
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "new", about = "Create a new OpenVM guest package")]
+pub struct NewCmd {
+    #[arg(help = "Directory to create the package in, must not exist")]
+    path: PathBuf,
+
+    #[clap(flatten)]
+    init_args: InitArgs,
+}
+
+impl NewCmd {
+    pub fn run(&self) -> Result<()> {
+        if self.path.exists() {
+            return Err(eyre::eyre!(
+                "destination `{}` already exists",
+                self.path.display()
+            ));
+        }
+        init_package(&self.path, &self.init_args)
+    }
+}
+
+#[derive(Clone, Default, clap::Args)]
+pub struct InitArgs {
+    #[arg(long, help = "Package name [default: the directory name]")]
+    pub name: Option<String>,
+
+    #[arg(
+        long,
+        action,
+        help = "Also create a host crate in `host/` that builds and executes the guest with the SDK"
+    )]
+    pub host: bool,
+
+    #[arg(long, action, help = "Enable the Keccak-256 extension")]
+    pub keccak: bool,
+
+    #[arg(long, action, help = "Enable the SHA-256 extension")]
+    pub sha256: bool,
+
+    #[arg(long, action, help = "Enable the 256-bit integer extension")]
+    pub bigint: bool,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Enable modular arithmetic for the given moduli, in decimal"
+    )]
+    pub moduli: Vec<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        help = "Enable elliptic curve operations for the given curves"
+    )]
+    pub curves: Vec<EccCurve>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        help = "Enable pairing checks for the given curves"
+    )]
+    pub pairing: Vec<PairingCurve>,
+
+    #[arg(
+        long,
+        help = "Path to a local OpenVM repository to depend on instead of the git repository"
+    )]
+    pub openvm_path: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum EccCurve {
+    /// secp256k1
+    K256,
+    /// NIST P-256
+    P256,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PairingCurve {
+    Bn254,
+    #[value(name = "bls12_381")]
+    Bls12_381,
+}
+
+/// Parameters of a short Weierstrass curve `y^2 = x^3 + ax + b`, in decimal.
+struct CurveParams {
+    modulus: &'static str,
+    scalar: &'static str,
+    a: &'static str,
+    b: &'static str,
+}
+
+impl EccCurve {
+    fn params(&self) -> CurveParams {
+        match self {
+            EccCurve::K256 => CurveParams {
+                modulus:
+                    "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+                scalar:
+                    "115792089237316195423570985008687907852837564279074904382605163141518161494337",
+                a: "0",
+                b: "7",
+            },
+            EccCurve::P256 => CurveParams {
+                modulus:
+                    "115792089210356248762697446949407573530086143415290314195533631308867097853951",
+                scalar:
+                    "115792089210356248762697446949407573529996955224135760342422259061068512044369",
+                a: "115792089210356248762697446949407573530086143415290314195533631308867097853948",
+                b: "41058363725152142129326129780047268409114441015993725554835256314039467401291",
+            },
+        }
+    }
+
+    /// Guest crate feature enabling the curve.
+    fn feature(&self) -> &'static str {
+        match self {
+            EccCurve::K256 => "k256",
+            EccCurve::P256 => "p256",
+        }
+    }
+
+    /// Path of the point type in `openvm-ecc-guest`.
+    fn point_type(&self) -> (&'static str, &'static str) {
+        match self {
+            EccCurve::K256 => ("k256", "Secp256k1Point"),
+            EccCurve::P256 => ("p256", "P256Point"),
+        }
+    }
+}
+
+impl PairingCurve {
+    /// Base field and scalar field moduli, in decimal.
+    fn moduli(&self) -> (&'static str, &'static str) {
+        match self {
+            PairingCurve::Bn254 => (
+                "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+                "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+            ),
+            PairingCurve::Bls12_381 => (
+                "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787",
+                "52435875175126190479447740508185965837690552500527637822603658699938581184513",
+            ),
+        }
+    }
+
+    /// Guest crate feature enabling the curve, which is also its module in `openvm-pairing-guest`.
+    fn feature(&self) -> &'static str {
+        match self {
+            PairingCurve::Bn254 => "bn254",
+            PairingCurve::Bls12_381 => "bls12_381",
+        }
+    }
+
+    /// Name of the curve in the VM config.
+    fn config_name(&self) -> &'static str {
+        match self {
+            PairingCurve::Bn254 => "Bn254",
+            PairingCurve::Bls12_381 => "Bls12_381",
+        }
+    }
+
+    fn fp2_type(&self) -> &'static str {
+        match self {
+            PairingCurve::Bn254 => "Bn254Fp2",
+            PairingCurve::Bls12_381 => "Bls12_381Fp2",
+        }
+    }
+}
+
+/// Creates the guest package, and the host crate if requested, in `path`.
+pub(crate) fn init_package(path: &Path, args: &InitArgs) -> Result<()> {
+    if path.join("Cargo.toml").exists() {
+        return Err(eyre::eyre!(
+            "`{}` already contains a Cargo package",
+            path.display()
+        ));
+    }
+    for modulus in &args.moduli {
+        if modulus.is_empty() || !modulus.chars().all(|c| c.is_ascii_digit()) {
+            return Err(eyre::eyre!("modulus `{}` must be in decimal", modulus));
+        }
+    }
+    let openvm_path = args
+        .openvm_path
+        .as_ref()
+        .map(|path| path.canonicalize())
+        .transpose()?;
+
+    create_dir_all(path.join("src"))?;
+    let name = match &args.name {
+        Some(name) => name.clone(),
+        None => path
+            .canonicalize()?
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| eyre::eyre!("cannot infer the package name, use --name"))?
+            .to_string(),
+    };
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(eyre::eyre!("invalid package name `{}`", name));
+    }
+
+    let template = Template::new(name, args, openvm_path);
+    write(path.join("Cargo.toml"), template.guest_manifest())?;
+    write(path.join("openvm.toml"), template.vm_config())?;
+    write(path.join("src/main.rs"), template.guest_main())?;
+    if !path.join(".gitignore").exists() {
+        write(path.join(".gitignore"), "/target\n/openvm\n")?;
+    }
+    if args.host {
+        create_dir_all(path.join("host/src"))?;
+        write(path.join("host/Cargo.toml"), template.host_manifest())?;
+        write(path.join("host/src/main.rs"), template.host_main())?;
+    }
+    println!(
+        "[openvm] Created guest package `{}` in {}",
+        template.name,
+        path.display()
+    );
+    Ok(())
+}
+
+struct Template<'a> {
+    name: String,
+    args: &'a InitArgs,
+    openvm_path: Option<PathBuf>,
+    /// All moduli, in the order of `moduli_init!` and the modular extension config.
+    moduli: Vec<String>,
+    /// Number of moduli given by the user, which come first and are declared as `Mod{i}`.
+    num_user_moduli: usize,
+}
+
+impl<'a> Template<'a> {
+    fn new(name: String, args: &'a InitArgs, openvm_path: Option<PathBuf>) -> Self {
+        let mut moduli: Vec<String> = vec![];
+        let mut push = |modulus: &str| {
+            if !moduli.iter().any(|m| m == modulus) {
+                moduli.push(modulus.to_string());
+            }
+        };
+        for modulus in &args.moduli {
+            push(modulus.trim_start_matches('0'));
+        }
+        let num_user_moduli = moduli.len();
+        for curve in &args.curves {
+            let params = curve.params();
+            push(params.modulus);
+            push(params.scalar);
+        }
+        for curve in &args.pairing {
+            let (fp, scalar) = curve.moduli();
+            push(fp);
+            push(scalar);
+        }
+        Self {
+            name,
+            args,
+            openvm_path,
+            moduli,
+            num_user_moduli,
+        }
+    }
+
+    fn mod_idx(&self, modulus: &str) -> usize {
+        self.moduli.iter().position(|m| m == modulus).unwrap()
+    }
+
+    /// Dependency on the OpenVM crate `crate_name` located at `rel_path` in the repository.
+    fn dependency(&self, crate_name: &str, rel_path: &str, features: &[&str]) -> String {
+        let source = match &self.openvm_path {
+            Some(openvm_path) => format!(
+                "path = \"{}\"",
+                openvm_path
+                    .join(rel_path)
+                    .display()
+                    .to_string()
+                    .replace('\\', "/")
+            ),
+            None => format!("git = \"{OPENVM_GIT_URL}\", tag = \"{OPENVM_GIT_TAG}\""),
+        };
+        let features = if features.is_empty() {
+            String::new()
+        } else {
+            let features: Vec<_> = features.iter().map(|f| format!("\"{f}\"")).collect();
+            format!(", features = [{}]", features.join(", "))
+        };
+        format!("{crate_name} = {{ {source}{features} }}\n")
+    }
+
+    /// Guest crates the package depends on, with the features they need.
+    fn guest_dependencies(&self) -> Vec<(&'static str, &'static str, Vec<&'static str>)> {
+        let mut deps = vec![("openvm", "crates/toolchain/openvm", vec![])];
+        if self.args.keccak {
+            deps.push((
+                "openvm-keccak256-guest",
+                "extensions/keccak256/guest",
+                vec![],
+            ));
+        }
+        if self.args.sha256 {
+            deps.push(("openvm-sha256-guest", "extensions/sha256/guest", vec![]));
+        }
+        if self.args.bigint {
+            deps.push(("openvm-bigint-guest", "extensions/bigint/guest", vec![]));
+        }
+        if !self.moduli.is_empty() {
+            deps.push(("openvm-algebra-guest", "extensions/algebra/guest", vec![]));
+        }
+        if !self.args.curves.is_empty() || !self.args.pairing.is_empty() {
+            deps.push((
+                "openvm-ecc-guest",
+                "extensions/ecc/guest",
+                self.args.curves.iter().map(|c| c.feature()).collect(),
+            ));
+        }
+        if !self.args.pairing.is_empty() {
+            deps.push((
+                "openvm-pairing-guest",
+                "extensions/pairing/guest",
+                self.args.pairing.iter().map(|c| c.feature()).collect(),
+            ));
+        }
+        deps
+    }
+
+    fn guest_manifest(&self) -> String {
+        let mut manifest = format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n",
+            self.name
+        );
+        manifest.push_str("[workspace]\nmembers = []\n\n[dependencies]\n");
+        let deps = self.guest_dependencies();
+        for (crate_name, rel_path, features) in &deps {
+            manifest.push_str(&self.dependency(crate_name, rel_path, features));
+        }
+        let std_features: Vec<_> = deps
+            .iter()
+            .map(|(crate_name, _, _)| format!("    \"{crate_name}/std\",\n"))
+            .collect();
+        write!(
+            manifest,
+            "\n[features]\ndefault = []\nstd = [\n{}]\n",
+            std_features.concat()
+        )
+        .unwrap();
+        manifest
+    }
+
+    fn vm_config(&self) -> String {
+        let mut config =
+            String::from("[app_vm_config.rv32i]\n[app_vm_config.rv32m]\n[app_vm_config.io]\n");
+        if self.args.keccak {
+            config.push_str("[app_vm_config.keccak]\n");
+        }
+        if self.args.sha256 {
+            config.push_str("[app_vm_config.sha256]\n");
+        }
+        if self.args.bigint {
+            config.push_str("[app_vm_config.bigint]\n");
+        }
+        let quoted_list = |values: &[&str]| {
+            values
+                .iter()
+                .map(|v| format!("    \"{v}\",\n"))
+                .collect::<String>()
+        };
+        if !self.moduli.is_empty() {
+            let moduli: Vec<_> = self.moduli.iter().map(|m| m.as_str()).collect();
+            write!(
+                config,
+                "\n[app_vm_config.modular]\nsupported_modulus = [\n{}]\n",
+                quoted_list(&moduli)
+            )
+            .unwrap();
+        }
+        if !self.args.pairing.is_empty() {
+            let fp2_moduli: Vec<_> = self.args.pairing.iter().map(|c| c.moduli().0).collect();
+            write!(
+                config,
+                "\n[app_vm_config.fp2]\nsupported_modulus = [\n{}]\n",
+                quoted_list(&fp2_moduli)
+            )
+            .unwrap();
+            let curves: Vec<_> = self
+                .args
+                .pairing
+                .iter()
+                .map(|c| format!("\"{}\"", c.config_name()))
+                .collect();
+            write!(
+                config,
+                "\n[app_vm_config.pairing]\nsupported_curves = [{}]\n",
+                curves.join(", ")
+            )
+            .unwrap();
+        }
+        for curve in &self.args.curves {
+            let params = curve.params();
+            write!(
+                config,
+                "\n[[app_vm_config.ecc.supported_curves]]\nmodulus = \"{}\"\nscalar = \"{}\"\na = \"{}\"\nb = \"{}\"\n",
+                params.modulus, params.scalar, params.a, params.b
+            )
+            .unwrap();
+        }
+        config
+    }
+
+    fn guest_main(&self) -> String {
+        let mut imports = String::from("use core::hint::black_box;\n\n");
+        let mut init = String::new();
+        let mut setup = String::new();
+        let mut body = String::new();
+
+        if self.args.keccak {
+            imports.push_str("use openvm_keccak256_guest::keccak256;\n");
+            body.push_str("    black_box(keccak256(b\"openvm\"));\n");
+        }
+        if self.args.sha256 {
+            imports.push_str("use openvm_sha256_guest::sha256;\n");
+            body.push_str("    black_box(sha256(b\"openvm\"));\n");
+        }
+        if self.args.bigint {
+            imports.push_str("use openvm_bigint_guest::U256;\n");
+            body.push_str("    let x = U256::from_u32(3);\n    black_box(&x * &x);\n");
+        }
+
+        if self.num_user_moduli > 0 {
+            imports
+                .push_str("use openvm_algebra_guest::{moduli_macros::moduli_declare, IntMod};\n");
+            init.push_str(
+                "// Declares a struct `Mod{i}` for arithmetic modulo the i-th modulus.\n",
+            );
+            init.push_str("moduli_declare! {\n");
+            for (i, modulus) in self.moduli[..self.num_user_moduli].iter().enumerate() {
+                writeln!(init, "    Mod{i} {{ modulus = \"{modulus}\" }},").unwrap();
+            }
+            init.push_str("}\n\n");
+            body.push_str("    let x = Mod0::from_u32(3);\n    black_box(x.clone() * &x);\n");
+        }
+        if !self.moduli.is_empty() {
+            init.push_str(
+                "// The order of the moduli must match `supported_modulus` in `openvm.toml`.\n",
+            );
+            init.push_str("openvm_algebra_guest::moduli_macros::moduli_init! {\n");
+            for modulus in &self.moduli {
+                writeln!(init, "    \"{modulus}\",").unwrap();
+            }
+            init.push_str("}\n\n");
+            setup.push_str("    setup_all_moduli();\n");
+        }
+
+        if !self.args.pairing.is_empty() {
+            imports.push_str("use openvm_algebra_guest::Field;\n");
+            init.push_str("openvm_algebra_guest::complex_macros::complex_init! {\n");
+            for curve in &self.args.pairing {
+                let fp2 = curve.fp2_type();
+                writeln!(
+                    imports,
+                    "use openvm_pairing_guest::{}::{};",
+                    curve.feature(),
+                    fp2
+                )
+                .unwrap();
+                writeln!(
+                    init,
+                    "    {} {{ mod_idx = {} }},",
+                    fp2,
+                    self.mod_idx(curve.moduli().0)
+                )
+                .unwrap();
+                writeln!(
+                    body,
+                    "    let x = {fp2}::ONE;\n    black_box(x.clone() * &x);"
+                )
+                .unwrap();
+            }
+            init.push_str("}\n\n");
+            setup.push_str("    setup_all_complex_extensions();\n");
+        }
+
+        if !self.args.curves.is_empty() {
+            imports.push_str("use openvm_ecc_guest::CyclicGroup;\n");
+            init.push_str(
+                "// The order of the curves must match `supported_curves` in `openvm.toml`.\n",
+            );
+            init.push_str("openvm_ecc_guest::sw_macros::sw_init! {\n");
+            for curve in &self.args.curves {
+                let (module, point) = curve.point_type();
+                writeln!(imports, "use openvm_ecc_guest::{module}::{point};").unwrap();
+                writeln!(init, "    {point},").unwrap();
+                writeln!(
+                    body,
+                    "    let g = {point}::GENERATOR;\n    black_box(&g + &g);"
+                )
+                .unwrap();
+            }
+            init.push_str("}\n\n");
+            setup.push_str("    setup_all_curves();\n");
+        }
+
+        if body.is_empty() {
+            body.push_str(
+                "    let n = black_box(10);\n    let (mut a, mut b) = (0u32, 1u32);\n    for _ in 0..n {\n        let sum = a.wrapping_add(b);\n        a = b;\n        b = sum;\n    }\n    openvm::io::commit(&a);\n",
+            );
+        }
+        if !setup.is_empty() {
+            setup.push('\n');
+        }
+
+        format!(
+            "#![cfg_attr(not(feature = \"std\"), no_main)]\n#![cfg_attr(not(feature = \"std\"), no_std)]\n\n{imports}\nopenvm::entry!(main);\n\n{init}pub fn main() {{\n{setup}{body}}}\n"
+        )
+    }
+
+    fn host_manifest(&self) -> String {
+        let mut manifest = format!(
+            "[package]\nname = \"{}-host\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n",
+            self.name
+        );
+        manifest.push_str("[workspace]\n\n[dependencies]\n");
+        manifest.push_str(&self.dependency("openvm-build", "crates/toolchain/build", &[]));
+        manifest.push_str(&self.dependency("openvm-sdk", "crates/sdk", &[]));
+        manifest.push_str("eyre = \"0.6\"\ntoml = \"0.8\"\n");
+        manifest
+    }
+
+    fn host_main(&self) -> String {
+        r#"use std::{fs::read_to_string, path::PathBuf};
+
+use eyre::Result;
+use openvm_build::GuestOptions;
+use openvm_sdk::{
+    config::{AppConfig, SdkVmConfig},
+    Sdk, StdIn,
+};
+
+fn main() -> Result<()> {
+    let guest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    let app_config: AppConfig<SdkVmConfig> =
+        toml::from_str(&read_to_string(guest_dir.join("openvm.toml"))?)?;
+
+    let elf = Sdk.build(GuestOptions::default(), &guest_dir, &None)?;
+    let exe = Sdk.transpile(elf, app_config.app_vm_config.transpiler())?;
+
+    let stdin = StdIn::default();
+    let public_values = Sdk.execute(exe, app_config.app_vm_config, stdin)?;
+    println!("public values: {:?}", public_values);
+    Ok(())
+}
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use eyre::Result;
+
+    use super::*;
+    use crate::util::read_config_toml_or_default;
+
+    #[test]
+    fn test_init_config_matches_macros() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("guest");
+        let args = InitArgs {
+            host: true,
+            keccak: true,
+            sha256: true,
+            bigint: true,
+            moduli: vec!["998244353".to_string()],
+            curves: vec![EccCurve::K256, EccCurve::P256],
+            pairing: vec![PairingCurve::Bn254],
+            ..Default::default()
+        };
+        init_package(&path, &args)?;
+
+        let app_config = read_config_toml_or_default(&path.join("openvm.toml"))?;
+        let vm_config = app_config.app_vm_config;
+        assert!(vm_config.keccak.is_some());
+        assert!(vm_config.sha256.is_some());
+        assert!(vm_config.bigint.is_some());
+        // One user modulus, and the base and scalar field moduli of each curve.
+        let moduli = vm_config.modular.unwrap().supported_modulus;
+        assert_eq!(moduli.len(), 7);
+        assert_eq!(moduli[0].to_string(), "998244353");
+        assert_eq!(vm_config.ecc.unwrap().supported_curves.len(), 2);
+        assert_eq!(
+            vm_config.fp2.unwrap().supported_modulus,
+            vec![moduli[5].clone()]
+        );
+        assert_eq!(vm_config.pairing.unwrap().supported_curves.len(), 1);
+
+        let main = read_to_string(path.join("src/main.rs"))?;
+        assert!(main.contains("Bn254Fp2 { mod_idx = 5 }"));
+        assert!(main.contains("setup_all_curves();"));
+        let manifest = read_to_string(path.join("Cargo.toml"))?;
+        assert!(manifest.contains("name = \"guest\""));
+        assert!(manifest.contains("features = [\"k256\", \"p256\"]"));
+        assert!(manifest.contains(&format!("tag = \"{OPENVM_GIT_TAG}\"")));
+        assert!(path.join("host/src/main.rs").exists());
+
+        assert!(init_package(&path, &args).is_err());
+        Ok(())
+    }
+}
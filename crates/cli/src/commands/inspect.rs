@@ -0,0 +1,130 @@
+use std::{fs::read, path::PathBuf};
+
+use clap::Parser;
+use eyre::Result;
+use openvm_circuit::arch::instructions::exe::VmExe;
+use openvm_native_recursion::halo2::EvmProof;
+use openvm_sdk::{
+    fs::{
+        decode_artifact, decode_artifact_header, AppVerifyingKeyJson, ArtifactKind,
+        ContinuationVmProofJson, EvmProofJson, RootVerifierInputJson, OPENVM_VERSION,
+    },
+    keygen::AppVerifyingKey,
+    prover::vm::ContinuationVmProof,
+    verifier::root::types::RootVmVerifierInput,
+    F, SC,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "inspect",
+    about = "Print the header and a summary of an OpenVM artifact file"
+)]
+pub struct InspectCmd {
+    #[arg(help = "Path to the artifact file")]
+    file: PathBuf,
+
+    #[arg(
+        long,
+        action,
+        help = "Print the JSON export of the artifact instead of a summary"
+    )]
+    json: bool,
+}
+
+impl InspectCmd {
+    pub fn run(&self) -> Result<()> {
+        let bytes = read(&self.file)?;
+        let (header, body) = decode_artifact_header(&bytes)?;
+        if self.json {
+            return print_json(header.kind, &bytes);
+        }
+        println!("kind: {}", header.kind);
+        println!("format version: {}", header.format_version);
+        println!("openvm version: {}", header.openvm_version);
+        println!(
+            "config hash: {}",
+            header.config_hash_hex().unwrap_or("none".to_string())
+        );
+        println!("compression: {:?}", header.compression);
+        println!("body size: {} bytes", body.len());
+
+        if header.openvm_version != OPENVM_VERSION {
+            println!(
+                "Summary unavailable: artifact was written by OpenVM {}, but this is OpenVM {}",
+                header.openvm_version, OPENVM_VERSION
+            );
+            return Ok(());
+        }
+        match header.kind {
+            ArtifactKind::Exe => {
+                let (_, exe): (_, VmExe<F>) = decode_artifact(header.kind, &bytes)?;
+                println!("pc start: {:#x}", exe.pc_start);
+                println!("instructions: {}", exe.program.num_defined_instructions());
+                println!("initial memory cells: {}", exe.init_memory.len());
+            }
+            ArtifactKind::AppVerifyingKey => {
+                let (_, app_vk): (_, AppVerifyingKey) = decode_artifact(header.kind, &bytes)?;
+                println!("fri params: {:?}", app_vk.fri_params);
+                println!("airs: {}", app_vk.app_vm_vk.per_air.len());
+            }
+            ArtifactKind::AppProof => {
+                let (_, proof): (_, ContinuationVmProof<SC>) =
+                    decode_artifact(header.kind, &bytes)?;
+                println!("segments: {}", proof.per_segment.len());
+                println!(
+                    "user public values: {}",
+                    proof.user_public_values.public_values.len()
+                );
+                println!(
+                    "user public values commit: {:?}",
+                    proof.user_public_values.public_values_commit
+                );
+            }
+            ArtifactKind::EvmProof => {
+                let (_, proof): (_, EvmProof) = decode_artifact(header.kind, &bytes)?;
+                println!(
+                    "instances: {}",
+                    proof.instances.iter().map(|i| i.len()).sum::<usize>()
+                );
+                println!("proof size: {} bytes", proof.proof.len());
+            }
+            ArtifactKind::RootVerifierInput => {
+                let (_, input): (_, RootVmVerifierInput<SC>) =
+                    decode_artifact(header.kind, &bytes)?;
+                println!("proofs: {}", input.proofs.len());
+                println!("user public values: {}", input.public_values.len());
+            }
+            // Proving keys are too large to decode just for a summary.
+            ArtifactKind::AppProvingKey
+            | ArtifactKind::AggProvingKey
+            | ArtifactKind::AppExecutionCommit
+            | ArtifactKind::Object => {}
+        }
+        Ok(())
+    }
+}
+
+fn print_json(kind: ArtifactKind, bytes: &[u8]) -> Result<()> {
+    let json = match kind {
+        ArtifactKind::AppVerifyingKey => {
+            let (_, app_vk): (_, AppVerifyingKey) = decode_artifact(kind, bytes)?;
+            serde_json::to_string_pretty(&AppVerifyingKeyJson::from(&app_vk))?
+        }
+        ArtifactKind::AppProof => {
+            let (_, proof): (_, ContinuationVmProof<SC>) = decode_artifact(kind, bytes)?;
+            serde_json::to_string_pretty(&ContinuationVmProofJson::from(&proof))?
+        }
+        ArtifactKind::RootVerifierInput => {
+            let (_, input): (_, RootVmVerifierInput<SC>) = decode_artifact(kind, bytes)?;
+            serde_json::to_string_pretty(&RootVerifierInputJson::from(&input))?
+        }
+        ArtifactKind::EvmProof => {
+            let (_, proof): (_, EvmProof) = decode_artifact(kind, bytes)?;
+            serde_json::to_string_pretty(&EvmProofJson::from(&proof))?
+        }
+        _ => eyre::bail!("artifacts of kind `{kind}` have no JSON export"),
+    };
+    println!("{json}");
+    Ok(())
+}
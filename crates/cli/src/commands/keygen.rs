@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use eyre::Result;
 use openvm_sdk::{
-    fs::{write_app_pk_to_file, write_app_vk_to_file},
+    fs::{write_artifact_to_file, ArtifactKind, ArtifactOptions, Compression},
     Sdk,
 };
 
@@ -33,14 +33,29 @@ pub struct KeygenCmd {
         default_value = DEFAULT_APP_VK_PATH
     )]
     vk_output: PathBuf,
+
+    #[arg(long, action, help = "Compress the output keys with zstd")]
+    compress: bool,
 }
 
 impl KeygenCmd {
     pub fn run(&self) -> Result<()> {
         let app_config = read_config_toml_or_default(&self.config)?;
         let app_pk = Sdk.app_keygen(app_config)?;
-        write_app_vk_to_file(app_pk.get_app_vk(), &self.vk_output)?;
-        write_app_pk_to_file(app_pk, &self.output)?;
+        let options = ArtifactOptions::default()
+            .with_config_hash(app_pk.config_hash())
+            .with_compression(if self.compress {
+                Compression::Zstd
+            } else {
+                Compression::None
+            });
+        write_artifact_to_file(
+            ArtifactKind::AppVerifyingKey,
+            &self.vk_output,
+            &app_pk.get_app_vk(),
+            &options,
+        )?;
+        write_artifact_to_file(ArtifactKind::AppProvingKey, &self.output, &app_pk, &options)?;
         Ok(())
     }
 }
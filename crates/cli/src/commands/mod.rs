@@ -1,6 +1,21 @@
 mod build;
 pub use build::*;
 
+mod commit;
+pub use commit::*;
+
+mod disasm;
+pub use disasm::*;
+
+mod estimate;
+pub use estimate::*;
+
+mod init;
+pub use init::*;
+
+mod inspect;
+pub use inspect::*;
+
 mod keygen;
 pub use keygen::*;
 
@@ -13,5 +28,8 @@ pub use run::*;
 mod setup;
 pub use setup::*;
 
+mod test;
+pub use test::*;
+
 mod verify;
 pub use verify::*;
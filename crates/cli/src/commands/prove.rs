@@ -7,8 +7,8 @@ use openvm_sdk::{
     commit::AppExecutionCommit,
     config::SdkVmConfig,
     fs::{
-        read_agg_pk_from_file, read_app_pk_from_file, read_exe_from_file, write_app_proof_to_file,
-        write_evm_proof_to_file,
+        read_agg_pk_from_file, read_app_pk_from_file, read_exe_from_file, write_artifact_to_file,
+        AppExecutionCommitJson, ArtifactKind, ArtifactOptions,
     },
     keygen::AppProvingKey,
     NonRootCommittedExe, Sdk, StdIn,
@@ -19,7 +19,7 @@ use crate::{
         DEFAULT_AGG_PK_PATH, DEFAULT_APP_EXE_PATH, DEFAULT_APP_PK_PATH, DEFAULT_APP_PROOF_PATH,
         DEFAULT_EVM_PROOF_PATH, DEFAULT_PARAMS_DIR,
     },
-    util::{read_to_stdin, Input},
+    util::{read_to_stdin, Input, KeyedInput, SegmentationArgs, VfsArgs},
 };
 
 #[derive(Parser)]
@@ -41,6 +41,19 @@ enum ProveSubCommand {
         #[arg(long, value_parser, help = "Input to OpenVM program")]
         input: Option<Input>,
 
+        #[arg(
+            long = "keyed-input",
+            value_parser,
+            help = "Keyed hint as <hex_key>=<hex_value | path>, can be repeated"
+        )]
+        keyed_inputs: Vec<KeyedInput>,
+
+        #[clap(flatten)]
+        segmentation: SegmentationArgs,
+
+        #[clap(flatten)]
+        vfs: VfsArgs,
+
         #[arg(long, action, help = "Path to output proof", default_value = DEFAULT_APP_PROOF_PATH)]
         output: PathBuf,
     },
@@ -54,6 +67,19 @@ enum ProveSubCommand {
         #[arg(long, value_parser, help = "Input to OpenVM program")]
         input: Option<Input>,
 
+        #[arg(
+            long = "keyed-input",
+            value_parser,
+            help = "Keyed hint as <hex_key>=<hex_value | path>, can be repeated"
+        )]
+        keyed_inputs: Vec<KeyedInput>,
+
+        #[clap(flatten)]
+        segmentation: SegmentationArgs,
+
+        #[clap(flatten)]
+        vfs: VfsArgs,
+
         #[arg(long, action, help = "Path to output proof", default_value = DEFAULT_EVM_PROOF_PATH)]
         output: PathBuf,
     },
@@ -66,27 +92,37 @@ impl ProveCmd {
                 app_pk,
                 exe,
                 input,
+                keyed_inputs,
                 output,
+                segmentation,
+                vfs,
             } => {
-                let (app_pk, committed_exe, input) = Self::prepare_execution(app_pk, exe, input)?;
+                let (app_pk, committed_exe, input) =
+                    Self::prepare_execution(app_pk, exe, input, keyed_inputs, segmentation, vfs)?;
+                let options = ArtifactOptions::default().with_config_hash(app_pk.config_hash());
                 let app_proof = Sdk.generate_app_proof(app_pk, committed_exe, input)?;
-                write_app_proof_to_file(app_proof, output)?;
+                write_artifact_to_file(ArtifactKind::AppProof, output, &app_proof, &options)?;
             }
             ProveSubCommand::Evm {
                 app_pk,
                 exe,
                 input,
+                keyed_inputs,
                 output,
+                segmentation,
+                vfs,
             } => {
                 let params_reader = CacheHalo2ParamsReader::new(DEFAULT_PARAMS_DIR);
-                let (app_pk, committed_exe, input) = Self::prepare_execution(app_pk, exe, input)?;
+                let (app_pk, committed_exe, input) =
+                    Self::prepare_execution(app_pk, exe, input, keyed_inputs, segmentation, vfs)?;
                 println!("Generating EVM proof, this may take a lot of compute and memory...");
                 let agg_pk = read_agg_pk_from_file(DEFAULT_AGG_PK_PATH).map_err(|e| {
                     eyre::eyre!("Failed to read aggregation proving key: {}\nPlease run 'cargo openvm setup' first", e)
                 })?;
+                let options = ArtifactOptions::default().with_config_hash(app_pk.config_hash());
                 let evm_proof =
                     Sdk.generate_evm_proof(&params_reader, app_pk, committed_exe, agg_pk, input)?;
-                write_evm_proof_to_file(evm_proof, output)?;
+                write_artifact_to_file(ArtifactKind::EvmProof, output, &evm_proof, &options)?;
             }
         }
         Ok(())
@@ -96,12 +132,23 @@ impl ProveCmd {
         app_pk: &PathBuf,
         exe: &PathBuf,
         input: &Option<Input>,
+        keyed_inputs: &[KeyedInput],
+        segmentation: &SegmentationArgs,
+        vfs: &VfsArgs,
     ) -> Result<(
         Arc<AppProvingKey<SdkVmConfig>>,
         Arc<NonRootCommittedExe>,
         StdIn,
     )> {
-        let app_pk: Arc<AppProvingKey<SdkVmConfig>> = Arc::new(read_app_pk_from_file(app_pk)?);
+        let mut app_pk: AppProvingKey<SdkVmConfig> = read_app_pk_from_file(app_pk)?;
+        if let Some(strategy) = segmentation.to_config(app_pk.app_fri_params().log_blowup) {
+            Arc::make_mut(&mut app_pk.app_vm_pk)
+                .vm_config
+                .system
+                .config
+                .set_segmentation_strategy(strategy);
+        }
+        let app_pk = Arc::new(app_pk);
         let app_exe = read_exe_from_file(exe)?;
         let committed_exe = Sdk.commit_app_exe(app_pk.app_fri_params(), app_exe)?;
 
@@ -110,10 +157,11 @@ impl ProveCmd {
             &committed_exe,
             &app_pk.leaf_committed_exe,
         );
-        println!("app_pk commit: {:?}", commits.app_config_commit_to_bn254());
-        println!("exe commit: {:?}", commits.exe_commit_to_bn254());
+        let commits = AppExecutionCommitJson::from(&commits);
+        println!("app_pk commit: {}", commits.leaf_vm_verifier_commit_bn254);
+        println!("exe commit: {}", commits.exe_commit_bn254);
 
-        let input = read_to_stdin(input)?;
+        let input = read_to_stdin(input, keyed_inputs, vfs, exe)?;
         Ok((app_pk, committed_exe, input))
     }
 }
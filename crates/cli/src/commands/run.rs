@@ -1,12 +1,14 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use eyre::Result;
-use openvm_sdk::{fs::read_exe_from_file, Sdk};
+use openvm_sdk::{fs::read_exe_from_file, public_values_to_bytes, Sdk};
 
 use crate::{
     default::{DEFAULT_APP_CONFIG_PATH, DEFAULT_APP_EXE_PATH},
-    util::{read_config_toml_or_default, read_to_stdin, Input},
+    util::{
+        read_config_toml_or_default, read_to_stdin, Input, KeyedInput, SegmentationArgs, VfsArgs,
+    },
 };
 
 #[derive(Parser)]
@@ -20,14 +22,72 @@ pub struct RunCmd {
 
     #[clap(long, value_parser, help = "Input to OpenVM program")]
     input: Option<Input>,
+
+    #[clap(
+        long = "keyed-input",
+        value_parser,
+        help = "Keyed hint as <hex_key>=<hex_value | path>, can be repeated"
+    )]
+    keyed_inputs: Vec<KeyedInput>,
+
+    #[clap(
+        long,
+        value_enum,
+        help = "Format to print the public output committed by the program",
+        default_value = "hex"
+    )]
+    output_format: OutputFormat,
+
+    #[clap(flatten)]
+    segmentation: SegmentationArgs,
+
+    #[clap(flatten)]
+    vfs: VfsArgs,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// Hex string of the output bytes
+    Hex,
+    /// Output bytes as a UTF-8 string
+    Utf8,
+    /// Output as little-endian u32 words, as published by `openvm::io::reveal`
+    Words,
 }
 
 impl RunCmd {
     pub fn run(&self) -> Result<()> {
         let exe = read_exe_from_file(&self.exe)?;
-        let app_config = read_config_toml_or_default(&self.config)?;
-        let output = Sdk.execute(exe, app_config.app_vm_config, read_to_stdin(&self.input)?)?;
-        println!("Execution output: {:?}", output);
+        let mut app_config = read_config_toml_or_default(&self.config)?;
+        self.segmentation.apply(&mut app_config);
+        let output = Sdk.execute(
+            exe,
+            app_config.effective_app_vm_config(),
+            read_to_stdin(&self.input, &self.keyed_inputs, &self.vfs, &self.exe)?,
+        )?;
+        let mut bytes = public_values_to_bytes(&output)?;
+        // Unused public values are zero. Trim them word by word.
+        while bytes.len() >= 4 && bytes[bytes.len() - 4..] == [0; 4] {
+            bytes.truncate(bytes.len() - 4);
+        }
+        match self.output_format {
+            OutputFormat::Hex => println!("Execution output: 0x{}", hex::encode(&bytes)),
+            OutputFormat::Utf8 => println!(
+                "Execution output: {}",
+                String::from_utf8_lossy(&bytes).trim_end_matches('\0')
+            ),
+            OutputFormat::Words => {
+                let words: Vec<u32> = bytes
+                    .chunks(4)
+                    .map(|chunk| {
+                        let mut word = [0u8; 4];
+                        word[..chunk.len()].copy_from_slice(chunk);
+                        u32::from_le_bytes(word)
+                    })
+                    .collect();
+                println!("Execution output: {:?}", words);
+            }
+        }
         Ok(())
     }
 }
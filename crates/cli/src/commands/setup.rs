@@ -6,7 +6,7 @@ use std::{
 use aws_config::{defaults, BehaviorVersion, Region};
 use aws_sdk_s3::Client;
 use clap::Parser;
-use eyre::{eyre, Result};
+use eyre::Result;
 use openvm_native_recursion::halo2::utils::CacheHalo2ParamsReader;
 use openvm_sdk::{
     config::AggConfig,
@@ -15,7 +15,10 @@ use openvm_sdk::{
     Sdk,
 };
 
-use crate::default::{DEFAULT_AGG_PK_PATH, DEFAULT_PARAMS_DIR, DEFAULT_VERIFIER_PATH};
+use crate::default::{
+    DEFAULT_AGG_PK_PATH, DEFAULT_HALO2_VERIFIER_SOL_PATH, DEFAULT_OPENVM_VERIFIER_SOL_PATH,
+    DEFAULT_PARAMS_DIR, DEFAULT_VERIFIER_PATH,
+};
 
 #[derive(Parser)]
 #[command(
@@ -26,15 +29,14 @@ pub struct EvmProvingSetupCmd {}
 
 impl EvmProvingSetupCmd {
     pub async fn run(&self) -> Result<()> {
+        let solc_installed = Self::check_solc_installed();
         if PathBuf::from(DEFAULT_AGG_PK_PATH).exists()
-            && PathBuf::from(DEFAULT_VERIFIER_PATH).exists()
+            && PathBuf::from(DEFAULT_HALO2_VERIFIER_SOL_PATH).exists()
+            && PathBuf::from(DEFAULT_OPENVM_VERIFIER_SOL_PATH).exists()
+            && (!solc_installed || PathBuf::from(DEFAULT_VERIFIER_PATH).exists())
         {
             println!("Aggregation proving key and verifier contract already exist");
             return Ok(());
-        } else if !Self::check_solc_installed() {
-            return Err(eyre!(
-                "solc is not installed, please install solc to continue"
-            ));
         }
 
         Self::download_params(10, 24).await?;
@@ -44,15 +46,36 @@ impl EvmProvingSetupCmd {
         println!("Generating proving key...");
         let agg_pk = Sdk.agg_keygen(agg_config, &params_reader, None::<&RootVerifierProvingKey>)?;
 
-        println!("Generating verifier contract...");
-        let verifier = Sdk.generate_snark_verifier_contract(&params_reader, &agg_pk)?;
+        println!("Generating verifier contract source...");
+        let source = Sdk.generate_snark_verifier_solidity(&params_reader, &agg_pk)?;
+        Self::write_source(DEFAULT_HALO2_VERIFIER_SOL_PATH, &source.halo2_verifier_code)?;
+        Self::write_source(
+            DEFAULT_OPENVM_VERIFIER_SOL_PATH,
+            &source.openvm_verifier_code,
+        )?;
+
+        if solc_installed {
+            println!("Compiling verifier contract...");
+            let verifier = Sdk.generate_snark_verifier_contract(&params_reader, &agg_pk)?;
+            write_evm_verifier_to_file(verifier, DEFAULT_VERIFIER_PATH)?;
+        } else {
+            println!(
+                "solc is not installed, skipping compilation of the verifier contract. \
+                 Install solc and run this command again to use 'cargo openvm verify evm'"
+            );
+        }
 
         println!("Writing proving key to file...");
         write_agg_pk_to_file(agg_pk, DEFAULT_AGG_PK_PATH)?;
 
-        println!("Writing verifier contract to file...");
-        write_evm_verifier_to_file(verifier, DEFAULT_VERIFIER_PATH)?;
+        Ok(())
+    }
 
+    fn write_source(path: &str, code: &str) -> Result<()> {
+        if let Some(parent) = PathBuf::from(path).parent() {
+            create_dir_all(parent)?;
+        }
+        write(path, code)?;
         Ok(())
     }
 
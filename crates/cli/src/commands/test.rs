@@ -0,0 +1,313 @@
+use std::{
+    fs::read,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use clap::Parser;
+use eyre::Result;
+use openvm_build::{build_guest_tests, get_package, GuestOptions};
+use openvm_circuit::arch::{instructions::exe::VmExe, ExecutionError, VmConfig, VmExecutor};
+use openvm_sdk::{
+    config::{AppConfig, SdkVmConfig},
+    decode_public_values,
+    keygen::AppProvingKey,
+    NonRootCommittedExe, Sdk, StdIn, F,
+};
+use openvm_transpiler::{elf::Elf, openvm_platform::memory::MEM_SIZE};
+
+use crate::{
+    default::{DEFAULT_APP_CONFIG_PATH, DEFAULT_MANIFEST_DIR},
+    util::read_config_toml_or_default,
+};
+
+/// Input that makes the guest test runner commit the test descriptions, see
+/// `openvm::testing::LIST_TESTS`.
+const LIST_TESTS: u32 = u32::MAX;
+/// Number of public values available to the guest test runner to list the tests.
+const LIST_NUM_PUBLIC_VALUES: usize = 1 << 14;
+
+#[derive(Parser)]
+#[command(
+    name = "test",
+    about = "Build the tests of a guest package and run each of them inside the VM"
+)]
+pub struct TestCmd {
+    #[arg(help = "Only run tests whose name contains this string")]
+    filter: Option<String>,
+
+    #[arg(
+        long,
+        help = "Path to the directory containing the Cargo.toml file for the guest code (relative to the current directory)",
+        default_value = DEFAULT_MANIFEST_DIR
+    )]
+    manifest_dir: PathBuf,
+
+    #[arg(long, help = "Path to the target directory")]
+    target_dir: Option<PathBuf>,
+
+    #[arg(long, value_delimiter = ',', help = "Feature flags passed to cargo")]
+    features: Vec<String>,
+
+    #[arg(long, default_value = "release", help = "Build profile")]
+    profile: String,
+
+    #[arg(
+        long,
+        help = "Size of the guest stack in bytes, must be a multiple of 16 [default: 2 MiB]"
+    )]
+    stack_size: Option<u32>,
+
+    #[arg(
+        long,
+        default_value = DEFAULT_APP_CONFIG_PATH,
+        help = "Path to the SDK config .toml file that specifies the VM extensions"
+    )]
+    config: PathBuf,
+
+    #[arg(
+        long,
+        action,
+        help = "Only match tests whose name is exactly the filter"
+    )]
+    exact: bool,
+
+    #[arg(long, action, help = "Also run ignored tests")]
+    include_ignored: bool,
+
+    #[arg(long, action, help = "List the tests instead of running them")]
+    list: bool,
+
+    #[arg(
+        long,
+        action,
+        help = "Generate and verify an app proof of each passing test that should not panic"
+    )]
+    prove: bool,
+}
+
+/// Description of a guest test, as committed by the guest test runner.
+struct TestDesc {
+    index: u32,
+    name: String,
+    ignore: bool,
+    /// Expected panic message, empty if any message is expected.
+    should_panic: Option<String>,
+}
+
+enum TestOutcome {
+    Passed { cycles: Option<usize> },
+    Failed(String),
+    Ignored,
+}
+
+impl TestCmd {
+    pub fn run(&self) -> Result<()> {
+        println!("[openvm] Building the tests...");
+        let mut guest_options = GuestOptions::default()
+            .with_features(self.features.clone())
+            .with_profile(self.profile.clone());
+        guest_options.target_dir = self.target_dir.clone();
+        guest_options.stack_size = self.stack_size;
+        let pkg = get_package(&self.manifest_dir);
+        let test_binaries = match build_guest_tests(&pkg, &guest_options) {
+            Ok(test_binaries) => test_binaries,
+            Err(None) => return Err(eyre::eyre!("Failed to build guest tests")),
+            Err(Some(code)) => {
+                return Err(eyre::eyre!("Failed to build guest tests: code = {}", code))
+            }
+        };
+
+        let app_config = read_config_toml_or_default(&self.config)?;
+        let app_pk = if self.prove && !self.list {
+            println!("[openvm] Generating app proving key...");
+            Some(Arc::new(Sdk.app_keygen(app_config.clone())?))
+        } else {
+            None
+        };
+
+        let mut num_failed = 0;
+        for test_binary in test_binaries {
+            println!("     Running {}", test_binary.display());
+            num_failed += self.run_test_binary(&test_binary, &app_config, app_pk.clone())?;
+        }
+        if num_failed > 0 {
+            return Err(eyre::eyre!("{} test(s) failed", num_failed));
+        }
+        Ok(())
+    }
+
+    /// Runs the tests of one test binary and returns the number of failed tests.
+    fn run_test_binary(
+        &self,
+        test_binary: &Path,
+        app_config: &AppConfig<SdkVmConfig>,
+        app_pk: Option<Arc<AppProvingKey<SdkVmConfig>>>,
+    ) -> Result<usize> {
+        let elf = Elf::decode(&read(test_binary)?, MEM_SIZE as u32)?;
+        let exe = Sdk.transpile(elf, app_config.app_vm_config.transpiler())?;
+        let vm_config = app_config.effective_app_vm_config();
+
+        let tests = list_tests(exe.clone(), vm_config.clone())?;
+        let total = tests.len();
+        let tests: Vec<_> = tests
+            .into_iter()
+            .filter(|test| self.matches(&test.name))
+            .collect();
+        let filtered_out = total - tests.len();
+
+        if self.list {
+            for test in &tests {
+                println!("{}: test", test.name);
+            }
+            println!();
+            println!("{} tests, 0 benchmarks", tests.len());
+            return Ok(0);
+        }
+
+        let committed_exe = app_pk
+            .as_ref()
+            .map(|app_pk| Sdk.commit_app_exe(app_pk.app_fri_params(), exe.clone()))
+            .transpose()?;
+
+        let start = Instant::now();
+        println!();
+        println!("running {} tests", tests.len());
+        let executor = VmExecutor::<F, _>::new(vm_config);
+        let (mut passed, mut ignored) = (0, 0);
+        let mut failures = vec![];
+        for test in &tests {
+            let outcome = if test.ignore && !self.include_ignored {
+                TestOutcome::Ignored
+            } else {
+                let outcome = run_test(&executor, exe.clone(), test);
+                match (&outcome, &app_pk, &committed_exe) {
+                    (TestOutcome::Passed { .. }, Some(app_pk), Some(committed_exe))
+                        if test.should_panic.is_none() =>
+                    {
+                        match prove_test(app_pk.clone(), committed_exe.clone(), test) {
+                            Ok(()) => outcome,
+                            Err(err) => TestOutcome::Failed(format!("proving failed: {err}")),
+                        }
+                    }
+                    _ => outcome,
+                }
+            };
+            match outcome {
+                TestOutcome::Passed {
+                    cycles: Some(cycles),
+                } => {
+                    passed += 1;
+                    println!("test {} ... ok <{} cycles>", test.name, cycles);
+                }
+                TestOutcome::Passed { cycles: None } => {
+                    passed += 1;
+                    println!("test {} ... ok", test.name);
+                }
+                TestOutcome::Ignored => {
+                    ignored += 1;
+                    println!("test {} ... ignored", test.name);
+                }
+                TestOutcome::Failed(reason) => {
+                    println!("test {} ... FAILED", test.name);
+                    failures.push((test.name.clone(), reason));
+                }
+            }
+        }
+
+        if !failures.is_empty() {
+            println!();
+            println!("failures:");
+            for (name, reason) in &failures {
+                println!();
+                println!("---- {} stdout ----", name);
+                println!("{}", reason);
+            }
+            println!();
+            println!("failures:");
+            for (name, _) in &failures {
+                println!("    {}", name);
+            }
+        }
+        println!();
+        println!(
+            "test result: {}. {} passed; {} failed; {} ignored; 0 measured; {} filtered out; finished in {:.2}s",
+            if failures.is_empty() { "ok" } else { "FAILED" },
+            passed,
+            failures.len(),
+            ignored,
+            filtered_out,
+            start.elapsed().as_secs_f64()
+        );
+        println!();
+        Ok(failures.len())
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match &self.filter {
+            None => true,
+            Some(filter) if self.exact => name == filter,
+            Some(filter) => name.contains(filter.as_str()),
+        }
+    }
+}
+
+fn test_input(command: u32) -> StdIn {
+    let mut stdin = StdIn::default();
+    stdin.write(&command);
+    stdin
+}
+
+/// Runs the guest test runner in list mode and decodes the test descriptions it commits.
+fn list_tests(exe: VmExe<F>, mut vm_config: SdkVmConfig) -> Result<Vec<TestDesc>> {
+    let system = vm_config.system_mut();
+    system.num_public_values = system.num_public_values.max(LIST_NUM_PUBLIC_VALUES);
+    let public_values = Sdk.execute(exe, vm_config, test_input(LIST_TESTS))?;
+    let descs: Vec<(String, bool, Option<String>)> = decode_public_values(&public_values)?;
+    Ok(descs
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, ignore, should_panic))| TestDesc {
+            index: index as u32,
+            name,
+            ignore,
+            should_panic,
+        })
+        .collect())
+}
+
+fn run_test(executor: &VmExecutor<F, SdkVmConfig>, exe: VmExe<F>, test: &TestDesc) -> TestOutcome {
+    let result = executor.execute_with_cycle_count(exe, test_input(test.index));
+    match (result, &test.should_panic) {
+        (Ok((_, cycles)), None) => TestOutcome::Passed {
+            cycles: Some(cycles),
+        },
+        (Ok(_), Some(_)) => TestOutcome::Failed("note: test did not panic as expected".into()),
+        (Err(ExecutionError::GuestPanic { message, .. }), Some(expected))
+            if message.contains(expected.as_str()) =>
+        {
+            TestOutcome::Passed { cycles: None }
+        }
+        (Err(ExecutionError::GuestPanic { message, .. }), Some(expected)) => {
+            TestOutcome::Failed(format!(
+                "panic did not contain expected string\n      panic message: `{message:?}`,\n expected substring: `{expected:?}`"
+            ))
+        }
+        (Err(ExecutionError::FailedWithExitCode(_)), Some(expected)) if expected.is_empty() => {
+            TestOutcome::Passed { cycles: None }
+        }
+        (Err(err), _) => TestOutcome::Failed(err.to_string()),
+    }
+}
+
+fn prove_test(
+    app_pk: Arc<AppProvingKey<SdkVmConfig>>,
+    committed_exe: Arc<NonRootCommittedExe>,
+    test: &TestDesc,
+) -> Result<()> {
+    let app_vk = app_pk.get_app_vk();
+    let proof = Sdk.generate_app_proof(app_pk, committed_exe, test_input(test.index))?;
+    Sdk.verify_app_proof(&app_vk, &proof)?;
+    Ok(())
+}
@@ -4,8 +4,8 @@ use clap::Parser;
 use eyre::Result;
 use openvm_sdk::{
     fs::{
-        read_app_proof_from_file, read_app_vk_from_file, read_evm_proof_from_file,
-        read_evm_verifier_from_file,
+        read_artifact_from_file, read_evm_proof_from_file, read_evm_verifier_from_file,
+        ArtifactKind,
     },
     Sdk,
 };
@@ -40,8 +40,15 @@ impl VerifyCmd {
     pub fn run(&self) -> Result<()> {
         match &self.command {
             VerifySubCommand::App { app_vk, proof } => {
-                let app_vk = read_app_vk_from_file(app_vk)?;
-                let app_proof = read_app_proof_from_file(proof)?;
+                let (vk_header, app_vk) =
+                    read_artifact_from_file(ArtifactKind::AppVerifyingKey, app_vk)?;
+                let (proof_header, app_proof) =
+                    read_artifact_from_file(ArtifactKind::AppProof, proof)?;
+                if let Some(vk_config_hash) = vk_header.config_hash {
+                    proof_header.check_config_hash(vk_config_hash).map_err(|e| {
+                        eyre::eyre!("{e}\nThe proof was generated with a different app config than the verifying key")
+                    })?;
+                }
                 Sdk.verify_app_proof(&app_vk, &app_proof)?;
             }
             VerifySubCommand::Evm { proof } => {
@@ -5,6 +5,10 @@ pub const DEFAULT_MANIFEST_DIR: &str = ".";
 
 pub const DEFAULT_AGG_PK_PATH: &str = concat!(env!("HOME"), "/.openvm/agg.pk");
 pub const DEFAULT_VERIFIER_PATH: &str = concat!(env!("HOME"), "/.openvm/verifier.sol");
+pub const DEFAULT_HALO2_VERIFIER_SOL_PATH: &str =
+    concat!(env!("HOME"), "/.openvm/halo2/Halo2Verifier.sol");
+pub const DEFAULT_OPENVM_VERIFIER_SOL_PATH: &str =
+    concat!(env!("HOME"), "/.openvm/halo2/OpenVmHalo2Verifier.sol");
 pub const DEFAULT_PARAMS_DIR: &str = concat!(env!("HOME"), "/.openvm/params/");
 
 pub const DEFAULT_APP_CONFIG_PATH: &str = "./openvm.toml";
@@ -31,5 +35,6 @@ pub fn default_app_config() -> AppConfig<SdkVmConfig> {
         )
         .into(),
         compiler_options: Default::default(),
+        segmentation_strategy: None,
     }
 }
@@ -4,7 +4,12 @@ use std::{
     str::FromStr,
 };
 
+use clap::Parser;
 use eyre::Result;
+use openvm_circuit::arch::{
+    CostModelSegmentationStrategy, DefaultSegmentationStrategy, SegmentCostModel,
+    SegmentationStrategyConfig,
+};
 use openvm_sdk::{
     config::{AppConfig, SdkVmConfig},
     StdIn,
@@ -47,6 +52,103 @@ impl FromStr for Input {
     }
 }
 
+/// A keyed hint given as `<hex_key>=<hex_value | path>`, read by the guest with
+/// `openvm::io::hint_load_by_key`.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyedInput {
+    pub key: Vec<u8>,
+    pub value: Input,
+}
+
+impl FromStr for KeyedInput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| "Keyed input must be <hex_key>=<hex_value | path>.".to_string())?;
+        if !is_valid_hex_string(key) {
+            return Err("Key of keyed input must be a hex string.".to_string());
+        }
+        let key = hex::decode(key.strip_prefix("0x").unwrap_or(key)).map_err(|e| e.to_string())?;
+        Ok(KeyedInput {
+            key,
+            value: value.parse()?,
+        })
+    }
+}
+
+/// A `<name>=<value>` pair given on the command line.
+#[derive(Debug, Clone)]
+pub(crate) struct NameValue {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for NameValue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s
+            .split_once('=')
+            .ok_or_else(|| "Expected <name>=<value>.".to_string())?;
+        Ok(NameValue {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Arguments, environment variables and files of the guest, read with `openvm::fs` or, with the
+/// `std` feature, `std::env` and `std::io::stdin`.
+#[derive(Clone, Default, Parser)]
+pub(crate) struct VfsArgs {
+    #[arg(
+        long = "file",
+        value_parser,
+        help = "Virtual file of the guest as <name>=<path>, can be repeated"
+    )]
+    pub files: Vec<NameValue>,
+
+    #[arg(
+        long = "env",
+        value_parser,
+        help = "Environment variable of the guest as <name>=<value>, can be repeated"
+    )]
+    pub envs: Vec<NameValue>,
+
+    #[arg(
+        long,
+        help = "Path to a file to use as the standard input of the guest"
+    )]
+    pub stdin_file: Option<PathBuf>,
+
+    #[arg(
+        last = true,
+        help = "Arguments of the guest, after the program name given by the executable path"
+    )]
+    pub args: Vec<String>,
+}
+
+impl VfsArgs {
+    /// Adds the virtual file system to `stdin`, with `program` as the first argument.
+    pub fn apply(&self, stdin: &mut StdIn, program: &Path) -> Result<()> {
+        for NameValue { name, value } in &self.files {
+            stdin.add_file(name, &read(value)?);
+        }
+        for NameValue { name, value } in &self.envs {
+            stdin.set_env(name, value);
+        }
+        if let Some(stdin_file) = &self.stdin_file {
+            stdin.set_stdin(&read(stdin_file)?);
+        }
+        stdin.set_args(
+            std::iter::once(program.display().to_string()).chain(self.args.iter().cloned()),
+        );
+        Ok(())
+    }
+}
+
 pub(crate) fn is_valid_hex_string(s: &str) -> bool {
     if s.len() % 2 != 0 {
         return false;
@@ -62,14 +164,27 @@ pub(crate) fn read_to_struct_toml<T: DeserializeOwned>(path: &PathBuf) -> Result
     Ok(ret)
 }
 
-pub(crate) fn read_to_stdin(input: &Option<Input>) -> Result<StdIn> {
+pub(crate) fn read_to_stdin(
+    input: &Option<Input>,
+    keyed_inputs: &[KeyedInput],
+    vfs: &VfsArgs,
+    program: &Path,
+) -> Result<StdIn> {
+    let mut stdin = match input {
+        Some(input) => StdIn::from_bytes(&read_input_bytes(input)?),
+        None => StdIn::default(),
+    };
+    for KeyedInput { key, value } in keyed_inputs {
+        stdin.add_keyed(key, &read_input_bytes(value)?);
+    }
+    vfs.apply(&mut stdin, program)?;
+    Ok(stdin)
+}
+
+fn read_input_bytes(input: &Input) -> Result<Vec<u8>> {
     match input {
-        Some(Input::FilePath(path)) => {
-            let bytes = read(path)?;
-            Ok(StdIn::from_bytes(&bytes))
-        }
-        Some(Input::HexBytes(bytes)) => Ok(StdIn::from_bytes(bytes)),
-        None => Ok(StdIn::default()),
+        Input::FilePath(path) => Ok(read(path)?),
+        Input::HexBytes(bytes) => Ok(bytes.clone()),
     }
 }
 
@@ -84,3 +199,60 @@ pub(crate) fn read_config_toml_or_default(config: &PathBuf) -> Result<AppConfig<
         Ok(default_app_config())
     }
 }
+
+/// Command line overrides of the segmentation strategy of the app VM.
+#[derive(Clone, Default, Parser)]
+pub(crate) struct SegmentationArgs {
+    #[arg(long, help = "Maximum trace height of any chip in a segment")]
+    pub max_segment_len: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Target for the estimated peak prover memory of each segment, in MiB"
+    )]
+    pub max_segment_memory_mb: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Target for the estimated proving time of each segment, in milliseconds"
+    )]
+    pub max_segment_proving_time_ms: Option<u64>,
+}
+
+impl SegmentationArgs {
+    /// Returns the segmentation strategy described by the arguments, if any of them is set.
+    /// The cost model based strategy is used whenever a memory or time target is given.
+    pub fn to_config(&self, app_log_blowup: usize) -> Option<SegmentationStrategyConfig> {
+        if self.max_segment_memory_mb.is_some() || self.max_segment_proving_time_ms.is_some() {
+            let mut strategy =
+                CostModelSegmentationStrategy::default().with_cost_model(SegmentCostModel {
+                    log_blowup: app_log_blowup,
+                    ..Default::default()
+                });
+            if let Some(max_segment_len) = self.max_segment_len {
+                strategy = strategy.with_max_segment_len(max_segment_len);
+            }
+            if let Some(max_segment_memory_mb) = self.max_segment_memory_mb {
+                strategy = strategy.with_max_memory_bytes(max_segment_memory_mb << 20);
+            }
+            if let Some(max_segment_proving_time_ms) = self.max_segment_proving_time_ms {
+                strategy = strategy.with_max_proving_time_ms(max_segment_proving_time_ms);
+            }
+            Some(SegmentationStrategyConfig::CostModel(strategy))
+        } else {
+            self.max_segment_len.map(|max_segment_len| {
+                SegmentationStrategyConfig::Default(
+                    DefaultSegmentationStrategy::new_with_max_segment_len(max_segment_len),
+                )
+            })
+        }
+    }
+
+    /// Overrides the segmentation strategy of `app_config` if any of the arguments is set.
+    pub fn apply(&self, app_config: &mut AppConfig<SdkVmConfig>) {
+        let app_log_blowup = app_config.app_fri_params.fri_params.log_blowup;
+        if let Some(strategy) = self.to_config(app_log_blowup) {
+            app_config.segmentation_strategy = Some(strategy);
+        }
+    }
+}
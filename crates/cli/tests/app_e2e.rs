@@ -52,6 +52,19 @@ fn test_cli_app_e2e() -> Result<()> {
         ],
     )?;
 
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "estimate",
+            "--exe",
+            temp_exe.to_str().unwrap(),
+            "--config",
+            "example/openvm.toml",
+            "--json",
+        ],
+    )?;
+
     run_cmd(
         "cargo",
         &[
@@ -83,6 +96,75 @@ fn test_cli_app_e2e() -> Result<()> {
     Ok(())
 }
 
+/// Proofs generated with segmentation overrides must verify against the key of the unmodified
+/// config, since segmentation does not change the proof system.
+#[test]
+fn test_cli_app_e2e_segmentation_override() -> Result<()> {
+    let temp_dir = tempdir()?;
+    run_cmd("cargo", &["install", "--path", ".", "--force"])?;
+    let temp_exe = temp_dir.path().join("example.vmexe");
+    let temp_pk = temp_dir.path().join("example.pk");
+    let temp_vk = temp_dir.path().join("example.vk");
+    let temp_proof = temp_dir.path().join("example.apppf");
+
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "build",
+            "--manifest-dir",
+            "example",
+            "--config",
+            "example/openvm.toml",
+            "--exe-output",
+            temp_exe.to_str().unwrap(),
+        ],
+    )?;
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "keygen",
+            "--config",
+            "example/openvm.toml",
+            "--output",
+            temp_pk.to_str().unwrap(),
+            "--vk-output",
+            temp_vk.to_str().unwrap(),
+        ],
+    )?;
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "prove",
+            "app",
+            "--app-pk",
+            temp_pk.to_str().unwrap(),
+            "--exe",
+            temp_exe.to_str().unwrap(),
+            "--max-segment-len",
+            "65536",
+            "--output",
+            temp_proof.to_str().unwrap(),
+        ],
+    )?;
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "verify",
+            "app",
+            "--app-vk",
+            temp_vk.to_str().unwrap(),
+            "--proof",
+            temp_proof.to_str().unwrap(),
+        ],
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn test_cli_app_e2e_default_paths() -> Result<()> {
     run_cmd("cargo", &["install", "--path", ".", "--force"])?;
@@ -94,6 +176,24 @@ fn test_cli_app_e2e_default_paths() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_cli_test() -> Result<()> {
+    run_cmd("cargo", &["install", "--path", ".", "--force"])?;
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "test",
+            "--manifest-dir",
+            "example-tests",
+            "--config",
+            "example/openvm.toml",
+            "--include-ignored",
+        ],
+    )?;
+    Ok(())
+}
+
 fn run_cmd(program: &str, args: &[&str]) -> Result<()> {
     let package_dir = env::current_dir()?;
     let prefix = "[test cli e2e]";
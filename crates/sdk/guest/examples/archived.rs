@@ -0,0 +1,39 @@
+#![cfg_attr(target_os = "zkvm", no_main)]
+#![cfg_attr(target_os = "zkvm", no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use openvm::io::read_archived;
+
+openvm::entry!(main);
+
+/// Same type as the one written by the host with `StdIn::write_archived`.
+#[derive(rkyv::Archive, rkyv::Serialize)]
+pub struct Record {
+    pub id: u32,
+    pub name: String,
+    pub values: Vec<u64>,
+}
+
+/// Exits with code 1 if the input is not a valid archive, and with code 2 if it does not hold
+/// the expected record.
+pub fn main() {
+    let Ok(record) = read_archived::<Record>() else {
+        openvm::process::exit(1);
+        return;
+    };
+    let expected_values = (0..100u64).map(|i| i * i);
+    if record.id.to_native() != 7
+        || record.name.as_str() != "openvm"
+        || record.values.len() != 100
+        || record
+            .values
+            .iter()
+            .zip(expected_values)
+            .any(|(v, expected)| v.to_native() != expected)
+    {
+        openvm::process::exit(2);
+    }
+}
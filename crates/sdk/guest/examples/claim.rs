@@ -0,0 +1,21 @@
+#![cfg_attr(target_os = "zkvm", no_main)]
+#![cfg_attr(target_os = "zkvm", no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::{
+    claim::{claim_openvm_proof, DIGEST_SIZE},
+    io::{commit, read},
+};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let vk_commit: [u32; DIGEST_SIZE] = read();
+    let exe_commit: [u32; DIGEST_SIZE] = read();
+    let public_values: Vec<u8> = read();
+    claim_openvm_proof(&vk_commit, &exe_commit, &public_values);
+    commit(&public_values.len());
+}
@@ -0,0 +1,19 @@
+#![cfg_attr(target_os = "zkvm", no_main)]
+#![cfg_attr(target_os = "zkvm", no_std)]
+
+use openvm::io::commit;
+
+openvm::entry!(main);
+
+pub fn main() {
+    let n: u64 = core::hint::black_box(10);
+    let mut a: u64 = 0;
+    let mut b: u64 = 1;
+    for _ in 0..n {
+        let sum = a + b;
+        a = b;
+        b = sum;
+    }
+    commit(&n);
+    commit(&(a, [1u8, 2, 3]));
+}
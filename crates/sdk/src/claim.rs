@@ -0,0 +1,86 @@
+pub use openvm::claim::ProofClaim;
+use openvm_circuit::arch::VmConfig;
+use openvm_native_compiler::ir::DIGEST_SIZE;
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    keygen::{AppProvingKey, AppVerifyingKey},
+    F,
+};
+
+#[derive(Error, Debug)]
+pub enum ProofClaimError {
+    #[error("expected {expected} proofs of claims, got {actual}")]
+    NumProofsMismatch { expected: usize, actual: usize },
+    #[error("proof of claim {index} does not verify: {source}")]
+    Verification {
+        index: usize,
+        source: openvm_circuit::arch::VmVerificationError,
+    },
+    #[error("claim {index} is for an App VM that is not trusted")]
+    UntrustedVkCommit { index: usize },
+    #[error("proof of claim {index} is for another executable")]
+    ExeCommitMismatch { index: usize },
+    #[error("proof of claim {index} has other public values than claimed")]
+    PublicValuesMismatch { index: usize },
+}
+
+/// An App VM whose proofs can resolve a [ProofClaim] recorded by a guest with
+/// `openvm::claim::claim_openvm_proof`.
+///
+/// The claim only names the App VM by `leaf_vm_verifier_commit`, which is not checked against
+/// `app_vk` here, so it must come from a source the verifier trusts, such as
+/// [TrustedAppVm::new] on its own proving key. It must never be taken from the prover.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TrustedAppVm {
+    pub app_vk: AppVerifyingKey,
+    /// Commitment of the leaf verifier program of the App VM of `app_vk`.
+    pub leaf_vm_verifier_commit: [F; DIGEST_SIZE],
+}
+
+impl TrustedAppVm {
+    pub fn new<VC: VmConfig<F>>(app_pk: &AppProvingKey<VC>) -> Self {
+        Self {
+            app_vk: app_pk.get_app_vk(),
+            leaf_vm_verifier_commit: app_pk
+                .leaf_committed_exe
+                .committed_program
+                .commitment
+                .into(),
+        }
+    }
+}
+
+/// Converts a commitment into the `u32` words expected by `openvm::claim::claim_openvm_proof`.
+pub fn digest_to_u32s(digest: &[F; DIGEST_SIZE]) -> [u32; DIGEST_SIZE] {
+    digest.map(|x| x.as_canonical_u32())
+}
+
+/// Returns whether `public_values`, one byte per field element, are `claimed` followed by zeros.
+pub(crate) fn public_values_match(public_values: &[F], claimed: &[u8]) -> bool {
+    claimed.len() <= public_values.len()
+        && public_values
+            .iter()
+            .zip(claimed.iter().copied().chain(std::iter::repeat(0)))
+            .all(|(v, b)| v.as_canonical_u32() == b as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use openvm_stark_backend::p3_field::FieldAlgebra;
+
+    use super::*;
+
+    #[test]
+    fn test_public_values_match() {
+        let public_values = [1, 2, 0, 0].map(F::from_canonical_u32);
+        assert!(public_values_match(&public_values, &[1, 2]));
+        assert!(public_values_match(&public_values, &[1, 2, 0, 0]));
+        assert!(!public_values_match(&public_values, &[1]));
+        assert!(!public_values_match(&public_values, &[1, 2, 0, 0, 0]));
+        let public_values = [1, 256].map(F::from_canonical_u32);
+        assert!(!public_values_match(&public_values, &[1, 0]));
+    }
+}
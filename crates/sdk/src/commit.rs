@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
 use openvm_circuit::{
-    arch::{instructions::exe::VmExe, VmConfig},
+    arch::{
+        hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
+        instructions::exe::VmExe,
+        VmConfig,
+    },
     system::program::trace::VmCommittedExe,
 };
 use openvm_native_compiler::{conversion::CompilerOptions, ir::DIGEST_SIZE};
@@ -78,6 +82,28 @@ pub(crate) fn babybear_digest_to_bn254(digest: &[F; DIGEST_SIZE]) -> Bn254Fr {
     ret
 }
 
+/// Computes the `batch_commit` exposed by the batch root verifier from the
+/// `(exe_commit, public_values_commit)` pair of each app execution in the batch, in proving order.
+/// Users should use this function to check the batch commit of the final proof.
+pub fn compute_batch_commit(
+    max_num_programs: usize,
+    programs: &[([F; DIGEST_SIZE], [F; DIGEST_SIZE])],
+) -> [F; DIGEST_SIZE] {
+    assert!(!programs.is_empty() && programs.len() <= max_num_programs);
+    let hasher = vm_poseidon2_hasher::<F>();
+    let mut layer = vec![[F::ZERO; DIGEST_SIZE]; max_num_programs.next_power_of_two()];
+    for (leaf, (exe_commit, public_values_commit)) in layer.iter_mut().zip(programs) {
+        *leaf = hasher.compress(exe_commit, public_values_commit);
+    }
+    while layer.len() > 1 {
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| hasher.compress(&pair[0], &pair[1]))
+            .collect();
+    }
+    layer[0]
+}
+
 pub fn generate_leaf_committed_exe<VC: VmConfig<F>>(
     leaf_fri_params: FriParameters,
     compiler_options: CompilerOptions,
@@ -1,15 +1,19 @@
 use bon::Builder;
 use derive_more::derive::From;
 use openvm_algebra_circuit::{
-    Fp2Extension, Fp2ExtensionExecutor, Fp2ExtensionPeriphery, ModularExtension,
-    ModularExtensionExecutor, ModularExtensionPeriphery,
+    FieldExprExtension, FieldExprExtensionExecutor, FieldExprExtensionPeriphery, Fp2Extension,
+    Fp2ExtensionExecutor, Fp2ExtensionPeriphery, ModularExtension, ModularExtensionExecutor,
+    ModularExtensionPeriphery,
+};
+use openvm_algebra_transpiler::{
+    FieldExprTranspilerExtension, Fp2TranspilerExtension, ModularTranspilerExtension,
 };
-use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
 use openvm_bigint_circuit::{Int256, Int256Executor, Int256Periphery};
 use openvm_bigint_transpiler::Int256TranspilerExtension;
 use openvm_circuit::{
     arch::{
-        SystemConfig, SystemExecutor, SystemPeriphery, VmChipComplex, VmConfig, VmInventoryError,
+        instructions::opcode_names::OpcodeNames, GuestRegisters, SystemConfig, SystemExecutor,
+        SystemPeriphery, VmChipComplex, VmConfig, VmExtension, VmInventoryError,
     },
     circuit_derive::{Chip, ChipUsageGetter},
     derive::{AnyEnum, InstructionExecutor},
@@ -61,6 +65,7 @@ pub struct SdkVmConfig {
     pub fp2: Option<Fp2Extension>,
     pub pairing: Option<PairingExtension>,
     pub ecc: Option<WeierstrassExtension>,
+    pub field_expr: Option<FieldExprExtension>,
 }
 
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
@@ -90,6 +95,8 @@ pub enum SdkVmConfigExecutor<F: PrimeField32> {
     #[any_enum]
     Ecc(WeierstrassExtensionExecutor<F>),
     #[any_enum]
+    FieldExpr(FieldExprExtensionExecutor<F>),
+    #[any_enum]
     CastF(CastFExtensionExecutor<F>),
 }
 
@@ -120,6 +127,8 @@ pub enum SdkVmConfigPeriphery<F: PrimeField32> {
     #[any_enum]
     Ecc(WeierstrassExtensionPeriphery<F>),
     #[any_enum]
+    FieldExpr(FieldExprExtensionPeriphery<F>),
+    #[any_enum]
     CastF(CastFExtensionPeriphery<F>),
 }
 
@@ -156,6 +165,9 @@ impl SdkVmConfig {
         if self.ecc.is_some() {
             transpiler = transpiler.with_extension(EccTranspilerExtension);
         }
+        if self.field_expr.is_some() {
+            transpiler = transpiler.with_extension(FieldExprTranspilerExtension);
+        }
         transpiler
     }
 }
@@ -228,9 +240,64 @@ impl<F: PrimeField32> VmConfig<F> for SdkVmConfig {
         if let Some(ref ecc) = self.ecc {
             complex = complex.extend(ecc)?;
         }
+        if let Some(ref field_expr) = self.field_expr {
+            complex = complex.extend(field_expr)?;
+        }
 
         Ok(complex)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        let mut names = VmConfig::<F>::opcode_names(&self.system.config);
+        if self.rv32i.is_some() {
+            names = names.merge(VmExtension::<F>::opcode_names(&Rv32I));
+        }
+        if self.io.is_some() {
+            names = names.merge(VmExtension::<F>::opcode_names(&Rv32Io));
+        }
+        if self.keccak.is_some() {
+            names = names.merge(VmExtension::<F>::opcode_names(&Keccak256));
+        }
+        if self.sha256.is_some() {
+            names = names.merge(VmExtension::<F>::opcode_names(&Sha256));
+        }
+        if self.native.is_some() {
+            names = names.merge(VmExtension::<F>::opcode_names(&Native));
+        }
+        if self.castf.is_some() {
+            names = names.merge(VmExtension::<F>::opcode_names(&CastFExtension));
+        }
+        if let Some(ref rv32m) = self.rv32m {
+            names = names.merge(VmExtension::<F>::opcode_names(rv32m));
+        }
+        if let Some(ref bigint) = self.bigint {
+            names = names.merge(VmExtension::<F>::opcode_names(bigint));
+        }
+        if let Some(ref modular) = self.modular {
+            names = names.merge(VmExtension::<F>::opcode_names(modular));
+        }
+        if let Some(ref fp2) = self.fp2 {
+            names = names.merge(VmExtension::<F>::opcode_names(fp2));
+        }
+        if let Some(ref pairing) = self.pairing {
+            names = names.merge(VmExtension::<F>::opcode_names(pairing));
+        }
+        if let Some(ref ecc) = self.ecc {
+            names = names.merge(VmExtension::<F>::opcode_names(ecc));
+        }
+        if let Some(ref field_expr) = self.field_expr {
+            names = names.merge(VmExtension::<F>::opcode_names(field_expr));
+        }
+        names
+    }
+
+    fn guest_registers(&self) -> Option<GuestRegisters<F>> {
+        if self.rv32i.is_some() {
+            VmExtension::<F>::guest_registers(&Rv32I)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1,8 +1,12 @@
-use openvm_circuit::arch::instructions::program::DEFAULT_MAX_NUM_PUBLIC_VALUES;
+use openvm_circuit::arch::{
+    instructions::program::DEFAULT_MAX_NUM_PUBLIC_VALUES, SegmentationStrategyConfig, VmConfig,
+};
 use openvm_native_compiler::conversion::CompilerOptions;
 use openvm_stark_sdk::config::FriParameters;
 use serde::{Deserialize, Serialize};
 
+use crate::F;
+
 mod global;
 pub use global::*;
 
@@ -21,6 +25,9 @@ pub struct AppConfig<VC> {
     /// Only for AggVM debugging. App VM users should not need this in regular flow.
     #[serde(default)]
     pub compiler_options: CompilerOptions,
+    /// Overrides the segmentation strategy of `app_vm_config` if set.
+    #[serde(default)]
+    pub segmentation_strategy: Option<SegmentationStrategyConfig>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -43,6 +50,10 @@ pub struct AggStarkConfig {
     pub compiler_options: CompilerOptions,
     /// Max constraint degree for FRI logup chunking
     pub root_max_constraint_degree: usize,
+    /// Maximum number of app executions aggregated into one batch proof. Batch aggregation is
+    /// disabled if 0.
+    #[serde(default)]
+    pub max_num_batch_programs: usize,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -62,6 +73,7 @@ impl<VC> AppConfig<VC> {
             app_vm_config,
             leaf_fri_params: Default::default(),
             compiler_options: Default::default(),
+            segmentation_strategy: None,
         }
     }
 
@@ -75,7 +87,21 @@ impl<VC> AppConfig<VC> {
             app_vm_config,
             leaf_fri_params: LeafFriParams::from(leaf_fri_params),
             compiler_options: Default::default(),
+            segmentation_strategy: None,
+        }
+    }
+}
+
+impl<VC: VmConfig<F>> AppConfig<VC> {
+    /// Returns `app_vm_config` with `segmentation_strategy` applied to its system config.
+    pub fn effective_app_vm_config(&self) -> VC {
+        let mut app_vm_config = self.app_vm_config.clone();
+        if let Some(segmentation_strategy) = &self.segmentation_strategy {
+            app_vm_config
+                .system_mut()
+                .set_segmentation_strategy(segmentation_strategy.clone());
         }
+        app_vm_config
     }
 }
 
@@ -95,6 +121,7 @@ impl Default for AggStarkConfig {
             profiling: false,
             compiler_options: Default::default(),
             root_max_constraint_degree: (1 << DEFAULT_ROOT_LOG_BLOWUP) + 1,
+            max_num_batch_programs: 0,
         }
     }
 }
@@ -0,0 +1,158 @@
+use openvm_circuit::arch::{
+    instructions::exe::VmExe, ExecutionError, SegmentCostEstimate, SegmentCostModel,
+    SegmentationStrategyConfig, VmConfig, VmExecutor,
+};
+use openvm_native_compiler::ir::DIGEST_SIZE;
+use openvm_stark_backend::p3_util::log2_ceil_usize;
+use openvm_stark_sdk::config::FriParameters;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::AppConfig, StdIn, F};
+
+/// Number of bytes of a base field element in a proof.
+const FIELD_ELEMENT_BYTES: usize = 4;
+/// Number of bytes of an extension field element in a proof.
+const EXT_ELEMENT_BYTES: usize = 4 * FIELD_ELEMENT_BYTES;
+/// Number of bytes of a Merkle tree digest in a proof.
+const DIGEST_BYTES: usize = DIGEST_SIZE * FIELD_ELEMENT_BYTES;
+
+/// Trace shape of a single AIR in a segment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AirEstimate {
+    pub name: String,
+    /// Trace height before padding to a power of two.
+    pub height: usize,
+    /// Main trace width.
+    pub width: usize,
+    /// Main trace cells before padding, i.e. `height * width`.
+    pub cells: usize,
+}
+
+/// Estimated cost of proving a single segment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SegmentEstimate {
+    /// AIRs with non-empty traces, ordered by AIR ID.
+    pub airs: Vec<AirEstimate>,
+    pub cost: SegmentCostEstimate,
+    /// Estimated size of the STARK proof of the segment, in bytes.
+    pub proof_size_bytes: usize,
+}
+
+/// Estimated cost of proving a program execution, computed by executing the program without
+/// generating any traces.
+///
+/// All resource figures are estimates of a linear [SegmentCostModel] and are only meant to
+/// compare executions and configurations. They should be calibrated against real proving runs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExecutionEstimate {
+    pub app_fri_params: FriParameters,
+    pub cost_model: SegmentCostModel,
+    pub segments: Vec<SegmentEstimate>,
+}
+
+impl ExecutionEstimate {
+    pub fn num_segments(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Estimated proving time if all segments are proven sequentially on one machine.
+    pub fn total_proving_time_ms(&self) -> u64 {
+        self.segments.iter().map(|s| s.cost.proving_time_ms).sum()
+    }
+
+    /// Estimated peak prover memory over all segments.
+    pub fn max_memory_bytes(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|s| s.cost.memory_bytes)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn total_proof_size_bytes(&self) -> usize {
+        self.segments.iter().map(|s| s.proof_size_bytes).sum()
+    }
+}
+
+/// Executes `exe` and estimates the cost of proving each segment of the execution.
+///
+/// The resource estimates use the cost model of the segmentation strategy in `app_config` if it
+/// is a [SegmentationStrategyConfig::CostModel], and a default cost model otherwise.
+pub fn estimate_execution<VC: VmConfig<F>>(
+    app_config: &AppConfig<VC>,
+    exe: VmExe<F>,
+    inputs: StdIn,
+) -> Result<ExecutionEstimate, ExecutionError> {
+    let app_fri_params = app_config.app_fri_params.fri_params;
+    let vm_config = app_config.effective_app_vm_config();
+    let cost_model = match vm_config.system().segmentation_strategy.to_config() {
+        Some(SegmentationStrategyConfig::CostModel(strategy)) => strategy.cost_model,
+        _ => SegmentCostModel {
+            log_blowup: app_fri_params.log_blowup,
+            ..Default::default()
+        },
+    };
+
+    let executor = VmExecutor::new(vm_config);
+    let segments = executor.execute_and_then(exe, inputs, |_, mut segment| {
+        segment.chip_complex.finalize_memory();
+        let heights = segment.current_trace_heights();
+        let cells = segment.current_trace_cells();
+        let airs: Vec<_> = segment
+            .air_names()
+            .iter()
+            .zip(heights.iter().zip(&cells))
+            .filter(|(_, (&height, _))| height > 0)
+            .map(|(name, (&height, &cells))| AirEstimate {
+                name: name.clone(),
+                height,
+                width: cells / height,
+                cells,
+            })
+            .collect();
+        SegmentEstimate {
+            cost: cost_model.estimate(&heights, &cells),
+            proof_size_bytes: estimate_proof_size_bytes(&app_fri_params, &airs),
+            airs,
+        }
+    })?;
+
+    Ok(ExecutionEstimate {
+        app_fri_params,
+        cost_model,
+        segments,
+    })
+}
+
+/// Rough estimate of the size of a STARK proof of traces with the given shapes.
+///
+/// Only the main traces are taken into account: the estimate counts the opened values at the
+/// out-of-domain point, the opened rows and Merkle paths of every query, and the FRI query
+/// proofs. Proofs are usually somewhat larger because of the after-challenge and quotient
+/// traces.
+pub fn estimate_proof_size_bytes(fri_params: &FriParameters, airs: &[AirEstimate]) -> usize {
+    let Some(log_max_height) = airs.iter().map(|air| log2_ceil_usize(air.height)).max() else {
+        return 0;
+    };
+    // Opened values at `zeta` and `zeta * g`.
+    let opened_values: usize =
+        airs.iter().map(|air| 2 * air.width).sum::<usize>() * EXT_ELEMENT_BYTES;
+    // Opened rows and Merkle paths of every trace, per query.
+    let trace_openings: usize = airs
+        .iter()
+        .map(|air| {
+            air.width * FIELD_ELEMENT_BYTES
+                + (log2_ceil_usize(air.height) + fri_params.log_blowup) * DIGEST_BYTES
+        })
+        .sum();
+    // FRI folding: one sibling and one Merkle path per round, per query.
+    let num_rounds = log_max_height.saturating_sub(fri_params.log_final_poly_len);
+    let fri_query: usize = (0..num_rounds)
+        .map(|round| {
+            EXT_ELEMENT_BYTES + (log_max_height + fri_params.log_blowup - round) * DIGEST_BYTES
+        })
+        .sum();
+    let commitments = (num_rounds + 3) * DIGEST_BYTES;
+    let final_poly = (1 << fri_params.log_final_poly_len) * EXT_ELEMENT_BYTES;
+    commitments + opened_values + final_poly + fri_params.num_queries * (trace_openings + fri_query)
+}
@@ -0,0 +1,321 @@
+//! Solidity sources of the EVM verifier and the ABI of `OpenVmHalo2Verifier`, which wraps the
+//! halo2 verifier of the wrapper circuit with typed public values.
+//!
+//! The instances of an [EvmProof] with the default public values handler are the 12 limbs of the
+//! KZG accumulator, the BN254 exe commit, the BN254 leaf verifier commit and the user public
+//! values. `OpenVmHalo2Verifier.verify` takes the accumulator as the beginning of `proof` and
+//! each user public value as a byte.
+
+use openvm_native_recursion::halo2::EvmProof;
+use serde::{Deserialize, Serialize};
+use snark_verifier_sdk::snark_verifier::halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+use thiserror::Error;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Number of instances of the wrapper circuit which hold the KZG accumulator.
+pub const NUM_ACCUMULATOR_INSTANCES: usize = 12;
+/// Name of the contract in [EvmVerifierSource::openvm_verifier_code].
+pub const OPENVM_VERIFIER_CONTRACT: &str = "OpenVmHalo2Verifier";
+/// Signature of the verification function of `OpenVmHalo2Verifier`.
+pub const OPENVM_VERIFY_SIGNATURE: &str = "verify(bytes,bytes32,bytes32,bytes)";
+
+/// Solidity sources of the EVM verifier of an aggregation proving key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvmVerifierSource {
+    /// Halo2 verifier of the wrapper circuit. It has no ABI: its fallback function takes
+    /// [EvmProof::to_calldata] and reverts unless the proof is valid.
+    pub halo2_verifier_code: String,
+    /// `OpenVmHalo2Verifier`, deployed with the address of the halo2 verifier. Call it with
+    /// [OpenVmVerifyArgs::to_calldata].
+    pub openvm_verifier_code: String,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EvmProofAbiError {
+    #[error("expected 1 instance column, got {0}")]
+    NumColumnsMismatch(usize),
+    #[error("expected {expected} instances, got {actual}")]
+    NumInstancesMismatch { expected: usize, actual: usize },
+    #[error("user public value {index} is not a byte")]
+    PublicValueNotByte { index: usize },
+    #[error("proof is shorter than the KZG accumulator")]
+    ProofTooShort,
+    #[error("{0} is not a canonical BN254 scalar")]
+    NonCanonical(&'static str),
+    #[error("calldata is not a call to {OPENVM_VERIFY_SIGNATURE}")]
+    InvalidCalldata,
+}
+
+/// Arguments of `OpenVmHalo2Verifier.verify`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenVmVerifyArgs {
+    /// The KZG accumulator as 12 big-endian words, followed by the halo2 proof.
+    pub proof: Vec<u8>,
+    /// Big-endian BN254 commitment of the app executable.
+    pub app_exe_commit: [u8; 32],
+    /// Big-endian BN254 commitment of the leaf verifier, which commits the App VM config.
+    pub app_vm_commit: [u8; 32],
+    pub user_public_values: Vec<u8>,
+}
+
+impl OpenVmVerifyArgs {
+    pub fn from_evm_proof(
+        evm_proof: &EvmProof,
+        num_user_public_values: usize,
+    ) -> Result<Self, EvmProofAbiError> {
+        let [instances] = evm_proof.instances.as_slice() else {
+            return Err(EvmProofAbiError::NumColumnsMismatch(
+                evm_proof.instances.len(),
+            ));
+        };
+        let expected = NUM_ACCUMULATOR_INSTANCES + 2 + num_user_public_values;
+        if instances.len() != expected {
+            return Err(EvmProofAbiError::NumInstancesMismatch {
+                expected,
+                actual: instances.len(),
+            });
+        }
+        let (accumulator, instances) = instances.split_at(NUM_ACCUMULATOR_INSTANCES);
+        let mut proof: Vec<u8> = accumulator.iter().flat_map(fr_to_word).collect();
+        proof.extend_from_slice(&evm_proof.proof);
+        let user_public_values = instances[2..]
+            .iter()
+            .enumerate()
+            .map(|(index, x)| {
+                let word = fr_to_word(x);
+                if word[..31].iter().any(|&b| b != 0) {
+                    return Err(EvmProofAbiError::PublicValueNotByte { index });
+                }
+                Ok(word[31])
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            proof,
+            app_exe_commit: fr_to_word(&instances[0]),
+            app_vm_commit: fr_to_word(&instances[1]),
+            user_public_values,
+        })
+    }
+
+    /// Inverse of [Self::from_evm_proof].
+    pub fn to_evm_proof(&self) -> Result<EvmProof, EvmProofAbiError> {
+        let accumulator_len = NUM_ACCUMULATOR_INSTANCES * 32;
+        if self.proof.len() < accumulator_len {
+            return Err(EvmProofAbiError::ProofTooShort);
+        }
+        let (accumulator, proof) = self.proof.split_at(accumulator_len);
+        let mut instances = accumulator
+            .chunks_exact(32)
+            .map(|word| fr_from_word(word.try_into().unwrap(), "accumulator"))
+            .collect::<Result<Vec<_>, _>>()?;
+        instances.push(fr_from_word(&self.app_exe_commit, "app_exe_commit")?);
+        instances.push(fr_from_word(&self.app_vm_commit, "app_vm_commit")?);
+        instances.extend(self.user_public_values.iter().map(|&x| Fr::from(x as u64)));
+        Ok(EvmProof {
+            instances: vec![instances],
+            proof: proof.to_vec(),
+        })
+    }
+
+    /// ABI-encoded call of `OpenVmHalo2Verifier.verify`.
+    pub fn to_calldata(&self) -> Vec<u8> {
+        let proof_tail_len = 32 + padded_len(self.proof.len());
+        let mut calldata = verify_selector().to_vec();
+        calldata.extend_from_slice(&usize_to_word(4 * 32));
+        calldata.extend_from_slice(&self.app_exe_commit);
+        calldata.extend_from_slice(&self.app_vm_commit);
+        calldata.extend_from_slice(&usize_to_word(4 * 32 + proof_tail_len));
+        for bytes in [&self.proof, &self.user_public_values] {
+            calldata.extend_from_slice(&usize_to_word(bytes.len()));
+            calldata.extend_from_slice(bytes);
+            calldata.resize(calldata.len() + padded_len(bytes.len()) - bytes.len(), 0);
+        }
+        calldata
+    }
+
+    /// Inverse of [Self::to_calldata].
+    pub fn from_calldata(calldata: &[u8]) -> Result<Self, EvmProofAbiError> {
+        let (selector, args) = calldata
+            .split_first_chunk::<4>()
+            .ok_or(EvmProofAbiError::InvalidCalldata)?;
+        if *selector != verify_selector() {
+            return Err(EvmProofAbiError::InvalidCalldata);
+        }
+        let slice = |start: usize, len: usize| -> Result<&[u8], EvmProofAbiError> {
+            start
+                .checked_add(len)
+                .and_then(|end| args.get(start..end))
+                .ok_or(EvmProofAbiError::InvalidCalldata)
+        };
+        let word = |offset: usize| -> Result<[u8; 32], EvmProofAbiError> {
+            Ok(slice(offset, 32)?.try_into().unwrap())
+        };
+        let bytes = |head: usize| -> Result<Vec<u8>, EvmProofAbiError> {
+            let offset = word_to_usize(&word(head)?)?;
+            let len = word_to_usize(&word(offset)?)?;
+            Ok(slice(offset + 32, len)?.to_vec())
+        };
+        Ok(Self {
+            proof: bytes(0)?,
+            app_exe_commit: word(32)?,
+            app_vm_commit: word(64)?,
+            user_public_values: bytes(96)?,
+        })
+    }
+}
+
+/// Generate the Solidity source of `OpenVmHalo2Verifier` for `num_user_public_values` user
+/// public values.
+pub fn generate_openvm_verifier_code(num_user_public_values: usize) -> String {
+    OPENVM_VERIFIER_TEMPLATE
+        .replace("{CONTRACT}", OPENVM_VERIFIER_CONTRACT)
+        .replace(
+            "{NUM_ACCUMULATOR_INSTANCES}",
+            &NUM_ACCUMULATOR_INSTANCES.to_string(),
+        )
+        .replace(
+            "{PUBLIC_VALUES_LENGTH}",
+            &num_user_public_values.to_string(),
+        )
+}
+
+const OPENVM_VERIFIER_TEMPLATE: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.19;
+
+/// @notice Verifies OpenVM EVM proofs with the halo2 verifier of the wrapper circuit.
+/// Generated by OpenVM.
+contract {CONTRACT} {
+    /// Number of bytes of user public values.
+    uint256 public constant PUBLIC_VALUES_LENGTH = {PUBLIC_VALUES_LENGTH};
+    /// Length in bytes of the KZG accumulator at the beginning of `proof`.
+    uint256 constant ACCUMULATOR_LENGTH = {NUM_ACCUMULATOR_INSTANCES} * 32;
+
+    /// The halo2 verifier of the wrapper circuit.
+    address public immutable halo2Verifier;
+
+    constructor(address _halo2Verifier) {
+        require(_halo2Verifier.code.length > 0, "halo2 verifier is not a contract");
+        halo2Verifier = _halo2Verifier;
+    }
+
+    /// @notice Reverts unless `proof` proves an execution of the app executable committed by
+    /// `appExeCommit`, on the App VM committed by `appVmCommit`, with `userPublicValues`.
+    /// @param proof The KZG accumulator as 12 words, followed by the halo2 proof.
+    function verify(
+        bytes calldata proof,
+        bytes32 appExeCommit,
+        bytes32 appVmCommit,
+        bytes calldata userPublicValues
+    ) external view {
+        require(proof.length >= ACCUMULATOR_LENGTH, "proof too short");
+        require(userPublicValues.length == PUBLIC_VALUES_LENGTH, "invalid public values length");
+
+        bytes memory publicValueWords = new bytes(PUBLIC_VALUES_LENGTH * 32);
+        for (uint256 i = 0; i < PUBLIC_VALUES_LENGTH; i++) {
+            uint256 value = uint8(userPublicValues[i]);
+            assembly {
+                mstore(add(add(publicValueWords, 0x20), mul(i, 0x20)), value)
+            }
+        }
+        bytes memory halo2Calldata = abi.encodePacked(
+            proof[:ACCUMULATOR_LENGTH],
+            appExeCommit,
+            appVmCommit,
+            publicValueWords,
+            proof[ACCUMULATOR_LENGTH:]
+        );
+        (bool success,) = halo2Verifier.staticcall(halo2Calldata);
+        require(success, "invalid proof");
+    }
+}
+"#;
+
+fn verify_selector() -> [u8; 4] {
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(OPENVM_VERIFY_SIGNATURE.as_bytes());
+    keccak.finalize(&mut hash);
+    hash[..4].try_into().unwrap()
+}
+
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(32) * 32
+}
+
+fn usize_to_word(x: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(x as u64).to_be_bytes());
+    word
+}
+
+fn word_to_usize(word: &[u8; 32]) -> Result<usize, EvmProofAbiError> {
+    if word[..24].iter().any(|&b| b != 0) {
+        return Err(EvmProofAbiError::InvalidCalldata);
+    }
+    usize::try_from(u64::from_be_bytes(word[24..].try_into().unwrap()))
+        .map_err(|_| EvmProofAbiError::InvalidCalldata)
+}
+
+fn fr_to_word(x: &Fr) -> [u8; 32] {
+    let mut bytes = x.to_bytes();
+    bytes.reverse();
+    bytes
+}
+
+fn fr_from_word(word: &[u8; 32], name: &'static str) -> Result<Fr, EvmProofAbiError> {
+    let mut bytes = *word;
+    bytes.reverse();
+    Option::from(Fr::from_bytes(&bytes)).ok_or(EvmProofAbiError::NonCanonical(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evm_proof(num_user_public_values: usize) -> EvmProof {
+        let instances = (0..NUM_ACCUMULATOR_INSTANCES + 2 + num_user_public_values)
+            .map(|i| Fr::from((i % 256) as u64))
+            .collect();
+        EvmProof {
+            instances: vec![instances],
+            proof: (0..100).collect(),
+        }
+    }
+
+    #[test]
+    fn test_verify_args_roundtrip() {
+        let proof = evm_proof(5);
+        let args = OpenVmVerifyArgs::from_evm_proof(&proof, 5).unwrap();
+        assert_eq!(args.proof.len(), NUM_ACCUMULATOR_INSTANCES * 32 + 100);
+        assert_eq!(args.app_exe_commit[31], 12);
+        assert_eq!(args.user_public_values, vec![14, 15, 16, 17, 18]);
+        let decoded = args.to_evm_proof().unwrap();
+        assert_eq!(decoded.instances, proof.instances);
+        assert_eq!(decoded.proof, proof.proof);
+
+        let calldata = args.to_calldata();
+        assert_eq!(calldata.len() % 32, 4);
+        assert_eq!(OpenVmVerifyArgs::from_calldata(&calldata).unwrap(), args);
+        assert_eq!(
+            OpenVmVerifyArgs::from_calldata(&calldata[..calldata.len() - 32]),
+            Err(EvmProofAbiError::InvalidCalldata)
+        );
+    }
+
+    #[test]
+    fn test_verify_args_errors() {
+        let mut proof = evm_proof(2);
+        assert_eq!(
+            OpenVmVerifyArgs::from_evm_proof(&proof, 3),
+            Err(EvmProofAbiError::NumInstancesMismatch {
+                expected: 17,
+                actual: 16
+            })
+        );
+        proof.instances[0][15] = Fr::from(256u64);
+        assert_eq!(
+            OpenVmVerifyArgs::from_evm_proof(&proof, 2),
+            Err(EvmProofAbiError::PublicValueNotByte { index: 1 })
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use std::{
+    fmt,
+    fs::{create_dir_all, read, write},
+    path::Path,
+};
+
+use openvm_circuit::arch::{get_default_segmentation_strategy, VmConfig};
+use openvm_stark_sdk::config::FriParameters;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::F;
+
+/// Magic number at the start of every artifact file written by OpenVM.
+pub const ARTIFACT_MAGIC: [u8; 8] = *b"\0OPENVM\0";
+/// Version of the artifact envelope layout. Bump when the layout of the header or the encoding
+/// of the body changes.
+pub const ARTIFACT_FORMAT_VERSION: u32 = 1;
+/// Version of OpenVM writing the artifacts. The body of an artifact is only guaranteed to be
+/// readable by the same version of OpenVM.
+pub const OPENVM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The kind of object stored in an artifact file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Exe,
+    AppProvingKey,
+    AppVerifyingKey,
+    AppProof,
+    AppExecutionCommit,
+    AggProvingKey,
+    /// Input of the root verifier, i.e. the aggregated STARK proof before the static verifier.
+    RootVerifierInput,
+    EvmProof,
+    /// Any other serializable object.
+    Object,
+}
+
+impl fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ArtifactKind::Exe => "exe",
+            ArtifactKind::AppProvingKey => "app proving key",
+            ArtifactKind::AppVerifyingKey => "app verifying key",
+            ArtifactKind::AppProof => "app proof",
+            ArtifactKind::AppExecutionCommit => "app execution commit",
+            ArtifactKind::AggProvingKey => "aggregation proving key",
+            ArtifactKind::RootVerifierInput => "root verifier input",
+            ArtifactKind::EvmProof => "EVM proof",
+            ArtifactKind::Object => "object",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Compression applied to the body of an artifact.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+}
+
+/// Self-describing header of an artifact file.
+///
+/// An artifact file consists of [ARTIFACT_MAGIC], the length of the header as a little-endian
+/// `u32`, the header encoded as JSON, and the body. The body is the `bitcode` encoding of the
+/// object, compressed according to `compression`. The header is JSON so that it can be read
+/// independently of the version of OpenVM that wrote the file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactHeader {
+    pub format_version: u32,
+    pub openvm_version: String,
+    pub kind: ArtifactKind,
+    /// Hash of the VM config and FRI parameters the artifact was produced with, if known.
+    /// See [config_hash].
+    pub config_hash: Option<[u8; 32]>,
+    pub compression: Compression,
+    /// Length of the body in bytes, as stored in the file.
+    pub body_len: u64,
+}
+
+/// Options for writing an artifact.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ArtifactOptions {
+    pub config_hash: Option<[u8; 32]>,
+    pub compression: Compression,
+}
+
+impl ArtifactOptions {
+    pub fn with_config_hash(mut self, config_hash: [u8; 32]) -> Self {
+        self.config_hash = Some(config_hash);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ArtifactError {
+    #[error("not an OpenVM artifact: missing magic number (files written before OpenVM used versioned artifacts must be regenerated)")]
+    MissingMagic,
+    #[error("malformed artifact header: {0}")]
+    MalformedHeader(String),
+    #[error("unsupported artifact format version {found} (supported: {supported})")]
+    UnsupportedFormatVersion { found: u32, supported: u32 },
+    #[error("artifact was written by OpenVM {found}, but this is OpenVM {expected}")]
+    OpenVmVersionMismatch { found: String, expected: String },
+    #[error("expected an artifact of kind `{expected}`, found `{found}`")]
+    KindMismatch {
+        expected: ArtifactKind,
+        found: ArtifactKind,
+    },
+    #[error("artifact config hash mismatch (expected: {}, found: {})", fmt_config_hash(.expected), fmt_config_hash(.found))]
+    ConfigHashMismatch {
+        expected: Option<[u8; 32]>,
+        found: Option<[u8; 32]>,
+    },
+    #[error("artifact body is truncated (expected {expected} bytes, found {found})")]
+    Truncated { expected: u64, found: u64 },
+    #[error("artifact has {0} trailing bytes after the body")]
+    TrailingBytes(u64),
+    #[error("failed to decode artifact body: {0}")]
+    Body(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+fn fmt_config_hash(hash: &Option<[u8; 32]>) -> String {
+    hash.map_or_else(
+        || "none".to_string(),
+        |hash| format!("0x{}", hex_string(&hash)),
+    )
+}
+
+pub(crate) fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+impl ArtifactHeader {
+    pub fn config_hash_hex(&self) -> Option<String> {
+        self.config_hash
+            .map(|hash| format!("0x{}", hex_string(&hash)))
+    }
+
+    /// Checks that the artifact was produced with the config with hash `expected`.
+    /// Artifacts without a config hash are accepted.
+    pub fn check_config_hash(&self, expected: [u8; 32]) -> Result<(), ArtifactError> {
+        match self.config_hash {
+            Some(found) if found != expected => Err(ArtifactError::ConfigHashMismatch {
+                expected: Some(expected),
+                found: Some(found),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Computes the Keccak-256 hash of the `bitcode` encodings of a VM config and the FRI parameters
+/// it is proven with. This identifies the config that produced an artifact.
+///
+/// Only the fields that change the proof system are hashed: the execution-only fields of the
+/// system config (segmentation strategy, stack limit and profiling) are reset to their defaults,
+/// so that proofs generated with different segmentation overrides are verified against the
+/// same key.
+pub fn config_hash<VC: VmConfig<F>>(vm_config: &VC, fri_params: &FriParameters) -> [u8; 32] {
+    let mut vm_config = vm_config.clone();
+    let system = vm_config.system_mut();
+    system.segmentation_strategy = get_default_segmentation_strategy();
+    system.stack_limit = None;
+    system.profiling = false;
+
+    let mut hasher = Keccak::v256();
+    hasher.update(&bitcode::serialize(&vm_config).expect("failed to serialize VM config"));
+    hasher.update(&bitcode::serialize(fri_params).expect("failed to serialize FRI parameters"));
+    let mut output = [0u8; 32];
+    hasher.finalize(&mut output);
+    output
+}
+
+/// Encodes `data` as an artifact of the given kind.
+pub fn encode_artifact<T: Serialize>(
+    kind: ArtifactKind,
+    data: &T,
+    options: &ArtifactOptions,
+) -> eyre::Result<Vec<u8>> {
+    let body = bitcode::serialize(data)?;
+    let body = match options.compression {
+        Compression::None => body,
+        Compression::Zstd => zstd::encode_all(body.as_slice(), 0)?,
+    };
+    let header = ArtifactHeader {
+        format_version: ARTIFACT_FORMAT_VERSION,
+        openvm_version: OPENVM_VERSION.to_string(),
+        kind,
+        config_hash: options.config_hash,
+        compression: options.compression,
+        body_len: body.len() as u64,
+    };
+    let header = serde_json::to_vec(&header)?;
+
+    let mut bytes = Vec::with_capacity(ARTIFACT_MAGIC.len() + 4 + header.len() + body.len());
+    bytes.extend_from_slice(&ARTIFACT_MAGIC);
+    bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&header);
+    bytes.extend_from_slice(&body);
+    Ok(bytes)
+}
+
+/// Parses the header of an artifact and returns it together with the (possibly compressed) body.
+/// Only the magic number, the format version and the length of the body are checked.
+pub fn decode_artifact_header(bytes: &[u8]) -> Result<(ArtifactHeader, &[u8]), ArtifactError> {
+    let rest = bytes
+        .strip_prefix(&ARTIFACT_MAGIC)
+        .ok_or(ArtifactError::MissingMagic)?;
+    if rest.len() < 4 {
+        return Err(ArtifactError::MalformedHeader(
+            "missing header length".to_string(),
+        ));
+    }
+    let (header_len, rest) = rest.split_at(4);
+    let header_len = u32::from_le_bytes(header_len.try_into().unwrap()) as usize;
+    if rest.len() < header_len {
+        return Err(ArtifactError::MalformedHeader(
+            "header is truncated".to_string(),
+        ));
+    }
+    let (header, body) = rest.split_at(header_len);
+    let header: ArtifactHeader = serde_json::from_slice(header)
+        .map_err(|e| ArtifactError::MalformedHeader(e.to_string()))?;
+    if header.format_version != ARTIFACT_FORMAT_VERSION {
+        return Err(ArtifactError::UnsupportedFormatVersion {
+            found: header.format_version,
+            supported: ARTIFACT_FORMAT_VERSION,
+        });
+    }
+    let found = body.len() as u64;
+    if found < header.body_len {
+        return Err(ArtifactError::Truncated {
+            expected: header.body_len,
+            found,
+        });
+    }
+    if found > header.body_len {
+        return Err(ArtifactError::TrailingBytes(found - header.body_len));
+    }
+    Ok((header, body))
+}
+
+/// Decodes an artifact of the given kind, checking its header against this version of OpenVM.
+pub fn decode_artifact<T: DeserializeOwned>(
+    kind: ArtifactKind,
+    bytes: &[u8],
+) -> Result<(ArtifactHeader, T), ArtifactError> {
+    let (header, body) = decode_artifact_header(bytes)?;
+    if header.openvm_version != OPENVM_VERSION {
+        return Err(ArtifactError::OpenVmVersionMismatch {
+            found: header.openvm_version,
+            expected: OPENVM_VERSION.to_string(),
+        });
+    }
+    if header.kind != kind {
+        return Err(ArtifactError::KindMismatch {
+            expected: kind,
+            found: header.kind,
+        });
+    }
+    let data = match header.compression {
+        Compression::None => bitcode::deserialize(body),
+        Compression::Zstd => {
+            let body = zstd::decode_all(body)?;
+            bitcode::deserialize(&body)
+        }
+    }
+    .map_err(|e| ArtifactError::Body(e.to_string()))?;
+    Ok((header, data))
+}
+
+pub fn read_artifact_from_file<T: DeserializeOwned, P: AsRef<Path>>(
+    kind: ArtifactKind,
+    path: P,
+) -> Result<(ArtifactHeader, T), ArtifactError> {
+    let bytes = read(path)?;
+    decode_artifact(kind, &bytes)
+}
+
+pub fn read_artifact_header_from_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<ArtifactHeader, ArtifactError> {
+    let bytes = read(path)?;
+    let (header, _) = decode_artifact_header(&bytes)?;
+    Ok(header)
+}
+
+pub fn write_artifact_to_file<T: Serialize, P: AsRef<Path>>(
+    kind: ArtifactKind,
+    path: P,
+    data: &T,
+    options: &ArtifactOptions,
+) -> eyre::Result<()> {
+    let bytes = encode_artifact(kind, data, options)?;
+    if let Some(parent) = path.as_ref().parent() {
+        create_dir_all(parent)?;
+    }
+    write(path, bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use openvm_circuit::arch::SystemConfig;
+
+    use super::*;
+    use crate::config::SdkVmConfig;
+
+    #[test]
+    fn test_artifact_roundtrip() {
+        let data = vec![1u32, 2, 3];
+        for compression in [Compression::None, Compression::Zstd] {
+            let options = ArtifactOptions::default()
+                .with_config_hash([7; 32])
+                .with_compression(compression);
+            let bytes = encode_artifact(ArtifactKind::Object, &data, &options).unwrap();
+            let (header, decoded): (_, Vec<u32>) =
+                decode_artifact(ArtifactKind::Object, &bytes).unwrap();
+            assert_eq!(decoded, data);
+            assert_eq!(header.compression, compression);
+            assert_eq!(header.config_hash, Some([7; 32]));
+            assert!(header.check_config_hash([7; 32]).is_ok());
+            assert!(matches!(
+                header.check_config_hash([8; 32]),
+                Err(ArtifactError::ConfigHashMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_config_hash_ignores_execution_options() {
+        let config = SdkVmConfig::builder()
+            .system(SystemConfig::default().with_continuations().into())
+            .rv32i(Default::default())
+            .build();
+        let fri_params = FriParameters::standard_fast();
+        let hash = config_hash(&config, &fri_params);
+
+        let mut overridden = config.clone();
+        overridden.system.config = overridden
+            .system
+            .config
+            .with_max_segment_len(1 << 10)
+            .with_stack_limit(0x1000);
+        assert_eq!(config_hash(&overridden, &fri_params), hash);
+
+        let mut other = config.clone();
+        other.system.config = other.system.config.with_public_values(4);
+        assert_ne!(config_hash(&other, &fri_params), hash);
+    }
+
+    #[test]
+    fn test_artifact_mismatch() {
+        let bytes =
+            encode_artifact(ArtifactKind::AppProof, &0u8, &ArtifactOptions::default()).unwrap();
+        assert!(matches!(
+            decode_artifact::<u8>(ArtifactKind::EvmProof, &bytes),
+            Err(ArtifactError::KindMismatch { .. })
+        ));
+        assert!(matches!(
+            decode_artifact::<u8>(ArtifactKind::AppProof, &bytes[..bytes.len() - 1]),
+            Err(ArtifactError::Truncated { .. })
+        ));
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(
+            decode_artifact::<u8>(ArtifactKind::AppProof, &trailing),
+            Err(ArtifactError::TrailingBytes(1))
+        ));
+        let raw = bitcode::serialize(&0u8).unwrap();
+        assert!(matches!(
+            decode_artifact::<u8>(ArtifactKind::AppProof, &raw),
+            Err(ArtifactError::MissingMagic)
+        ));
+    }
+}
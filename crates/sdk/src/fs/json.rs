@@ -0,0 +1,978 @@
+use std::{
+    fs::{create_dir_all, read, write},
+    path::Path,
+};
+
+use openvm_circuit::system::memory::{
+    dimensions::MemoryDimensions, tree::public_values::UserPublicValuesProof, CHUNK,
+};
+use openvm_native_compiler::{constraints::halo2::compiler::convert_fr, ir::DIGEST_SIZE};
+use openvm_native_recursion::{
+    halo2::EvmProof,
+    hints::{
+        InnerBatchOpening, InnerChallenge, InnerCommitPhaseStep, InnerFriProof, InnerQueryProof,
+    },
+};
+use openvm_stark_sdk::{
+    config::FriParameters,
+    openvm_stark_backend::{
+        config::Com,
+        interaction::fri_log_up::FriLogUpPartialProof,
+        keygen::types::{MultiStarkVerifyingKey, StarkVerifyingKey},
+        p3_field::{FieldAlgebra, FieldExtensionAlgebra, PrimeField32},
+        proof::{
+            AdjacentOpenedValues, AirProofData, Commitments, OpenedValues, OpeningProof, Proof,
+        },
+    },
+    p3_bn254_fr::Bn254Fr,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use snark_verifier_sdk::snark_verifier::halo2_base::halo2_proofs::halo2curves::bn256::Fr;
+use thiserror::Error;
+
+use super::{hex_string, ArtifactKind, OPENVM_VERSION};
+use crate::{
+    commit::{babybear_digest_to_bn254, AppExecutionCommit},
+    keygen::AppVerifyingKey,
+    prover::vm::ContinuationVmProof,
+    verifier::root::types::RootVmVerifierInput,
+    F, SC,
+};
+
+/// Degree of the extension field of BabyBear used for challenges.
+const EXT_DEGREE: usize = 4;
+
+/// Version of the JSON schema of exported objects. Bump when a field is removed or its encoding
+/// changes.
+pub const JSON_SCHEMA_VERSION: u32 = 2;
+
+/// Fields common to all exported JSON objects.
+///
+/// Encodings used by the schema:
+/// - BabyBear field elements are canonical `u32` numbers.
+/// - BN254 field elements are `0x`-prefixed, 32-byte big-endian hex strings.
+/// - Elements of the degree 4 extension of BabyBear are arrays of 4 BabyBear coefficients.
+/// - Poseidon2 digests are arrays of 8 BabyBear field elements.
+/// - Byte strings are `0x`-prefixed hex strings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonHeader {
+    pub schema_version: u32,
+    pub openvm_version: String,
+    pub kind: ArtifactKind,
+}
+
+impl JsonHeader {
+    fn new(kind: ArtifactKind) -> Self {
+        Self {
+            schema_version: JSON_SCHEMA_VERSION,
+            openvm_version: OPENVM_VERSION.to_string(),
+            kind,
+        }
+    }
+
+    fn check(&self, kind: ArtifactKind) -> Result<(), JsonError> {
+        if self.schema_version != JSON_SCHEMA_VERSION {
+            return Err(JsonError::UnsupportedSchemaVersion {
+                found: self.schema_version,
+                supported: JSON_SCHEMA_VERSION,
+            });
+        }
+        if self.kind != kind {
+            return Err(JsonError::KindMismatch {
+                expected: kind,
+                found: self.kind,
+            });
+        }
+        Ok(())
+    }
+
+    /// Like [Self::check], but also requires the object to be written by this version of
+    /// OpenVM, because it contains `serde` representations of internal types.
+    fn check_same_version(&self, kind: ArtifactKind) -> Result<(), JsonError> {
+        self.check(kind)?;
+        if self.openvm_version != OPENVM_VERSION {
+            return Err(JsonError::OpenVmVersionMismatch {
+                found: self.openvm_version.clone(),
+                expected: OPENVM_VERSION.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum JsonError {
+    #[error("unsupported JSON schema version {found} (supported: {supported})")]
+    UnsupportedSchemaVersion { found: u32, supported: u32 },
+    #[error("expected a JSON object of kind `{expected}`, found `{found}`")]
+    KindMismatch {
+        expected: ArtifactKind,
+        found: ArtifactKind,
+    },
+    #[error("JSON object was written by OpenVM {found}, but this is OpenVM {expected}")]
+    OpenVmVersionMismatch { found: String, expected: String },
+    #[error("invalid hex string: {0}")]
+    InvalidHex(String),
+    #[error("invalid field element: {0}")]
+    InvalidFieldElement(String),
+    #[error("field `{0}` does not match the verifying key")]
+    Inconsistent(&'static str),
+    #[error("invalid verifying key: {0}")]
+    InvalidVerifyingKey(String),
+}
+
+/// JSON export of [AppVerifyingKey].
+///
+/// `per_air` lists what an external verifier needs to know about each AIR besides its
+/// constraints. `app_vm_vk` is the full verifying key, including the constraints of each AIR as a
+/// DAG of symbolic expressions, in the `serde` representation of the version of OpenVM that wrote
+/// it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AppVerifyingKeyJson {
+    #[serde(flatten)]
+    pub header: JsonHeader,
+    pub fri_params: FriParameters,
+    pub memory_dimensions: MemoryDimensions,
+    pub log_up_pow_bits: usize,
+    pub per_air: Vec<AirVerifyingKeyJson>,
+    pub app_vm_vk: serde_json::Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AirVerifyingKeyJson {
+    /// Commitment to the preprocessed trace, if the AIR has one.
+    pub preprocessed_commit: Option<[u32; DIGEST_SIZE]>,
+    pub preprocessed_width: Option<usize>,
+    pub cached_main_widths: Vec<usize>,
+    pub common_main_width: usize,
+    pub after_challenge_widths: Vec<usize>,
+    pub quotient_degree: usize,
+    pub num_public_values: usize,
+    pub num_challenges_to_sample: Vec<usize>,
+    pub num_exposed_values_after_challenge: Vec<usize>,
+}
+
+impl From<&StarkVerifyingKey<F, Com<SC>>> for AirVerifyingKeyJson {
+    fn from(vk: &StarkVerifyingKey<F, Com<SC>>) -> Self {
+        Self {
+            preprocessed_commit: vk
+                .preprocessed_data
+                .as_ref()
+                .map(|data| digest_to_u32(data.commit)),
+            preprocessed_width: vk.params.width.preprocessed,
+            cached_main_widths: vk.params.width.cached_mains.clone(),
+            common_main_width: vk.params.width.common_main,
+            after_challenge_widths: vk.params.width.after_challenge.clone(),
+            quotient_degree: vk.quotient_degree as usize,
+            num_public_values: vk.params.num_public_values,
+            num_challenges_to_sample: vk.params.num_challenges_to_sample.clone(),
+            num_exposed_values_after_challenge: vk
+                .params
+                .num_exposed_values_after_challenge
+                .clone(),
+        }
+    }
+}
+
+impl From<&AppVerifyingKey> for AppVerifyingKeyJson {
+    fn from(app_vk: &AppVerifyingKey) -> Self {
+        Self {
+            header: JsonHeader::new(ArtifactKind::AppVerifyingKey),
+            fri_params: app_vk.fri_params,
+            memory_dimensions: app_vk.memory_dimensions,
+            log_up_pow_bits: app_vk.app_vm_vk.log_up_pow_bits,
+            per_air: app_vk.app_vm_vk.per_air.iter().map(Into::into).collect(),
+            app_vm_vk: serde_json::to_value(&app_vk.app_vm_vk)
+                .expect("failed to serialize verifying key"),
+        }
+    }
+}
+
+impl TryFrom<AppVerifyingKeyJson> for AppVerifyingKey {
+    type Error = JsonError;
+
+    fn try_from(json: AppVerifyingKeyJson) -> Result<Self, JsonError> {
+        json.header
+            .check_same_version(ArtifactKind::AppVerifyingKey)?;
+        let app_vm_vk: MultiStarkVerifyingKey<SC> = serde_json::from_value(json.app_vm_vk)
+            .map_err(|e| JsonError::InvalidVerifyingKey(e.to_string()))?;
+        if app_vm_vk.log_up_pow_bits != json.log_up_pow_bits {
+            return Err(JsonError::Inconsistent("log_up_pow_bits"));
+        }
+        if app_vm_vk
+            .per_air
+            .iter()
+            .map(AirVerifyingKeyJson::from)
+            .ne(json.per_air)
+        {
+            return Err(JsonError::Inconsistent("per_air"));
+        }
+        Ok(Self {
+            fri_params: json.fri_params,
+            app_vm_vk,
+            memory_dimensions: json.memory_dimensions,
+        })
+    }
+}
+
+/// JSON export of [AppExecutionCommit]. The BN254 commits are the values exposed as public
+/// values by the EVM proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppExecutionCommitJson {
+    #[serde(flatten)]
+    pub header: JsonHeader,
+    pub exe_commit: [u32; DIGEST_SIZE],
+    pub exe_commit_bn254: String,
+    pub leaf_vm_verifier_commit: [u32; DIGEST_SIZE],
+    pub leaf_vm_verifier_commit_bn254: String,
+}
+
+impl From<&AppExecutionCommit<F>> for AppExecutionCommitJson {
+    fn from(commit: &AppExecutionCommit<F>) -> Self {
+        Self {
+            header: JsonHeader::new(ArtifactKind::AppExecutionCommit),
+            exe_commit: commit.exe_commit.map(|x| x.as_canonical_u32()),
+            exe_commit_bn254: bn254_to_hex(&commit.exe_commit_to_bn254()),
+            leaf_vm_verifier_commit: commit.leaf_vm_verifier_commit.map(|x| x.as_canonical_u32()),
+            leaf_vm_verifier_commit_bn254: bn254_to_hex(&commit.app_config_commit_to_bn254()),
+        }
+    }
+}
+
+impl TryFrom<AppExecutionCommitJson> for AppExecutionCommit<F> {
+    type Error = JsonError;
+
+    fn try_from(json: AppExecutionCommitJson) -> Result<Self, JsonError> {
+        json.header.check(ArtifactKind::AppExecutionCommit)?;
+        let exe_commit = babybear_array_from_u32(json.exe_commit)?;
+        let leaf_vm_verifier_commit = babybear_array_from_u32(json.leaf_vm_verifier_commit)?;
+        if !bn254_hex_eq(
+            &json.exe_commit_bn254,
+            &babybear_digest_to_bn254(&exe_commit),
+        )? {
+            return Err(JsonError::Inconsistent("exe_commit_bn254"));
+        }
+        if !bn254_hex_eq(
+            &json.leaf_vm_verifier_commit_bn254,
+            &babybear_digest_to_bn254(&leaf_vm_verifier_commit),
+        )? {
+            return Err(JsonError::Inconsistent("leaf_vm_verifier_commit_bn254"));
+        }
+        Ok(Self {
+            leaf_vm_verifier_commit,
+            exe_commit,
+        })
+    }
+}
+
+/// An element of the degree 4 extension of BabyBear, as its coefficients in the base field.
+pub type ExtJson = [u32; EXT_DEGREE];
+
+/// JSON export of a STARK [Proof], with the fields of the proof in the order the verifier reads
+/// them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StarkProofJson {
+    pub commitments: CommitmentsJson,
+    pub opened_values: OpenedValuesJson,
+    pub fri_proof: FriProofJson,
+    pub per_air: Vec<AirProofDataJson>,
+    /// Proof of work witness of the LogUp phase, if any AIR has interactions.
+    pub logup_pow_witness: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentsJson {
+    pub main_trace: Vec<[u32; DIGEST_SIZE]>,
+    pub after_challenge: Vec<[u32; DIGEST_SIZE]>,
+    pub quotient: [u32; DIGEST_SIZE],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenedValuesJson {
+    /// For each AIR with a preprocessed trace
+    pub preprocessed: Vec<AdjacentOpenedValuesJson>,
+    /// For each main trace commitment, for each matrix in the commitment
+    pub main: Vec<Vec<AdjacentOpenedValuesJson>>,
+    /// For each AIR, for each chunk of the quotient polynomial
+    pub quotient: Vec<Vec<Vec<ExtJson>>>,
+    /// For each phase after the challenges, for each matrix
+    pub after_challenge: Vec<Vec<AdjacentOpenedValuesJson>>,
+}
+
+/// Values of a trace opened at a point and at the next point of the trace domain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdjacentOpenedValuesJson {
+    pub local: Vec<ExtJson>,
+    pub next: Vec<ExtJson>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriProofJson {
+    pub commit_phase_commits: Vec<[u32; DIGEST_SIZE]>,
+    pub query_proofs: Vec<QueryProofJson>,
+    pub final_poly: Vec<ExtJson>,
+    pub pow_witness: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryProofJson {
+    /// For each committed batch of matrices, the opened rows and their Merkle proof
+    pub input_proof: Vec<BatchOpeningJson>,
+    pub commit_phase_openings: Vec<CommitPhaseProofStepJson>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchOpeningJson {
+    pub opened_values: Vec<Vec<u32>>,
+    pub opening_proof: Vec<[u32; DIGEST_SIZE]>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitPhaseProofStepJson {
+    pub sibling_value: ExtJson,
+    pub opening_proof: Vec<[u32; DIGEST_SIZE]>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AirProofDataJson {
+    pub air_id: usize,
+    /// Height of the trace
+    pub degree: usize,
+    pub public_values: Vec<u32>,
+    /// For each phase after the challenges
+    pub exposed_values_after_challenge: Vec<Vec<ExtJson>>,
+}
+
+impl From<&Proof<SC>> for StarkProofJson {
+    fn from(proof: &Proof<SC>) -> Self {
+        let commitments = &proof.commitments;
+        let values = &proof.opening.values;
+        let fri_proof = &proof.opening.proof;
+        Self {
+            commitments: CommitmentsJson {
+                main_trace: commitments
+                    .main_trace
+                    .iter()
+                    .map(|&c| digest_to_u32(c))
+                    .collect(),
+                after_challenge: commitments
+                    .after_challenge
+                    .iter()
+                    .map(|&c| digest_to_u32(c))
+                    .collect(),
+                quotient: digest_to_u32(commitments.quotient),
+            },
+            opened_values: OpenedValuesJson {
+                preprocessed: values.preprocessed.iter().map(Into::into).collect(),
+                main: values
+                    .main
+                    .iter()
+                    .map(|v| v.iter().map(Into::into).collect())
+                    .collect(),
+                quotient: values
+                    .quotient
+                    .iter()
+                    .map(|v| v.iter().map(|v| exts_to_u32(v)).collect())
+                    .collect(),
+                after_challenge: values
+                    .after_challenge
+                    .iter()
+                    .map(|v| v.iter().map(Into::into).collect())
+                    .collect(),
+            },
+            fri_proof: FriProofJson {
+                commit_phase_commits: fri_proof
+                    .commit_phase_commits
+                    .iter()
+                    .map(|&c| digest_to_u32(c))
+                    .collect(),
+                query_proofs: fri_proof
+                    .query_proofs
+                    .iter()
+                    .map(|query| QueryProofJson {
+                        input_proof: query
+                            .input_proof
+                            .iter()
+                            .map(|opening| BatchOpeningJson {
+                                opened_values: opening
+                                    .opened_values
+                                    .iter()
+                                    .map(|row| babybear_to_u32(row))
+                                    .collect(),
+                                opening_proof: opening
+                                    .opening_proof
+                                    .iter()
+                                    .map(|&d| digest_to_u32(d))
+                                    .collect(),
+                            })
+                            .collect(),
+                        commit_phase_openings: query
+                            .commit_phase_openings
+                            .iter()
+                            .map(|step| CommitPhaseProofStepJson {
+                                sibling_value: ext_to_u32(&step.sibling_value),
+                                opening_proof: step
+                                    .opening_proof
+                                    .iter()
+                                    .map(|&d| digest_to_u32(d))
+                                    .collect(),
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                final_poly: exts_to_u32(&fri_proof.final_poly),
+                pow_witness: fri_proof.pow_witness.as_canonical_u32(),
+            },
+            per_air: proof
+                .per_air
+                .iter()
+                .map(|air| AirProofDataJson {
+                    air_id: air.air_id,
+                    degree: air.degree,
+                    public_values: babybear_to_u32(&air.public_values),
+                    exposed_values_after_challenge: air
+                        .exposed_values_after_challenge
+                        .iter()
+                        .map(|v| exts_to_u32(v))
+                        .collect(),
+                })
+                .collect(),
+            logup_pow_witness: proof
+                .rap_phase_seq_proof
+                .as_ref()
+                .map(|p| p.logup_pow_witness.as_canonical_u32()),
+        }
+    }
+}
+
+impl From<&AdjacentOpenedValues<InnerChallenge>> for AdjacentOpenedValuesJson {
+    fn from(values: &AdjacentOpenedValues<InnerChallenge>) -> Self {
+        Self {
+            local: exts_to_u32(&values.local),
+            next: exts_to_u32(&values.next),
+        }
+    }
+}
+
+impl TryFrom<AdjacentOpenedValuesJson> for AdjacentOpenedValues<InnerChallenge> {
+    type Error = JsonError;
+
+    fn try_from(json: AdjacentOpenedValuesJson) -> Result<Self, JsonError> {
+        Ok(Self {
+            local: exts_from_u32(json.local)?,
+            next: exts_from_u32(json.next)?,
+        })
+    }
+}
+
+impl TryFrom<StarkProofJson> for Proof<SC> {
+    type Error = JsonError;
+
+    fn try_from(json: StarkProofJson) -> Result<Self, JsonError> {
+        let commitments = Commitments {
+            main_trace: digests_from_u32(json.commitments.main_trace)?,
+            after_challenge: digests_from_u32(json.commitments.after_challenge)?,
+            quotient: digest_from_u32(json.commitments.quotient)?,
+        };
+        let values = OpenedValues {
+            preprocessed: json
+                .opened_values
+                .preprocessed
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            main: json
+                .opened_values
+                .main
+                .into_iter()
+                .map(|v| v.into_iter().map(TryInto::try_into).collect())
+                .collect::<Result<_, _>>()?,
+            quotient: json
+                .opened_values
+                .quotient
+                .into_iter()
+                .map(|v| v.into_iter().map(exts_from_u32).collect())
+                .collect::<Result<_, _>>()?,
+            after_challenge: json
+                .opened_values
+                .after_challenge
+                .into_iter()
+                .map(|v| v.into_iter().map(TryInto::try_into).collect())
+                .collect::<Result<_, _>>()?,
+        };
+        let query_proofs = json
+            .fri_proof
+            .query_proofs
+            .into_iter()
+            .map(|query| {
+                let input_proof = query
+                    .input_proof
+                    .into_iter()
+                    .map(|opening| {
+                        Ok(InnerBatchOpening {
+                            opened_values: opening
+                                .opened_values
+                                .into_iter()
+                                .map(babybear_vec_from_u32)
+                                .collect::<Result<_, _>>()?,
+                            opening_proof: opening
+                                .opening_proof
+                                .into_iter()
+                                .map(babybear_array_from_u32)
+                                .collect::<Result<_, _>>()?,
+                        })
+                    })
+                    .collect::<Result<_, JsonError>>()?;
+                let commit_phase_openings = query
+                    .commit_phase_openings
+                    .into_iter()
+                    .map(|step| {
+                        Ok(InnerCommitPhaseStep {
+                            sibling_value: ext_from_u32(step.sibling_value)?,
+                            opening_proof: step
+                                .opening_proof
+                                .into_iter()
+                                .map(babybear_array_from_u32)
+                                .collect::<Result<_, _>>()?,
+                        })
+                    })
+                    .collect::<Result<_, JsonError>>()?;
+                Ok(InnerQueryProof {
+                    input_proof,
+                    commit_phase_openings,
+                })
+            })
+            .collect::<Result<_, JsonError>>()?;
+        let fri_proof = InnerFriProof {
+            commit_phase_commits: digests_from_u32(json.fri_proof.commit_phase_commits)?,
+            query_proofs,
+            final_poly: exts_from_u32(json.fri_proof.final_poly)?,
+            pow_witness: babybear_from_u32(json.fri_proof.pow_witness)?,
+        };
+        let per_air = json
+            .per_air
+            .into_iter()
+            .map(|air| {
+                Ok(AirProofData {
+                    air_id: air.air_id,
+                    degree: air.degree,
+                    public_values: babybear_vec_from_u32(air.public_values)?,
+                    exposed_values_after_challenge: air
+                        .exposed_values_after_challenge
+                        .into_iter()
+                        .map(exts_from_u32)
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect::<Result<_, JsonError>>()?;
+        let rap_phase_seq_proof = json
+            .logup_pow_witness
+            .map(|w| {
+                Ok::<_, JsonError>(FriLogUpPartialProof {
+                    logup_pow_witness: babybear_from_u32(w)?,
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            commitments,
+            opening: OpeningProof {
+                proof: fri_proof,
+                values,
+            },
+            per_air,
+            rap_phase_seq_proof,
+        })
+    }
+}
+
+/// JSON export of an app proof ([ContinuationVmProof]).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContinuationVmProofJson {
+    #[serde(flatten)]
+    pub header: JsonHeader,
+    /// The proof of each segment, in the execution order.
+    pub per_segment: Vec<StarkProofJson>,
+    pub user_public_values: Vec<u32>,
+    pub user_public_values_commit: [u32; CHUNK],
+    /// Merkle proof of `user_public_values_commit` in the final memory state, as the sibling
+    /// hashes from the root of the public values to the memory root.
+    pub user_public_values_proof: Vec<[u32; CHUNK]>,
+}
+
+impl From<&ContinuationVmProof<SC>> for ContinuationVmProofJson {
+    fn from(proof: &ContinuationVmProof<SC>) -> Self {
+        let user_public_values = &proof.user_public_values;
+        Self {
+            header: JsonHeader::new(ArtifactKind::AppProof),
+            per_segment: proof.per_segment.iter().map(Into::into).collect(),
+            user_public_values: babybear_to_u32(&user_public_values.public_values),
+            user_public_values_commit: user_public_values
+                .public_values_commit
+                .map(|x| x.as_canonical_u32()),
+            user_public_values_proof: user_public_values
+                .proof
+                .iter()
+                .map(|hash| hash.map(|x| x.as_canonical_u32()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ContinuationVmProofJson> for ContinuationVmProof<SC> {
+    type Error = JsonError;
+
+    fn try_from(json: ContinuationVmProofJson) -> Result<Self, JsonError> {
+        json.header.check(ArtifactKind::AppProof)?;
+        Ok(Self {
+            per_segment: json
+                .per_segment
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            user_public_values: UserPublicValuesProof {
+                proof: json
+                    .user_public_values_proof
+                    .into_iter()
+                    .map(babybear_array_from_u32)
+                    .collect::<Result<_, _>>()?,
+                public_values: babybear_vec_from_u32(json.user_public_values)?,
+                public_values_commit: babybear_array_from_u32(json.user_public_values_commit)?,
+            },
+        })
+    }
+}
+
+/// JSON export of the proof verified by the root verifier ([RootVmVerifierInput]).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootVerifierInputJson {
+    #[serde(flatten)]
+    pub header: JsonHeader,
+    /// The proofs of the leaf or internal verifier, in the execution order.
+    pub proofs: Vec<StarkProofJson>,
+    pub public_values: Vec<u32>,
+}
+
+impl From<&RootVmVerifierInput<SC>> for RootVerifierInputJson {
+    fn from(input: &RootVmVerifierInput<SC>) -> Self {
+        Self {
+            header: JsonHeader::new(ArtifactKind::RootVerifierInput),
+            proofs: input.proofs.iter().map(Into::into).collect(),
+            public_values: babybear_to_u32(&input.public_values),
+        }
+    }
+}
+
+impl TryFrom<RootVerifierInputJson> for RootVmVerifierInput<SC> {
+    type Error = JsonError;
+
+    fn try_from(json: RootVerifierInputJson) -> Result<Self, JsonError> {
+        json.header.check(ArtifactKind::RootVerifierInput)?;
+        Ok(Self {
+            proofs: json
+                .proofs
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            public_values: babybear_vec_from_u32(json.public_values)?,
+        })
+    }
+}
+
+/// JSON export of [EvmProof]. Unlike other exports, it does not depend on the version of OpenVM:
+/// `instances` and `proof` are exactly what the EVM verifier checks.
+///
+/// With the default public values handler, the instances are the 12 limbs of the KZG
+/// accumulator, the BN254 exe commit, the BN254 leaf verifier commit and the user public values.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvmProofJson {
+    #[serde(flatten)]
+    pub header: JsonHeader,
+    pub instances: Vec<Vec<String>>,
+    pub proof: String,
+}
+
+impl From<&EvmProof> for EvmProofJson {
+    fn from(proof: &EvmProof) -> Self {
+        Self {
+            header: JsonHeader::new(ArtifactKind::EvmProof),
+            instances: proof
+                .instances
+                .iter()
+                .map(|column| column.iter().map(fr_to_hex).collect())
+                .collect(),
+            proof: format!("0x{}", hex_string(&proof.proof)),
+        }
+    }
+}
+
+impl TryFrom<EvmProofJson> for EvmProof {
+    type Error = JsonError;
+
+    fn try_from(json: EvmProofJson) -> Result<Self, JsonError> {
+        json.header.check(ArtifactKind::EvmProof)?;
+        let instances = json
+            .instances
+            .iter()
+            .map(|column| column.iter().map(|x| fr_from_hex(x)).collect())
+            .collect::<Result<_, _>>()?;
+        Ok(EvmProof {
+            instances,
+            proof: parse_hex(&json.proof)?,
+        })
+    }
+}
+
+pub fn read_json_from_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> eyre::Result<T> {
+    let bytes = read(path)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+pub fn write_json_to_file<T: Serialize, P: AsRef<Path>>(path: P, data: &T) -> eyre::Result<()> {
+    if let Some(parent) = path.as_ref().parent() {
+        create_dir_all(parent)?;
+    }
+    write(path, serde_json::to_string_pretty(data)?)?;
+    Ok(())
+}
+
+/// Parses a hex string, with or without the `0x` prefix.
+fn parse_hex(s: &str) -> Result<Vec<u8>, JsonError> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    if digits.len() % 2 != 0 {
+        return Err(JsonError::InvalidHex(s.to_string()));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            digits
+                .get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| JsonError::InvalidHex(s.to_string()))
+        })
+        .collect()
+}
+
+fn babybear_to_u32(values: &[F]) -> Vec<u32> {
+    values.iter().map(|x| x.as_canonical_u32()).collect()
+}
+
+fn babybear_from_u32(x: u32) -> Result<F, JsonError> {
+    if x >= F::ORDER_U32 {
+        return Err(JsonError::InvalidFieldElement(x.to_string()));
+    }
+    Ok(F::from_canonical_u32(x))
+}
+
+fn babybear_vec_from_u32(values: Vec<u32>) -> Result<Vec<F>, JsonError> {
+    values.into_iter().map(babybear_from_u32).collect()
+}
+
+fn babybear_array_from_u32<const N: usize>(values: [u32; N]) -> Result<[F; N], JsonError> {
+    if let Some(x) = values.iter().find(|&&x| x >= F::ORDER_U32) {
+        return Err(JsonError::InvalidFieldElement(x.to_string()));
+    }
+    Ok(values.map(F::from_canonical_u32))
+}
+
+fn digest_to_u32(digest: impl Into<[F; DIGEST_SIZE]>) -> [u32; DIGEST_SIZE] {
+    digest.into().map(|x| x.as_canonical_u32())
+}
+
+fn digest_from_u32(digest: [u32; DIGEST_SIZE]) -> Result<Com<SC>, JsonError> {
+    Ok(babybear_array_from_u32(digest)?.into())
+}
+
+fn digests_from_u32(digests: Vec<[u32; DIGEST_SIZE]>) -> Result<Vec<Com<SC>>, JsonError> {
+    digests.into_iter().map(digest_from_u32).collect()
+}
+
+fn ext_to_u32(x: &InnerChallenge) -> ExtJson {
+    std::array::from_fn(|i| x.as_base_slice()[i].as_canonical_u32())
+}
+
+fn exts_to_u32(values: &[InnerChallenge]) -> Vec<ExtJson> {
+    values.iter().map(ext_to_u32).collect()
+}
+
+fn ext_from_u32(x: ExtJson) -> Result<InnerChallenge, JsonError> {
+    Ok(InnerChallenge::from_base_slice(&babybear_array_from_u32(
+        x,
+    )?))
+}
+
+fn exts_from_u32(values: Vec<ExtJson>) -> Result<Vec<InnerChallenge>, JsonError> {
+    values.into_iter().map(ext_from_u32).collect()
+}
+
+fn fr_to_hex(x: &Fr) -> String {
+    let mut bytes = x.to_bytes();
+    bytes.reverse();
+    format!("0x{}", hex_string(&bytes))
+}
+
+fn fr_from_hex(s: &str) -> Result<Fr, JsonError> {
+    let mut bytes: [u8; 32] = parse_hex(s)?
+        .try_into()
+        .map_err(|_| JsonError::InvalidFieldElement(s.to_string()))?;
+    bytes.reverse();
+    Option::from(Fr::from_bytes(&bytes))
+        .ok_or_else(|| JsonError::InvalidFieldElement(s.to_string()))
+}
+
+fn bn254_to_hex(x: &Bn254Fr) -> String {
+    fr_to_hex(&convert_fr(x))
+}
+
+fn bn254_hex_eq(s: &str, x: &Bn254Fr) -> Result<bool, JsonError> {
+    Ok(fr_from_hex(s)? == convert_fr(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_execution_commit_json_roundtrip() {
+        let commit = AppExecutionCommit {
+            leaf_vm_verifier_commit: std::array::from_fn(|i| F::from_canonical_usize(i)),
+            exe_commit: [F::NEG_ONE; DIGEST_SIZE],
+        };
+        let json = AppExecutionCommitJson::from(&commit);
+        let json: AppExecutionCommitJson =
+            serde_json::from_str(&serde_json::to_string(&json).unwrap()).unwrap();
+        let decoded = AppExecutionCommit::<F>::try_from(json.clone()).unwrap();
+        assert_eq!(decoded.exe_commit, commit.exe_commit);
+        assert_eq!(
+            decoded.leaf_vm_verifier_commit,
+            commit.leaf_vm_verifier_commit
+        );
+
+        let mut tampered = json.clone();
+        tampered.exe_commit[0] = 0;
+        assert!(matches!(
+            AppExecutionCommit::<F>::try_from(tampered),
+            Err(JsonError::Inconsistent("exe_commit_bn254"))
+        ));
+        let mut tampered = json;
+        tampered.exe_commit[0] = F::ORDER_U32;
+        assert!(matches!(
+            AppExecutionCommit::<F>::try_from(tampered),
+            Err(JsonError::InvalidFieldElement(_))
+        ));
+    }
+
+    #[test]
+    fn test_evm_proof_json_roundtrip() {
+        let proof = EvmProof {
+            instances: vec![vec![Fr::from(1), Fr::from(0x1234), -Fr::from(1)]],
+            proof: vec![0xde, 0xad, 0xbe, 0xef],
+        };
+        let json = EvmProofJson::from(&proof);
+        assert_eq!(json.proof, "0xdeadbeef");
+        assert_eq!(json.instances[0][1], format!("0x{}1234", "0".repeat(60)));
+        let decoded = EvmProof::try_from(json.clone()).unwrap();
+        assert_eq!(decoded.instances, proof.instances);
+        assert_eq!(decoded.proof, proof.proof);
+
+        let mut tampered = json;
+        tampered.header.kind = ArtifactKind::AppProof;
+        assert!(matches!(
+            EvmProof::try_from(tampered),
+            Err(JsonError::KindMismatch { .. })
+        ));
+    }
+
+    fn test_stark_proof_json() -> StarkProofJson {
+        let digest = |seed: u32| std::array::from_fn(|i| seed + i as u32);
+        let ext = |seed: u32| [seed, seed + 1, seed + 2, F::ORDER_U32 - 1];
+        let adjacent = |seed: u32| AdjacentOpenedValuesJson {
+            local: vec![ext(seed), ext(seed + 10)],
+            next: vec![ext(seed + 20), ext(seed + 30)],
+        };
+        StarkProofJson {
+            commitments: CommitmentsJson {
+                main_trace: vec![digest(0), digest(100)],
+                after_challenge: vec![digest(200)],
+                quotient: digest(300),
+            },
+            opened_values: OpenedValuesJson {
+                preprocessed: vec![adjacent(1)],
+                main: vec![vec![adjacent(2), adjacent(3)], vec![adjacent(4)]],
+                quotient: vec![vec![vec![ext(5)], vec![ext(6)]]],
+                after_challenge: vec![vec![adjacent(7)]],
+            },
+            fri_proof: FriProofJson {
+                commit_phase_commits: vec![digest(400)],
+                query_proofs: vec![QueryProofJson {
+                    input_proof: vec![BatchOpeningJson {
+                        opened_values: vec![vec![1, 2, 3], vec![4]],
+                        opening_proof: vec![digest(500), digest(600)],
+                    }],
+                    commit_phase_openings: vec![CommitPhaseProofStepJson {
+                        sibling_value: ext(8),
+                        opening_proof: vec![digest(700)],
+                    }],
+                }],
+                final_poly: vec![ext(9)],
+                pow_witness: 1234,
+            },
+            per_air: vec![AirProofDataJson {
+                air_id: 3,
+                degree: 1 << 10,
+                public_values: vec![0, 1, F::ORDER_U32 - 1],
+                exposed_values_after_challenge: vec![vec![ext(11), ext(12)]],
+            }],
+            logup_pow_witness: Some(5678),
+        }
+    }
+
+    #[test]
+    fn test_stark_proof_json_roundtrip() {
+        let json = test_stark_proof_json();
+        let proof = Proof::<SC>::try_from(json.clone()).unwrap();
+        assert_eq!(
+            digest_to_u32(proof.commitments.quotient),
+            json.commitments.quotient
+        );
+        assert_eq!(
+            proof.per_air[0].public_values,
+            babybear_vec_from_u32(json.per_air[0].public_values.clone()).unwrap()
+        );
+        assert_eq!(StarkProofJson::from(&proof), json);
+
+        let serialized = serde_json::to_value(&json).unwrap();
+        assert_eq!(serialized["commitments"]["quotient"][1], 301);
+        assert_eq!(serialized["per_air"][0]["degree"], 1024);
+
+        let mut tampered = json;
+        tampered.opened_values.main[1][0].next[0][2] = F::ORDER_U32;
+        assert!(matches!(
+            Proof::<SC>::try_from(tampered),
+            Err(JsonError::InvalidFieldElement(_))
+        ));
+    }
+
+    #[test]
+    fn test_continuation_vm_proof_json_roundtrip() {
+        let json = ContinuationVmProofJson {
+            header: JsonHeader::new(ArtifactKind::AppProof),
+            per_segment: vec![test_stark_proof_json(), test_stark_proof_json()],
+            user_public_values: (0..32).collect(),
+            user_public_values_commit: [7; CHUNK],
+            user_public_values_proof: vec![[8; CHUNK], [9; CHUNK]],
+        };
+        let json: ContinuationVmProofJson =
+            serde_json::from_str(&serde_json::to_string(&json).unwrap()).unwrap();
+        let proof = ContinuationVmProof::<SC>::try_from(json.clone()).unwrap();
+        assert_eq!(proof.per_segment.len(), 2);
+        assert_eq!(ContinuationVmProofJson::from(&proof), json);
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("0x00ff10").unwrap(), vec![0, 255, 16]);
+        assert_eq!(parse_hex("00FF").unwrap(), vec![0, 255]);
+        assert!(parse_hex("0x0").is_err());
+        assert!(parse_hex("0xzz").is_err());
+    }
+}
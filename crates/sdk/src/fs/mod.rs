@@ -14,60 +14,75 @@ use crate::{
     F, SC,
 };
 
+mod artifact;
+pub use artifact::*;
+mod json;
+pub use json::*;
+
 pub fn read_exe_from_file<P: AsRef<Path>>(path: P) -> Result<VmExe<F>> {
-    read_from_file_bitcode(path)
+    read_from_file_bitcode(ArtifactKind::Exe, path)
 }
 
 pub fn write_exe_to_file<P: AsRef<Path>>(exe: VmExe<F>, path: P) -> Result<()> {
-    write_to_file_bitcode(path, exe)
+    write_to_file_bitcode(ArtifactKind::Exe, path, exe)
 }
 
 pub fn read_app_pk_from_file<VC: VmConfig<F>, P: AsRef<Path>>(
     path: P,
 ) -> Result<AppProvingKey<VC>> {
-    read_from_file_bitcode(path)
+    read_from_file_bitcode(ArtifactKind::AppProvingKey, path)
 }
 
 pub fn write_app_pk_to_file<VC: VmConfig<F>, P: AsRef<Path>>(
     app_pk: AppProvingKey<VC>,
     path: P,
 ) -> Result<()> {
-    write_to_file_bitcode(path, app_pk)
+    let options = ArtifactOptions::default().with_config_hash(app_pk.config_hash());
+    write_artifact_to_file(ArtifactKind::AppProvingKey, path, &app_pk, &options)
 }
 
 pub fn read_app_vk_from_file<P: AsRef<Path>>(path: P) -> Result<AppVerifyingKey> {
-    read_from_file_bitcode(path)
+    read_from_file_bitcode(ArtifactKind::AppVerifyingKey, path)
 }
 
-pub fn write_app_vk_to_file<P: AsRef<Path>>(app_vk: AppVerifyingKey, path: P) -> Result<()> {
-    write_to_file_bitcode(path, app_vk)
+/// Writes `app_vk` with the [AppProvingKey::config_hash] of the key it was taken from.
+pub fn write_app_vk_to_file<P: AsRef<Path>>(
+    app_vk: AppVerifyingKey,
+    config_hash: [u8; 32],
+    path: P,
+) -> Result<()> {
+    let options = ArtifactOptions::default().with_config_hash(config_hash);
+    write_artifact_to_file(ArtifactKind::AppVerifyingKey, path, &app_vk, &options)
 }
 
 pub fn read_app_proof_from_file<P: AsRef<Path>>(path: P) -> Result<ContinuationVmProof<SC>> {
-    read_from_file_bitcode(path)
+    read_from_file_bitcode(ArtifactKind::AppProof, path)
 }
 
+/// Writes `proof` with the [AppProvingKey::config_hash] of the key it was generated with.
 pub fn write_app_proof_to_file<P: AsRef<Path>>(
     proof: ContinuationVmProof<SC>,
+    config_hash: [u8; 32],
     path: P,
 ) -> Result<()> {
-    write_to_file_bitcode(path, proof)
+    let options = ArtifactOptions::default().with_config_hash(config_hash);
+    write_artifact_to_file(ArtifactKind::AppProof, path, &proof, &options)
 }
 
 pub fn read_agg_pk_from_file<P: AsRef<Path>>(path: P) -> Result<AggProvingKey> {
-    read_from_file_bitcode(path)
+    read_from_file_bitcode(ArtifactKind::AggProvingKey, path)
 }
 
 pub fn write_agg_pk_to_file<P: AsRef<Path>>(agg_pk: AggProvingKey, path: P) -> Result<()> {
-    write_to_file_bitcode(path, agg_pk)
+    write_to_file_bitcode(ArtifactKind::AggProvingKey, path, agg_pk)
 }
 
 pub fn read_evm_proof_from_file<P: AsRef<Path>>(path: P) -> Result<EvmProof> {
-    read_from_file_bitcode(path)
+    read_from_file_bitcode(ArtifactKind::EvmProof, path)
 }
 
 pub fn write_evm_proof_to_file<P: AsRef<Path>>(proof: EvmProof, path: P) -> Result<()> {
-    write_to_file_bitcode(path, proof)
+    write_to_file_bitcode(ArtifactKind::EvmProof, path, proof)
 }
 
 pub fn read_evm_verifier_from_file<P: AsRef<Path>>(path: P) -> Result<EvmVerifier> {
@@ -79,26 +94,27 @@ pub fn write_evm_verifier_to_file<P: AsRef<Path>>(verifier: EvmVerifier, path: P
 }
 
 pub fn read_object_from_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
-    read_from_file_bitcode(path)
+    read_from_file_bitcode(ArtifactKind::Object, path)
 }
 
 pub fn write_object_to_file<T: Serialize, P: AsRef<Path>>(path: P, data: T) -> Result<()> {
-    write_to_file_bitcode(path, data)
+    write_to_file_bitcode(ArtifactKind::Object, path, data)
 }
 
-pub(crate) fn read_from_file_bitcode<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
-    let data = std::fs::read(path)?;
-    let ret = bitcode::deserialize(&data)?;
+pub(crate) fn read_from_file_bitcode<T: DeserializeOwned, P: AsRef<Path>>(
+    kind: ArtifactKind,
+    path: P,
+) -> Result<T> {
+    let (_, ret) = read_artifact_from_file(kind, path)?;
     Ok(ret)
 }
 
-pub(crate) fn write_to_file_bitcode<T: Serialize, P: AsRef<Path>>(path: P, data: T) -> Result<()> {
-    let bytes = bitcode::serialize(&data)?;
-    if let Some(parent) = path.as_ref().parent() {
-        create_dir_all(parent)?;
-    }
-    write(path, bytes)?;
-    Ok(())
+pub(crate) fn write_to_file_bitcode<T: Serialize, P: AsRef<Path>>(
+    kind: ArtifactKind,
+    path: P,
+    data: T,
+) -> Result<()> {
+    write_artifact_to_file(kind, path, &data, &ArtifactOptions::default())
 }
 
 pub fn read_from_file_bytes<T: From<Vec<u8>>, P: AsRef<Path>>(path: P) -> Result<T> {
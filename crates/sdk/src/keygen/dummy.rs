@@ -34,7 +34,7 @@ use crate::{
     verifier::{
         internal::types::InternalVmVerifierInput,
         leaf::{types::LeafVmVerifierInput, LeafVmVerifierConfig},
-        root::types::RootVmVerifierInput,
+        root::types::{BatchRootVmVerifierInput, RootVmVerifierInput},
     },
     NonRootCommittedExe, F, SC,
 };
@@ -47,16 +47,11 @@ use crate::{
 pub(super) fn compute_root_proof_heights(
     root_vm_config: NativeConfig,
     root_exe: VmExe<F>,
-    dummy_internal_proof: &Proof<SC>,
+    dummy_root_input: Vec<Vec<F>>,
 ) -> (Vec<usize>, VmComplexTraceHeights) {
-    let num_user_public_values = root_vm_config.system.num_public_values - 2 * DIGEST_SIZE;
-    let root_input = RootVmVerifierInput {
-        proofs: vec![dummy_internal_proof.clone()],
-        public_values: vec![F::ZERO; num_user_public_values],
-    };
     let vm = SingleSegmentVmExecutor::new(root_vm_config);
     let res = vm
-        .execute_and_compute_heights(root_exe, root_input.write())
+        .execute_and_compute_heights(root_exe, dummy_root_input)
         .unwrap();
     let air_heights: Vec<_> = res
         .air_heights
@@ -68,6 +63,29 @@ pub(super) fn compute_root_proof_heights(
     (air_heights, internal_heights)
 }
 
+/// Input for the root verifier which verifies `dummy_internal_proof`.
+pub(crate) fn dummy_root_verifier_input(
+    root_vm_config: &NativeConfig,
+    dummy_internal_proof: &Proof<SC>,
+) -> RootVmVerifierInput<SC> {
+    let num_user_public_values = root_vm_config.system.num_public_values - 2 * DIGEST_SIZE;
+    RootVmVerifierInput {
+        proofs: vec![dummy_internal_proof.clone()],
+        public_values: vec![F::ZERO; num_user_public_values],
+    }
+}
+
+/// Input for the batch root verifier with `max_num_programs` copies of `dummy_internal_proof`.
+pub(crate) fn dummy_batch_root_verifier_input(
+    max_num_programs: usize,
+    dummy_internal_proof: &Proof<SC>,
+) -> BatchRootVmVerifierInput<SC> {
+    BatchRootVmVerifierInput {
+        proofs: vec![dummy_internal_proof.clone(); max_num_programs],
+        num_programs: max_num_programs,
+    }
+}
+
 pub(super) fn dummy_internal_proof(
     internal_vm_pk: Arc<VmProvingKey<SC, NativeConfig>>,
     internal_exe: Arc<NonRootCommittedExe>,
@@ -1,15 +1,23 @@
 use std::sync::Arc;
 
 use derivative::Derivative;
-use dummy::{compute_root_proof_heights, dummy_internal_proof_riscv_app_vm};
+use dummy::{
+    compute_root_proof_heights, dummy_batch_root_verifier_input, dummy_internal_proof_riscv_app_vm,
+    dummy_root_verifier_input,
+};
 use openvm_circuit::{
-    arch::{VirtualMachine, VmConfig},
+    arch::{instructions::program::Program, VirtualMachine, VmConfig},
     system::{memory::dimensions::MemoryDimensions, program::trace::VmCommittedExe},
 };
 use openvm_native_circuit::NativeConfig;
 use openvm_native_compiler::ir::DIGEST_SIZE;
-use openvm_native_recursion::halo2::{
-    utils::Halo2ParamsReader, verifier::Halo2VerifierProvingKey, wrapper::Halo2WrapperProvingKey,
+use openvm_native_recursion::{
+    groth16::verifier::Groth16VerifierProvingKey,
+    halo2::{
+        utils::Halo2ParamsReader, verifier::Halo2VerifierProvingKey,
+        wrapper::Halo2WrapperProvingKey,
+    },
+    hints::Hintable,
 };
 use openvm_stark_sdk::{
     config::{
@@ -30,12 +38,15 @@ use tracing::info_span;
 
 use crate::{
     commit::babybear_digest_to_bn254,
-    config::{AggConfig, AggStarkConfig, AppConfig},
+    config::{AggConfig, AggStarkConfig, AppConfig, Halo2Config},
+    fs::config_hash,
     keygen::perm::AirIdPermutation,
     prover::vm::types::VmProvingKey,
-    static_verifier::StaticVerifierPvHandler,
+    static_verifier::{BatchRootVerifierPvHandler, StaticVerifierPvHandler},
     verifier::{
-        internal::InternalVmVerifierConfig, leaf::LeafVmVerifierConfig, root::RootVmVerifierConfig,
+        internal::InternalVmVerifierConfig,
+        leaf::LeafVmVerifierConfig,
+        root::{BatchRootVmVerifierConfig, RootVmVerifierConfig},
     },
     NonRootCommittedExe, RootSC, F, SC,
 };
@@ -61,6 +72,8 @@ pub struct AppVerifyingKey {
 pub struct AggProvingKey {
     pub agg_stark_pk: AggStarkProvingKey,
     pub halo2_pk: Halo2ProvingKey,
+    /// Halo2 proving key for the batch root verifier. `None` if batch aggregation is disabled.
+    pub batch_halo2_pk: Option<Halo2ProvingKey>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -69,6 +82,10 @@ pub struct AggStarkProvingKey {
     pub internal_vm_pk: Arc<VmProvingKey<SC, NativeConfig>>,
     pub internal_committed_exe: Arc<NonRootCommittedExe>,
     pub root_verifier_pk: RootVerifierProvingKey,
+    /// Root verifier for batches of app executions. `None` if batch aggregation is disabled.
+    pub batch_root_verifier_pk: Option<RootVerifierProvingKey>,
+    /// Maximum number of app executions aggregated by the batch root verifier.
+    pub max_num_batch_programs: usize,
 }
 
 /// Attention: the size of this struct is VERY large, usually >10GB.
@@ -82,6 +99,14 @@ pub struct Halo2ProvingKey {
     pub profiling: bool,
 }
 
+impl<VC: VmConfig<F>> AppProvingKey<VC> {
+    /// Hash of the app VM config and FRI parameters, recorded in the headers of artifacts
+    /// produced with this key.
+    pub fn config_hash(&self) -> [u8; 32] {
+        config_hash(&self.app_vm_pk.vm_config, &self.app_vm_pk.fri_params)
+    }
+}
+
 impl<VC: VmConfig<F>> AppProvingKey<VC>
 where
     VC::Executor: Chip<SC>,
@@ -89,8 +114,9 @@ where
 {
     pub fn keygen(config: AppConfig<VC>) -> Self {
         let app_engine = BabyBearPoseidon2Engine::new(config.app_fri_params.fri_params);
+        let app_vm_config = config.effective_app_vm_config();
         let app_vm_pk = {
-            let vm = VirtualMachine::new(app_engine, config.app_vm_config.clone());
+            let vm = VirtualMachine::new(app_engine, app_vm_config.clone());
             let vm_pk = vm.keygen();
             assert!(
                 vm_pk.max_constraint_degree
@@ -98,7 +124,7 @@ where
             );
             VmProvingKey {
                 fri_params: config.app_fri_params.fri_params,
-                vm_config: config.app_vm_config.clone(),
+                vm_config: app_vm_config,
                 vm_pk,
             }
         };
@@ -152,6 +178,19 @@ where
     }
 }
 
+/// Groth16 alternative to [Halo2ProvingKey]: the static verifier is proven directly with Groth16,
+/// without a wrapper circuit.
+///
+/// Attention: Groth16 needs a trusted setup for each circuit. Keys generated by
+/// [Groth16ProvingKey::keygen_with_local_setup] are only for testing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Groth16ProvingKey {
+    /// Static verifier to verify a stark proof of the root verifier.
+    pub verifier: Groth16VerifierProvingKey,
+    /// Whether to collect detailed profiling metrics
+    pub profiling: bool,
+}
+
 impl AggStarkProvingKey {
     pub fn keygen(config: AggStarkConfig) -> Self {
         tracing::info_span!("agg_stark_keygen", group = "agg_stark_keygen")
@@ -210,8 +249,6 @@ impl AggStarkProvingKey {
         );
 
         let root_verifier_pk = {
-            let mut root_engine = BabyBearPoseidon2RootEngine::new(config.root_fri_params);
-            root_engine.max_constraint_degree = config.root_max_constraint_degree;
             let root_program = RootVmVerifierConfig {
                 leaf_fri_params: config.leaf_fri_params,
                 internal_fri_params: config.internal_fri_params,
@@ -220,40 +257,42 @@ impl AggStarkProvingKey {
                 compiler_options: config.compiler_options,
             }
             .build_program(&leaf_vm_vk, &internal_vm_vk);
-            let root_committed_exe = Arc::new(VmCommittedExe::<RootSC>::commit(
-                root_program.into(),
-                root_engine.config.pcs(),
-            ));
-
-            let vm = VirtualMachine::new(root_engine, root_vm_config.clone());
-            let mut vm_pk = vm.keygen();
-            assert!(vm_pk.max_constraint_degree <= config.root_fri_params.max_constraint_degree());
-
-            let (air_heights, _internal_heights) = compute_root_proof_heights(
-                root_vm_config.clone(),
-                root_committed_exe.exe.clone(),
-                &internal_proof,
-            );
-            let root_air_perm = AirIdPermutation::compute(&air_heights);
-            root_air_perm.permute(&mut vm_pk.per_air);
-
-            RootVerifierProvingKey {
-                vm_pk: Arc::new(VmProvingKey {
-                    fri_params: config.root_fri_params,
-                    vm_config: root_vm_config,
-                    vm_pk,
-                }),
-                root_committed_exe,
-                air_heights,
-            }
+            let dummy_root_input = dummy_root_verifier_input(&root_vm_config, &internal_proof);
+            root_verifier_keygen(
+                &config,
+                root_vm_config,
+                root_program,
+                dummy_root_input.write(),
+            )
         };
 
+        let batch_root_verifier_pk = (config.max_num_batch_programs > 0).then(|| {
+            let batch_root_program = BatchRootVmVerifierConfig {
+                leaf_fri_params: config.leaf_fri_params,
+                internal_fri_params: config.internal_fri_params,
+                max_num_programs: config.max_num_batch_programs,
+                internal_vm_verifier_commit: internal_committed_exe.get_program_commit().into(),
+                compiler_options: config.compiler_options,
+            }
+            .build_program(&leaf_vm_vk, &internal_vm_vk);
+            let dummy_batch_root_input =
+                dummy_batch_root_verifier_input(config.max_num_batch_programs, &internal_proof);
+            root_verifier_keygen(
+                &config,
+                config.batch_root_verifier_vm_config(),
+                batch_root_program,
+                dummy_batch_root_input.write(),
+            )
+        });
+
         (
             Self {
                 leaf_vm_pk,
                 internal_vm_pk,
                 internal_committed_exe,
                 root_verifier_pk,
+                batch_root_verifier_pk,
+                max_num_batch_programs: config.max_num_batch_programs,
             },
             internal_proof,
         )
@@ -318,8 +357,49 @@ impl AggProvingKey {
             AggStarkProvingKey::dummy_proof_and_keygen(agg_stark_config);
         let dummy_root_proof = agg_stark_pk
             .root_verifier_pk
-            .generate_dummy_root_proof(dummy_internal_proof);
-        let verifier = agg_stark_pk.root_verifier_pk.keygen_static_verifier(
+            .generate_dummy_root_proof(dummy_internal_proof.clone());
+        let halo2_pk = Halo2ProvingKey::keygen(
+            &halo2_config,
+            reader,
+            &agg_stark_pk.root_verifier_pk,
+            dummy_root_proof,
+            pv_handler,
+        );
+        let batch_halo2_pk =
+            agg_stark_pk
+                .batch_root_verifier_pk
+                .as_ref()
+                .map(|batch_root_verifier_pk| {
+                    let dummy_batch_root_proof = batch_root_verifier_pk
+                        .generate_dummy_batch_root_proof(
+                            agg_stark_pk.max_num_batch_programs,
+                            dummy_internal_proof,
+                        );
+                    Halo2ProvingKey::keygen(
+                        &halo2_config,
+                        reader,
+                        batch_root_verifier_pk,
+                        dummy_batch_root_proof,
+                        Some(&BatchRootVerifierPvHandler),
+                    )
+                });
+        Self {
+            agg_stark_pk,
+            halo2_pk,
+            batch_halo2_pk,
+        }
+    }
+}
+
+impl Halo2ProvingKey {
+    fn keygen(
+        halo2_config: &Halo2Config,
+        reader: &impl Halo2ParamsReader,
+        root_verifier_pk: &RootVerifierProvingKey,
+        dummy_root_proof: Proof<RootSC>,
+        pv_handler: Option<&impl StaticVerifierPvHandler>,
+    ) -> Self {
+        let verifier = root_verifier_pk.keygen_static_verifier(
             &reader.read_params(halo2_config.verifier_k),
             dummy_root_proof,
             pv_handler,
@@ -330,18 +410,82 @@ impl AggProvingKey {
         } else {
             Halo2WrapperProvingKey::keygen_auto_tune(reader, dummy_snark)
         };
-        let halo2_pk = Halo2ProvingKey {
+        Self {
             verifier,
             wrapper,
             profiling: halo2_config.profiling,
-        };
+        }
+    }
+}
+
+impl Groth16ProvingKey {
+    /// Keygen the Groth16 static verifier of the root verifier in `agg_stark_pk`, with a trusted
+    /// setup run on this machine. For testing only.
+    #[cfg(feature = "test-utils")]
+    pub fn keygen_with_local_setup(
+        agg_stark_pk: &AggStarkProvingKey,
+        pv_handler: Option<&impl StaticVerifierPvHandler>,
+        profiling: bool,
+        rng: &mut (impl rand::RngCore + rand::CryptoRng),
+    ) -> Self {
+        let dummy_internal_proof = dummy_internal_proof_riscv_app_vm(
+            agg_stark_pk.leaf_vm_pk.clone(),
+            agg_stark_pk.internal_vm_pk.clone(),
+            agg_stark_pk.internal_committed_exe.clone(),
+            agg_stark_pk.num_public_values(),
+        );
+        let root_verifier_pk = &agg_stark_pk.root_verifier_pk;
+        let dummy_root_proof = root_verifier_pk.generate_dummy_root_proof(dummy_internal_proof);
+        let verifier = root_verifier_pk.keygen_groth16_static_verifier_with_local_setup(
+            dummy_root_proof,
+            pv_handler,
+            rng,
+        );
         Self {
-            agg_stark_pk,
-            halo2_pk,
+            verifier,
+            profiling,
         }
     }
 }
 
+/// Generates the proving key of a root verifier program. The constant trace heights are
+/// computed by executing the program on `dummy_root_input`.
+fn root_verifier_keygen(
+    config: &AggStarkConfig,
+    root_vm_config: NativeConfig,
+    root_program: Program<F>,
+    dummy_root_input: Vec<Vec<F>>,
+) -> RootVerifierProvingKey {
+    let mut root_engine = BabyBearPoseidon2RootEngine::new(config.root_fri_params);
+    root_engine.max_constraint_degree = config.root_max_constraint_degree;
+    let root_committed_exe = Arc::new(VmCommittedExe::<RootSC>::commit(
+        root_program.into(),
+        root_engine.config.pcs(),
+    ));
+
+    let vm = VirtualMachine::new(root_engine, root_vm_config.clone());
+    let mut vm_pk = vm.keygen();
+    assert!(vm_pk.max_constraint_degree <= config.root_fri_params.max_constraint_degree());
+
+    let (air_heights, _internal_heights) = compute_root_proof_heights(
+        root_vm_config.clone(),
+        root_committed_exe.exe.clone(),
+        dummy_root_input,
+    );
+    let root_air_perm = AirIdPermutation::compute(&air_heights);
+    root_air_perm.permute(&mut vm_pk.per_air);
+
+    RootVerifierProvingKey {
+        vm_pk: Arc::new(VmProvingKey {
+            fri_params: config.root_fri_params,
+            vm_config: root_vm_config,
+            vm_pk,
+        }),
+        root_committed_exe,
+        air_heights,
+    }
+}
+
 pub fn leaf_keygen(
     fri_params: FriParameters,
     leaf_vm_config: NativeConfig,
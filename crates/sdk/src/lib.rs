@@ -4,6 +4,8 @@ use std::{fs::read, path::Path, sync::Arc};
 
 use commit::commit_app_exe;
 use config::AppConfig;
+use estimate::{estimate_execution, ExecutionEstimate};
+use evm::{generate_openvm_verifier_code, EvmVerifierSource};
 use eyre::Result;
 use keygen::{AppProvingKey, AppVerifyingKey};
 use openvm_build::{
@@ -20,6 +22,7 @@ use openvm_circuit::{
     },
 };
 use openvm_native_recursion::{
+    groth16::Groth16EvmProof,
     halo2::{
         utils::Halo2ParamsReader,
         wrapper::{EvmVerifier, Halo2WrapperProvingKey},
@@ -47,22 +50,30 @@ use openvm_transpiler::{
 use prover::vm::ContinuationVmProof;
 use verifier::root::types::RootVmVerifierInput;
 
+pub mod claim;
 pub mod commit;
 pub mod config;
+pub mod estimate;
+pub mod evm;
 pub mod keygen;
 pub mod prover;
 pub mod static_verifier;
 pub mod verifier;
 
+mod public_values;
+pub use public_values::*;
 mod stdin;
 use static_verifier::StaticVerifierPvHandler;
 pub use stdin::*;
 pub mod fs;
 
 use crate::{
+    claim::{digest_to_u32s, public_values_match, ProofClaim, ProofClaimError, TrustedAppVm},
     config::AggConfig,
-    keygen::{AggProvingKey, AggStarkProvingKey},
-    prover::{AppProver, ContinuationProver, StarkProver},
+    keygen::{AggProvingKey, AggStarkProvingKey, Groth16ProvingKey},
+    prover::{
+        AggStarkProver, AppProver, ContinuationProver, Groth16Prover, Halo2Prover, StarkProver,
+    },
 };
 
 pub type SC = BabyBearPoseidon2Config;
@@ -140,6 +151,17 @@ impl Sdk {
         Ok(public_values)
     }
 
+    /// Executes `exe` without generating traces and estimates, per segment, the trace shapes,
+    /// the resources needed to prove the segment and the size of its proof.
+    pub fn estimate<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        app_config: &AppConfig<VC>,
+        inputs: StdIn,
+    ) -> Result<ExecutionEstimate, ExecutionError> {
+        estimate_execution(app_config, exe, inputs)
+    }
+
     pub fn commit_app_exe(
         &self,
         app_fri_params: FriParameters,
@@ -203,6 +225,46 @@ impl Sdk {
         })
     }
 
+    /// Checks the unverified claims recorded by a guest with
+    /// `openvm::claim::claim_openvm_proof`, in the order they were recorded: verifies each of
+    /// `proofs` with [Sdk::verify_app_proof] against the App VM of `trusted` named by the claim at
+    /// the same index, and checks it against the claim. Claims naming an App VM that is not in
+    /// `trusted` are rejected.
+    ///
+    /// The claims are part of the user public values of the guest, so they must be decoded from
+    /// an already verified proof of the guest. Nothing else checks them: the aggregation programs
+    /// and the EVM verifier accept a proof of the guest whatever its claims are, so a guest
+    /// making claims is only meaningful to a verifier running this check on the host.
+    pub fn check_proof_claims(
+        &self,
+        trusted: &[TrustedAppVm],
+        claims: &[ProofClaim],
+        proofs: &[ContinuationVmProof<SC>],
+    ) -> Result<(), ProofClaimError> {
+        if claims.len() != proofs.len() {
+            return Err(ProofClaimError::NumProofsMismatch {
+                expected: claims.len(),
+                actual: proofs.len(),
+            });
+        }
+        for (index, (claim, proof)) in claims.iter().zip(proofs).enumerate() {
+            let app_vm = trusted
+                .iter()
+                .find(|app_vm| digest_to_u32s(&app_vm.leaf_vm_verifier_commit) == claim.vk_commit)
+                .ok_or(ProofClaimError::UntrustedVkCommit { index })?;
+            let payload = self
+                .verify_app_proof(&app_vm.app_vk, proof)
+                .map_err(|source| ProofClaimError::Verification { index, source })?;
+            if digest_to_u32s(&payload.exe_commit) != claim.exe_commit {
+                return Err(ProofClaimError::ExeCommitMismatch { index });
+            }
+            if !public_values_match(&payload.user_public_values, &claim.public_values) {
+                return Err(ProofClaimError::PublicValuesMismatch { index });
+            }
+        }
+        Ok(())
+    }
+
     pub fn verify_app_proof_without_continuations(
         &self,
         app_vk: &AppVerifyingKey,
@@ -255,6 +317,59 @@ impl Sdk {
         Ok(proof)
     }
 
+    /// Generates one EVM proof for several app executions of the same App VM. The executables
+    /// may differ. The proof exposes the commitment of the leaf verifier program, the number of
+    /// app executions and the Merkle root of their `(exe_commit, public_values_commit)` pairs,
+    /// which can be recomputed with [commit::compute_batch_commit].
+    pub fn generate_batch_evm_proof<VC: VmConfig<F>>(
+        &self,
+        reader: &impl Halo2ParamsReader,
+        app_pk: Arc<AppProvingKey<VC>>,
+        agg_pk: AggProvingKey,
+        programs: Vec<(Arc<NonRootCommittedExe>, StdIn)>,
+    ) -> Result<EvmProof>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let AggProvingKey {
+            agg_stark_pk,
+            batch_halo2_pk,
+            ..
+        } = agg_pk;
+        let batch_halo2_pk = batch_halo2_pk.ok_or_else(|| {
+            eyre::eyre!("Batch aggregation is disabled in the aggregation proving key")
+        })?;
+        let max_num_programs = agg_stark_pk.max_num_batch_programs;
+        if programs.is_empty() || programs.len() > max_num_programs {
+            return Err(eyre::eyre!(
+                "Expected between 1 and {max_num_programs} app executions, got {}",
+                programs.len()
+            ));
+        }
+        if app_pk.leaf_fri_params != agg_stark_pk.leaf_vm_pk.fri_params {
+            return Err(eyre::eyre!(
+                "App VM is incompatible with Agg VM because of leaf FRI parameters"
+            ));
+        }
+        if app_pk.app_vm_pk.vm_config.system().num_public_values != agg_stark_pk.num_public_values()
+        {
+            return Err(eyre::eyre!(
+                "App VM is incompatible with Agg VM because of the number of public values"
+            ));
+        }
+        let app_proofs = programs
+            .into_iter()
+            .map(|(app_exe, inputs)| {
+                AppProver::new(app_pk.app_vm_pk.clone(), app_exe).generate_app_proof(inputs)
+            })
+            .collect();
+        let agg_prover = AggStarkProver::new(agg_stark_pk, app_pk.leaf_committed_exe.clone());
+        let root_proof = agg_prover.generate_batch_agg_proof(app_proofs);
+        let proof = Halo2Prover::new(reader, batch_halo2_pk).prove_for_evm(&root_proof);
+        Ok(proof)
+    }
+
     pub fn generate_snark_verifier_contract(
         &self,
         reader: &impl Halo2ParamsReader,
@@ -265,6 +380,42 @@ impl Sdk {
         Ok(evm_verifier)
     }
 
+    /// Generates the Solidity sources of the verifier contract of [Sdk::generate_snark_verifier_contract],
+    /// and of the `OpenVmHalo2Verifier` wrapper which exposes a typed `verify` function. Unlike
+    /// [Sdk::generate_snark_verifier_contract], this does not need `solc`.
+    ///
+    /// The wrapper assumes the default public values handler.
+    pub fn generate_snark_verifier_solidity(
+        &self,
+        reader: &impl Halo2ParamsReader,
+        agg_pk: &AggProvingKey,
+    ) -> Result<EvmVerifierSource> {
+        let params = reader.read_params(agg_pk.halo2_pk.wrapper.pinning.metadata.config_params.k);
+        Ok(EvmVerifierSource {
+            halo2_verifier_code: agg_pk
+                .halo2_pk
+                .wrapper
+                .generate_evm_verifier_sol_code(&params),
+            openvm_verifier_code: generate_openvm_verifier_code(
+                agg_pk.agg_stark_pk.num_public_values(),
+            ),
+        })
+    }
+
+    /// Generates the verifier contract of proofs from [Sdk::generate_batch_evm_proof].
+    pub fn generate_batch_snark_verifier_contract(
+        &self,
+        reader: &impl Halo2ParamsReader,
+        agg_pk: &AggProvingKey,
+    ) -> Result<EvmVerifier> {
+        let batch_halo2_pk = agg_pk.batch_halo2_pk.as_ref().ok_or_else(|| {
+            eyre::eyre!("Batch aggregation is disabled in the aggregation proving key")
+        })?;
+        let params = reader.read_params(batch_halo2_pk.wrapper.pinning.metadata.config_params.k);
+        let evm_verifier = batch_halo2_pk.wrapper.generate_evm_verifier(&params);
+        Ok(evm_verifier)
+    }
+
     pub fn verify_evm_proof(
         &self,
         evm_verifier: &EvmVerifier,
@@ -274,4 +425,67 @@ impl Sdk {
             .map_err(|reason| eyre::eyre!("Sdk::verify_evm_proof: {reason:?}"))?;
         Ok(gas_cost)
     }
+
+    /// Generates the Groth16 proving key of the static verifier of `agg_stark_pk` with a trusted
+    /// setup run on this machine.
+    ///
+    /// Attention: anyone who knows the randomness of the setup can forge proofs. This is only for
+    /// testing.
+    #[cfg(feature = "test-utils")]
+    pub fn groth16_keygen_with_local_setup(
+        &self,
+        agg_stark_pk: &AggStarkProvingKey,
+        pv_handler: Option<&impl StaticVerifierPvHandler>,
+        profiling: bool,
+    ) -> Result<Groth16ProvingKey> {
+        let groth16_pk = Groth16ProvingKey::keygen_with_local_setup(
+            agg_stark_pk,
+            pv_handler,
+            profiling,
+            &mut rand::rngs::OsRng,
+        );
+        Ok(groth16_pk)
+    }
+
+    /// Same as [Sdk::generate_evm_proof], but the final proof is a Groth16 proof of the static
+    /// verifier instead of a halo2 proof of the wrapper circuit.
+    pub fn generate_groth16_evm_proof<VC: VmConfig<F>>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_exe: Arc<NonRootCommittedExe>,
+        agg_stark_pk: AggStarkProvingKey,
+        groth16_pk: Groth16ProvingKey,
+        inputs: StdIn,
+    ) -> Result<Groth16EvmProof>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let stark_prover = StarkProver::new(app_pk, app_exe, agg_stark_pk);
+        let root_proof = stark_prover.generate_proof_for_outer_recursion(inputs);
+        let proof = Groth16Prover::new(groth16_pk).prove_for_evm(&root_proof);
+        Ok(proof)
+    }
+
+    /// Generates the Solidity source of the verifier contract of proofs from
+    /// [Sdk::generate_groth16_evm_proof]. Proofs are submitted with
+    /// [Groth16EvmProof::calldata].
+    pub fn generate_groth16_verifier_contract(
+        &self,
+        groth16_pk: &Groth16ProvingKey,
+    ) -> Result<String> {
+        Ok(groth16_pk.verifier.generate_solidity_verifier())
+    }
+
+    /// Verifies a proof from [Sdk::generate_groth16_evm_proof] natively.
+    pub fn verify_groth16_evm_proof(
+        &self,
+        groth16_pk: &Groth16ProvingKey,
+        evm_proof: &Groth16EvmProof,
+    ) -> Result<()> {
+        if !groth16_pk.verifier.verify(evm_proof) {
+            return Err(eyre::eyre!("Sdk::verify_groth16_evm_proof: invalid proof"));
+        }
+        Ok(())
+    }
 }
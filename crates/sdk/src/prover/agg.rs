@@ -14,10 +14,11 @@ use crate::{
         RootVerifierLocalProver,
     },
     verifier::{
-        internal::types::InternalVmVerifierInput, leaf::types::LeafVmVerifierInput,
-        root::types::RootVmVerifierInput,
+        internal::types::InternalVmVerifierInput,
+        leaf::types::LeafVmVerifierInput,
+        root::types::{BatchRootVmVerifierInput, RootVmVerifierInput},
     },
-    NonRootCommittedExe, RootSC, F, SC,
+    NonRootCommittedExe, RootSC, SC,
 };
 
 pub const DEFAULT_NUM_CHILDREN_LEAF: usize = 1;
@@ -30,6 +31,8 @@ pub struct AggStarkProver {
 
     internal_prover: VmLocalProver<SC, NativeConfig, BabyBearPoseidon2Engine>,
     root_prover: RootVerifierLocalProver,
+    batch_root_prover: Option<RootVerifierLocalProver>,
+    max_num_batch_programs: usize,
 
     pub num_children_internal: usize,
     pub max_internal_wrapper_layers: usize,
@@ -57,11 +60,16 @@ impl AggStarkProver {
             agg_stark_pk.internal_committed_exe,
         );
         let root_prover = RootVerifierLocalProver::new(agg_stark_pk.root_verifier_pk);
+        let batch_root_prover = agg_stark_pk
+            .batch_root_verifier_pk
+            .map(RootVerifierLocalProver::new);
         Self {
             leaf_prover,
             leaf_controller,
             internal_prover,
             root_prover,
+            batch_root_prover,
+            max_num_batch_programs: agg_stark_pk.max_num_batch_programs,
             num_children_internal: DEFAULT_NUM_CHILDREN_INTERNAL,
             max_internal_wrapper_layers: DEFAULT_MAX_INTERNAL_WRAPPER_LAYERS,
         }
@@ -96,17 +104,95 @@ impl AggStarkProver {
             .leaf_controller
             .generate_proof(&self.leaf_prover, &app_proofs);
         let public_values = app_proofs.user_public_values.public_values;
-        let internal_proof = self.generate_internal_proof_impl(leaf_proofs, &public_values);
+        let internal_proof = self.generate_internal_proof_impl(leaf_proofs, |proof| {
+            let actual_air_heights = self.root_prover.execute_for_air_heights(
+                RootVmVerifierInput {
+                    proofs: vec![proof.clone()],
+                    public_values: public_values.clone(),
+                }
+                .write(),
+            );
+            heights_le(
+                &actual_air_heights,
+                &self.root_prover.root_verifier_pk.air_heights,
+            )
+        });
         RootVmVerifierInput {
             proofs: vec![internal_proof],
             public_values,
         }
     }
 
+    /// Generate a batch root proof to aggregate the app proofs of several app executions.
+    pub fn generate_batch_agg_proof(
+        &self,
+        app_proofs: Vec<ContinuationVmProof<SC>>,
+    ) -> Proof<RootSC> {
+        let batch_root_input = self.generate_batch_root_verifier_input(app_proofs);
+        info_span!("agg_layer", group = "batch_root", idx = 0).in_scope(|| {
+            SingleSegmentVmProver::prove(self.batch_root_prover(), batch_root_input.write())
+        })
+    }
+
+    /// Aggregates the app proofs of each app execution into a single proof, and pads them to the
+    /// capacity of the batch root verifier.
+    pub fn generate_batch_root_verifier_input(
+        &self,
+        app_proofs: Vec<ContinuationVmProof<SC>>,
+    ) -> BatchRootVmVerifierInput<SC> {
+        let batch_root_prover = self.batch_root_prover();
+        let max_num_programs = self.max_num_batch_programs;
+        assert!(
+            !app_proofs.is_empty() && app_proofs.len() <= max_num_programs,
+            "The number of app executions in a batch should be between 1 and {max_num_programs}"
+        );
+        let num_programs = app_proofs.len();
+        let mut proofs: Vec<_> = app_proofs
+            .iter()
+            .enumerate()
+            .map(|(program_idx, app_proof)| {
+                info_span!("batch_program", idx = program_idx).in_scope(|| {
+                    let leaf_proofs = self
+                        .leaf_controller
+                        .generate_proof(&self.leaf_prover, app_proof);
+                    self.generate_internal_proof_impl(leaf_proofs, |proof| {
+                        let actual_air_heights = batch_root_prover.execute_for_air_heights(
+                            BatchRootVmVerifierInput {
+                                proofs: vec![proof.clone(); max_num_programs],
+                                num_programs: 1,
+                            }
+                            .write(),
+                        );
+                        heights_le(
+                            &actual_air_heights,
+                            &batch_root_prover.root_verifier_pk.air_heights,
+                        )
+                    })
+                })
+            })
+            .collect();
+        // The batch root verifier always verifies `max_num_programs` proofs. The padding proofs
+        // are not committed.
+        let last_proof = proofs.last().unwrap().clone();
+        proofs.resize(max_num_programs, last_proof);
+        BatchRootVmVerifierInput {
+            proofs,
+            num_programs,
+        }
+    }
+
+    fn batch_root_prover(&self) -> &RootVerifierLocalProver {
+        self.batch_root_prover
+            .as_ref()
+            .expect("Batch aggregation is disabled in the aggregation proving key")
+    }
+
+    /// Aggregates `leaf_proofs` with internal verifiers until a single proof remains which
+    /// `fits_root` accepts.
     fn generate_internal_proof_impl(
         &self,
         leaf_proofs: Vec<Proof<SC>>,
-        public_values: &[F],
+        fits_root: impl Fn(&Proof<SC>) -> bool,
     ) -> Proof<SC> {
         let mut internal_node_idx = -1;
         let mut internal_node_height = 0;
@@ -114,17 +200,8 @@ impl AggStarkProver {
         let mut wrapper_layers = 0;
         loop {
             if proofs.len() == 1 {
-                let actual_air_heights =
-                    self.root_prover
-                        .execute_for_air_heights(RootVmVerifierInput {
-                            proofs: vec![proofs[0].clone()],
-                            public_values: public_values.to_vec(),
-                        });
                 // Root verifier can handle the internal proof. We can stop here.
-                if heights_le(
-                    &actual_air_heights,
-                    &self.root_prover.root_verifier_pk.air_heights,
-                ) {
+                if fits_root(&proofs[0]) {
                     break;
                 }
                 if wrapper_layers >= self.max_internal_wrapper_layers {
@@ -0,0 +1,27 @@
+use openvm_native_compiler::prelude::Witness;
+use openvm_native_recursion::{groth16::Groth16EvmProof, witness::Witnessable};
+use openvm_stark_sdk::openvm_stark_backend::proof::Proof;
+use rand::rngs::OsRng;
+use tracing::info_span;
+
+use crate::{keygen::Groth16ProvingKey, RootSC};
+
+pub struct Groth16Prover {
+    groth16_pk: Groth16ProvingKey,
+}
+
+impl Groth16Prover {
+    pub fn new(groth16_pk: Groth16ProvingKey) -> Self {
+        Self { groth16_pk }
+    }
+
+    pub fn prove_for_evm(&self, root_proof: &Proof<RootSC>) -> Groth16EvmProof {
+        let mut witness = Witness::default();
+        root_proof.write(&mut witness);
+        info_span!("prove_for_evm", group = "groth16_outer").in_scope(|| {
+            self.groth16_pk
+                .verifier
+                .prove(witness, self.groth16_pk.profiling, &mut OsRng)
+        })
+    }
+}
@@ -12,6 +12,8 @@ mod app;
 pub use app::*;
 use openvm_native_recursion::halo2::utils::Halo2ParamsReader;
 
+mod groth16;
+pub use groth16::*;
 mod halo2;
 #[allow(unused_imports)]
 pub use halo2::*;
@@ -43,6 +45,7 @@ impl<VC> ContinuationProver<VC> {
         let AggProvingKey {
             agg_stark_pk,
             halo2_pk,
+            ..
         } = agg_pk;
         let stark_prover = StarkProver::new(app_pk, app_committed_exe, agg_stark_pk);
         Self {
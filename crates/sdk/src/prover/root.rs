@@ -1,7 +1,6 @@
 use async_trait::async_trait;
 use openvm_circuit::arch::{SingleSegmentVmExecutor, Streams};
 use openvm_native_circuit::NativeConfig;
-use openvm_native_recursion::hints::Hintable;
 use openvm_stark_sdk::{
     config::{baby_bear_poseidon2_root::BabyBearPoseidon2RootEngine, FriParameters},
     engine::{StarkEngine, StarkFriEngine},
@@ -11,8 +10,7 @@ use openvm_stark_sdk::{
 use crate::{
     keygen::RootVerifierProvingKey,
     prover::vm::{AsyncSingleSegmentVmProver, SingleSegmentVmProver},
-    verifier::root::types::RootVmVerifierInput,
-    RootSC, F, SC,
+    RootSC, F,
 };
 
 /// Local prover for a root verifier.
@@ -30,12 +28,12 @@ impl RootVerifierLocalProver {
             executor_for_heights,
         }
     }
-    pub fn execute_for_air_heights(&self, input: RootVmVerifierInput<SC>) -> Vec<usize> {
+    pub fn execute_for_air_heights(&self, input: impl Into<Streams<F>>) -> Vec<usize> {
         let result = self
             .executor_for_heights
             .execute_and_compute_heights(
                 self.root_verifier_pk.root_committed_exe.exe.clone(),
-                input.write(),
+                input,
             )
             .unwrap();
         result.air_heights
@@ -0,0 +1,94 @@
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::F;
+
+#[derive(Error, Debug)]
+pub enum PublicValuesError {
+    #[error("public value {value} at index {index} is not a byte")]
+    NotByte { index: usize, value: u32 },
+    #[error("public value {index} is not zero but is not in a whole word of public values")]
+    TrailingByte { index: usize },
+    #[error("failed to deserialize public values: {0}")]
+    Deserialize(#[from] openvm::serde::Error),
+}
+
+/// Converts user public values, one byte per field element, into bytes.
+pub fn public_values_to_bytes(public_values: &[F]) -> Result<Vec<u8>, PublicValuesError> {
+    public_values
+        .iter()
+        .enumerate()
+        .map(|(index, v)| {
+            let value = v.as_canonical_u32();
+            u8::try_from(value).map_err(|_| PublicValuesError::NotByte { index, value })
+        })
+        .collect()
+}
+
+/// Decodes user public values written by `openvm::io::commit` in the guest. Consecutive commits
+/// of `a: A`, `b: B`, ... are decoded as the tuple `(A, B, ...)`. Unused public values are zero
+/// and are ignored.
+///
+/// The guest commits whole words, so if the number of public values is not a multiple of 4, the
+/// last public values after the last whole word must be zero.
+pub fn decode_public_values<T: DeserializeOwned>(
+    public_values: &[F],
+) -> Result<T, PublicValuesError> {
+    let mut bytes = public_values_to_bytes(public_values)?;
+    let len = bytes.len() - bytes.len() % 4;
+    if let Some(index) = (len..bytes.len()).find(|&i| bytes[i] != 0) {
+        return Err(PublicValuesError::TrailingByte { index });
+    }
+    bytes.truncate(len);
+    Ok(openvm::serde::from_slice(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use openvm_stark_backend::p3_field::FieldAlgebra;
+
+    use super::*;
+
+    fn to_public_values(value: &impl serde::Serialize, num_public_values: usize) -> Vec<F> {
+        let mut ret: Vec<_> = openvm::serde::to_vec(value)
+            .unwrap()
+            .into_iter()
+            .flat_map(|w| w.to_le_bytes())
+            .map(F::from_canonical_u8)
+            .collect();
+        ret.resize(num_public_values, F::ZERO);
+        ret
+    }
+
+    #[test]
+    fn test_decode_public_values() {
+        let public_values = to_public_values(&(7u32, [1u8, 2, 3], "hello".to_string()), 64);
+        let decoded: (u32, [u8; 3], String) = decode_public_values(&public_values).unwrap();
+        assert_eq!(decoded, (7, [1, 2, 3], "hello".to_string()));
+    }
+
+    #[test]
+    fn test_decode_public_values_not_byte() {
+        let mut public_values = to_public_values(&1u64, 32);
+        public_values[3] = F::from_canonical_u32(256);
+        assert!(matches!(
+            decode_public_values::<u64>(&public_values),
+            Err(PublicValuesError::NotByte { index: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_public_values_unaligned() {
+        let public_values = to_public_values(&(5u32, 6u16), 30);
+        let decoded: (u32, u16) = decode_public_values(&public_values).unwrap();
+        assert_eq!(decoded, (5, 6));
+
+        let mut public_values = to_public_values(&7u32, 6);
+        public_values[5] = F::ONE;
+        assert!(matches!(
+            decode_public_values::<u32>(&public_values),
+            Err(PublicValuesError::TrailingByte { index: 5 })
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 use openvm_circuit::arch::PROGRAM_CACHED_TRACE_INDEX;
 use openvm_native_compiler::prelude::*;
+#[cfg(feature = "test-utils")]
+use openvm_native_recursion::groth16::verifier::Groth16VerifierProvingKey;
 use openvm_native_recursion::{
     challenger::multi_field32::MultiField32ChallengerVariable,
     config::outer::{new_from_outer_multi_vk, OuterConfig},
@@ -12,22 +14,22 @@ use openvm_native_recursion::{
     vars::StarkProofVariable,
     witness::Witnessable,
 };
-use openvm_stark_sdk::{
-    openvm_stark_backend::{p3_field::FieldAlgebra, proof::Proof},
-    p3_bn254_fr::Bn254Fr,
-};
+use openvm_stark_sdk::{openvm_stark_backend::proof::Proof, p3_bn254_fr::Bn254Fr};
 
 use crate::{
-    keygen::RootVerifierProvingKey,
+    keygen::{
+        dummy::{dummy_batch_root_verifier_input, dummy_root_verifier_input},
+        RootVerifierProvingKey,
+    },
     prover::{vm::SingleSegmentVmProver, RootVerifierLocalProver},
     verifier::{
         common::{
             assert_single_segment_vm_exit_successfully_with_connector_air_id, types::SpecialAirIds,
         },
-        root::types::{RootVmVerifierInput, RootVmVerifierPvs},
+        root::types::{BatchRootVmVerifierPvs, RootVmVerifierPvs},
         utils::compress_babybear_var_to_bn254,
     },
-    RootSC, F, SC,
+    RootSC, SC,
 };
 
 impl RootVerifierProvingKey {
@@ -47,24 +49,35 @@ impl RootVerifierProvingKey {
         }
     }
 
+    /// Keygen the Groth16 static verifier for this root verifier, with a trusted setup run on
+    /// this machine. For testing only.
+    #[cfg(feature = "test-utils")]
+    pub fn keygen_groth16_static_verifier_with_local_setup(
+        &self,
+        root_proof: Proof<RootSC>,
+        pv_handler: Option<&impl StaticVerifierPvHandler>,
+        rng: &mut (impl rand::RngCore + rand::CryptoRng),
+    ) -> Groth16VerifierProvingKey {
+        let dsl_operations = build_static_verifier_operations(self, &root_proof, pv_handler);
+        Groth16VerifierProvingKey::keygen_with_local_setup(dsl_operations, rng)
+    }
+
     pub fn generate_dummy_root_proof(&self, dummy_internal_proof: Proof<SC>) -> Proof<RootSC> {
         let prover = RootVerifierLocalProver::new(self.clone());
-        // 2 * DIGEST_SIZE for exe_commit and leaf_commit
-        let num_public_values = prover
-            .root_verifier_pk
-            .vm_pk
-            .vm_config
-            .system
-            .num_public_values
-            - 2 * DIGEST_SIZE;
-        SingleSegmentVmProver::prove(
-            &prover,
-            RootVmVerifierInput {
-                proofs: vec![dummy_internal_proof],
-                public_values: vec![F::ZERO; num_public_values],
-            }
-            .write(),
-        )
+        let input = dummy_root_verifier_input(prover.vm_config(), &dummy_internal_proof);
+        SingleSegmentVmProver::prove(&prover, input.write())
+    }
+
+    /// Generates a dummy proof of this root verifier, assuming it is a batch root verifier for
+    /// at most `max_num_programs` app executions.
+    pub fn generate_dummy_batch_root_proof(
+        &self,
+        max_num_programs: usize,
+        dummy_internal_proof: Proof<SC>,
+    ) -> Proof<RootSC> {
+        let prover = RootVerifierLocalProver::new(self.clone());
+        let input = dummy_batch_root_verifier_input(max_num_programs, &dummy_internal_proof);
+        SingleSegmentVmProver::prove(&prover, input.write())
     }
 }
 
@@ -108,6 +121,36 @@ impl StaticVerifierPvHandler for RootVerifierProvingKey {
     }
 }
 
+/// Public values handler for the static verifier of a batch root verifier. The public values of
+/// the static verifier are the leaf verifier commit and the batch commit in BN254, followed by
+/// the number of app executions in the batch.
+pub struct BatchRootVerifierPvHandler;
+
+impl StaticVerifierPvHandler for BatchRootVerifierPvHandler {
+    fn handle_public_values(
+        &self,
+        builder: &mut Builder<OuterConfig>,
+        input: &StarkProofVariable<OuterConfig>,
+        _root_verifier_pk: &RootVerifierProvingKey,
+        special_air_ids: &SpecialAirIds,
+    ) -> usize {
+        let pv_air = builder.get(&input.per_air, special_air_ids.public_values_air_id);
+        let public_values: Vec<_> = pv_air
+            .public_values
+            .vec()
+            .into_iter()
+            .map(|x| builder.cast_felt_to_var(x))
+            .collect();
+        let pvs = BatchRootVmVerifierPvs::from_flatten(public_values);
+        let leaf_commit = compress_babybear_var_to_bn254(builder, pvs.leaf_verifier_commit);
+        let batch_commit = compress_babybear_var_to_bn254(builder, pvs.batch_commit);
+        builder.static_commit_public_value(0, leaf_commit);
+        builder.static_commit_public_value(1, batch_commit);
+        builder.static_commit_public_value(2, pvs.num_programs);
+        3
+    }
+}
+
 fn build_static_verifier_operations(
     root_verifier_pk: &RootVerifierProvingKey,
     proof: &Proof<RootSC>,
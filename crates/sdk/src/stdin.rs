@@ -1,5 +1,6 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
+use openvm::fs::{file_key, stdin_chunk_key, VfsIndex, STDIN_CHUNK_SIZE, VFS_INDEX_KEY};
 use openvm_circuit::arch::Streams;
 use openvm_stark_backend::p3_field::FieldAlgebra;
 use serde::{Deserialize, Serialize};
@@ -9,6 +10,11 @@ use crate::F;
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct StdIn {
     pub buffer: VecDeque<Vec<F>>,
+    /// Hints read by the guest with `openvm::io::hint_load_by_key`.
+    pub kv_store: HashMap<Vec<u8>, Vec<F>>,
+    /// Arguments, environment variables and files of the guest, read with `openvm::fs`.
+    #[serde(default)]
+    pub vfs: VfsIndex,
 }
 
 impl StdIn {
@@ -23,9 +29,7 @@ impl StdIn {
     }
 
     pub fn write<T: Serialize>(&mut self, data: &T) {
-        let words = openvm::serde::to_vec(data).unwrap();
-        let bytes: Vec<u8> = words.into_iter().flat_map(|w| w.to_le_bytes()).collect();
-        self.write_bytes(&bytes);
+        self.write_bytes(&to_bytes(data));
     }
 
     pub fn write_bytes(&mut self, data: &[u8]) {
@@ -33,9 +37,75 @@ impl StdIn {
         self.buffer.push_back(field_data);
     }
 
+    /// Writes the rkyv archive of `value` for the guest to read in place with
+    /// `openvm::io::read_archived`. The guest must enable the same rkyv format features as the
+    /// host for the archive to validate.
+    #[cfg(feature = "rkyv")]
+    pub fn write_archived<T>(&mut self, value: &T) -> Result<(), rkyv::rancor::Error>
+    where
+        T: for<'a> rkyv::Serialize<
+            rkyv::api::high::HighSerializer<
+                rkyv::util::AlignedVec,
+                rkyv::ser::allocator::ArenaHandle<'a>,
+                rkyv::rancor::Error,
+            >,
+        >,
+    {
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(value)?;
+        self.write_bytes(&bytes);
+        Ok(())
+    }
+
     pub fn write_field(&mut self, data: &[F]) {
         self.buffer.push_back(data.to_vec());
     }
+
+    /// Stores `value` under `key` for the guest to read with `openvm::io::hint_load_by_key`.
+    /// Replaces any value previously stored under `key`.
+    pub fn add_keyed(&mut self, key: &[u8], value: &[u8]) {
+        let field_data = value.iter().map(|b| F::from_canonical_u8(*b)).collect();
+        self.kv_store.insert(key.to_vec(), field_data);
+    }
+
+    /// Adds the file `name` to the virtual file system of the guest, read with
+    /// `openvm::fs::read`. Replaces any file previously added under `name`.
+    pub fn add_file(&mut self, name: &str, data: &[u8]) {
+        if !self.vfs.files.iter().any(|file| file == name) {
+            self.vfs.files.push(name.to_string());
+        }
+        self.add_keyed(&file_key(name), data);
+    }
+
+    /// Sets the environment variable `name` of the guest, read with `std::env::var` or
+    /// `openvm::fs::var`.
+    pub fn set_env(&mut self, name: &str, value: &str) {
+        self.vfs.env.retain(|(key, _)| key != name);
+        self.vfs.env.push((name.to_string(), value.to_string()));
+    }
+
+    /// Sets the arguments of the guest, read with `std::env::args` or `openvm::fs::args`. The
+    /// first argument is conventionally the program name.
+    pub fn set_args<S: Into<String>>(&mut self, args: impl IntoIterator<Item = S>) {
+        self.vfs.args = args.into_iter().map(Into::into).collect();
+    }
+
+    /// Sets the standard input of the guest, read with `std::io::stdin` or
+    /// `openvm::fs::read_stdin`. Unlike [StdIn::write_bytes], it can be read incrementally.
+    pub fn set_stdin(&mut self, data: &[u8]) {
+        let num_old_chunks = (self.vfs.stdin_len as usize).div_ceil(STDIN_CHUNK_SIZE);
+        for index in 0..num_old_chunks {
+            self.kv_store.remove(&stdin_chunk_key(index as u32));
+        }
+        for (index, chunk) in data.chunks(STDIN_CHUNK_SIZE).enumerate() {
+            self.add_keyed(&stdin_chunk_key(index as u32), chunk);
+        }
+        self.vfs.stdin_len = data.len() as u32;
+    }
+}
+
+fn to_bytes<T: Serialize>(data: &T) -> Vec<u8> {
+    let words = openvm::serde::to_vec(data).unwrap();
+    words.into_iter().flat_map(|w| w.to_le_bytes()).collect()
 }
 
 impl From<StdIn> for Streams<F> {
@@ -44,7 +114,9 @@ impl From<StdIn> for Streams<F> {
         while let Some(input) = std_in.read() {
             data.push(input);
         }
-        Streams::new(data)
+        let vfs_index = to_bytes(&std_in.vfs);
+        std_in.add_keyed(VFS_INDEX_KEY, &vfs_index);
+        Streams::new(data).with_kv_store(std_in.kv_store)
     }
 }
 
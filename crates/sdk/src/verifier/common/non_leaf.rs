@@ -6,7 +6,7 @@ use openvm_native_recursion::{
     challenger::duplex::DuplexChallengerVariable, fri::TwoAdicFriPcsVariable, stark::StarkVerifier,
     types::MultiStarkVerificationAdvice, vars::StarkProofVariable,
 };
-use openvm_stark_sdk::openvm_stark_backend::p3_field::PrimeField32;
+use openvm_stark_sdk::openvm_stark_backend::p3_field::{FieldAlgebra, PrimeField32};
 
 use crate::verifier::{
     common::{
@@ -90,6 +90,32 @@ impl<C: Config> NonLeafVerifierVariables<C> {
         });
         (pvs, leaf_verifier_commit)
     }
+
+    /// Verify a proof of internal verifier or leaf verifier which aggregates all the segments of
+    /// an app execution, and assert that the app execution terminated successfully.
+    /// Returns the public values of the proof.
+    pub fn verify_terminated_execution_proof(
+        &self,
+        builder: &mut Builder<C>,
+        proof: &StarkProofVariable<C>,
+    ) -> InternalVmVerifierPvs<Felt<C::F>>
+    where
+        C::F: PrimeField32,
+    {
+        assert_required_air_for_agg_vm_present(builder, proof);
+        let proof_vm_pvs = self.verify_internal_or_leaf_verifier_proof(builder, proof);
+        assert_single_segment_vm_exit_successfully(builder, proof);
+
+        // App Program should terminate
+        builder.assert_felt_eq(
+            proof_vm_pvs.vm_verifier_pvs.connector.is_terminate,
+            C::F::ONE,
+        );
+        // App Program should exit successfully
+        builder.assert_felt_eq(proof_vm_pvs.vm_verifier_pvs.connector.exit_code, C::F::ZERO);
+        proof_vm_pvs
+    }
+
     fn verify_internal_or_leaf_verifier_proof(
         &self,
         builder: &mut Builder<C>,
@@ -44,6 +44,12 @@ impl LeafVmVerifierConfig {
         &self,
         app_vm_vk: &MultiStarkVerifyingKey<BabyBearPoseidon2Config>,
     ) -> Program<F> {
+        self.build(app_vm_vk)
+            .compile_isa_with_options(self.compiler_options)
+    }
+
+    /// The DSL program of the leaf verifier, before it is compiled.
+    fn build(&self, app_vm_vk: &MultiStarkVerifyingKey<BabyBearPoseidon2Config>) -> Builder<C> {
         let m_advice = new_from_inner_multi_vk(app_vm_vk);
         let mut builder = Builder::<C>::default();
 
@@ -102,7 +108,7 @@ impl LeafVmVerifierConfig {
             builder.halt();
         }
 
-        builder.compile_isa_with_options(self.compiler_options)
+        builder
     }
 
     /// Read the public values root proof from the input stream and verify it.
@@ -137,3 +143,58 @@ impl LeafVmVerifierConfig {
         (root_proof.public_values_commit, curr_commit)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use openvm_circuit::arch::VirtualMachine;
+    use openvm_native_circuit::{Native, NativeConfig};
+    use openvm_stark_sdk::{
+        config::baby_bear_poseidon2::BabyBearPoseidon2Engine, engine::StarkFriEngine,
+        openvm_stark_backend::p3_field::FieldExtensionAlgebra,
+    };
+
+    use super::*;
+
+    /// Whether `operations` contain an `AddEFFI` whose constant has a nonzero base coefficient,
+    /// the only case where its lowering emits `AddFI` instead of `CopyF`.
+    fn has_add_effi_with_base(operations: &[DslIr<C>]) -> bool {
+        operations.iter().any(|op| match op {
+            DslIr::AddEFFI(_, _, rhs) => rhs.as_base_slice()[0] != F::ZERO,
+            DslIr::ZipFor(_, _, _, _, body) | DslIr::FnDef(_, _, body) => {
+                has_add_effi_with_base(&body.vec)
+            }
+            DslIr::IfEq(_, _, then_block, else_block)
+            | DslIr::IfNe(_, _, then_block, else_block)
+            | DslIr::IfEqI(_, _, then_block, else_block)
+            | DslIr::IfNeI(_, _, then_block, else_block) => {
+                has_add_effi_with_base(&then_block.vec) || has_add_effi_with_base(&else_block.vec)
+            }
+            _ => false,
+        })
+    }
+
+    /// Without optimizations, the leaf verifier only adds felts to constants with a zero base
+    /// coefficient, which are lowered to the same `CopyF` as before the `AddEFFI` fix, so its
+    /// program and commit are unchanged.
+    #[test]
+    fn test_leaf_verifier_unchanged_without_optimizations() {
+        let app_fri_params = FriParameters::new_for_testing(3);
+        let app_system_config = SystemConfig::default()
+            .with_continuations()
+            .with_public_values(16);
+        let engine = BabyBearPoseidon2Engine::new(app_fri_params);
+        let vm = VirtualMachine::new(engine, NativeConfig::new(app_system_config.clone(), Native));
+        let app_vm_vk = vm.keygen().get_vk();
+
+        let config = LeafVmVerifierConfig {
+            app_fri_params,
+            app_system_config,
+            compiler_options: CompilerOptions {
+                enable_optimizations: false,
+                ..Default::default()
+            },
+        };
+        let builder = config.build(&app_vm_vk);
+        assert!(!has_add_effi_with_base(&builder.operations.vec));
+    }
+}
@@ -2,7 +2,10 @@ use internal::types::InternalVmVerifierPvs;
 use openvm_native_circuit::NativeConfig;
 use openvm_native_compiler::ir::DIGEST_SIZE;
 
-use crate::{config::AggStarkConfig, verifier::common::types::VmVerifierPvs};
+use crate::{
+    config::AggStarkConfig,
+    verifier::{common::types::VmVerifierPvs, root::types::BatchRootVmVerifierPvs},
+};
 
 pub mod common;
 pub mod internal;
@@ -38,4 +41,13 @@ impl AggStarkConfig {
         config.system.profiling = self.profiling;
         config
     }
+    pub fn batch_root_verifier_vm_config(&self) -> NativeConfig {
+        let mut config = NativeConfig::aggregation(
+            // leaf_verifier_commit + batch_commit + num_programs
+            BatchRootVmVerifierPvs::<u8>::width(),
+            SBOX_SIZE.min(self.root_fri_params.max_constraint_degree()),
+        );
+        config.system.profiling = self.profiling;
+        config
+    }
 }
@@ -15,10 +15,13 @@ use crate::{
     verifier::{
         common::non_leaf::NonLeafVerifierVariables,
         root::{
-            types::{RootVmVerifierInput, RootVmVerifierPvs},
-            vars::RootVmVerifierInputVariable,
+            types::{
+                BatchRootVmVerifierInput, BatchRootVmVerifierPvs, RootVmVerifierInput,
+                RootVmVerifierPvs,
+            },
+            vars::{BatchRootVmVerifierInputVariable, RootVmVerifierInputVariable},
         },
-        utils::VariableP2Hasher,
+        utils::{assign_slice_to_array, VariableP2Hasher},
     },
     C, F, SC,
 };
@@ -110,6 +113,141 @@ impl RootVmVerifierConfig {
     }
 }
 
+/// Config to generate the batch root VM verifier program, which verifies proofs of several
+/// terminated app executions of the same App VM and commits them into one Merkle root.
+pub struct BatchRootVmVerifierConfig {
+    pub leaf_fri_params: FriParameters,
+    pub internal_fri_params: FriParameters,
+    /// Maximum number of app executions in a batch.
+    pub max_num_programs: usize,
+    pub internal_vm_verifier_commit: [F; DIGEST_SIZE],
+    pub compiler_options: CompilerOptions,
+}
+impl BatchRootVmVerifierConfig {
+    pub fn build_program(
+        &self,
+        leaf_vm_vk: &MultiStarkVerifyingKey<SC>,
+        internal_vm_vk: &MultiStarkVerifyingKey<SC>,
+    ) -> Program<F> {
+        assert!(self.max_num_programs > 0);
+        let leaf_advice = new_from_inner_multi_vk(leaf_vm_vk);
+        let internal_advice = new_from_inner_multi_vk(internal_vm_vk);
+        let mut builder = Builder::<C>::default();
+
+        {
+            builder.cycle_tracker_start("ReadProofsFromInput");
+            let BatchRootVmVerifierInputVariable {
+                proofs,
+                num_programs,
+            } = BatchRootVmVerifierInput::<SC>::read(&mut builder);
+            builder.cycle_tracker_end("ReadProofsFromInput");
+            // Always verify `max_num_programs` proofs so that the trace heights are constant.
+            builder.assert_usize_eq(proofs.len(), RVar::from(self.max_num_programs));
+            // 1 <= num_programs <= max_num_programs
+            builder.assert_less_than_slow_small_rhs(
+                num_programs - RVar::one(),
+                RVar::from(self.max_num_programs),
+            );
+            builder.cycle_tracker_start("InitializePcsConst");
+            let leaf_pcs = TwoAdicFriPcsVariable {
+                config: const_fri_config(&mut builder, &self.leaf_fri_params),
+            };
+            let internal_pcs = TwoAdicFriPcsVariable {
+                config: const_fri_config(&mut builder, &self.internal_fri_params),
+            };
+            builder.cycle_tracker_end("InitializePcsConst");
+            builder.cycle_tracker_start("VerifyProofs");
+            let internal_program_commit =
+                array::from_fn(|i| builder.eval(self.internal_vm_verifier_commit[i]));
+            let non_leaf_verifier = NonLeafVerifierVariables {
+                internal_program_commit,
+                leaf_pcs,
+                leaf_advice,
+                internal_pcs,
+                internal_advice,
+            };
+            let hasher = VariableP2Hasher::new(&mut builder);
+            let num_leaves = self.max_num_programs.next_power_of_two();
+            let leaves: Array<C, Array<C, Felt<F>>> = builder.array(num_leaves);
+            for i in 0..num_leaves {
+                builder.set_value(&leaves, i, hasher.const_zeros.clone());
+            }
+            let leaf_verifier_commit = array::from_fn(|_| builder.uninit());
+            let is_included: Var<F> = builder.eval(F::ONE);
+            builder.range(0, proofs.len()).for_each(|i_vec, builder| {
+                let i = i_vec[0];
+                let proof = builder.get(&proofs, i);
+                let proof_pvs =
+                    non_leaf_verifier.verify_terminated_execution_proof(builder, &proof);
+                // All app executions should be of the same App VM.
+                builder.if_eq(i, RVar::zero()).then_or_else(
+                    |builder| {
+                        builder.assign(
+                            &leaf_verifier_commit,
+                            proof_pvs.extra_pvs.leaf_verifier_commit,
+                        );
+                    },
+                    |builder| {
+                        builder.assert_eq::<[_; DIGEST_SIZE]>(
+                            leaf_verifier_commit,
+                            proof_pvs.extra_pvs.leaf_verifier_commit,
+                        );
+                    },
+                );
+                builder
+                    .if_eq(i, num_programs)
+                    .then(|builder| builder.assign(&is_included, F::ZERO));
+                builder.if_eq(is_included, F::ONE).then(|builder| {
+                    let exe_commit = compute_exe_commit(
+                        builder,
+                        &hasher,
+                        proof_pvs.vm_verifier_pvs.app_commit,
+                        proof_pvs.vm_verifier_pvs.memory.initial_root,
+                        proof_pvs.vm_verifier_pvs.connector.initial_pc,
+                    );
+                    let leaf = hasher.compressor.compress(
+                        builder,
+                        &exe_commit,
+                        &proof_pvs.vm_verifier_pvs.public_values_commit,
+                    );
+                    let leaf_array = builder.array(DIGEST_SIZE);
+                    assign_slice_to_array(builder, &leaf_array, &leaf);
+                    builder.set_value(&leaves, i, leaf_array);
+                });
+            });
+            builder.cycle_tracker_end("VerifyProofs");
+
+            builder.cycle_tracker_start("ComputeBatchCommit");
+            let mut layer: Vec<_> = (0..num_leaves).map(|i| builder.get(&leaves, i)).collect();
+            while layer.len() > 1 {
+                layer = layer
+                    .chunks_exact(2)
+                    .map(|pair| {
+                        hasher
+                            .compressor
+                            .compress_array(&mut builder, &pair[0], &pair[1])
+                    })
+                    .collect();
+            }
+            let batch_commit = array::from_fn(|i| builder.get(&layer[0], i));
+            builder.cycle_tracker_end("ComputeBatchCommit");
+
+            let pvs = BatchRootVmVerifierPvs {
+                leaf_verifier_commit,
+                batch_commit,
+                num_programs: builder.unsafe_cast_var_to_felt(num_programs),
+            };
+            pvs.flatten()
+                .into_iter()
+                .for_each(|v| builder.commit_public_value(v));
+
+            builder.halt();
+        }
+
+        builder.compile_isa_with_options(self.compiler_options)
+    }
+}
+
 fn compute_exe_commit<C: Config>(
     builder: &mut Builder<C>,
     hasher: &VariableP2Hasher<C>,
@@ -37,6 +37,34 @@ pub struct RootVmVerifierInput<SC: StarkGenericConfig> {
 }
 assert_impl_all!(RootVmVerifierInput<BabyBearPoseidon2Config>: Serialize, DeserializeOwned);
 
+/// Input for the batch root VM verifier.
+#[derive(Serialize, Deserialize, Derivative)]
+#[serde(bound = "")]
+#[derivative(Clone(bound = "Com<SC>: Clone"))]
+pub struct BatchRootVmVerifierInput<SC: StarkGenericConfig> {
+    /// One proof of leaf verifier or internal verifier per app execution. The length is always
+    /// the capacity of the batch root verifier, so only the first `num_programs` proofs are
+    /// committed.
+    pub proofs: Vec<Proof<SC>>,
+    /// The number of app executions in the batch.
+    pub num_programs: usize,
+}
+assert_impl_all!(BatchRootVmVerifierInput<BabyBearPoseidon2Config>: Serialize, DeserializeOwned);
+
+/// Public values of the batch root VM verifier, which aggregates several app executions of the
+/// same App VM.
+#[derive(Debug)]
+pub struct BatchRootVmVerifierPvs<T> {
+    /// The commitment of the leaf verifier program, which commits the VM config of App VM.
+    pub leaf_verifier_commit: [T; DIGEST_SIZE],
+    /// Merkle root of `compress(exe_commit, public_values_commit)` of each app execution in the
+    /// batch. The leaves are padded with zero digests to the next power of two of the capacity
+    /// of the batch root verifier.
+    pub batch_commit: [T; DIGEST_SIZE],
+    /// The number of app executions in the batch.
+    pub num_programs: T,
+}
+
 impl<F: PrimeField32> RootVmVerifierPvs<Felt<F>> {
     pub fn uninit<C: Config<F = F>>(builder: &mut Builder<C>, num_public_values: usize) -> Self {
         Self {
@@ -65,3 +93,25 @@ impl<F: Copy> RootVmVerifierPvs<F> {
         }
     }
 }
+
+impl<F: Copy> BatchRootVmVerifierPvs<F> {
+    pub fn width() -> usize {
+        2 * DIGEST_SIZE + 1
+    }
+    pub fn flatten(self) -> Vec<F> {
+        let mut ret = self.leaf_verifier_commit.to_vec();
+        ret.extend(self.batch_commit);
+        ret.push(self.num_programs);
+        ret
+    }
+    pub fn from_flatten(flatten: Vec<F>) -> Self {
+        assert_eq!(flatten.len(), Self::width());
+        let leaf_verifier_commit = flatten[..DIGEST_SIZE].try_into().unwrap();
+        let batch_commit = flatten[DIGEST_SIZE..2 * DIGEST_SIZE].try_into().unwrap();
+        Self {
+            leaf_verifier_commit,
+            batch_commit,
+            num_programs: flatten[2 * DIGEST_SIZE],
+        }
+    }
+}
@@ -2,7 +2,10 @@ use openvm_native_compiler::prelude::*;
 use openvm_native_recursion::{hints::Hintable, vars::StarkProofVariable};
 use openvm_stark_sdk::openvm_stark_backend::{config::Val, proof::Proof};
 
-use crate::{verifier::root::types::RootVmVerifierInput, C, SC};
+use crate::{
+    verifier::root::types::{BatchRootVmVerifierInput, RootVmVerifierInput},
+    C, SC,
+};
 
 #[derive(DslVariable, Clone)]
 pub struct RootVmVerifierInputVariable<C: Config> {
@@ -30,3 +33,30 @@ impl Hintable<C> for RootVmVerifierInput<SC> {
         stream
     }
 }
+
+#[derive(DslVariable, Clone)]
+pub struct BatchRootVmVerifierInputVariable<C: Config> {
+    /// One proof of leaf verifier or internal verifier per app execution.
+    pub proofs: Array<C, StarkProofVariable<C>>,
+    /// The number of app executions in the batch.
+    pub num_programs: Var<C::N>,
+}
+
+impl Hintable<C> for BatchRootVmVerifierInput<SC> {
+    type HintVariable = BatchRootVmVerifierInputVariable<C>;
+
+    fn read(builder: &mut Builder<C>) -> Self::HintVariable {
+        let proofs = Vec::<Proof<SC>>::read(builder);
+        let num_programs = usize::read(builder);
+        Self::HintVariable {
+            proofs,
+            num_programs,
+        }
+    }
+
+    fn write(&self) -> Vec<Vec<<C as Config>::N>> {
+        let mut stream = self.proofs.write();
+        stream.extend(<usize as Hintable<C>>::write(&self.num_programs));
+        stream
+    }
+}
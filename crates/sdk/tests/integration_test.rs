@@ -1,6 +1,6 @@
 use std::{borrow::Borrow, path::PathBuf, sync::Arc};
 
-use openvm_build::GuestOptions;
+use openvm_build::{GuestOptions, TargetFilter};
 use openvm_circuit::{
     arch::{
         hasher::poseidon2::vm_poseidon2_hasher, ExecutionError, SingleSegmentVmExecutor,
@@ -11,19 +11,26 @@ use openvm_circuit::{
 use openvm_native_circuit::{Native, NativeConfig};
 use openvm_native_compiler::{conversion::CompilerOptions, prelude::*};
 use openvm_native_recursion::{
-    config::outer::OuterConfig, halo2::utils::CacheHalo2ParamsReader, types::InnerConfig,
+    config::outer::OuterConfig,
+    halo2::{utils::CacheHalo2ParamsReader, EvmProof},
+    hints::Hintable,
+    types::InnerConfig,
     vars::StarkProofVariable,
 };
 use openvm_rv32im_transpiler::{Rv32ITranspilerExtension, Rv32MTranspilerExtension};
 use openvm_sdk::{
-    commit::AppExecutionCommit,
-    config::{AggConfig, AggStarkConfig, AppConfig, Halo2Config},
-    keygen::{AppProvingKey, RootVerifierProvingKey},
+    claim::{digest_to_u32s, ProofClaim, ProofClaimError, TrustedAppVm},
+    commit::{compute_batch_commit, AppExecutionCommit},
+    config::{AggConfig, AggStarkConfig, AppConfig, Halo2Config, SdkVmConfig},
+    decode_public_values,
+    evm::{OpenVmVerifyArgs, NUM_ACCUMULATOR_INSTANCES},
+    keygen::{AggStarkProvingKey, AppProvingKey, RootVerifierProvingKey},
+    prover::AggStarkProver,
     static_verifier::StaticVerifierPvHandler,
     verifier::{
         common::types::{SpecialAirIds, VmVerifierPvs},
         leaf::types::{LeafVmVerifierInput, UserPublicValuesRootProof},
-        root::types::RootVmVerifierPvs,
+        root::types::{BatchRootVmVerifierPvs, RootVmVerifierPvs},
         utils::compress_babybear_var_to_bn254,
     },
     Sdk, StdIn,
@@ -39,6 +46,11 @@ use openvm_stark_sdk::{
     p3_bn254_fr::Bn254Fr,
 };
 use openvm_transpiler::transpiler::Transpiler;
+use revm::{
+    primitives::{Address, ExecutionResult, Output, TxKind},
+    Evm, InMemoryDB,
+};
+use snark_verifier_sdk::snark_verifier::loader::evm::compile_solidity;
 
 type SC = BabyBearPoseidon2Config;
 type C = InnerConfig;
@@ -117,6 +129,7 @@ fn agg_stark_config_for_test() -> AggStarkConfig {
             ..Default::default()
         },
         root_max_constraint_degree: (1 << ROOT_LOG_BLOWUP) + 1,
+        max_num_batch_programs: 0,
     }
 }
 
@@ -135,6 +148,7 @@ fn small_test_app_config(app_log_blowup: usize) -> AppConfig<NativeConfig> {
             enable_cycle_tracker: true,
             ..Default::default()
         },
+        segmentation_strategy: None,
     }
 }
 
@@ -382,6 +396,305 @@ fn test_e2e_proof_generation_and_verification() {
     assert!(Sdk.verify_evm_proof(&evm_verifier, &evm_proof).is_ok());
 }
 
+/// Executes a transaction on a local EVM, panicking if it halts.
+fn transact(evm: &mut Evm<'_, (), InMemoryDB>, to: TxKind, data: Vec<u8>) -> ExecutionResult {
+    let tx = evm.tx_mut();
+    tx.caller = Address::with_last_byte(1);
+    tx.transact_to = to;
+    tx.data = data.into();
+    tx.gas_limit = u64::MAX;
+    let result = evm.transact_commit().unwrap();
+    assert!(
+        !matches!(result, ExecutionResult::Halt { .. }),
+        "EVM halted: {result:?}"
+    );
+    result
+}
+
+fn deploy(evm: &mut Evm<'_, (), InMemoryDB>, deployment_code: Vec<u8>) -> Address {
+    match transact(evm, TxKind::Create, deployment_code) {
+        ExecutionResult::Success {
+            output: Output::Create(_, Some(address)),
+            ..
+        } => address,
+        result => panic!("deployment failed: {result:?}"),
+    }
+}
+
+#[test]
+fn test_openvm_verifier_contract() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Sdk.app_keygen(app_config).unwrap();
+    let params_reader = CacheHalo2ParamsReader::new_with_default_params_dir();
+    let agg_pk = Sdk
+        .agg_keygen(
+            agg_config_for_test(),
+            &params_reader,
+            None::<&RootVerifierProvingKey>,
+        )
+        .unwrap();
+    let source = Sdk
+        .generate_snark_verifier_solidity(&params_reader, &agg_pk)
+        .unwrap();
+    let evm_proof = Sdk
+        .generate_evm_proof(
+            &params_reader,
+            Arc::new(app_pk),
+            app_committed_exe_for_test(app_log_blowup),
+            agg_pk,
+            StdIn::default(),
+        )
+        .unwrap();
+
+    let num_instances = NUM_ACCUMULATOR_INSTANCES + 2 + NUM_PUB_VALUES;
+    let decoded = EvmProof::from_calldata(&evm_proof.to_calldata(), num_instances).unwrap();
+    assert_eq!(decoded.instances, evm_proof.instances);
+    assert_eq!(decoded.proof, evm_proof.proof);
+    let args = OpenVmVerifyArgs::from_evm_proof(&evm_proof, NUM_PUB_VALUES).unwrap();
+    let calldata = args.to_calldata();
+    assert_eq!(OpenVmVerifyArgs::from_calldata(&calldata).unwrap(), args);
+
+    let mut evm = Evm::builder().with_db(InMemoryDB::default()).build();
+    evm.cfg_mut().limit_contract_code_size = Some(usize::MAX);
+    let halo2_verifier = deploy(&mut evm, compile_solidity(&source.halo2_verifier_code));
+    let mut deployment_code = compile_solidity(&source.openvm_verifier_code);
+    deployment_code.extend_from_slice(halo2_verifier.into_word().as_slice());
+    let openvm_verifier = deploy(&mut evm, deployment_code);
+
+    let result = transact(
+        &mut evm,
+        TxKind::Call(halo2_verifier),
+        evm_proof.to_calldata(),
+    );
+    assert!(result.is_success(), "halo2 verifier failed: {result:?}");
+    let result = transact(&mut evm, TxKind::Call(openvm_verifier), calldata);
+    assert!(result.is_success(), "OpenVM verifier failed: {result:?}");
+    println!("OpenVmHalo2Verifier gas used: {}", result.gas_used());
+
+    let mut tampered = args;
+    tampered.user_public_values[0] ^= 1;
+    let result = transact(
+        &mut evm,
+        TxKind::Call(openvm_verifier),
+        tampered.to_calldata(),
+    );
+    assert!(
+        matches!(result, ExecutionResult::Revert { .. }),
+        "tampered proof was accepted"
+    );
+
+    // The constructor rejects a halo2 verifier address without code.
+    let mut deployment_code = compile_solidity(&source.openvm_verifier_code);
+    deployment_code.extend_from_slice(Address::with_last_byte(1).into_word().as_slice());
+    let result = transact(&mut evm, TxKind::Create, deployment_code);
+    assert!(
+        matches!(result, ExecutionResult::Revert { .. }),
+        "deployment without a halo2 verifier was accepted"
+    );
+}
+
+#[test]
+fn test_e2e_groth16_proof_generation_and_verification() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Sdk.app_keygen(app_config).unwrap();
+    let agg_stark_pk = AggStarkProvingKey::keygen(agg_stark_config_for_test());
+    let groth16_pk = Sdk
+        .groth16_keygen_with_local_setup(&agg_stark_pk, None::<&RootVerifierProvingKey>, false)
+        .unwrap();
+    let contract = Sdk.generate_groth16_verifier_contract(&groth16_pk).unwrap();
+
+    let evm_proof = Sdk
+        .generate_groth16_evm_proof(
+            Arc::new(app_pk),
+            app_committed_exe_for_test(app_log_blowup),
+            agg_stark_pk,
+            groth16_pk.clone(),
+            StdIn::default(),
+        )
+        .unwrap();
+    // exe commit, leaf verifier commit and the user public values
+    assert_eq!(evm_proof.instances.len(), 2 + NUM_PUB_VALUES);
+    assert!(Sdk
+        .verify_groth16_evm_proof(&groth16_pk, &evm_proof)
+        .is_ok());
+
+    let mut evm = Evm::builder().with_db(InMemoryDB::default()).build();
+    evm.cfg_mut().limit_contract_code_size = Some(usize::MAX);
+    let verifier = deploy(&mut evm, compile_solidity(&contract));
+    let result = transact(&mut evm, TxKind::Call(verifier), evm_proof.calldata());
+    assert!(result.is_success(), "Groth16 verifier failed: {result:?}");
+    println!("Groth16Verifier gas used: {}", result.gas_used());
+
+    let mut tampered = evm_proof;
+    tampered.instances[2][31] ^= 1;
+    assert!(Sdk
+        .verify_groth16_evm_proof(&groth16_pk, &tampered)
+        .is_err());
+    let result = transact(&mut evm, TxKind::Call(verifier), tampered.calldata());
+    assert!(
+        matches!(result, ExecutionResult::Revert { .. }),
+        "tampered proof was accepted"
+    );
+}
+
+#[test]
+fn test_batch_root_verifier_commit() {
+    const MAX_NUM_PROGRAMS: usize = 3;
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config.clone()).unwrap());
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let agg_stark_pk = AggStarkProvingKey::keygen(AggStarkConfig {
+        max_num_batch_programs: MAX_NUM_PROGRAMS,
+        ..agg_stark_config_for_test()
+    });
+    let batch_root_verifier_pk = agg_stark_pk.batch_root_verifier_pk.clone().unwrap();
+    let agg_prover = AggStarkProver::new(agg_stark_pk, app_pk.leaf_committed_exe.clone());
+
+    let app_proofs: Vec<_> = (0..2)
+        .map(|_| {
+            Sdk.generate_app_proof(app_pk.clone(), app_committed_exe.clone(), StdIn::default())
+                .unwrap()
+        })
+        .collect();
+    let commits = AppExecutionCommit::compute(
+        &app_config.app_vm_config,
+        &app_committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
+    let programs: Vec<_> = app_proofs
+        .iter()
+        .map(|proof| {
+            (
+                commits.exe_commit,
+                proof.user_public_values.public_values_commit,
+            )
+        })
+        .collect();
+
+    let batch_root_input = agg_prover.generate_batch_root_verifier_input(app_proofs);
+    assert_eq!(batch_root_input.proofs.len(), MAX_NUM_PROGRAMS);
+    let batch_root_vm =
+        SingleSegmentVmExecutor::new(batch_root_verifier_pk.vm_pk.vm_config.clone());
+    let exe_result = batch_root_vm
+        .execute_and_compute_heights(
+            batch_root_verifier_pk.root_committed_exe.exe.clone(),
+            batch_root_input.write(),
+        )
+        .unwrap();
+    let runtime_pvs: Vec<_> = exe_result
+        .public_values
+        .iter()
+        .map(|v| v.unwrap())
+        .collect();
+    let pvs = BatchRootVmVerifierPvs::from_flatten(runtime_pvs);
+    assert_eq!(pvs.leaf_verifier_commit, commits.leaf_vm_verifier_commit);
+    assert_eq!(pvs.num_programs, F::from_canonical_usize(2));
+    assert_eq!(
+        pvs.batch_commit,
+        compute_batch_commit(MAX_NUM_PROGRAMS, &programs)
+    );
+    assert_eq!(exe_result.air_heights, batch_root_verifier_pk.air_heights);
+}
+
+/// Proves the inner app, then runs a guest recording a claim about it and resolves the claim.
+#[test]
+fn test_check_proof_claims() {
+    let app_log_blowup = 1;
+    let app_pk = Sdk
+        .app_keygen(small_test_app_config(app_log_blowup))
+        .unwrap();
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let commit = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &app_committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
+    let proof = Sdk
+        .generate_app_proof(
+            Arc::new(app_pk.clone()),
+            app_committed_exe,
+            StdIn::default(),
+        )
+        .unwrap();
+    let trusted = [TrustedAppVm::new(&app_pk)];
+
+    let mut pkg_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).to_path_buf();
+    pkg_dir.push("guest");
+    let elf = Sdk
+        .build(
+            GuestOptions::default(),
+            &pkg_dir,
+            &Some(TargetFilter {
+                name: "claim".to_string(),
+                kind: "example".to_string(),
+            }),
+        )
+        .unwrap();
+    let vm_config = SdkVmConfig::builder()
+        .system(SystemConfig::default().with_public_values(256).into())
+        .rv32i(Default::default())
+        .rv32m(Default::default())
+        .io(Default::default())
+        .build();
+    let exe = Sdk.transpile(elf, vm_config.transpiler()).unwrap();
+    // Runs the guest claiming a proof of `exe_commit` on the App VM `vk_commit` and decodes the
+    // claim from its public values.
+    let run_guest = |vk_commit: [u32; 8], exe_commit: [u32; 8]| -> ProofClaim {
+        let mut stdin = StdIn::default();
+        stdin.write(&vk_commit);
+        stdin.write(&exe_commit);
+        // The test program does not write public values.
+        stdin.write(&vec![0u8; 4]);
+        let public_values = Sdk.execute(exe.clone(), vm_config.clone(), stdin).unwrap();
+        let (claim, len): (ProofClaim, usize) = decode_public_values(&public_values).unwrap();
+        assert_eq!(len, 4);
+        claim
+    };
+
+    let vk_commit = digest_to_u32s(&commit.leaf_vm_verifier_commit);
+    let exe_commit = digest_to_u32s(&commit.exe_commit);
+    let claim = run_guest(vk_commit, exe_commit);
+    Sdk.check_proof_claims(&trusted, &[claim.clone()], &[proof.clone()])
+        .unwrap();
+
+    assert!(matches!(
+        Sdk.check_proof_claims(&trusted, &[], &[proof.clone()]),
+        Err(ProofClaimError::NumProofsMismatch {
+            expected: 0,
+            actual: 1
+        })
+    ));
+    // A claim about another App VM is rejected even with a valid proof, since the App VM is not
+    // trusted.
+    let mut wrong_vk = vk_commit;
+    wrong_vk[0] ^= 1;
+    let wrong_vk_claim = run_guest(wrong_vk, exe_commit);
+    assert!(matches!(
+        Sdk.check_proof_claims(&trusted, &[wrong_vk_claim], &[proof.clone()]),
+        Err(ProofClaimError::UntrustedVkCommit { index: 0 })
+    ));
+    assert!(matches!(
+        Sdk.check_proof_claims(&[], &[claim.clone()], &[proof.clone()]),
+        Err(ProofClaimError::UntrustedVkCommit { index: 0 })
+    ));
+    let mut wrong_exe = exe_commit;
+    wrong_exe[0] ^= 1;
+    let wrong_exe_claim = run_guest(vk_commit, wrong_exe);
+    assert!(matches!(
+        Sdk.check_proof_claims(&trusted, &[wrong_exe_claim], &[proof.clone()]),
+        Err(ProofClaimError::ExeCommitMismatch { index: 0 })
+    ));
+    let mut wrong_public_values = claim;
+    wrong_public_values.public_values = vec![1];
+    assert!(matches!(
+        Sdk.check_proof_claims(&trusted, &[wrong_public_values], &[proof]),
+        Err(ProofClaimError::PublicValuesMismatch { index: 0 })
+    ));
+}
+
 #[test]
 fn test_sdk_guest_build_and_transpile() {
     let sdk = Sdk;
@@ -404,3 +717,81 @@ fn test_sdk_guest_build_and_transpile() {
         .with_extension(Rv32MTranspilerExtension);
     let _exe = sdk.transpile(one, transpiler).unwrap();
 }
+
+#[test]
+fn test_commit_and_decode_public_values() {
+    let mut pkg_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).to_path_buf();
+    pkg_dir.push("guest");
+    let elf = Sdk
+        .build(
+            GuestOptions::default(),
+            &pkg_dir,
+            &Some(TargetFilter {
+                name: "commit".to_string(),
+                kind: "example".to_string(),
+            }),
+        )
+        .unwrap();
+    let vm_config = SdkVmConfig::builder()
+        .system(SystemConfig::default().with_public_values(30).into())
+        .rv32i(Default::default())
+        .rv32m(Default::default())
+        .io(Default::default())
+        .build();
+    let exe = Sdk.transpile(elf, vm_config.transpiler()).unwrap();
+    let public_values = Sdk.execute(exe, vm_config, StdIn::default()).unwrap();
+    assert_eq!(public_values.len(), 30);
+
+    let (n, (fib, bytes)): (u64, (u64, [u8; 3])) = decode_public_values(&public_values).unwrap();
+    assert_eq!(n, 10);
+    assert_eq!(fib, 55);
+    assert_eq!(bytes, [1, 2, 3]);
+}
+
+#[derive(rkyv::Archive, rkyv::Serialize)]
+struct Record {
+    id: u32,
+    name: String,
+    values: Vec<u64>,
+}
+
+#[test]
+fn test_read_archived() {
+    let mut pkg_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).to_path_buf();
+    pkg_dir.push("guest");
+    let elf = Sdk
+        .build(
+            GuestOptions::default(),
+            &pkg_dir,
+            &Some(TargetFilter {
+                name: "archived".to_string(),
+                kind: "example".to_string(),
+            }),
+        )
+        .unwrap();
+    let vm_config = SdkVmConfig::builder()
+        .system(Default::default())
+        .rv32i(Default::default())
+        .rv32m(Default::default())
+        .io(Default::default())
+        .build();
+    let exe = Sdk.transpile(elf, vm_config.transpiler()).unwrap();
+
+    let record = Record {
+        id: 7,
+        name: "openvm".to_string(),
+        values: (0..100).map(|i| i * i).collect(),
+    };
+    let mut stdin = StdIn::default();
+    stdin.write_archived(&record).unwrap();
+    Sdk.execute(exe.clone(), vm_config.clone(), stdin.clone())
+        .unwrap();
+
+    // The guest exits with code 1 when the archive does not validate.
+    let archive = stdin.buffer.back_mut().unwrap();
+    archive.fill(F::from_canonical_u8(0xff));
+    assert!(matches!(
+        Sdk.execute(exe, vm_config, stdin),
+        Err(ExecutionError::FailedWithExitCode(1))
+    ));
+}
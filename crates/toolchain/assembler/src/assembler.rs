@@ -0,0 +1,250 @@
+use std::collections::{BTreeMap, HashMap};
+
+use openvm_instructions::{
+    exe::{FnBound, VmExe},
+    instruction::{Instruction, NUM_OPERANDS},
+    opcode_names::OpcodeNames,
+    program::{Program, DEFAULT_MAX_NUM_PUBLIC_VALUES, DEFAULT_PC_STEP},
+    VmOpcode,
+};
+use openvm_stark_backend::p3_field::PrimeField32;
+use thiserror::Error;
+
+use crate::syntax::{parse_field, parse_u32, tokenize, Token};
+
+#[derive(Debug, Error)]
+#[error("line {line}: {kind}")]
+pub struct AssemblerError {
+    /// Line of the error, starting from 1.
+    pub line: usize,
+    pub kind: AssemblerErrorKind,
+}
+
+#[derive(Debug, Error)]
+pub enum AssemblerErrorKind {
+    #[error("unknown opcode {0}")]
+    UnknownOpcode(String),
+    #[error("unknown directive {0}")]
+    UnknownDirective(String),
+    #[error("invalid number {0}")]
+    InvalidNumber(String),
+    #[error("unexpected token {0:?}")]
+    UnexpectedToken(String),
+    #[error("{directive} expects {expected} arguments, got {actual}")]
+    WrongArgumentCount {
+        directive: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("instructions have at most {NUM_OPERANDS} operands, got {0}")]
+    TooManyOperands(usize),
+    #[error("unterminated string")]
+    UnterminatedString,
+    #[error("undefined label {0}")]
+    UndefinedLabel(String),
+    #[error("label {0} is defined more than once")]
+    AmbiguousLabel(String),
+    #[error("{0} must come before the first instruction, label or .org")]
+    LateDirective(String),
+    #[error("pc {pc:#x} is before the current pc {current:#x}")]
+    BackwardsOrg { pc: u32, current: u32 },
+    #[error("pc {0:#x} is not a multiple of the step after the pc base")]
+    MisalignedPc(u32),
+    #[error("step must be nonzero")]
+    ZeroStep,
+}
+
+enum Operand<F> {
+    Value(F),
+    /// Offset from the pc of the instruction to a label.
+    Label(String),
+}
+
+/// Parses OpenVM assembly, as printed by [disassemble](crate::disassemble), into an executable.
+pub fn assemble<F: PrimeField32>(
+    source: &str,
+    names: &OpcodeNames,
+) -> Result<VmExe<F>, AssemblerError> {
+    let mut pc_base = 0;
+    let mut step = DEFAULT_PC_STEP;
+    let mut pc_start = None;
+    let mut max_num_public_values = DEFAULT_MAX_NUM_PUBLIC_VALUES;
+    let mut init_memory = BTreeMap::new();
+    let mut fn_bounds = BTreeMap::new();
+    // Pc of each label, or `None` if the label is defined more than once.
+    let mut labels: HashMap<String, Option<u32>> = HashMap::new();
+    // Instructions with their line and pc, since operands may refer to labels defined later.
+    let mut instructions: Vec<(usize, u32, VmOpcode, Vec<Operand<F>>)> = Vec::new();
+    // Pc of the next instruction, once set by an instruction or `.org`.
+    let mut pc = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let error = |kind| AssemblerError {
+            line: index + 1,
+            kind,
+        };
+        let tokens = tokenize(line).map_err(error)?;
+        let current_pc = pc.unwrap_or(pc_base);
+        match tokens.as_slice() {
+            [] => {}
+            [Token::Word(label) | Token::Str(label), Token::Colon] => {
+                labels
+                    .entry(label.clone())
+                    .and_modify(|pc| *pc = None)
+                    .or_insert(Some(current_pc));
+            }
+            [Token::Word(directive), args @ ..] if directive.starts_with('.') => {
+                let expect_args = |expected: usize| {
+                    if args.len() == expected {
+                        Ok(())
+                    } else {
+                        Err(error(AssemblerErrorKind::WrongArgumentCount {
+                            directive: directive.clone(),
+                            expected,
+                            actual: args.len(),
+                        }))
+                    }
+                };
+                let before_instructions = || {
+                    if pc.is_none() && labels.is_empty() {
+                        Ok(())
+                    } else {
+                        Err(error(AssemblerErrorKind::LateDirective(directive.clone())))
+                    }
+                };
+                match directive.as_str() {
+                    ".pc_base" => {
+                        expect_args(1)?;
+                        before_instructions()?;
+                        pc_base = word(&args[0]).and_then(parse_u32).map_err(error)?;
+                    }
+                    ".step" => {
+                        expect_args(1)?;
+                        before_instructions()?;
+                        step = word(&args[0]).and_then(parse_u32).map_err(error)?;
+                        if step == 0 {
+                            return Err(error(AssemblerErrorKind::ZeroStep));
+                        }
+                    }
+                    ".pc_start" => {
+                        expect_args(1)?;
+                        pc_start = Some(word(&args[0]).and_then(parse_u32).map_err(error)?);
+                    }
+                    ".max_public_values" => {
+                        expect_args(1)?;
+                        max_num_public_values =
+                            word(&args[0]).and_then(parse_u32).map_err(error)? as usize;
+                    }
+                    ".memory" => {
+                        expect_args(3)?;
+                        let address_space = word(&args[0]).and_then(parse_u32).map_err(error)?;
+                        let address = word(&args[1]).and_then(parse_u32).map_err(error)?;
+                        let value = word(&args[2]).and_then(parse_field).map_err(error)?;
+                        init_memory.insert((address_space, address), value);
+                    }
+                    ".fn" => {
+                        expect_args(3)?;
+                        let start = word(&args[0]).and_then(parse_u32).map_err(error)?;
+                        let end = word(&args[1]).and_then(parse_u32).map_err(error)?;
+                        let name = match &args[2] {
+                            Token::Word(name) | Token::Str(name) => name.clone(),
+                            token => return Err(error(unexpected(token))),
+                        };
+                        fn_bounds.insert(start, FnBound { start, end, name });
+                    }
+                    ".org" => {
+                        expect_args(1)?;
+                        let org = word(&args[0]).and_then(parse_u32).map_err(error)?;
+                        if org < current_pc {
+                            return Err(error(AssemblerErrorKind::BackwardsOrg {
+                                pc: org,
+                                current: current_pc,
+                            }));
+                        }
+                        if (org - pc_base) % step != 0 {
+                            return Err(error(AssemblerErrorKind::MisalignedPc(org)));
+                        }
+                        pc = Some(org);
+                    }
+                    _ => {
+                        return Err(error(AssemblerErrorKind::UnknownDirective(
+                            directive.clone(),
+                        )))
+                    }
+                }
+            }
+            [Token::Word(opcode), operands @ ..] => {
+                let opcode = names
+                    .opcode(opcode)
+                    .or_else(|| {
+                        let number = opcode.strip_prefix("0x")?;
+                        usize::from_str_radix(number, 16)
+                            .ok()
+                            .map(VmOpcode::from_usize)
+                    })
+                    .ok_or_else(|| error(AssemblerErrorKind::UnknownOpcode(opcode.clone())))?;
+                if operands.len() > NUM_OPERANDS {
+                    return Err(error(AssemblerErrorKind::TooManyOperands(operands.len())));
+                }
+                let operands = operands
+                    .iter()
+                    .map(|operand| match operand {
+                        Token::Word(value) => parse_field(value).map(Operand::Value),
+                        Token::Ref(label) => Ok(Operand::Label(label.clone())),
+                        token => Err(unexpected(token)),
+                    })
+                    .collect::<Result<_, _>>()
+                    .map_err(error)?;
+                instructions.push((index + 1, current_pc, opcode, operands));
+                pc = Some(current_pc + step);
+            }
+            [token, ..] => return Err(error(unexpected(token))),
+        }
+    }
+
+    let mut program = Program::new_empty(step, pc_base, max_num_public_values);
+    for (line, pc, opcode, operands) in instructions {
+        let error = |kind| AssemblerError { line, kind };
+        let mut values = [F::ZERO; NUM_OPERANDS];
+        for (value, operand) in values.iter_mut().zip(operands) {
+            *value = match operand {
+                Operand::Value(value) => value,
+                Operand::Label(label) => match labels.get(&label) {
+                    Some(Some(target)) => {
+                        F::from_canonical_u32(*target) - F::from_canonical_u32(pc)
+                    }
+                    Some(None) => return Err(error(AssemblerErrorKind::AmbiguousLabel(label))),
+                    None => return Err(error(AssemblerErrorKind::UndefinedLabel(label))),
+                },
+            };
+        }
+        let [a, b, c, d, e, f, g] = values;
+        let index = ((pc - pc_base) / step) as usize;
+        program.instructions_and_debug_infos.resize(index, None);
+        program.push_instruction(Instruction::new(opcode, a, b, c, d, e, f, g));
+    }
+
+    Ok(VmExe {
+        program,
+        pc_start: pc_start.unwrap_or(pc_base),
+        init_memory,
+        fn_bounds,
+        debug_sections: Default::default(),
+    })
+}
+
+fn word(token: &Token) -> Result<&str, AssemblerErrorKind> {
+    match token {
+        Token::Word(word) => Ok(word),
+        token => Err(unexpected(token)),
+    }
+}
+
+fn unexpected(token: &Token) -> AssemblerErrorKind {
+    AssemblerErrorKind::UnexpectedToken(match token {
+        Token::Word(word) => word.clone(),
+        Token::Str(string) => format!("\"{string}\""),
+        Token::Ref(label) => format!("@{label}"),
+        Token::Colon => ":".to_string(),
+    })
+}
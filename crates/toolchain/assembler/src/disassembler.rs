@@ -0,0 +1,101 @@
+use std::fmt::Write;
+
+use openvm_instructions::{
+    exe::VmExe,
+    instruction::{Instruction, NUM_OPERANDS},
+    opcode_names::OpcodeNames,
+    VmOpcode,
+};
+use openvm_stark_backend::p3_field::PrimeField32;
+
+use crate::syntax::{format_field, format_name};
+
+/// Column at which the pc of each instruction is printed as a comment.
+const PC_COMMENT_COLUMN: usize = 48;
+
+/// Prints `exe` in OpenVM assembly, naming opcodes with `names` and labeling the start of each
+/// function of [VmExe::fn_bounds].
+pub fn disassemble<F: PrimeField32>(exe: &VmExe<F>, names: &OpcodeNames) -> String {
+    let program = &exe.program;
+    let mut out = String::new();
+    writeln!(out, ".pc_base {:#010x}", program.pc_base).unwrap();
+    writeln!(out, ".step {}", program.step).unwrap();
+    writeln!(out, ".pc_start {:#010x}", exe.pc_start).unwrap();
+    writeln!(out, ".max_public_values {}", program.max_num_public_values).unwrap();
+    for bound in exe.fn_bounds.values() {
+        writeln!(
+            out,
+            ".fn {:#010x} {:#010x} {}",
+            bound.start,
+            bound.end,
+            format_name(&bound.name)
+        )
+        .unwrap();
+    }
+    for (&(address_space, address), &value) in &exe.init_memory {
+        writeln!(
+            out,
+            ".memory {} {:#010x} {}",
+            address_space,
+            address,
+            format_field(value)
+        )
+        .unwrap();
+    }
+
+    let mut next_pc = program.pc_base;
+    for (pc, instruction, _) in program.enumerate_by_pc() {
+        if pc != next_pc {
+            writeln!(out, ".org {:#010x}", pc).unwrap();
+        }
+        next_pc = pc + program.step;
+        if let Some(bound) = exe.fn_bounds.get(&pc) {
+            writeln!(out, "\n{}:", format_name(&bound.name)).unwrap();
+        }
+        let line = format!("    {}", format_instruction(&instruction, names));
+        writeln!(
+            out,
+            "{:width$} ; {:#010x}",
+            line,
+            pc,
+            width = PC_COMMENT_COLUMN
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Prints an instruction as its opcode name followed by its operands, omitting trailing zeros.
+pub fn format_instruction<F: PrimeField32>(
+    instruction: &Instruction<F>,
+    names: &OpcodeNames,
+) -> String {
+    let Instruction {
+        opcode,
+        a,
+        b,
+        c,
+        d,
+        e,
+        f,
+        g,
+    } = instruction;
+    let operands: [F; NUM_OPERANDS] = [*a, *b, *c, *d, *e, *f, *g];
+    let len = operands
+        .iter()
+        .rposition(|operand| !operand.is_zero())
+        .map_or(0, |last| last + 1);
+    let mut line = format_opcode(*opcode, names);
+    for (i, operand) in operands[..len].iter().enumerate() {
+        line.push_str(if i == 0 { " " } else { ", " });
+        line.push_str(&format_field(*operand));
+    }
+    line
+}
+
+fn format_opcode(opcode: VmOpcode, names: &OpcodeNames) -> String {
+    match names.name(opcode) {
+        Some(name) => name.to_string(),
+        None => format!("{:#x}", opcode.as_usize()),
+    }
+}
@@ -0,0 +1,33 @@
+//! A textual assembly format for OpenVM executables, with an assembler and a disassembler.
+//!
+//! A program is a sequence of lines. Everything after a `;` is a comment.
+//!
+//! ```text
+//! .pc_base 0x00200800          ; pc of the first instruction, 0 by default
+//! .step 4                      ; pc increment between instructions, 4 by default
+//! .pc_start 0x00200800         ; pc at which execution starts, `.pc_base` by default
+//! .max_public_values 32
+//! .memory 2 0x00010000 42      ; initial memory: address space, address, value
+//! .fn 0x00200800 0x00200810 main  ; function bounds: start, end, name
+//!
+//! main:                        ; label for the pc of the next instruction
+//!     ADD 8, 8, 4, 1, 0
+//!     native.BEQ 8, 0, @main, 1, 0  ; `@label` is the offset from this pc to the label
+//! .org 0x00200900              ; skips to the given pc
+//!     TERMINATE
+//! ```
+//!
+//! Instructions are an opcode name from [OpcodeNames], or the opcode number for opcodes without
+//! a name, followed by up to seven comma-separated operands. Missing operands are zero. Operands
+//! are decimal or `0x`-prefixed hexadecimal numbers, optionally negated with `-`.
+//! Labels and function names which are not made of letters, digits, `_`, `.` and `$` are written
+//! as double-quoted strings, with `\"` and `\\` escapes.
+//!
+//! Debug infos and DWARF sections of an executable are not part of the format.
+
+mod assembler;
+mod disassembler;
+mod syntax;
+
+pub use assembler::*;
+pub use disassembler::*;
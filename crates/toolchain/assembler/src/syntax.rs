@@ -0,0 +1,123 @@
+use openvm_stark_backend::p3_field::PrimeField32;
+
+use crate::AssemblerErrorKind;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// A bare word, such as an opcode name, a directive or a number.
+    Word(String),
+    /// A double-quoted string.
+    Str(String),
+    /// A `@label` reference.
+    Ref(String),
+    Colon,
+}
+
+/// Splits a line into tokens, dropping commas, whitespace and comments.
+pub(crate) fn tokenize(line: &str) -> Result<Vec<Token>, AssemblerErrorKind> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ';' => break,
+            ',' => {
+                chars.next();
+            }
+            ':' => {
+                chars.next();
+                tokens.push(Token::Colon);
+            }
+            '"' => {
+                chars.next();
+                tokens.push(Token::Str(quoted(&mut chars)?));
+            }
+            '@' => {
+                chars.next();
+                let label = if chars.peek() == Some(&'"') {
+                    chars.next();
+                    quoted(&mut chars)?
+                } else {
+                    word(&mut chars)
+                };
+                tokens.push(Token::Ref(label));
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            _ => tokens.push(Token::Word(word(&mut chars))),
+        }
+    }
+    Ok(tokens)
+}
+
+fn word(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut word = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || matches!(c, ',' | ':' | ';' | '"') {
+            break;
+        }
+        word.push(c);
+        chars.next();
+    }
+    word
+}
+
+/// Reads a string up to its closing quote, which has already been opened.
+fn quoted(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String, AssemblerErrorKind> {
+    let mut string = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(string),
+            Some('\\') => match chars.next() {
+                Some(c) => string.push(c),
+                None => return Err(AssemblerErrorKind::UnterminatedString),
+            },
+            Some(c) => string.push(c),
+            None => return Err(AssemblerErrorKind::UnterminatedString),
+        }
+    }
+}
+
+/// Formats a label or function name so that [tokenize] reads it back as a single token.
+pub(crate) fn format_name(name: &str) -> String {
+    let is_word = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$'));
+    if is_word {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Formats a field element as a signed number, so that e.g. negative jump offsets are readable.
+pub(crate) fn format_field<F: PrimeField32>(value: F) -> String {
+    let value = value.as_canonical_u32();
+    if value > F::ORDER_U32 / 2 {
+        format!("-{}", F::ORDER_U32 - value)
+    } else {
+        value.to_string()
+    }
+}
+
+pub(crate) fn parse_u32(word: &str) -> Result<u32, AssemblerErrorKind> {
+    let parsed = match word.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => word.parse(),
+    };
+    parsed.map_err(|_| AssemblerErrorKind::InvalidNumber(word.to_string()))
+}
+
+pub(crate) fn parse_field<F: PrimeField32>(word: &str) -> Result<F, AssemblerErrorKind> {
+    let (negative, magnitude) = match word.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, word),
+    };
+    let magnitude = parse_u32(magnitude)
+        .ok()
+        .filter(|&magnitude| magnitude < F::ORDER_U32)
+        .ok_or_else(|| AssemblerErrorKind::InvalidNumber(word.to_string()))?;
+    let value = F::from_canonical_u32(magnitude);
+    Ok(if negative { -value } else { value })
+}
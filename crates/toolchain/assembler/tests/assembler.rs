@@ -0,0 +1,144 @@
+use openvm_assembler::{assemble, disassemble, AssemblerErrorKind};
+use openvm_circuit::arch::{VmConfig, VmExecutor};
+use openvm_instructions::{
+    instruction::Instruction, opcode_names::OpcodeNames, program::DEFAULT_PC_STEP, LocalOpcode,
+    SystemOpcode::TERMINATE,
+};
+use openvm_native_circuit::NativeConfig;
+use openvm_native_compiler::{
+    FieldArithmeticOpcode::*, NativeBranchEqualOpcode, NativeJalOpcode::*,
+};
+use openvm_rv32im_transpiler::BranchEqualOpcode::BEQ;
+use openvm_stark_backend::p3_field::FieldAlgebra;
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+type F = BabyBear;
+
+/// Decrements word[0]_4 from 6 down to 0.
+const COUNTDOWN: &str = r#"
+.fn 0x0 0x14 main
+.memory 4 0x10 -1
+
+main:
+    native.ADD 0, 6, 0, 4      ; word[0]_4 <- 6
+loop:
+    native.BEQ 0, 0, @end, 4   ; if word[0]_4 == 0 then goto end
+    native.SUB 0, 0, 1, 4, 4
+    native.JAL 2, @loop, 0, 4
+end:
+    TERMINATE
+"#;
+
+fn opcode_names() -> OpcodeNames {
+    VmConfig::<F>::opcode_names(&NativeConfig::default())
+}
+
+#[test]
+fn test_assemble_native_program() {
+    let exe = assemble::<F>(COUNTDOWN, &opcode_names()).unwrap();
+    let step = DEFAULT_PC_STEP as isize;
+    let expected = vec![
+        Instruction::large_from_isize(ADD.global_opcode(), 0, 6, 0, 4, 0, 0, 0),
+        Instruction::from_isize(
+            NativeBranchEqualOpcode(BEQ).global_opcode(),
+            0,
+            0,
+            3 * step,
+            4,
+            0,
+        ),
+        Instruction::large_from_isize(SUB.global_opcode(), 0, 0, 1, 4, 4, 0, 0),
+        Instruction::from_isize(JAL.global_opcode(), 2, -2 * step, 0, 4, 0),
+        Instruction::from_isize(TERMINATE.global_opcode(), 0, 0, 0, 0, 0),
+    ];
+    assert_eq!(exe.program.defined_instructions(), expected);
+    assert_eq!(exe.fn_bounds[&0].name, "main");
+    assert_eq!(exe.init_memory[&(4, 0x10)], -F::ONE);
+
+    let final_memory = VmExecutor::<F, NativeConfig>::new(NativeConfig::default())
+        .execute(exe, vec![])
+        .unwrap()
+        .unwrap();
+    let word = |address| final_memory.get(&(4, address)).copied().unwrap_or(F::ZERO);
+    assert_eq!(word(0), F::ZERO);
+    assert_eq!(word(2), F::from_canonical_u32(4 * DEFAULT_PC_STEP));
+}
+
+#[test]
+fn test_disassemble_roundtrip() {
+    let names = opcode_names();
+    let exe = assemble::<F>(COUNTDOWN, &names).unwrap();
+    let listing = disassemble(&exe, &names);
+    assert!(listing.contains("\nmain:\n"));
+    assert!(listing.contains("native.BEQ 0, 0, 12, 4"));
+    assert!(listing.contains("native.JAL 2, -8, 0, 4"));
+
+    let reassembled = assemble::<F>(&listing, &names).unwrap();
+    assert_eq!(
+        reassembled.program.defined_instructions(),
+        exe.program.defined_instructions()
+    );
+    assert_eq!(reassembled.pc_start, exe.pc_start);
+    assert_eq!(reassembled.init_memory, exe.init_memory);
+    assert_eq!(
+        reassembled
+            .fn_bounds
+            .values()
+            .map(|f| &f.name)
+            .collect::<Vec<_>>(),
+        exe.fn_bounds.values().map(|f| &f.name).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_assemble_gaps_and_quoted_names() {
+    let names = opcode_names();
+    let source = r#"
+.pc_base 0x100
+.fn 0x108 0x10c "<[u8; 4] as Debug>::fmt"
+    native.JAL 0, @"<[u8; 4] as Debug>::fmt", 0, 4
+.org 0x108
+"<[u8; 4] as Debug>::fmt":
+    TERMINATE
+"#;
+    let exe = assemble::<F>(source, &names).unwrap();
+    assert_eq!(exe.program.pc_base, 0x100);
+    assert_eq!(exe.pc_start, 0x100);
+    assert_eq!(exe.program.len(), 3);
+    assert!(exe.program.instructions_and_debug_infos[1].is_none());
+    assert_eq!(
+        exe.program.defined_instructions()[0].b,
+        F::from_canonical_u32(8)
+    );
+
+    let listing = disassemble(&exe, &names);
+    assert!(listing.contains(".org 0x00000108"));
+    let reassembled = assemble::<F>(&listing, &names).unwrap();
+    assert_eq!(
+        reassembled.program.defined_instructions(),
+        exe.program.defined_instructions()
+    );
+    assert_eq!(reassembled.program.len(), exe.program.len());
+    assert_eq!(
+        reassembled.fn_bounds[&0x108].name,
+        "<[u8; 4] as Debug>::fmt"
+    );
+}
+
+#[test]
+fn test_assemble_errors() {
+    let names = opcode_names();
+    let err = assemble::<F>("TERMINATE\nnative.JAL 0, @nowhere", &names).unwrap_err();
+    assert_eq!(err.line, 2);
+    assert!(matches!(err.kind, AssemblerErrorKind::UndefinedLabel(label) if label == "nowhere"));
+
+    let err = assemble::<F>("ADD 1, 2, 3", &names).unwrap_err();
+    assert!(matches!(err.kind, AssemblerErrorKind::UnknownOpcode(_)));
+
+    let err = assemble::<F>("TERMINATE\n.pc_base 4", &names).unwrap_err();
+    assert!(matches!(err.kind, AssemblerErrorKind::LateDirective(_)));
+
+    let err = assemble::<F>(".step 0\n.org 4", &names).unwrap_err();
+    assert_eq!(err.line, 1);
+    assert!(matches!(err.kind, AssemblerErrorKind::ZeroStep));
+}
@@ -16,6 +16,10 @@ pub struct GuestOptions {
     pub profile: Option<String>,
     /// Target directory
     pub target_dir: Option<PathBuf>,
+    /// Size of the guest stack in bytes. Defaults to [openvm_platform::memory::DEFAULT_STACK_SIZE].
+    /// The stack occupies `[GUEST_MIN_MEM, GUEST_MIN_MEM + stack_size)` and the program is loaded
+    /// right above it.
+    pub stack_size: Option<u32>,
     /// Custom options to pass as args to `cargo build`.
     pub(crate) options: Vec<String>,
 }
@@ -54,6 +58,12 @@ impl GuestOptions {
         self
     }
 
+    /// Set the size of the guest stack in bytes. It must be a multiple of 16.
+    pub fn with_stack_size(mut self, stack_size: u32) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
     #[allow(dead_code)]
     pub(crate) fn with_metadata(mut self, metadata: GuestMetadata) -> Self {
         self.rustc_flags = metadata.rustc_flags.unwrap_or_default();
@@ -12,7 +12,7 @@ use std::{
     process::{Command, Stdio},
 };
 
-use cargo_metadata::{MetadataCommand, Package};
+use cargo_metadata::{Message, MetadataCommand, Package};
 use openvm_platform::memory;
 
 pub use self::config::GuestOptions;
@@ -165,7 +165,8 @@ fn sanitized_cmd(tool: &str) -> Command {
 
 /// Creates a std::process::Command to execute the given cargo
 /// command in an environment suitable for targeting the zkvm guest.
-pub fn cargo_command(subcmd: &str, rust_flags: &[&str]) -> Command {
+/// The program is linked for a stack of `stack_size` bytes.
+pub fn cargo_command(subcmd: &str, rust_flags: &[&str], stack_size: u32) -> Command {
     let toolchain = format!("+{RUSTUP_TOOLCHAIN_NAME}");
 
     let rustc = sanitized_cmd("rustup")
@@ -199,7 +200,7 @@ pub fn cargo_command(subcmd: &str, rust_flags: &[&str]) -> Command {
 
     println!("Building guest package: cargo {}", args.join(" "));
 
-    let encoded_rust_flags = encode_rust_flags(rust_flags);
+    let encoded_rust_flags = encode_rust_flags(rust_flags, stack_size);
 
     cmd.env("RUSTC", rustc)
         .env("CARGO_ENCODED_RUSTFLAGS", encoded_rust_flags)
@@ -208,7 +209,16 @@ pub fn cargo_command(subcmd: &str, rust_flags: &[&str]) -> Command {
 }
 
 /// Returns a string that can be set as the value of CARGO_ENCODED_RUSTFLAGS when compiling guests
-pub(crate) fn encode_rust_flags(rustc_flags: &[&str]) -> String {
+pub(crate) fn encode_rust_flags(rustc_flags: &[&str], stack_size: u32) -> String {
+    assert!(
+        stack_size % 16 == 0 && stack_size > 0,
+        "stack size must be a positive multiple of 16"
+    );
+    let text_start = memory::text_start(stack_size);
+    assert!(
+        (text_start as usize) < memory::GUEST_MAX_MEM,
+        "stack size is too large"
+    );
     [
         // Append other rust flags
         rustc_flags,
@@ -222,7 +232,13 @@ pub(crate) fn encode_rust_flags(rustc_flags: &[&str]) -> String {
             // https://ftp.gnu.org/old-gnu/Manuals/ld-2.9.1/html_mono/ld.html#SEC3
             // for details.
             "-C",
-            &format!("link-arg=-Ttext=0x{:08X}", memory::TEXT_START),
+            &format!("link-arg=-Ttext=0x{:08X}", text_start),
+            // Top of the stack, read by the guest entry point.
+            "-C",
+            &format!(
+                "link-arg=--defsym=_stack_top=0x{:08X}",
+                memory::stack_top(stack_size)
+            ),
             // Apparently not having an entry point is only a linker warning(!), so
             // error out in this case.
             "-C",
@@ -269,38 +285,7 @@ pub fn build_guest_package(
         return Err(None);
     }
 
-    let target_dir = guest_opts
-        .target_dir
-        .clone()
-        .unwrap_or_else(|| get_target_dir(pkg.manifest_path.clone()));
-
-    fs::create_dir_all(&target_dir).unwrap();
-
-    let runtime_rust_flags = runtime_lib
-        .map(|lib| vec![String::from("-C"), format!("link_arg={}", lib)])
-        .unwrap_or_default();
-    let rust_flags: Vec<_> = [
-        runtime_rust_flags
-            .iter()
-            .map(|s| s.as_str())
-            .collect::<Vec<_>>(),
-        guest_opts.rustc_flags.iter().map(|s| s.as_str()).collect(),
-    ]
-    .concat();
-
-    let mut cmd = cargo_command("build", &rust_flags);
-
-    let features_str = guest_opts.features.join(",");
-    if !features_str.is_empty() {
-        cmd.args(["--features", &features_str]);
-    }
-
-    cmd.args([
-        "--manifest-path",
-        pkg.manifest_path.as_str(),
-        "--target-dir",
-        target_dir.to_str().unwrap(),
-    ]);
+    let (mut cmd, target_dir, profile) = guest_cargo_command("build", pkg, guest_opts, runtime_lib);
 
     if let Some(target_filter) = target_filter {
         cmd.args([
@@ -309,13 +294,6 @@ pub fn build_guest_package(
         ]);
     }
 
-    let profile = if let Some(profile) = &guest_opts.profile {
-        profile
-    } else {
-        "release"
-    };
-    cmd.args(["--profile", profile]);
-
     cmd.args(&guest_opts.options);
 
     let command_string = format!(
@@ -347,7 +325,7 @@ pub fn build_guest_package(
     } else {
         Ok(get_dir_with_profile(
             &target_dir,
-            profile,
+            &profile,
             target_filter
                 .as_ref()
                 .map(|t| t.kind == "example")
@@ -356,6 +334,113 @@ pub fn build_guest_package(
     }
 }
 
+/// Builds the test binaries of a package that targets the riscv guest, without running them,
+/// and returns their paths.
+///
+/// The tests must use the `openvm::testing` harness to run inside the VM.
+pub fn build_guest_tests(
+    pkg: &Package,
+    guest_opts: &GuestOptions,
+) -> Result<Vec<PathBuf>, Option<i32>> {
+    if is_skip_build() {
+        return Err(None);
+    }
+
+    let (mut cmd, _, _) = guest_cargo_command("test", pkg, guest_opts, None);
+    cmd.args(["--no-run", "--message-format", "json-render-diagnostics"]);
+    cmd.args(&guest_opts.options);
+
+    tty_println(&format!(
+        "{}: Starting test build for {RUSTC_TARGET}",
+        pkg.name
+    ));
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env("CARGO_TERM_COLOR", "always")
+        .spawn()
+        .expect("cargo test failed");
+    let stderr = child.stderr.take().unwrap();
+    let name = pkg.name.clone();
+    let stderr_thread = std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            tty_println(&format!("{}: {}", name, line.unwrap()));
+        }
+    });
+
+    let mut test_binaries = vec![];
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    for message in Message::parse_stream(stdout) {
+        if let Message::CompilerArtifact(artifact) = message.unwrap() {
+            if artifact.profile.test && artifact.package_id == pkg.id {
+                if let Some(executable) = artifact.executable {
+                    test_binaries.push(executable.into_std_path_buf());
+                }
+            }
+        }
+    }
+    stderr_thread.join().unwrap();
+
+    let res = child.wait().expect("Guest 'cargo test' failed");
+    if !res.success() {
+        Err(res.code())
+    } else {
+        Ok(test_binaries)
+    }
+}
+
+/// Returns the cargo command `subcmd` for the guest package, along with the target directory
+/// and profile it uses.
+fn guest_cargo_command(
+    subcmd: &str,
+    pkg: &Package,
+    guest_opts: &GuestOptions,
+    runtime_lib: Option<&str>,
+) -> (Command, PathBuf, String) {
+    let target_dir = guest_opts
+        .target_dir
+        .clone()
+        .unwrap_or_else(|| get_target_dir(pkg.manifest_path.clone()));
+
+    fs::create_dir_all(&target_dir).unwrap();
+
+    let runtime_rust_flags = runtime_lib
+        .map(|lib| vec![String::from("-C"), format!("link_arg={}", lib)])
+        .unwrap_or_default();
+    let rust_flags: Vec<_> = [
+        runtime_rust_flags
+            .iter()
+            .map(|s| s.as_str())
+            .collect::<Vec<_>>(),
+        guest_opts.rustc_flags.iter().map(|s| s.as_str()).collect(),
+    ]
+    .concat();
+
+    let stack_size = guest_opts.stack_size.unwrap_or(memory::DEFAULT_STACK_SIZE);
+    let mut cmd = cargo_command(subcmd, &rust_flags, stack_size);
+
+    let features_str = guest_opts.features.join(",");
+    if !features_str.is_empty() {
+        cmd.args(["--features", &features_str]);
+    }
+
+    cmd.args([
+        "--manifest-path",
+        pkg.manifest_path.as_str(),
+        "--target-dir",
+        target_dir.to_str().unwrap(),
+    ]);
+
+    let profile = guest_opts
+        .profile
+        .clone()
+        .unwrap_or_else(|| "release".to_string());
+    cmd.args(["--profile", &profile]);
+
+    (cmd, target_dir, profile)
+}
+
 /// A filter for selecting a target from a package.
 #[derive(Default)]
 pub struct TargetFilter {
@@ -9,6 +9,8 @@ use crate::program::Program;
 pub type MemoryImage<F> = BTreeMap<(u32, u32), F>;
 /// Stores the starting address, end address, and name of a set of function.
 pub type FnBounds = BTreeMap<u32, FnBound>;
+/// DWARF sections of the ELF, such as `.debug_line`, by section name.
+pub type DebugSections = BTreeMap<String, Vec<u8>>;
 
 /// Executable program for OpenVM.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -25,6 +27,9 @@ pub struct VmExe<F> {
     pub init_memory: MemoryImage<F>,
     /// Starting + ending bounds for each function.
     pub fn_bounds: FnBounds,
+    /// DWARF sections used to symbolize guest backtraces. Empty if the ELF has no debug info.
+    #[serde(default)]
+    pub debug_sections: DebugSections,
 }
 
 impl<F> VmExe<F> {
@@ -34,6 +39,7 @@ impl<F> VmExe<F> {
             pc_start: 0,
             init_memory: BTreeMap::new(),
             fn_bounds: Default::default(),
+            debug_sections: Default::default(),
         }
     }
     pub fn with_pc_start(mut self, pc_start: u32) -> Self {
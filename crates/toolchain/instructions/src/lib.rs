@@ -9,6 +9,7 @@ use strum_macros::{EnumCount, EnumIter, FromRepr};
 
 pub mod exe;
 pub mod instruction;
+pub mod opcode_names;
 mod phantom;
 pub mod program;
 /// Module with traits and constants for RISC-V instruction definitions for custom OpenVM instructions.
@@ -0,0 +1,95 @@
+use std::{collections::HashMap, fmt::Debug};
+
+use strum::IntoEnumIterator;
+
+use crate::{LocalOpcode, PublishOpcode, SystemOpcode, VmOpcode};
+
+/// Names of the opcodes supported by a VM, as printed and parsed in OpenVM assembly.
+///
+/// The name of an opcode is the `Debug` name of its local opcode, e.g. `ADD` for
+/// `BaseAluOpcode::ADD`. Extensions whose local opcodes would clash with those of another
+/// extension qualify them with a prefix, e.g. `native.ADD`.
+#[derive(Clone, Debug, Default)]
+pub struct OpcodeNames {
+    names: HashMap<VmOpcode, String>,
+    opcodes: HashMap<String, VmOpcode>,
+}
+
+impl OpcodeNames {
+    /// Names of the opcodes handled by the system, which every VM supports.
+    pub fn system() -> Self {
+        Self::default()
+            .with_opcodes("", SystemOpcode::iter())
+            .with_opcodes("", PublishOpcode::iter())
+    }
+
+    /// Names `opcodes` by their `Debug` names, qualified by `prefix` unless it is empty.
+    pub fn with_opcodes<T: LocalOpcode + Debug>(
+        self,
+        prefix: &str,
+        opcodes: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.with_opcodes_at(prefix, T::CLASS_OFFSET, opcodes)
+    }
+
+    /// Same as [Self::with_opcodes], but for a class of opcodes starting at `class_offset`
+    /// instead of `T::CLASS_OFFSET`, e.g. for opcodes repeated for each supported modulus.
+    pub fn with_opcodes_at<T: LocalOpcode + Debug>(
+        mut self,
+        prefix: &str,
+        class_offset: usize,
+        opcodes: impl IntoIterator<Item = T>,
+    ) -> Self {
+        for opcode in opcodes {
+            let name = if prefix.is_empty() {
+                format!("{:?}", opcode)
+            } else {
+                format!("{}.{:?}", prefix, opcode)
+            };
+            self.insert(
+                VmOpcode::from_usize(class_offset + opcode.local_usize()),
+                name,
+            );
+        }
+        self
+    }
+
+    /// Adds all names of `other`.
+    pub fn merge(mut self, other: OpcodeNames) -> Self {
+        for (opcode, name) in other.names {
+            self.insert(opcode, name);
+        }
+        self
+    }
+
+    /// Names `opcode`. Panics if the opcode or the name is already taken.
+    pub fn insert(&mut self, opcode: VmOpcode, name: String) {
+        assert!(
+            !self.opcodes.contains_key(&name),
+            "opcode name {name} is already taken"
+        );
+        assert!(
+            !self.names.contains_key(&opcode),
+            "opcode {opcode} is already named {}",
+            self.names[&opcode]
+        );
+        self.opcodes.insert(name.clone(), opcode);
+        self.names.insert(opcode, name);
+    }
+
+    pub fn name(&self, opcode: VmOpcode) -> Option<&str> {
+        self.names.get(&opcode).map(String::as_str)
+    }
+
+    pub fn opcode(&self, name: &str) -> Option<VmOpcode> {
+        self.opcodes.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
@@ -0,0 +1,49 @@
+//! Unverified claims about OpenVM app proofs.
+//!
+//! A guest cannot verify a STARK proof. [claim_openvm_proof] only records a [ProofClaim] in the
+//! public output of the guest, which nothing in the proof of the guest checks: the aggregation
+//! programs and the EVM verifier treat it as any other public value. A proof of the guest
+//! therefore says nothing about the claimed proof. The claim is only resolved if whoever
+//! verifies the proof of the guest also checks, on the host, a proof of the claimed execution on
+//! an App VM it trusts, with `openvm_sdk::Sdk::check_proof_claims`.
+
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::commit;
+
+/// Number of `u32` words in a commitment, each a canonical BabyBear element.
+pub const DIGEST_SIZE: usize = 8;
+
+/// Unverified claim that an OpenVM app proof verifies.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofClaim {
+    /// Commitment of the leaf verifier program of the App VM, `leaf_vm_verifier_commit` of
+    /// `openvm_sdk::commit::AppExecutionCommit`.
+    pub vk_commit: [u32; DIGEST_SIZE],
+    /// Commitment of the executable, `exe_commit` of `openvm_sdk::commit::AppExecutionCommit`.
+    pub exe_commit: [u32; DIGEST_SIZE],
+    /// User public values of the execution. The claim holds the values instead of their
+    /// commitment so that the guest can use them without computing Poseidon2. Public values
+    /// after the end of `public_values` must be zero.
+    pub public_values: Vec<u8>,
+}
+
+/// Claims that there is a valid proof of the execution of the executable committed by
+/// `exe_commit` on the App VM committed by `vk_commit`, with user public values `public_values`.
+/// The claim is not verified: the guest continues whether or not such a proof exists.
+///
+/// The claim is appended to the public output with [commit], so it is decoded by the host along
+/// with the other committed values, as a `ProofClaim` at the position of the call.
+pub fn claim_openvm_proof(
+    vk_commit: &[u32; DIGEST_SIZE],
+    exe_commit: &[u32; DIGEST_SIZE],
+    public_values: &[u8],
+) {
+    commit(&ProofClaim {
+        vk_commit: *vk_commit,
+        exe_commit: *exe_commit,
+        public_values: public_values.to_vec(),
+    });
+}
@@ -0,0 +1,170 @@
+//! Virtual file system provided by the host.
+//!
+//! The host maps file names to byte blobs and sets the arguments, environment variables and
+//! standard input of the guest (see `openvm_sdk::StdIn::add_file`). They are stored as keyed
+//! hints and read on demand with [crate::io::hint_load_by_key]. With the `std` feature,
+//! `std::env::args`, `std::env::var` and `std::io::stdin` are served from them. The standard
+//! library does not support `std::fs` in the zkVM, so files are read with [read].
+//!
+//! Like any hint, everything read from the virtual file system is controlled by the host and
+//! must be validated by the guest.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::hint_load_by_key;
+
+/// Key of the serialized [VfsIndex].
+pub const VFS_INDEX_KEY: &[u8] = b"openvm/vfs/index";
+/// Standard input is stored in chunks of this many bytes, so that it can be read incrementally.
+pub const STDIN_CHUNK_SIZE: usize = 1 << 12;
+
+/// Arguments, environment variables and names of the files of the virtual file system.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VfsIndex {
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub files: Vec<String>,
+    pub stdin_len: u32,
+}
+
+/// Key of the file `name`.
+pub fn file_key(name: &str) -> Vec<u8> {
+    [&b"openvm/vfs/file/"[..], name.as_bytes()].concat()
+}
+
+/// Key of the `index`-th chunk of standard input.
+pub fn stdin_chunk_key(index: u32) -> Vec<u8> {
+    [&b"openvm/vfs/stdin/"[..], &index.to_le_bytes()].concat()
+}
+
+/// Reads the index of the virtual file system.
+pub fn index() -> VfsIndex {
+    crate::serde::from_slice(&hint_load_by_key(VFS_INDEX_KEY)).expect("invalid vfs index")
+}
+
+/// Index of the virtual file system, read on first use.
+static mut INDEX: Option<&'static VfsIndex> = None;
+
+/// Like [index], but reads the index only on the first call, since it is queried by every
+/// lookup of an argument, environment variable or file.
+fn cached_index() -> &'static VfsIndex {
+    // SAFETY: the guest is single-threaded.
+    if let Some(index) = unsafe { INDEX } {
+        return index;
+    }
+    let index: &'static VfsIndex = Box::leak(Box::new(index()));
+    unsafe {
+        INDEX = Some(index);
+    }
+    index
+}
+
+/// Returns the arguments of the guest. The first argument is conventionally the program name.
+pub fn args() -> Vec<String> {
+    cached_args().to_vec()
+}
+
+/// Like [args], but without copying, for syscalls querying one argument at a time.
+pub(crate) fn cached_args() -> &'static [String] {
+    &cached_index().args
+}
+
+/// Returns the value of the environment variable `name`, if it is set.
+pub fn var(name: &str) -> Option<String> {
+    cached_index()
+        .env
+        .iter()
+        .find_map(|(key, value)| (key == name).then(|| value.clone()))
+}
+
+/// Returns whether the file `name` exists.
+pub fn exists(name: &str) -> bool {
+    cached_index().files.iter().any(|file| file == name)
+}
+
+/// Reads the file `name`, or returns `None` if it does not exist.
+pub fn read(name: &str) -> Option<Vec<u8>> {
+    exists(name).then(|| hint_load_by_key(&file_key(name)))
+}
+
+/// Reads the file `name` as a string, or returns `None` if it does not exist or is not UTF-8.
+pub fn read_to_string(name: &str) -> Option<String> {
+    String::from_utf8(read(name)?).ok()
+}
+
+/// Number of bytes of standard input read so far.
+static mut STDIN_OFFSET: usize = 0;
+
+/// Reads up to `buf.len()` bytes of standard input into `buf` and returns the number of bytes
+/// read, which is 0 only at the end of standard input or if `buf` is empty.
+pub fn read_stdin(buf: &mut [u8]) -> usize {
+    // SAFETY: the guest is single-threaded.
+    let offset = unsafe { STDIN_OFFSET };
+    let len = cached_index().stdin_len as usize;
+    if offset >= len || buf.is_empty() {
+        return 0;
+    }
+    // Only read from the current chunk, short reads are allowed.
+    let chunk = hint_load_by_key(&stdin_chunk_key((offset / STDIN_CHUNK_SIZE) as u32));
+    let start = offset % STDIN_CHUNK_SIZE;
+    let n = buf.len().min(chunk.len() - start);
+    buf[..n].copy_from_slice(&chunk[start..start + n]);
+    unsafe {
+        STDIN_OFFSET = offset + n;
+    }
+    n
+}
+
+#[cfg(all(test, not(target_os = "zkvm")))]
+mod tests {
+    use alloc::{collections::BTreeMap, string::ToString, vec};
+
+    use super::*;
+    use crate::host::set_keyed_hints;
+
+    fn to_bytes(index: &VfsIndex) -> Vec<u8> {
+        crate::serde::to_vec(index)
+            .unwrap()
+            .into_iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_vfs() {
+        let stdin: Vec<u8> = (0..STDIN_CHUNK_SIZE + 10).map(|i| i as u8).collect();
+        let index = VfsIndex {
+            args: vec!["guest".to_string(), "--flag".to_string()],
+            env: vec![("KEY".to_string(), "value".to_string())],
+            files: vec!["a.txt".to_string()],
+            stdin_len: stdin.len() as u32,
+        };
+        let mut hints = BTreeMap::from([
+            (VFS_INDEX_KEY.to_vec(), to_bytes(&index)),
+            (file_key("a.txt"), b"hello".to_vec()),
+        ]);
+        for (i, chunk) in stdin.chunks(STDIN_CHUNK_SIZE).enumerate() {
+            hints.insert(stdin_chunk_key(i as u32), chunk.to_vec());
+        }
+        set_keyed_hints(hints);
+
+        assert_eq!(args(), vec!["guest", "--flag"]);
+        assert_eq!(var("KEY").as_deref(), Some("value"));
+        assert_eq!(var("MISSING"), None);
+        assert_eq!(read_to_string("a.txt").as_deref(), Some("hello"));
+        assert_eq!(read("b.txt"), None);
+
+        let mut read_back = vec![];
+        let mut buf = [0u8; 1000];
+        loop {
+            let n = read_stdin(&mut buf);
+            if n == 0 {
+                break;
+            }
+            read_back.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(read_back, stdin);
+    }
+}
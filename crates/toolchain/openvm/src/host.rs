@@ -1,6 +1,6 @@
 //! Hints emulation for the non-zkVM environment.
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::cell::RefCell;
 
 /// Simulated input stream on host
@@ -31,6 +31,9 @@ pub static HINTS: RefCell<HostInputStream> = RefCell::new(HostInputStream::new()
 /// Current hint stream in the non-zkVM environment.
 #[thread_local]
 pub static HINT_STREAM: RefCell<Vec<u8>> = RefCell::new(Vec::new());
+/// Hints looked up by key in the non-zkVM environment.
+#[thread_local]
+pub static KEYED_HINTS: RefCell<BTreeMap<Vec<u8>, Vec<u8>>> = RefCell::new(BTreeMap::new());
 
 /// Set the hints and reset the current hint stream.
 pub fn set_hints(hints: Vec<Vec<u8>>) {
@@ -71,6 +74,19 @@ pub fn hint_input() {
     }
 }
 
+/// Set the hints looked up by key.
+pub fn set_keyed_hints(hints: BTreeMap<Vec<u8>, Vec<u8>>) {
+    KEYED_HINTS.replace(hints);
+}
+
+/// Reset the current hint stream with the hint stored under `key`.
+pub fn hint_load_by_key(key: &[u8]) {
+    let hints = KEYED_HINTS.borrow();
+    let hint = hints.get(key).expect("No hint stored under the key");
+    let hint = [&(hint.len() as u32).to_le_bytes(), &hint[..]].concat();
+    HINT_STREAM.replace(hint);
+}
+
 /// Read the next `n` bytes from the hint stream.
 pub fn read_n_bytes(n: usize) -> Vec<u8> {
     HINT_STREAM.borrow_mut().drain(..n).collect()
@@ -87,7 +103,7 @@ mod tests {
     use alloc::vec;
 
     use super::*;
-    use crate::io::read_vec;
+    use crate::io::{self, read_vec};
 
     #[test]
     fn test_read_hints() {
@@ -98,4 +114,12 @@ mod tests {
         assert_eq!(read_n_bytes(8), vec![4, 0, 0, 0, 1, 2, 3, 4]);
         assert_eq!(read_vec(), vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_read_keyed_hints() {
+        set_keyed_hints(BTreeMap::from([(b"a".to_vec(), vec![1, 2, 3])]));
+        assert_eq!(io::hint_load_by_key(b"a"), vec![1, 2, 3]);
+        // Keyed hints can be loaded more than once.
+        assert_eq!(io::hint_load_by_key(b"a"), vec![1, 2, 3]);
+    }
 }
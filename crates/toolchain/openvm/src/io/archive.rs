@@ -0,0 +1,29 @@
+//! Zero-copy reads of [rkyv] archives written by `openvm_sdk::StdIn::write_archived`.
+//!
+//! The archive is hinted directly into an aligned buffer in guest memory and accessed in place,
+//! so reading it costs no deserialization. The host and the guest must enable the same rkyv
+//! format features (e.g. `unaligned` or `big_endian`), otherwise the archive will not validate.
+
+use rkyv::{api::high::HighValidator, bytecheck::CheckBytes, rancor::Error, Archive, Archived};
+
+use super::read_aligned;
+
+/// Read the next input as the archive of a `T` and validate it, without copying or
+/// deserializing it. The returned view borrows from a buffer that lives for the rest of the
+/// program.
+pub fn read_archived<T: Archive>() -> Result<&'static Archived<T>, Error>
+where
+    Archived<T>: for<'a> CheckBytes<HighValidator<'a, Error>>,
+{
+    rkyv::access::<Archived<T>, Error>(read_aligned())
+}
+
+/// Read the next input as the archive of a `T` without validating it.
+///
+/// # Safety
+/// The next input must be a valid archive of a `T`, for example one written by
+/// `StdIn::write_archived` with the same rkyv format features as the guest. Prefer
+/// [read_archived] unless the validation cost matters and the input is trusted.
+pub unsafe fn read_archived_unchecked<T: Archive>() -> &'static Archived<T> {
+    rkyv::access_unchecked::<Archived<T>>(read_aligned())
+}
@@ -1,20 +1,22 @@
 //! User IO functions
 
 use alloc::vec::Vec;
-#[cfg(target_os = "zkvm")]
-use core::alloc::Layout;
-use core::fmt::Write;
+use core::{alloc::Layout, fmt::Write};
 
 #[cfg(target_os = "zkvm")]
 use openvm_rv32im_guest::{hint_buffer_u32, hint_input, hint_store_u32};
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 
 #[cfg(not(target_os = "zkvm"))]
 use crate::host::{hint_input, read_n_bytes, read_u32};
 use crate::serde::Deserializer;
 
+#[cfg(feature = "rkyv")]
+mod archive;
 mod read;
 
+#[cfg(feature = "rkyv")]
+pub use archive::{read_archived, read_archived_unchecked};
 pub use openvm_platform::print::{print, println};
 
 /// Read `size: u32` and then `size` bytes from the hint stream into a vector.
@@ -23,6 +25,29 @@ pub fn read_vec() -> Vec<u8> {
     read_vec_by_len(read_u32() as usize)
 }
 
+/// Alignment of the buffers returned by [read_aligned], enough for any archived type.
+pub const ARCHIVE_ALIGN: usize = 16;
+
+/// Read `size: u32` and then `size` bytes from the hint stream directly into a buffer aligned to
+/// [ARCHIVE_ALIGN] bytes.
+///
+/// Unlike [read_vec], the buffer is never freed, so views borrowed from it, such as the archives
+/// read by `read_archived`, can be kept for the rest of the program without copying.
+pub fn read_aligned() -> &'static [u8] {
+    hint_input();
+    read_aligned_by_len(read_u32() as usize)
+}
+
+/// Read the hint stored under `key` into a vector. Unlike [read_vec], keyed hints can be read in
+/// any order and any number of times.
+pub fn hint_load_by_key(key: &[u8]) -> Vec<u8> {
+    #[cfg(target_os = "zkvm")]
+    openvm_rv32im_guest::hint_load_by_key(key.as_ptr(), key.len());
+    #[cfg(not(target_os = "zkvm"))]
+    crate::host::hint_load_by_key(key);
+    read_vec_by_len(read_u32() as usize)
+}
+
 /// Read the next vec and deserialize it into a type `T`.
 pub fn read<T: DeserializeOwned>() -> T {
     let reader = read::Reader::new();
@@ -81,6 +106,34 @@ pub(crate) fn read_vec_by_len(len: usize) -> Vec<u8> {
     }
 }
 
+/// Read the next `len` bytes from the hint stream into a leaked buffer aligned to [ARCHIVE_ALIGN]
+/// bytes.
+fn read_aligned_by_len(len: usize) -> &'static [u8] {
+    if len == 0 {
+        return &[];
+    }
+    let num_words = (len + 3) / 4;
+    let layout =
+        Layout::from_size_align(num_words * 4, ARCHIVE_ALIGN).expect("buffer is too large");
+    // SAFETY: `layout` has non-zero size.
+    let ptr_start = unsafe { alloc::alloc::alloc(layout) };
+    if ptr_start.is_null() {
+        alloc::alloc::handle_alloc_error(layout);
+    }
+
+    #[cfg(target_os = "zkvm")]
+    // Note: if len % 4 != 0, the last word is padded with the extra hinted bytes
+    hint_buffer_u32!(ptr_start, num_words);
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        let bytes = read_n_bytes(len);
+        // SAFETY: the allocation holds at least `len` bytes.
+        unsafe { core::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr_start, len) };
+    }
+    // SAFETY: the first `len` bytes were initialized above and the allocation is never freed.
+    unsafe { core::slice::from_raw_parts(ptr_start, len) }
+}
+
 /// Publish `x` as the `index`-th u32 output.
 #[allow(unused_variables)]
 pub fn reveal(x: u32, index: usize) {
@@ -91,6 +144,41 @@ pub fn reveal(x: u32, index: usize) {
     println!("reveal {} at byte location {}", x, index * 4);
 }
 
+/// Byte offset of the next public output written by [commit] or [commit_bytes].
+static mut PUBLIC_OUTPUT_OFFSET: usize = 0;
+
+/// Serialize `value` with [crate::serde] and append it to the public output.
+///
+/// The host can decode the public output of consecutive commits of `a: A`, `b: B`, ... as the
+/// tuple `(A, B, ...)` with `openvm_sdk::decode_public_values`.
+pub fn commit<T: Serialize + ?Sized>(value: &T) {
+    let words = crate::serde::to_vec(value).unwrap();
+    commit_words(&words);
+}
+
+/// Append `bytes` to the public output, padding with zeros up to the next word boundary.
+pub fn commit_bytes(bytes: &[u8]) {
+    let mut words = Vec::with_capacity((bytes.len() + 3) / 4);
+    crate::serde::WordWrite::write_padded_bytes(&mut words, bytes).unwrap();
+    commit_words(&words);
+}
+
+/// Publish `words` as u32 outputs starting at the current public output offset.
+///
+/// Public outputs can only be set once, so [commit] and [commit_bytes] should not be mixed with
+/// [reveal] at the same indices.
+fn commit_words(words: &[u32]) {
+    // SAFETY: the guest is single-threaded.
+    let offset = unsafe { PUBLIC_OUTPUT_OFFSET };
+    let start = offset / 4;
+    for (i, &word) in words.iter().enumerate() {
+        reveal(word, start + i);
+    }
+    unsafe {
+        PUBLIC_OUTPUT_OFFSET = offset + words.len() * 4;
+    }
+}
+
 /// A no-alloc writer to print to stdout on host machine for debugging purposes.
 pub struct Writer;
 
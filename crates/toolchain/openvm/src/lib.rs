@@ -5,6 +5,7 @@
 // #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![feature(thread_local)]
+#![cfg_attr(feature = "test-harness", feature(test))]
 
 #[macro_use]
 extern crate alloc;
@@ -19,12 +20,18 @@ pub use openvm_platform as platform;
 use openvm_platform::rust_rt;
 #[cfg(target_os = "zkvm")]
 pub use openvm_rv32im_guest::*;
+#[cfg(feature = "rkyv")]
+pub use rkyv;
 
+pub mod claim;
+pub mod fs;
 pub mod io;
 #[cfg(all(feature = "std", target_os = "zkvm"))]
 pub mod pal_abi;
 pub mod process;
 pub mod serde;
+#[cfg(feature = "test-harness")]
+pub mod testing;
 
 #[cfg(not(target_os = "zkvm"))]
 pub mod utils;
@@ -111,19 +118,19 @@ unsafe extern "C" fn __start() -> ! {
         main()
     }
 
-    process::exit();
+    process::exit(0);
     unreachable!()
 }
 
-#[cfg(target_os = "zkvm")]
-static STACK_TOP: u32 = openvm_platform::memory::STACK_TOP;
-
 // Entry point; sets up global pointer and stack pointer and passes
-// to zkvm_start.  TODO: when asm_const is stabilized, use that here
-// instead of defining a symbol and dereferencing it.
+// to zkvm_start. The top of the stack is the address of the `_stack_top` symbol, which
+// `openvm-build` sets according to the configured stack size. It defaults to
+// [openvm_platform::memory::STACK_TOP].
 #[cfg(target_os = "zkvm")]
 core::arch::global_asm!(
     r#"
+.weak _stack_top;
+.set _stack_top, {0};
 .section .text._start;
 .globl _start;
 _start:
@@ -131,11 +138,10 @@ _start:
     .option norelax;
     la gp, __global_pointer$;
     .option pop;
-    la sp, {0};
-    lw sp, 0(sp);
+    la sp, _stack_top;
     call __start;
 "#,
-    sym STACK_TOP
+    const openvm_platform::memory::STACK_TOP
 );
 
 /// Require that accesses to behind the given pointer before the memory
@@ -161,6 +167,10 @@ fn panic_impl(panic_info: &core::panic::PanicInfo) -> ! {
     use core::fmt::Write;
     let mut writer = crate::io::Writer;
     let _ = write!(writer, "{}\n", panic_info);
+    let (file, line) = panic_info
+        .location()
+        .map_or(("", 0), |location| (location.file(), location.line()));
+    openvm_platform::rust_rt::report_panic(file, line, format_args!("{}", panic_info.message()));
     openvm_platform::rust_rt::terminate::<1>();
     unreachable!()
 }
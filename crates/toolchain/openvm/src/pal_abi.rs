@@ -4,7 +4,12 @@
 /// This will be removed once a dedicated rust toolchain is used because OpenVM does not handle system
 /// operations in the same way: there is no operating system and even the standard library should be
 /// directly handled with intrinsics.
-use openvm_platform::{fileno::*, memory::sys_alloc_aligned, rust_rt::terminate, WORD_SIZE};
+use openvm_platform::{
+    fileno::*,
+    memory::sys_alloc_aligned,
+    rust_rt::{report_panic, terminate},
+    WORD_SIZE,
+};
 use openvm_rv32im_guest::{hint_buffer_u32, hint_random, raw_print_str_from_bytes};
 
 const DIGEST_WORDS: usize = 8;
@@ -82,6 +87,20 @@ pub unsafe extern "C" fn sys_rand(recv_buf: *mut u32, words: usize) {
 #[no_mangle]
 unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
     raw_print_str_from_bytes(msg_ptr, len);
+    let msg = if msg_ptr.is_null() {
+        &[]
+    } else {
+        core::slice::from_raw_parts(msg_ptr, len)
+    };
+    // std does not pass the panic location through the PAL, so only the message is reported.
+    report_panic(
+        "",
+        0,
+        format_args!(
+            "{}",
+            core::str::from_utf8(msg).unwrap_or("<non-utf8 panic message>")
+        ),
+    );
     terminate::<{ exit_code::PANIC }>();
     unreachable!()
 }
@@ -115,10 +134,14 @@ pub extern "C" fn sys_cycle_count() -> u64 {
 ///
 /// `recv_ptr` must be aligned and dereferenceable.
 #[no_mangle]
-pub unsafe extern "C" fn sys_read(_fd: u32, _recv_ptr: *mut u8, _nread: usize) -> usize {
-    crate::io::println("sys_read is todo");
-    terminate::<{ exit_code::UNIMP }>();
-    0
+pub unsafe extern "C" fn sys_read(fd: u32, recv_ptr: *mut u8, nread: usize) -> usize {
+    if fd != STDIN {
+        unimplemented_fd("sys_read", fd);
+    }
+    if nread == 0 {
+        return 0;
+    }
+    crate::fs::read_stdin(core::slice::from_raw_parts_mut(recv_ptr, nread))
 }
 
 /// Reads up to the given number of words into the buffer [recv_buf,
@@ -140,10 +163,23 @@ pub unsafe extern "C" fn sys_read(_fd: u32, _recv_ptr: *mut u8, _nread: usize) -
 /// `recv_ptr' must be a word-aligned pointer and point to a region of
 /// `nwords' size.
 #[no_mangle]
-pub unsafe extern "C" fn sys_read_words(_fd: u32, _recv_ptr: *mut u32, _nwords: usize) -> usize {
-    crate::io::println("sys_read_words is todo");
-    terminate::<{ exit_code::UNIMP }>();
-    0
+pub unsafe extern "C" fn sys_read_words(fd: u32, recv_ptr: *mut u32, nwords: usize) -> usize {
+    if fd != STDIN {
+        unimplemented_fd("sys_read_words", fd);
+    }
+    if nwords == 0 {
+        return 0;
+    }
+    let recv_buf = core::slice::from_raw_parts_mut(recv_ptr as *mut u8, nwords * WORD_SIZE);
+    let mut nread = 0;
+    while nread < recv_buf.len() {
+        let n = crate::fs::read_stdin(&mut recv_buf[nread..]);
+        if n == 0 {
+            break;
+        }
+        nread += n;
+    }
+    nread
 }
 
 /// # Safety
@@ -155,11 +191,27 @@ pub unsafe extern "C" fn sys_write(fd: u32, write_ptr: *const u8, nbytes: usize)
         // We always print to host stdout using UTF-8 encoding.
         raw_print_str_from_bytes(write_ptr, nbytes);
     } else {
-        use core::fmt::Write;
-        let mut writer = crate::io::Writer;
-        let _ = write!(writer, "sys_write to fd={fd} not supported.\n");
-        terminate::<{ exit_code::UNIMP }>();
+        unimplemented_fd("sys_write", fd);
+    }
+}
+
+/// Terminates with [exit_code::UNIMP] because `syscall` does not support the file descriptor.
+fn unimplemented_fd(syscall: &str, fd: u32) -> ! {
+    use core::fmt::Write;
+    let mut writer = crate::io::Writer;
+    let _ = write!(writer, "{syscall} to fd={fd} not supported.\n");
+    terminate::<{ exit_code::UNIMP }>();
+    unreachable!()
+}
+
+/// Copies as much of `bytes` as fits into [out_words, out_words + out_nwords) and returns the
+/// length of `bytes`.
+unsafe fn copy_to_words(bytes: &[u8], out_words: *mut u32, out_nwords: usize) -> usize {
+    let n = bytes.len().min(out_nwords * WORD_SIZE);
+    if n > 0 {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), out_words as *mut u8, n);
     }
+    bytes.len()
 }
 
 /// Retrieves the value of an environment variable, and stores as much
@@ -181,13 +233,20 @@ pub unsafe extern "C" fn sys_write(fd: u32, write_ptr: *const u8, nbytes: usize)
 /// `out_words` and `varname` must be aligned and dereferenceable.
 #[no_mangle]
 pub unsafe extern "C" fn sys_getenv(
-    _out_words: *mut u32,
-    _out_nwords: usize,
-    _varname: *const u8,
-    _varname_len: usize,
+    out_words: *mut u32,
+    out_nwords: usize,
+    varname: *const u8,
+    varname_len: usize,
 ) -> usize {
-    crate::io::println("sys_getenv is todo; returning 0");
-    0
+    let varname = if varname.is_null() {
+        &[]
+    } else {
+        core::slice::from_raw_parts(varname, varname_len)
+    };
+    match core::str::from_utf8(varname).ok().and_then(crate::fs::var) {
+        Some(value) => copy_to_words(value.as_bytes(), out_words, out_nwords),
+        None => usize::MAX,
+    }
 }
 
 /// Retrieves the count of arguments provided to program execution.
@@ -196,8 +255,7 @@ pub unsafe extern "C" fn sys_getenv(
 /// data being returned. Returned data is entirely in the control of the host.
 #[no_mangle]
 pub extern "C" fn sys_argc() -> usize {
-    crate::io::println("sys_argc is todo; returning 0");
-    0
+    crate::fs::cached_args().len()
 }
 
 /// Retrieves the argument with arg_index, and stores as much
@@ -205,8 +263,8 @@ pub extern "C" fn sys_argc() -> usize {
 /// out_nwords).
 ///
 /// Returns the length, in bytes, of the argument string. If the requested
-/// argument index does not exist (i.e. `arg_index` >= argc) then this syscall
-/// will not return.
+/// argument index does not exist (i.e. `arg_index` >= argc), nothing is written
+/// and 0 is returned.
 ///
 /// This is normally called twice to read an argument: Once to get the length of
 /// the value, and once to fill in allocated memory.
@@ -219,12 +277,14 @@ pub extern "C" fn sys_argc() -> usize {
 /// `out_words` must be aligned and dereferenceable.
 #[no_mangle]
 pub unsafe extern "C" fn sys_argv(
-    _out_words: *mut u32,
-    _out_nwords: usize,
-    _arg_index: usize,
+    out_words: *mut u32,
+    out_nwords: usize,
+    arg_index: usize,
 ) -> usize {
-    crate::io::println("sys_argv is todo; returning 0");
-    0
+    match crate::fs::cached_args().get(arg_index) {
+        Some(arg) => copy_to_words(arg.as_bytes(), out_words, out_nwords),
+        None => 0,
+    }
 }
 
 /// Deprecated, use `sys_alloc_aligned` instead.
@@ -1,8 +1,9 @@
 //! System exit and panic functions.
 
-/// Exit the program with exit code 0.
-pub fn exit() {
-    openvm_platform::rust_rt::terminate::<0>();
+/// Exit the program with the given exit code. A non-zero exit code is reported to the host as
+/// `ExecutionError::FailedWithExitCode`.
+pub fn exit(code: u8) {
+    openvm_platform::rust_rt::terminate_with_code(code);
 }
 
 /// Exit the program with exit code 1.
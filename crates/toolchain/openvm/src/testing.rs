@@ -0,0 +1,59 @@
+//! Test harness to run `#[test]` functions inside the VM, used by `cargo openvm test`.
+//!
+//! Enable the `test-harness` feature for the guest's tests, e.g. with
+//! `openvm = { ..., features = ["test-harness"] }` in `[dev-dependencies]`, and register the
+//! runner at the crate root:
+//!
+//! ```ignore
+//! #![cfg_attr(all(test, target_os = "zkvm"), feature(custom_test_frameworks))]
+//! #![cfg_attr(all(test, target_os = "zkvm"), test_runner(openvm::testing::runner))]
+//! ```
+//!
+//! The host selects what the test binary does with its first input, a `u32`: [LIST_TESTS] commits
+//! the test descriptions to the public values, and any other value runs the test at that index.
+//! A test passes if the program terminates successfully.
+
+extern crate test;
+
+use alloc::vec::Vec;
+
+use test::{ShouldPanic, TestDescAndFn, TestFn};
+
+/// Input that makes the runner commit the test descriptions instead of running a test.
+pub const LIST_TESTS: u32 = u32::MAX;
+
+/// Runner for `#![test_runner]`.
+///
+/// In list mode, commits a `Vec<(name, ignore, should_panic)>` of type
+/// `Vec<(String, bool, Option<String>)>`, where `should_panic` holds the expected panic message,
+/// empty if any message is expected.
+pub fn runner(tests: &[&TestDescAndFn]) {
+    let command: u32 = crate::io::read();
+    if command == LIST_TESTS {
+        let descs: Vec<(&str, bool, Option<&str>)> = tests
+            .iter()
+            .map(|test| {
+                let should_panic = match test.desc.should_panic {
+                    ShouldPanic::No => None,
+                    ShouldPanic::Yes => Some(""),
+                    ShouldPanic::YesWithMessage(msg) => Some(msg),
+                };
+                (test.desc.name.as_slice(), test.desc.ignore, should_panic)
+            })
+            .collect();
+        crate::io::commit(&descs);
+        return;
+    }
+
+    let test = tests
+        .get(command as usize)
+        .unwrap_or_else(|| panic!("test index {command} out of range"));
+    match test.testfn {
+        TestFn::StaticTestFn(f) => {
+            if let Err(err) = f() {
+                panic!("{err}");
+            }
+        }
+        _ => panic!("only #[test] functions are supported"),
+    }
+}
@@ -3,11 +3,24 @@ pub const MEM_SIZE: usize = 1 << MEM_BITS;
 pub const GUEST_MIN_MEM: usize = 0x0000_0400;
 pub const GUEST_MAX_MEM: usize = MEM_SIZE;
 
-/// Top of stack; stack grows down from this location.
-pub const STACK_TOP: u32 = 0x0020_0400;
+/// Default size of the stack in bytes.
+pub const DEFAULT_STACK_SIZE: u32 = 0x0020_0000;
+/// Top of stack with the default stack size; stack grows down from this location.
+pub const STACK_TOP: u32 = stack_top(DEFAULT_STACK_SIZE);
 /// Program (text followed by data and then bss) gets loaded in
-/// starting at this location.  HEAP begins right afterwards.
-pub const TEXT_START: u32 = 0x0020_0800;
+/// starting at this location with the default stack size.  HEAP begins right afterwards.
+pub const TEXT_START: u32 = text_start(DEFAULT_STACK_SIZE);
+
+/// Top of a stack of `stack_size` bytes. The stack occupies `[GUEST_MIN_MEM, stack_top)`, so
+/// `GUEST_MIN_MEM` is the stack limit for any stack size.
+pub const fn stack_top(stack_size: u32) -> u32 {
+    GUEST_MIN_MEM as u32 + stack_size
+}
+
+/// Start of the program text when the stack has `stack_size` bytes.
+pub const fn text_start(stack_size: u32) -> u32 {
+    stack_top(stack_size) + 0x400
+}
 
 /// Returns whether `addr` is within guest memory bounds.
 pub fn is_guest_memory(addr: u32) -> bool {
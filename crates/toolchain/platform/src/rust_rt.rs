@@ -7,7 +7,6 @@
 //! * It includes an allocator.
 
 /// WARNING: the [SYSTEM_OPCODE] here should be equal to `SYSTEM_OPCODE` in `extensions_rv32im_guest`
-/// Can't import `openvm_rv32im_guest` here because would create a circular dependency
 #[cfg(target_os = "zkvm")]
 /// This is custom-0 defined in RISC-V spec document
 const SYSTEM_OPCODE: u8 = 0x0b;
@@ -29,3 +28,74 @@ pub fn terminate<const EXIT_CODE: u8>() {
         unimplemented!()
     }
 }
+
+/// Terminate with an exit code only known at runtime.
+///
+/// The exit code is an immediate of the terminate instruction, so this dispatches to one of the
+/// 256 monomorphizations of [terminate].
+#[inline(never)]
+pub fn terminate_with_code(code: u8) {
+    macro_rules! by_low_nibble {
+        ($hi:literal, [$($lo:literal),*]) => {
+            match code & 0xf {
+                $($lo => terminate::<{ $hi * 16 + $lo }>(),)*
+                _ => unreachable!(),
+            }
+        };
+    }
+    macro_rules! by_high_nibble {
+        ([$($hi:literal),*], $lo:tt) => {
+            match code >> 4 {
+                $($hi => by_low_nibble!($hi, $lo),)*
+                _ => unreachable!(),
+            }
+        };
+    }
+    by_high_nibble!(
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+    );
+}
+
+/// Maximum size in bytes of the panic payload reported by [report_panic]. Longer messages are
+/// truncated.
+pub const PANIC_PAYLOAD_MAX_LEN: usize = 1024;
+
+/// Report the panic message and location to the host, which surfaces them as
+/// `ExecutionError::GuestPanic`. The payload is laid out as
+/// `[line: u32 LE][file_len: u32 LE][file][message]`.
+///
+/// This does not allocate, so it is safe to call on allocation failure.
+pub fn report_panic(file: &str, line: u32, message: core::fmt::Arguments) {
+    struct PayloadWriter<'a> {
+        buf: &'a mut [u8],
+        len: usize,
+    }
+    impl core::fmt::Write for PayloadWriter<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let n = s.len().min(self.buf.len() - self.len);
+            self.buf[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+            self.len += n;
+            Ok(())
+        }
+    }
+
+    let mut buf = [0u8; PANIC_PAYLOAD_MAX_LEN];
+    let mut writer = PayloadWriter {
+        buf: &mut buf,
+        len: 8,
+    };
+    let _ = core::fmt::Write::write_str(&mut writer, file);
+    let file_len = (writer.len - 8) as u32;
+    let _ = core::fmt::Write::write_fmt(&mut writer, message);
+    let len = writer.len;
+    buf[0..4].copy_from_slice(&line.to_le_bytes());
+    buf[4..8].copy_from_slice(&file_len.to_le_bytes());
+    #[cfg(target_os = "zkvm")]
+    openvm_rv32im_guest::raw_report_panic(buf.as_ptr(), len);
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        let _ = len;
+        unimplemented!()
+    }
+}
@@ -11,6 +11,7 @@ use openvm_algebra_circuit::{
     ModularExtensionExecutor, ModularExtensionPeriphery,
 };
 use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
+use openvm_assembler::{assemble, disassemble};
 use openvm_bigint_circuit::{Int256, Int256Executor, Int256Periphery};
 use openvm_circuit::{
     arch::{
@@ -90,6 +91,34 @@ fn test_rv32im_runtime(elf_path: &str) -> Result<()> {
     Ok(())
 }
 
+#[test_case("tests/data/rv32im-exp-from-as")]
+#[test_case("tests/data/rv32im-intrin-from-as")]
+fn test_disassemble_roundtrip(elf_path: &str) -> Result<()> {
+    let elf = get_elf(elf_path)?;
+    let exe = VmExe::from_elf(
+        elf,
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension)
+            .with_extension(ModularTranspilerExtension),
+    )?;
+    let names = VmConfig::<F>::opcode_names(&Rv32ImConfig::default());
+    let listing = disassemble(&exe, &names);
+    let reassembled = assemble::<F>(&listing, &names)?;
+    let instructions_by_pc = |exe: &VmExe<F>| {
+        exe.program
+            .enumerate_by_pc()
+            .into_iter()
+            .map(|(pc, instruction, _)| (pc, instruction))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(instructions_by_pc(&reassembled), instructions_by_pc(&exe));
+    assert_eq!(reassembled.pc_start, exe.pc_start);
+    assert_eq!(reassembled.init_memory, exe.init_memory);
+    Ok(())
+}
+
 #[derive(Clone, Debug, VmConfig, Serialize, Deserialize)]
 pub struct Rv32ModularFp2Int256Config {
     #[system]
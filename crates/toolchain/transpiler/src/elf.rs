@@ -9,9 +9,10 @@ use elf::{
     ElfBytes,
 };
 use eyre::{self, bail, ContextCompat};
-#[cfg(feature = "function-span")]
-use openvm_instructions::exe::FnBound;
-use openvm_instructions::{exe::FnBounds, program::MAX_ALLOWED_PC};
+use openvm_instructions::{
+    exe::{DebugSections, FnBound, FnBounds},
+    program::MAX_ALLOWED_PC,
+};
 use openvm_platform::WORD_SIZE;
 
 pub const ELF_DEFAULT_MAX_NUM_PUBLIC_VALUES: usize = 32;
@@ -38,8 +39,10 @@ pub struct Elf {
     /// The upper bound of the number of public values the program would publish.
     /// TODO: read from project config.
     pub(crate) max_num_public_values: usize,
-    /// Debug info for spanning benchmark metrics by function.
+    /// Debug info for spanning benchmark metrics by function and symbolizing backtraces.
     pub(crate) fn_bounds: FnBounds,
+    /// DWARF sections for symbolizing backtraces with source locations.
+    pub(crate) debug_sections: DebugSections,
 }
 
 impl Elf {
@@ -50,6 +53,7 @@ impl Elf {
         pc_base: u32,
         memory_image: BTreeMap<u32, u32>,
         fn_bounds: FnBounds,
+        debug_sections: DebugSections,
     ) -> Self {
         Self {
             instructions,
@@ -58,6 +62,7 @@ impl Elf {
             memory_image,
             max_num_public_values: ELF_DEFAULT_MAX_NUM_PUBLIC_VALUES,
             fn_bounds,
+            debug_sections,
         }
     }
 
@@ -85,28 +90,32 @@ impl Elf {
             bail!("Invalid ELF type, must be executable");
         }
 
-        #[cfg(not(feature = "function-span"))]
-        let fn_bounds = Default::default();
-
-        #[cfg(feature = "function-span")]
         let mut fn_bounds = FnBounds::new();
-        #[cfg(feature = "function-span")]
-        {
-            if let Some((symtab, stringtab)) = elf.symbol_table()? {
-                for symbol in symtab.iter() {
-                    if symbol.st_symtype() == elf::abi::STT_FUNC {
-                        fn_bounds.insert(
-                            symbol.st_value as u32,
-                            FnBound {
-                                start: symbol.st_value as u32,
-                                end: (symbol.st_value + symbol.st_size - (WORD_SIZE as u64)) as u32,
-                                name: stringtab.get(symbol.st_name as usize).unwrap().to_string(),
-                            },
-                        );
+        if let Some((symtab, stringtab)) = elf.symbol_table()? {
+            for symbol in symtab.iter() {
+                if symbol.st_symtype() == elf::abi::STT_FUNC && symbol.st_size > 0 {
+                    fn_bounds.insert(
+                        symbol.st_value as u32,
+                        FnBound {
+                            start: symbol.st_value as u32,
+                            end: (symbol.st_value + symbol.st_size - (WORD_SIZE as u64)) as u32,
+                            name: stringtab.get(symbol.st_name as usize)?.to_string(),
+                        },
+                    );
+                }
+            }
+        }
+
+        // Keep the uncompressed DWARF sections, which are only present in builds with debug info.
+        let mut debug_sections = DebugSections::new();
+        if let (Some(shdrs), Some(strtab)) = elf.section_headers_with_strtab()? {
+            for shdr in shdrs.iter() {
+                let name = strtab.get(shdr.sh_name as usize)?;
+                if name.starts_with(".debug_") {
+                    if let (data, None) = elf.section_data(&shdr)? {
+                        debug_sections.insert(name.to_string(), data.to_vec());
                     }
                 }
-            } else {
-                println!("No symbol table found");
             }
         }
 
@@ -202,6 +211,7 @@ impl Elf {
             base_address,
             image,
             fn_bounds,
+            debug_sections,
         ))
     }
 }
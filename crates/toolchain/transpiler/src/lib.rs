@@ -42,6 +42,7 @@ impl<F: PrimeField32> FromElf for VmExe<F> {
             pc_start: elf.pc_start,
             init_memory,
             fn_bounds: elf.fn_bounds,
+            debug_sections: elf.debug_sections,
         })
     }
 }
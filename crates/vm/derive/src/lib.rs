@@ -250,6 +250,8 @@ pub fn vm_generic_config_derive(input: proc_macro::TokenStream) -> proc_macro::T
             let mut executor_enum_fields = Vec::new();
             let mut periphery_enum_fields = Vec::new();
             let mut create_chip_complex = Vec::new();
+            let mut opcode_names = Vec::new();
+            let mut guest_registers = Vec::new();
             for &e in extensions.iter() {
                 let (field_name, field_name_upper) =
                     gen_name_with_uppercase_idents(&e.ident.clone().unwrap());
@@ -319,6 +321,12 @@ pub fn vm_generic_config_derive(input: proc_macro::TokenStream) -> proc_macro::T
                 create_chip_complex.push(quote! {
                     let complex: VmChipComplex<F, Self::Executor, Self::Periphery> = complex.extend(&self.#field_name)?;
                 });
+                opcode_names.push(quote! {
+                    let names = names.merge(::openvm_circuit::arch::VmExtension::<F>::opcode_names(&self.#field_name));
+                });
+                guest_registers.push(quote! {
+                    let registers = registers.or_else(|| ::openvm_circuit::arch::VmExtension::<F>::guest_registers(&self.#field_name));
+                });
             }
 
             let (source_executor_type, source_periphery_type) = match &source {
@@ -376,6 +384,18 @@ pub fn vm_generic_config_derive(input: proc_macro::TokenStream) -> proc_macro::T
                         #(#create_chip_complex)*
                         Ok(complex)
                     }
+
+                    fn opcode_names(&self) -> ::openvm_circuit::arch::instructions::opcode_names::OpcodeNames {
+                        let names = VmConfig::<F>::opcode_names(&self.#source_name);
+                        #(#opcode_names)*
+                        names
+                    }
+
+                    fn guest_registers(&self) -> Option<::openvm_circuit::arch::GuestRegisters<F>> {
+                        let registers = VmConfig::<F>::guest_registers(&self.#source_name);
+                        #(#guest_registers)*
+                        registers
+                    }
                 }
             })
         }
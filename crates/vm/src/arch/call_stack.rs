@@ -0,0 +1,178 @@
+use std::{fmt, rc::Rc};
+
+use addr2line::gimli;
+use openvm_instructions::exe::{DebugSections, FnBounds};
+
+use crate::system::memory::MemoryController;
+
+/// Reads a register of the guest without changing the memory state.
+pub type GuestRegisterReader<F> = fn(&MemoryController<F>) -> u32;
+
+/// Readers of the guest registers that the VM inspects during execution. They are given by the
+/// extension defining the guest ABI, since the VM itself does not know the register layout.
+#[derive(Clone, Copy)]
+pub struct GuestRegisters<F> {
+    /// Reads the return address register, to reconstruct the [ShadowCallStack].
+    pub read_ra: GuestRegisterReader<F>,
+    /// Reads the stack pointer, to check
+    /// [SystemConfig::stack_limit](super::SystemConfig::stack_limit).
+    pub read_sp: GuestRegisterReader<F>,
+}
+
+/// Call stack of a RISC-V guest, reconstructed from the control flow during execution.
+///
+/// A jump that writes its return address `pc + pc_step` to `ra` is a call, and a jump to the
+/// return address of a frame on the stack is a return from that frame.
+#[derive(Clone, Debug, Default)]
+pub struct ShadowCallStack {
+    /// Call site and return address of each active call, outermost first.
+    frames: Vec<(u32, u32)>,
+    /// Program counter of the last instruction that started executing.
+    pc: u32,
+}
+
+impl ShadowCallStack {
+    /// Records that the instruction at `pc` is executing.
+    pub fn set_pc(&mut self, pc: u32) {
+        self.pc = pc;
+    }
+
+    /// Updates the call stack after the instruction at `pc` moved to `next_pc`. `read_ra` reads
+    /// the return address register. It is only called on jumps that do not return from a frame,
+    /// to tell whether the jump is a call, i.e. wrote `pc + pc_step` to the register.
+    pub fn update(&mut self, pc: u32, next_pc: u32, pc_step: u32, read_ra: impl FnOnce() -> u32) {
+        let return_pc = pc.wrapping_add(pc_step);
+        if next_pc == return_pc {
+            return;
+        }
+        // Returning from a frame also pops the frames above it, e.g. on unwinding.
+        if let Some(depth) = self.frames.iter().rposition(|&(_, ret)| ret == next_pc) {
+            self.frames.truncate(depth);
+        } else if read_ra() == return_pc {
+            self.frames.push((pc, return_pc));
+        }
+    }
+
+    /// Program counters of the backtrace, innermost first: the current instruction, then the
+    /// call site of each active call.
+    pub fn pcs(&self) -> Vec<u32> {
+        std::iter::once(self.pc)
+            .chain(self.frames.iter().rev().map(|&(call_site, _)| call_site))
+            .collect()
+    }
+}
+
+type DwarfReader = gimli::EndianRcSlice<gimli::LittleEndian>;
+
+/// Maps guest program counters to function names with the ELF symbol table and, if the ELF was
+/// built with debug info, to source locations with its DWARF sections.
+pub struct Symbolizer<'a> {
+    fn_bounds: &'a FnBounds,
+    dwarf: Option<addr2line::Context<DwarfReader>>,
+}
+
+impl<'a> Symbolizer<'a> {
+    pub fn new(fn_bounds: &'a FnBounds, debug_sections: &DebugSections) -> Self {
+        Self {
+            fn_bounds,
+            dwarf: load_dwarf(debug_sections),
+        }
+    }
+
+    /// Returns the demangled name of the function containing `pc`.
+    pub fn function(&self, pc: u32) -> Option<String> {
+        let (_, bound) = self.fn_bounds.range(..=pc).next_back()?;
+        (pc <= bound.end).then(|| format!("{:#}", rustc_demangle::demangle(&bound.name)))
+    }
+
+    /// Returns the source file and line of the instruction at `pc`.
+    pub fn location(&self, pc: u32) -> Option<(String, u32)> {
+        let location = self.dwarf.as_ref()?.find_location(pc as u64).ok()??;
+        Some((location.file?.to_string(), location.line?))
+    }
+
+    /// Formats the backtrace given by `call_stack`.
+    pub fn backtrace(&self, call_stack: &ShadowCallStack) -> GuestBacktrace {
+        GuestBacktrace(
+            call_stack
+                .pcs()
+                .into_iter()
+                .map(|pc| (pc, self.function(pc), self.location(pc)))
+                .collect(),
+        )
+    }
+}
+
+fn load_dwarf(sections: &DebugSections) -> Option<addr2line::Context<DwarfReader>> {
+    if sections.is_empty() {
+        return None;
+    }
+    let dwarf = gimli::Dwarf::load(|id| -> Result<DwarfReader, gimli::Error> {
+        let data = sections.get(id.name()).map_or(&[][..], |data| &data[..]);
+        Ok(gimli::EndianRcSlice::new(
+            Rc::from(data),
+            gimli::LittleEndian,
+        ))
+    })
+    .ok()?;
+    addr2line::Context::from_dwarf(dwarf).ok()
+}
+
+/// Symbolized guest backtrace, innermost frame first.
+pub struct GuestBacktrace(pub Vec<(u32, Option<String>, Option<(String, u32)>)>);
+
+impl fmt::Display for GuestBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (pc, function, location)) in self.0.iter().enumerate() {
+            let function = function.as_deref().unwrap_or("<unknown>");
+            writeln!(f, "{i:4}: {pc:#010x} - {function}")?;
+            if let Some((file, line)) = location {
+                writeln!(f, "                 at {file}:{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openvm_instructions::exe::FnBound;
+
+    use super::*;
+
+    const RA: u32 = 0x100;
+
+    #[test]
+    fn test_shadow_call_stack() {
+        let mut stack = ShadowCallStack::default();
+        // Straight-line code does not read `ra`.
+        stack.update(0x0, 0x4, 4, || unreachable!());
+        // Call from 0x4 to 0x40, then from 0x44 to 0x80.
+        stack.update(0x4, 0x40, 4, || 0x8);
+        stack.update(0x44, 0x80, 4, || 0x48);
+        // A jump that does not link is not a call.
+        stack.update(0x80, 0x90, 4, || RA);
+        stack.set_pc(0x90);
+        assert_eq!(stack.pcs(), vec![0x90, 0x44, 0x4]);
+        // Returning to the outermost frame pops both frames.
+        stack.update(0x94, 0x8, 4, || RA);
+        stack.set_pc(0x8);
+        assert_eq!(stack.pcs(), vec![0x8]);
+    }
+
+    #[test]
+    fn test_symbolize_function() {
+        let fn_bounds = FnBounds::from([(
+            0x40,
+            FnBound {
+                start: 0x40,
+                end: 0x7c,
+                name: "_ZN4main3foo17h0123456789abcdefE".to_string(),
+            },
+        )]);
+        let symbolizer = Symbolizer::new(&fn_bounds, &DebugSections::new());
+        assert_eq!(symbolizer.function(0x44).as_deref(), Some("main::foo"));
+        assert_eq!(symbolizer.function(0x80), None);
+        assert_eq!(symbolizer.location(0x44), None);
+    }
+}
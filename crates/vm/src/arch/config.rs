@@ -2,15 +2,15 @@ use std::sync::Arc;
 
 use derive_new::new;
 use openvm_circuit::system::memory::MemoryTraceHeights;
-use openvm_instructions::program::DEFAULT_MAX_NUM_PUBLIC_VALUES;
+use openvm_instructions::{opcode_names::OpcodeNames, program::DEFAULT_MAX_NUM_PUBLIC_VALUES};
 use openvm_poseidon2_air::Poseidon2Config;
 use openvm_stark_backend::{p3_field::PrimeField32, ChipUsageGetter};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::{
-    segment::{DefaultSegmentationStrategy, SegmentationStrategy},
-    AnyEnum, InstructionExecutor, SystemComplex, SystemExecutor, SystemPeriphery, VmChipComplex,
-    VmInventoryError, PUBLIC_VALUES_AIR_ID,
+    segment::{DefaultSegmentationStrategy, SegmentationStrategy, SegmentationStrategyConfig},
+    AnyEnum, GuestRegisters, InstructionExecutor, SystemComplex, SystemExecutor, SystemPeriphery,
+    VmChipComplex, VmInventoryError, PUBLIC_VALUES_AIR_ID,
 };
 use crate::system::memory::BOUNDARY_AIR_OFFSET;
 
@@ -35,6 +35,18 @@ pub trait VmConfig<F: PrimeField32>: Clone + Serialize + DeserializeOwned {
     fn create_chip_complex(
         &self,
     ) -> Result<VmChipComplex<F, Self::Executor, Self::Periphery>, VmInventoryError>;
+
+    /// Names of all opcodes supported by the VM, used to print and parse OpenVM assembly.
+    /// Defaults to no names, in which case opcodes are printed as numbers.
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default()
+    }
+
+    /// Readers of the guest registers, given by the extension defining the guest ABI. Without
+    /// them, the guest call stack is not tracked and [SystemConfig::stack_limit] is not checked.
+    fn guest_registers(&self) -> Option<GuestRegisters<F>> {
+        None
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, new, Copy)]
@@ -86,16 +98,53 @@ pub struct SystemConfig {
     /// **Warning**: this slows down the runtime.
     pub profiling: bool,
     /// Segmentation strategy
-    /// This field is skipped in serde as it's only used in execution and
-    /// not needed after any serialize/deserialize.
-    #[serde(skip, default = "get_default_segmentation_strategy")]
+    /// Only strategies with a [SegmentationStrategyConfig] are preserved by serde. Any other
+    /// strategy is replaced by the default one after a serialize/deserialize round trip.
+    #[serde(
+        default = "get_default_segmentation_strategy",
+        with = "segmentation_strategy_serde"
+    )]
     pub segmentation_strategy: Arc<dyn SegmentationStrategy>,
+    /// Lowest address the stack pointer `x2` of a RISC-V guest may hold. If set, execution fails
+    /// with [ExecutionError::StackOverflow](super::ExecutionError::StackOverflow) as soon as the
+    /// stack pointer is below it, or wraps around below zero. Only checked if the VM has an
+    /// extension reading the stack pointer, see [VmConfig::guest_registers].
+    /// **Warning**: this slows down the runtime.
+    #[serde(default)]
+    pub stack_limit: Option<u32>,
 }
 
 pub fn get_default_segmentation_strategy() -> Arc<dyn SegmentationStrategy> {
     Arc::new(DefaultSegmentationStrategy::default())
 }
 
+mod segmentation_strategy_serde {
+    use std::sync::Arc;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{
+        get_default_segmentation_strategy, SegmentationStrategy, SegmentationStrategyConfig,
+    };
+
+    pub fn serialize<S: Serializer>(
+        strategy: &Arc<dyn SegmentationStrategy>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        strategy.to_config().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Arc<dyn SegmentationStrategy>, D::Error> {
+        let config = Option::<SegmentationStrategyConfig>::deserialize(deserializer)?;
+        Ok(match config {
+            Some(config) => Arc::new(config),
+            None => get_default_segmentation_strategy(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SystemTraceHeights {
     pub memory: MemoryTraceHeights,
@@ -120,6 +169,7 @@ impl SystemConfig {
             num_public_values,
             segmentation_strategy,
             profiling: false,
+            stack_limit: None,
         }
     }
 
@@ -154,6 +204,14 @@ impl SystemConfig {
         self.segmentation_strategy = Arc::new(strategy);
     }
 
+    pub fn with_segmentation_strategy<S: SegmentationStrategy + 'static>(
+        mut self,
+        strategy: S,
+    ) -> Self {
+        self.set_segmentation_strategy(strategy);
+        self
+    }
+
     pub fn with_profiling(mut self) -> Self {
         self.profiling = true;
         self
@@ -164,6 +222,11 @@ impl SystemConfig {
         self
     }
 
+    pub fn with_stack_limit(mut self, stack_limit: u32) -> Self {
+        self.stack_limit = Some(stack_limit);
+        self
+    }
+
     pub fn has_public_values_chip(&self) -> bool {
         !self.continuation_enabled && self.num_public_values > 0
     }
@@ -218,4 +281,8 @@ impl<F: PrimeField32> VmConfig<F> for SystemConfig {
         let complex = SystemComplex::new(self.clone());
         Ok(complex)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::system()
+    }
 }
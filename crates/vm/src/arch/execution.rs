@@ -62,10 +62,19 @@ pub enum ExecutionError {
         discriminant: PhantomDiscriminant,
         inner: eyre::Error,
     },
+    #[error("at pc {pc}, stack pointer {sp:#x} overflowed the stack limit {stack_limit:#x}")]
+    StackOverflow { pc: u32, sp: u32, stack_limit: u32 },
     #[error("program must terminate")]
     DidNotTerminate,
     #[error("program exit code {0}")]
     FailedWithExitCode(u32),
+    #[error("guest panicked at {file}:{line} (pc {pc}): {message}")]
+    GuestPanic {
+        message: String,
+        file: String,
+        line: u32,
+        pc: u32,
+    },
 }
 
 pub trait InstructionExecutor<F> {
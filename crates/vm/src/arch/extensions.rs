@@ -16,7 +16,8 @@ use openvm_circuit_primitives::{
 };
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
 use openvm_instructions::{
-    program::Program, LocalOpcode, PhantomDiscriminant, PublishOpcode, SystemOpcode, VmOpcode,
+    opcode_names::OpcodeNames, program::Program, LocalOpcode, PhantomDiscriminant, PublishOpcode,
+    SystemOpcode, VmOpcode,
 };
 use openvm_stark_backend::{
     config::{Domain, StarkGenericConfig},
@@ -31,8 +32,8 @@ use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    vm_poseidon2_config, ExecutionBus, InstructionExecutor, PhantomSubExecutor, Streams,
-    SystemConfig, SystemTraceHeights,
+    vm_poseidon2_config, ExecutionBus, GuestRegisters, InstructionExecutor, PhantomSubExecutor,
+    Streams, SystemConfig, SystemTraceHeights,
 };
 #[cfg(feature = "bench-metrics")]
 use crate::metrics::VmMetrics;
@@ -82,6 +83,16 @@ pub trait VmExtension<F: PrimeField32> {
         &self,
         builder: &mut VmInventoryBuilder<F>,
     ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError>;
+
+    /// Names of the opcodes this extension executes, used to print and parse OpenVM assembly.
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default()
+    }
+
+    /// Readers of the guest registers, if this extension defines the guest ABI.
+    fn guest_registers(&self) -> Option<GuestRegisters<F>> {
+        None
+    }
 }
 
 impl<F: PrimeField32, E: VmExtension<F>> VmExtension<F> for Option<E> {
@@ -98,6 +109,16 @@ impl<F: PrimeField32, E: VmExtension<F>> VmExtension<F> for Option<E> {
             Ok(VmInventory::new())
         }
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        self.as_ref()
+            .map(VmExtension::<F>::opcode_names)
+            .unwrap_or_default()
+    }
+
+    fn guest_registers(&self) -> Option<GuestRegisters<F>> {
+        self.as_ref().and_then(VmExtension::<F>::guest_registers)
+    }
 }
 
 /// SystemPort combines system resources needed by most extensions
@@ -1192,6 +1213,9 @@ where
 
 #[cfg(test)]
 mod tests {
+    use openvm_circuit_primitives::fixed_table::{
+        FixedTable, FixedTableLookupBus, SharedFixedTableLookupChip,
+    };
     use p3_baby_bear::BabyBear;
 
     use super::*;
@@ -1282,4 +1306,56 @@ mod tests {
             _ => unreachable!(),
         };
     }
+
+    const DIGITS_OF_PI: [u8; 8] = [3, 1, 4, 1, 5, 9, 2, 6];
+
+    #[derive(FixedTable)]
+    #[table(DIGITS_OF_PI)]
+    struct DigitsOfPi;
+
+    /// Extension looking up [DigitsOfPi], whose chip is shared with the other extensions.
+    struct DigitsOfPiExtension;
+
+    #[derive(AnyEnum, From)]
+    enum DigitsOfPiPeriphery<F: PrimeField32> {
+        #[any_enum]
+        System(SystemPeriphery<F>),
+        DigitsOfPi(SharedFixedTableLookupChip<DigitsOfPi>),
+    }
+
+    impl<F: PrimeField32> VmExtension<F> for DigitsOfPiExtension {
+        type Executor = SystemExecutor<F>;
+        type Periphery = DigitsOfPiPeriphery<F>;
+
+        fn build(
+            &self,
+            builder: &mut VmInventoryBuilder<F>,
+        ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError> {
+            let mut inventory = VmInventory::new();
+            if builder
+                .find_chip::<SharedFixedTableLookupChip<DigitsOfPi>>()
+                .is_empty()
+            {
+                let bus = FixedTableLookupBus::new(builder.new_bus_idx());
+                inventory.add_periphery_chip(SharedFixedTableLookupChip::new::<F>(bus));
+            }
+            Ok(inventory)
+        }
+    }
+
+    #[test]
+    fn test_find_shared_fixed_table_chip() {
+        type Complex =
+            VmChipComplex<BabyBear, SystemExecutor<BabyBear>, DigitsOfPiPeriphery<BabyBear>>;
+        let complex = SystemComplex::<BabyBear>::new(SystemConfig::default());
+        let num_periphery = complex.inventory.periphery().len();
+        let complex: Complex = complex.extend(&DigitsOfPiExtension).unwrap();
+        let complex: Complex = complex.extend(&DigitsOfPiExtension).unwrap();
+        // The second extension finds the chip of the first one instead of adding its own.
+        assert_eq!(complex.inventory.periphery().len(), num_periphery + 1);
+        let builder = complex.inventory_builder();
+        let chips = builder.find_chip::<SharedFixedTableLookupChip<DigitsOfPi>>();
+        assert_eq!(chips.len(), 1);
+        assert_eq!(chips[0].rows()[2], vec![2, 4]);
+    }
 }
@@ -1,3 +1,5 @@
+/// Guest call stack tracking and symbolized backtraces.
+mod call_stack;
 mod config;
 /// Instruction execution traits and types.
 /// Execution bus and interface.
@@ -18,6 +20,7 @@ pub mod hasher;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod testing;
 
+pub use call_stack::*;
 pub use config::*;
 pub use execution::*;
 pub use extensions::*;
@@ -2,7 +2,7 @@ use backtrace::Backtrace;
 use openvm_instructions::{
     exe::FnBounds,
     instruction::{DebugInfo, Instruction},
-    program::Program,
+    program::{Program, DEFAULT_PC_STEP},
 };
 use openvm_stark_backend::{
     config::{Domain, StarkGenericConfig},
@@ -12,8 +12,10 @@ use openvm_stark_backend::{
     utils::metrics_span,
     Chip,
 };
+use serde::{Deserialize, Serialize};
 
 use super::{
+    call_stack::{GuestRegisterReader, GuestRegisters, ShadowCallStack},
     ExecutionError, Streams, SystemBase, SystemConfig, VmChipComplex, VmComplexTraceHeights,
     VmConfig,
 };
@@ -21,7 +23,7 @@ use super::{
 use crate::metrics::VmMetrics;
 use crate::{
     arch::{instructions::*, ExecutionState, InstructionExecutor},
-    system::memory::MemoryImage,
+    system::memory::{MemoryController, MemoryImage},
 };
 
 /// Check segment every 100 instructions.
@@ -45,10 +47,56 @@ pub trait SegmentationStrategy:
         trace_heights: &[usize],
         trace_cells: &[usize],
     ) -> bool;
+
+    /// Returns the serializable description of this strategy, if there is one.
+    ///
+    /// Strategies without a description are not preserved by serialization of [SystemConfig]:
+    /// they are replaced by the default strategy upon deserialization.
+    fn to_config(&self) -> Option<SegmentationStrategyConfig> {
+        None
+    }
+}
+
+/// Serializable description of the built-in segmentation strategies. This is how the
+/// segmentation strategy is specified in [SystemConfig] when it is read from a config file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentationStrategyConfig {
+    Default(DefaultSegmentationStrategy),
+    CostModel(CostModelSegmentationStrategy),
+}
+
+impl Default for SegmentationStrategyConfig {
+    fn default() -> Self {
+        Self::Default(DefaultSegmentationStrategy::default())
+    }
+}
+
+impl SegmentationStrategy for SegmentationStrategyConfig {
+    fn should_segment(
+        &self,
+        air_names: &[String],
+        trace_heights: &[usize],
+        trace_cells: &[usize],
+    ) -> bool {
+        match self {
+            Self::Default(strategy) => {
+                strategy.should_segment(air_names, trace_heights, trace_cells)
+            }
+            Self::CostModel(strategy) => {
+                strategy.should_segment(air_names, trace_heights, trace_cells)
+            }
+        }
+    }
+
+    fn to_config(&self) -> Option<SegmentationStrategyConfig> {
+        Some(self.clone())
+    }
 }
 
 /// Default segmentation strategy: segment if any chip's height or cells exceed the limits.
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DefaultSegmentationStrategy {
     max_segment_len: usize,
     max_cells_per_chip_in_segment: usize,
@@ -110,6 +158,178 @@ impl SegmentationStrategy for DefaultSegmentationStrategy {
         }
         false
     }
+
+    fn to_config(&self) -> Option<SegmentationStrategyConfig> {
+        Some(SegmentationStrategyConfig::Default(self.clone()))
+    }
+}
+
+/// Coefficients of the linear model used by [CostModelSegmentationStrategy] to estimate the
+/// resources needed to prove a segment.
+///
+/// The model works in terms of the number of cells of the low-degree extension of the main
+/// traces: each trace is padded to a power of two height and blown up by `2^log_blowup`.
+/// The default coefficients are rough figures for `BabyBearPoseidon2Engine` on a recent
+/// multi-core machine; calibrate them against `cargo openvm estimate` or a real proving run.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SegmentCostModel {
+    /// Log2 of the FRI blowup factor the segment will be proven with.
+    pub log_blowup: usize,
+    /// Estimated peak prover memory per cell of the low-degree extension, in bytes. This
+    /// accounts for the after-challenge and quotient traces and the Merkle trees on top of them.
+    pub bytes_per_lde_cell: f64,
+    /// Estimated proving time per cell of the low-degree extension, in nanoseconds.
+    pub ns_per_lde_cell: f64,
+    /// Memory used by the prover independently of the segment size, in bytes.
+    pub base_memory_bytes: usize,
+}
+
+impl Default for SegmentCostModel {
+    fn default() -> Self {
+        Self {
+            log_blowup: 1,
+            bytes_per_lde_cell: 24.0,
+            ns_per_lde_cell: 12.0,
+            base_memory_bytes: 1 << 30,
+        }
+    }
+}
+
+/// Estimated resources needed to prove a single segment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentCostEstimate {
+    /// Total number of cells of the main traces, after padding each trace height to a power of
+    /// two.
+    pub main_cells: usize,
+    /// Estimated peak prover memory, in bytes.
+    pub memory_bytes: usize,
+    /// Estimated proving time, in milliseconds.
+    pub proving_time_ms: u64,
+}
+
+impl SegmentCostModel {
+    /// Estimates the resources needed to prove a segment with the given trace heights and cells,
+    /// both ordered as in `air_names`. An AIR with height `0` but a positive number of cells is
+    /// treated as a constant height AIR whose cell count is already padded.
+    pub fn estimate(&self, trace_heights: &[usize], trace_cells: &[usize]) -> SegmentCostEstimate {
+        let main_cells: usize = trace_heights
+            .iter()
+            .zip(trace_cells)
+            .map(|(&height, &cells)| {
+                if height == 0 {
+                    cells
+                } else {
+                    let width = cells.div_ceil(height);
+                    height.next_power_of_two() * width
+                }
+            })
+            .sum();
+        let lde_cells = (main_cells << self.log_blowup) as f64;
+        SegmentCostEstimate {
+            main_cells,
+            memory_bytes: self.base_memory_bytes + (lde_cells * self.bytes_per_lde_cell) as usize,
+            proving_time_ms: (lde_cells * self.ns_per_lde_cell / 1_000_000.0) as u64,
+        }
+    }
+}
+
+/// Segmentation strategy driven by a cost model: segment once the estimated peak memory or
+/// proving time of the current segment exceeds the configured budget.
+///
+/// Since trace heights are padded to the next power of two, the estimates jump whenever a chip
+/// crosses a power of two. The strategy still enforces `max_segment_len` as a hard cap on the
+/// height of every chip.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostModelSegmentationStrategy {
+    /// Maximum height of any chip in a segment.
+    pub max_segment_len: usize,
+    /// Target for the estimated peak prover memory of a segment, in bytes.
+    pub max_memory_bytes: Option<usize>,
+    /// Target for the estimated proving time of a segment, in milliseconds.
+    pub max_proving_time_ms: Option<u64>,
+    pub cost_model: SegmentCostModel,
+}
+
+impl Default for CostModelSegmentationStrategy {
+    fn default() -> Self {
+        Self {
+            max_segment_len: DEFAULT_MAX_SEGMENT_LEN,
+            max_memory_bytes: None,
+            max_proving_time_ms: None,
+            cost_model: SegmentCostModel::default(),
+        }
+    }
+}
+
+impl CostModelSegmentationStrategy {
+    pub fn with_max_memory_bytes(mut self, max_memory_bytes: usize) -> Self {
+        self.max_memory_bytes = Some(max_memory_bytes);
+        self
+    }
+
+    pub fn with_max_proving_time_ms(mut self, max_proving_time_ms: u64) -> Self {
+        self.max_proving_time_ms = Some(max_proving_time_ms);
+        self
+    }
+
+    pub fn with_max_segment_len(mut self, max_segment_len: usize) -> Self {
+        self.max_segment_len = max_segment_len;
+        self
+    }
+
+    pub fn with_cost_model(mut self, cost_model: SegmentCostModel) -> Self {
+        self.cost_model = cost_model;
+        self
+    }
+}
+
+impl SegmentationStrategy for CostModelSegmentationStrategy {
+    fn should_segment(
+        &self,
+        air_names: &[String],
+        trace_heights: &[usize],
+        trace_cells: &[usize],
+    ) -> bool {
+        for (i, &height) in trace_heights.iter().enumerate() {
+            if height > self.max_segment_len {
+                tracing::info!(
+                    "Should segment because chip {} (name: {}) has height {}",
+                    i,
+                    air_names[i],
+                    height
+                );
+                return true;
+            }
+        }
+        let estimate = self.cost_model.estimate(trace_heights, trace_cells);
+        if let Some(max_memory_bytes) = self.max_memory_bytes {
+            if estimate.memory_bytes > max_memory_bytes {
+                tracing::info!(
+                    "Should segment because estimated memory {} bytes exceeds {} bytes",
+                    estimate.memory_bytes,
+                    max_memory_bytes
+                );
+                return true;
+            }
+        }
+        if let Some(max_proving_time_ms) = self.max_proving_time_ms {
+            if estimate.proving_time_ms > max_proving_time_ms {
+                tracing::info!(
+                    "Should segment because estimated proving time {}ms exceeds {}ms",
+                    estimate.proving_time_ms,
+                    max_proving_time_ms
+                );
+                return true;
+            }
+        }
+        false
+    }
+
+    fn to_config(&self) -> Option<SegmentationStrategyConfig> {
+        Some(SegmentationStrategyConfig::CostModel(self.clone()))
+    }
 }
 
 pub struct ExecutionSegment<F, VC>
@@ -125,6 +345,11 @@ where
 
     /// Air names for debug purposes only.
     pub(crate) air_names: Vec<String>,
+    /// Guest call stack for backtraces on failure, tracked when profiling a program transpiled
+    /// from an ELF.
+    pub call_stack: Option<ShadowCallStack>,
+    /// Readers of the guest registers, given by the VM config.
+    guest_registers: Option<GuestRegisters<F>>,
     /// Metrics collected for this execution segment alone.
     #[cfg(feature = "bench-metrics")]
     pub metrics: VmMetrics,
@@ -142,7 +367,7 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
         program: Program<F>,
         init_streams: Streams<F>,
         initial_memory: Option<MemoryImage<F>>,
-        #[allow(unused_variables)] fn_bounds: FnBounds,
+        fn_bounds: FnBounds,
     ) -> Self {
         let mut chip_complex = config.create_chip_complex().unwrap();
         chip_complex.set_streams(init_streams);
@@ -157,11 +382,19 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             chip_complex.set_initial_memory(initial_memory);
         }
         let air_names = chip_complex.air_names();
+        // Only programs transpiled from an ELF have function bounds, and only those follow the
+        // calling convention of the guest registers the call stack is reconstructed from.
+        let guest_registers = config.guest_registers();
+        let call_stack =
+            (config.system().profiling && !fn_bounds.is_empty() && guest_registers.is_some())
+                .then(ShadowCallStack::default);
 
         Self {
             chip_complex,
             final_memory: None,
             air_names,
+            call_stack,
+            guest_registers,
             #[cfg(feature = "bench-metrics")]
             metrics: VmMetrics {
                 fn_bounds,
@@ -195,12 +428,16 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             .begin(ExecutionState::new(pc, timestamp));
 
         let mut did_terminate = false;
+        let stack_limit = self.system_config().stack_limit;
+        let guest_registers = self.guest_registers;
+        let pointer_max_bits = self.system_config().memory_config.pointer_max_bits;
 
         loop {
             #[allow(unused_variables)]
             let (opcode, dsl_instr) = {
                 let Self {
                     chip_complex,
+                    call_stack,
                     #[cfg(feature = "bench-metrics")]
                     metrics,
                     ..
@@ -211,6 +448,9 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
                     ..
                 } = &mut chip_complex.base;
 
+                if let Some(call_stack) = call_stack.as_mut() {
+                    call_stack.set_pc(pc);
+                }
                 let (instruction, debug_info) = program_chip.get_instruction(pc)?;
                 tracing::trace!("pc: {pc:#x} | time: {timestamp} | {:?}", instruction);
 
@@ -276,6 +516,22 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
                         ExecutionState::new(pc, timestamp),
                     )?;
                     assert!(next_state.timestamp > timestamp);
+                    if let (Some(stack_limit), Some(registers)) = (stack_limit, guest_registers) {
+                        check_stack_pointer(
+                            memory_controller,
+                            registers.read_sp,
+                            pc,
+                            stack_limit,
+                            pointer_max_bits,
+                        )?;
+                    }
+                    if let (Some(call_stack), Some(registers)) =
+                        (call_stack.as_mut(), guest_registers)
+                    {
+                        call_stack.update(pc, next_state.pc, DEFAULT_PC_STEP, || {
+                            (registers.read_ra)(memory_controller)
+                        });
+                    }
                     pc = next_state.pc;
                     timestamp = next_state.timestamp;
                 } else {
@@ -351,6 +607,11 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
         )
     }
 
+    /// Names of all AIRs, ordered by AIR ID.
+    pub fn air_names(&self) -> &[String] {
+        &self.air_names
+    }
+
     pub fn current_trace_cells(&self) -> Vec<usize> {
         self.chip_complex.current_trace_cells()
     }
@@ -360,3 +621,87 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
         self.chip_complex.current_trace_heights()
     }
 }
+
+/// Returns [ExecutionError::StackOverflow] if the stack pointer read by `read_sp` is below
+/// `stack_limit` or outside of memory. The stack pointer is zero until the guest initializes it,
+/// so zero is allowed.
+fn check_stack_pointer<F: PrimeField32>(
+    memory_controller: &MemoryController<F>,
+    read_sp: GuestRegisterReader<F>,
+    pc: u32,
+    stack_limit: u32,
+    pointer_max_bits: usize,
+) -> Result<(), ExecutionError> {
+    let sp = read_sp(memory_controller);
+    if sp != 0 && (sp < stack_limit || (sp as u64) >= (1u64 << pointer_max_bits)) {
+        return Err(ExecutionError::StackOverflow {
+            pc,
+            sp,
+            stack_limit,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_model_estimate() {
+        let cost_model = SegmentCostModel {
+            log_blowup: 1,
+            bytes_per_lde_cell: 2.0,
+            ns_per_lde_cell: 1_000_000.0,
+            base_memory_bytes: 100,
+        };
+        // The first AIR has height 3 and width 2, padded to height 4. The second AIR has
+        // constant height.
+        let estimate = cost_model.estimate(&[3, 0], &[6, 16]);
+        assert_eq!(estimate.main_cells, 8 + 16);
+        assert_eq!(estimate.memory_bytes, 100 + 2 * 2 * 24);
+        assert_eq!(estimate.proving_time_ms, 2 * 24);
+    }
+
+    #[test]
+    fn test_cost_model_should_segment() {
+        let air_names = vec!["A".to_string(), "B".to_string()];
+        let strategy = CostModelSegmentationStrategy::default()
+            .with_cost_model(SegmentCostModel {
+                log_blowup: 0,
+                bytes_per_lde_cell: 1.0,
+                ns_per_lde_cell: 0.0,
+                base_memory_bytes: 0,
+            })
+            .with_max_memory_bytes(1 << 10);
+        assert!(!strategy.should_segment(&air_names, &[256, 256], &[512, 256]));
+        assert!(strategy.should_segment(&air_names, &[257, 256], &[514, 256]));
+        let strategy = strategy.with_max_segment_len(100);
+        assert!(strategy.should_segment(&air_names, &[101, 0], &[101, 0]));
+    }
+
+    #[test]
+    fn test_segmentation_strategy_serde() {
+        let strategy = CostModelSegmentationStrategy::default()
+            .with_max_memory_bytes(1 << 34)
+            .with_max_proving_time_ms(60_000);
+        let config = SystemConfig::default().with_segmentation_strategy(strategy);
+
+        let toml_str = toml::to_string(&config).unwrap();
+        let deserialized: SystemConfig = toml::from_str(&toml_str).unwrap();
+        match deserialized.segmentation_strategy.to_config() {
+            Some(SegmentationStrategyConfig::CostModel(strategy)) => {
+                assert_eq!(strategy.max_memory_bytes, Some(1 << 34));
+                assert_eq!(strategy.max_proving_time_ms, Some(60_000));
+            }
+            other => panic!("unexpected segmentation strategy: {other:?}"),
+        }
+
+        let bytes = bitcode::serialize(&config).unwrap();
+        let deserialized: SystemConfig = bitcode::deserialize(&bytes).unwrap();
+        assert!(matches!(
+            deserialized.segmentation_strategy.to_config(),
+            Some(SegmentationStrategyConfig::CostModel(_))
+        ));
+    }
+}
@@ -1,4 +1,10 @@
-use std::{borrow::Borrow, collections::VecDeque, marker::PhantomData, mem, sync::Arc};
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    marker::PhantomData,
+    mem,
+    sync::Arc,
+};
 
 use openvm_circuit::system::program::trace::compute_exe_commit;
 use openvm_instructions::exe::VmExe;
@@ -18,8 +24,8 @@ use thiserror::Error;
 use tracing::info_span;
 
 use super::{
-    ExecutionError, VmComplexTraceHeights, VmConfig, CONNECTOR_AIR_ID, MERKLE_AIR_ID,
-    PROGRAM_AIR_ID, PROGRAM_CACHED_TRACE_INDEX,
+    ExecutionError, ShadowCallStack, Symbolizer, VmComplexTraceHeights, VmConfig, CONNECTOR_AIR_ID,
+    MERKLE_AIR_ID, PROGRAM_AIR_ID, PROGRAM_CACHED_TRACE_INDEX,
 };
 #[cfg(feature = "bench-metrics")]
 use crate::metrics::VmMetrics;
@@ -43,6 +49,18 @@ pub struct Streams<F> {
     pub input_stream: VecDeque<Vec<F>>,
     pub hint_stream: VecDeque<F>,
     pub hint_space: Vec<Vec<F>>,
+    /// Hints looked up by key, which guests load in any order and any number of times.
+    pub kv_store: HashMap<Vec<u8>, Vec<F>>,
+    /// Panic message and location reported by the guest before it terminated.
+    pub guest_panic: Option<GuestPanicInfo>,
+}
+
+/// Panic message and location reported by the guest panic handler.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct GuestPanicInfo {
+    pub message: String,
+    pub file: String,
+    pub line: u32,
 }
 
 impl<F> Streams<F> {
@@ -51,8 +69,15 @@ impl<F> Streams<F> {
             input_stream: input_stream.into(),
             hint_stream: VecDeque::default(),
             hint_space: Vec::default(),
+            kv_store: HashMap::default(),
+            guest_panic: None,
         }
     }
+
+    pub fn with_kv_store(mut self, kv_store: HashMap<Vec<u8>, Vec<F>>) -> Self {
+        self.kv_store = kv_store;
+        self
+    }
 }
 
 impl<F> From<VecDeque<Vec<F>>> for Streams<F> {
@@ -90,6 +115,8 @@ pub struct VmExecutorNextSegmentState<F: PrimeField32> {
     pub memory: MemoryImage<F>,
     pub input: Streams<F>,
     pub pc: u32,
+    /// Guest call stack at the end of the previous segment, if tracked.
+    pub call_stack: Option<ShadowCallStack>,
     #[cfg(feature = "bench-metrics")]
     pub metrics: VmMetrics,
 }
@@ -100,6 +127,7 @@ impl<F: PrimeField32> VmExecutorNextSegmentState<F> {
             memory,
             input: input.into(),
             pc,
+            call_stack: None,
             #[cfg(feature = "bench-metrics")]
             metrics: VmMetrics::default(),
         }
@@ -207,6 +235,9 @@ where
             Some(from_state.memory),
             exe.fn_bounds.clone(),
         );
+        if from_state.call_stack.is_some() {
+            segment.call_stack = from_state.call_stack;
+        }
         #[cfg(feature = "bench-metrics")]
         {
             segment.metrics = from_state.metrics;
@@ -214,9 +245,15 @@ where
         if let Some(overridden_heights) = self.overridden_heights.as_ref() {
             segment.set_override_trace_heights(overridden_heights.clone());
         }
-        let state = metrics_span("execute_time_ms", || segment.execute_from_pc(from_state.pc))?;
+        let state = metrics_span("execute_time_ms", || segment.execute_from_pc(from_state.pc))
+            .inspect_err(|_| print_guest_backtrace(&segment, &exe))?;
 
         if state.is_terminated {
+            let end_state = segment.chip_complex.connector_chip().boundary_states[1]
+                .expect("end state must be set");
+            if end_state.exit_code != ExitCode::Success as u32 {
+                print_guest_backtrace(&segment, &exe);
+            }
             return Ok(VmExecutorOneSegmentResult {
                 segment,
                 next_state: None,
@@ -244,6 +281,7 @@ where
                 memory: final_memory,
                 input: streams,
                 pc: state.pc,
+                call_stack: segment.call_stack.take(),
                 #[cfg(feature = "bench-metrics")]
                 metrics,
             }),
@@ -255,19 +293,47 @@ where
         exe: impl Into<VmExe<F>>,
         input: impl Into<Streams<F>>,
     ) -> Result<Option<VmMemoryState<F>>, ExecutionError> {
+        self.execute_with_cycle_count(exe, input)
+            .map(|(final_memory, _)| final_memory)
+    }
+
+    /// Same as [VmExecutor::execute], but also returns the number of instructions executed over
+    /// all segments.
+    pub fn execute_with_cycle_count(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+    ) -> Result<(Option<VmMemoryState<F>>, usize), ExecutionError> {
         let mut last = None;
-        self.execute_and_then(exe, input, |_, seg| last = Some(seg))?;
-        let last = last.expect("at least one segment must be executed");
-        let final_memory = last.final_memory;
+        let mut cycle_count = 0;
+        self.execute_and_then(exe, input, |_, seg| {
+            cycle_count += seg
+                .chip_complex
+                .program_chip()
+                .execution_frequencies
+                .iter()
+                .sum::<usize>();
+            last = Some(seg);
+        })?;
+        let mut last = last.expect("at least one segment must be executed");
+        let final_memory = last.final_memory.take();
         let end_state =
             last.chip_complex.connector_chip().boundary_states[1].expect("end state must be set");
         if end_state.is_terminate != 1 {
             return Err(ExecutionError::DidNotTerminate);
         }
         if end_state.exit_code != ExitCode::Success as u32 {
+            if let Some(panic) = last.chip_complex.take_streams().guest_panic {
+                return Err(ExecutionError::GuestPanic {
+                    message: panic.message,
+                    file: panic.file,
+                    line: panic.line,
+                    pc: end_state.pc,
+                });
+            }
             return Err(ExecutionError::FailedWithExitCode(end_state.exit_code));
         }
-        Ok(final_memory)
+        Ok((final_memory, cycle_count))
     }
 
     pub fn execute_and_generate<SC: StarkGenericConfig>(
@@ -424,16 +490,28 @@ where
             exe.program.clone(),
             input.into(),
             None,
-            exe.fn_bounds,
+            exe.fn_bounds.clone(),
         );
         if let Some(overridden_heights) = self.overridden_heights.as_ref() {
             segment.set_override_trace_heights(overridden_heights.clone());
         }
-        metrics_span("execute_time_ms", || segment.execute_from_pc(pc_start))?;
+        metrics_span("execute_time_ms", || segment.execute_from_pc(pc_start))
+            .inspect_err(|_| print_guest_backtrace(&segment, &exe))?;
         Ok(segment)
     }
 }
 
+/// Prints the symbolized guest backtrace of `segment` to stderr, if its call stack is tracked.
+fn print_guest_backtrace<F: PrimeField32, VC: VmConfig<F>>(
+    segment: &ExecutionSegment<F, VC>,
+    exe: &VmExe<F>,
+) {
+    if let Some(call_stack) = &segment.call_stack {
+        let symbolizer = Symbolizer::new(&exe.fn_bounds, &exe.debug_sections);
+        eprintln!("guest backtrace:\n{}", symbolizer.backtrace(call_stack));
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum VmVerificationError {
     #[error("no proof is provided")]
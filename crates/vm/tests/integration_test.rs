@@ -313,6 +313,7 @@ fn test_vm_initial_memory() {
         pc_start: 0,
         init_memory,
         fn_bounds: Default::default(),
+        debug_sections: Default::default(),
     };
     air_test(config, exe);
 }
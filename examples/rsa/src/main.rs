@@ -0,0 +1,67 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+use openvm_algebra_guest::{moduli_macros::*, IntMod};
+use openvm_sha256_guest::sha256;
+
+openvm::entry!(main);
+
+// A 512-bit RSA modulus `n = p * q`. It is not prime, so it must be declared with
+// `is_prime = false`, and division is not available for `Rsa512`.
+moduli_declare! {
+    Rsa512 {
+        modulus = "0xc2f16787881cf9e6ab9ec96d66663a1927e671a42098099abd6418a58542195c6763b3a1404db1c23757c6b21a1cdd2b7bb72b4fc7c3878027f715bc67026c8d",
+        is_prime = false,
+    },
+}
+
+moduli_init! {
+    "0xc2f16787881cf9e6ab9ec96d66663a1927e671a42098099abd6418a58542195c6763b3a1404db1c23757c6b21a1cdd2b7bb72b4fc7c3878027f715bc67026c8d",
+}
+
+/// DER encoding of the SHA-256 `DigestInfo` prefix, from RFC 8017, Section 9.2.
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = hex!("3031300d060960864801650304020105000420");
+
+/// Returns the PKCS#1 v1.5 encoding `0x00 || 0x01 || 0xff..ff || 0x00 || DigestInfo` of the
+/// SHA-256 hash of `msg`, big-endian.
+fn emsa_pkcs1_v15_encode(msg: &[u8]) -> [u8; 64] {
+    let mut em = [0xff; 64];
+    let digest = sha256(msg);
+    let t_len = SHA256_DIGEST_INFO_PREFIX.len() + digest.len();
+    em[0] = 0x00;
+    em[1] = 0x01;
+    em[64 - t_len - 1] = 0x00;
+    em[64 - t_len..64 - digest.len()].copy_from_slice(&SHA256_DIGEST_INFO_PREFIX);
+    em[64 - digest.len()..].copy_from_slice(&digest);
+    em
+}
+
+/// Verifies an RSASSA-PKCS1-v1_5 signature with SHA-256 and public exponent 65537.
+fn verify(msg: &[u8], signature: &[u8; 64]) -> bool {
+    let s = Rsa512::from_be_bytes(signature);
+    // The signature must be less than the modulus. `assert_unique` makes the proof fail otherwise.
+    s.assert_unique();
+    // s^65537 = s^(2^16) * s
+    let mut m = s.clone();
+    for _ in 0..16 {
+        m.square_assign();
+    }
+    m *= &s;
+    m == Rsa512::from_be_bytes(&emsa_pkcs1_v15_encode(msg))
+}
+
+pub fn main() {
+    setup_all_moduli();
+
+    let msg = b"Hello, OpenVM!";
+    let signature = hex!(
+        "21c8d3d107c667e325eb2b0ebd5f73bbaf36131e0dce9f7072874bc3af8d2911"
+        "eb2a48a85fed58349712d4cedf89bfcf342d9f4c04b7d652d11d3d5f584eb0d4"
+    );
+    assert!(verify(msg, &signature));
+
+    let mut forged = signature;
+    forged[63] ^= 1;
+    assert!(!verify(msg, &forged));
+}
@@ -0,0 +1,153 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use num_bigint::BigUint;
+use openvm_circuit::{arch::VmChipWrapper, system::memory::OfflineMemory};
+use openvm_circuit_derive::InstructionExecutor;
+use openvm_circuit_primitives::var_range::{
+    SharedVariableRangeCheckerChip, VariableRangeCheckerBus,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_mod_circuit_builder::{
+    ExprBuilder, ExprBuilderConfig, FieldExpr, FieldExpressionCoreChip, FieldVariable,
+};
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+use openvm_stark_backend::p3_field::PrimeField32;
+
+use crate::FieldExprOp;
+
+mod parser;
+pub use parser::*;
+
+#[cfg(test)]
+mod tests;
+
+// Input: 2 * BLOCKS blocks, split over rs1 and rs2
+// Output: BLOCKS blocks
+#[derive(Chip, ChipUsageGetter, InstructionExecutor)]
+pub struct FieldExprChip<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>(
+    pub  VmChipWrapper<
+        F,
+        Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        FieldExpressionCoreChip,
+    >,
+);
+
+impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    FieldExprChip<F, BLOCKS, BLOCK_SIZE>
+{
+    /// `offset` is the global opcode of the first local opcode of the operation.
+    pub fn new(
+        adapter: Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        op: &FieldExprOp,
+        num_limbs: usize,
+        offset: usize,
+        range_checker: SharedVariableRangeCheckerChip,
+        offline_memory: Arc<Mutex<OfflineMemory<F>>>,
+    ) -> Self {
+        let elements = op.elements_per_pointer();
+        assert_eq!(BLOCKS * BLOCK_SIZE, elements * num_limbs);
+        let expr = field_expr_op_expr(op, num_limbs, range_checker.bus());
+        let num_ops = op.flags.len().max(1);
+        let core = FieldExpressionCoreChip::new(
+            expr,
+            offset,
+            (0..=num_ops).collect(),
+            (0..op.flags.len()).collect(),
+            range_checker,
+            &op.name,
+            false,
+        );
+        Self(VmChipWrapper::new(adapter, core, offline_memory))
+    }
+}
+
+/// Builds the expression of `op` with `num_limbs` limbs of 8 bits.
+///
+/// The inputs and outputs are padded to the number of elements read and written by the chip,
+/// see [FieldExprOp::elements_per_pointer]: unused inputs are appended, and the last output is
+/// repeated.
+///
+/// Panics if an output expression cannot be parsed.
+pub fn field_expr_op_expr(
+    op: &FieldExprOp,
+    num_limbs: usize,
+    range_bus: VariableRangeCheckerBus,
+) -> FieldExpr {
+    op.check_valid();
+    let config = ExprBuilderConfig {
+        modulus: op.modulus.clone(),
+        num_limbs,
+        limb_bits: 8,
+        is_prime: op.is_prime,
+    };
+    config.check_valid();
+    let builder = ExprBuilder::new(config, range_bus.range_max_bits);
+    let builder = Rc::new(RefCell::new(builder));
+
+    let elements = op.elements_per_pointer();
+    let inputs: Vec<_> = (0..2 * elements)
+        .map(|_| ExprBuilder::new_input(builder.clone()))
+        .collect();
+    for _ in &op.flags {
+        builder.borrow_mut().new_flag();
+    }
+
+    let mut outputs: Vec<_> = op
+        .outputs
+        .iter()
+        .map(|output| {
+            let ast = parse_field_expr(output, &op.inputs, &op.flags)
+                .unwrap_or_else(|e| panic!("{}: invalid output {output:?}: {e}", op.name));
+            build(&ast, &inputs, &builder)
+        })
+        .collect();
+    for output in outputs.iter_mut() {
+        output.save_output();
+    }
+    for _ in op.outputs.len()..elements {
+        outputs.last_mut().unwrap().save_output();
+    }
+
+    let builder = builder.borrow().clone();
+    FieldExpr::new(builder, range_bus, true)
+}
+
+fn build(
+    ast: &FieldExprAst,
+    inputs: &[FieldVariable],
+    builder: &Rc<RefCell<ExprBuilder>>,
+) -> FieldVariable {
+    match ast {
+        FieldExprAst::Input(i) => inputs[*i].clone(),
+        FieldExprAst::Const(c) => ExprBuilder::new_const(builder.clone(), BigUint::from(*c)),
+        FieldExprAst::Add(a, b) => build(a, inputs, builder) + build(b, inputs, builder),
+        FieldExprAst::Sub(a, b) => build(a, inputs, builder) - build(b, inputs, builder),
+        FieldExprAst::Mul(a, b) => build(a, inputs, builder) * build(b, inputs, builder),
+        FieldExprAst::Div(a, b) => build(a, inputs, builder) / build(b, inputs, builder),
+        FieldExprAst::Neg(a) => build(a, inputs, builder).int_mul(-1),
+        FieldExprAst::Pow(a, exp) => {
+            if *exp == 0 {
+                return ExprBuilder::new_const(builder.clone(), BigUint::from(1u32));
+            }
+            // square and multiply, from the most significant bit
+            let mut base = build(a, inputs, builder);
+            let mut acc = base.clone();
+            for i in (0..u64::BITS - 1 - exp.leading_zeros()).rev() {
+                acc = acc.square();
+                if (exp >> i) & 1 == 1 {
+                    acc = &mut acc * &mut base;
+                }
+            }
+            acc
+        }
+        FieldExprAst::Select(flag, a, b) => FieldVariable::select(
+            *flag,
+            &build(a, inputs, builder),
+            &build(b, inputs, builder),
+        ),
+    }
+}
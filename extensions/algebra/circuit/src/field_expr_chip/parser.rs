@@ -0,0 +1,297 @@
+//! Parser of the output expressions of a [crate::FieldExprOp].
+//!
+//! The grammar is a subset of Rust expressions:
+//! ```text
+//! expr    := term (("+" | "-") term)*
+//! term    := unary (("*" | "/") unary)*
+//! unary   := "-" unary | primary
+//! primary := integer | input | "(" expr ")"
+//!          | "pow" "(" expr "," integer ")"
+//!          | "select" "(" flag "," expr "," expr ")"
+//! ```
+//! where integers are decimal `u64` literals, and inputs and flags are identifiers.
+
+use std::{iter::Peekable, str::CharIndices};
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldExprAst {
+    /// Index of an input.
+    Input(usize),
+    Const(u64),
+    Add(Box<FieldExprAst>, Box<FieldExprAst>),
+    Sub(Box<FieldExprAst>, Box<FieldExprAst>),
+    Mul(Box<FieldExprAst>, Box<FieldExprAst>),
+    Div(Box<FieldExprAst>, Box<FieldExprAst>),
+    Neg(Box<FieldExprAst>),
+    Pow(Box<FieldExprAst>, u64),
+    /// `Select(flag, a, b)` is `a` if the flag with index `flag` is set, `b` otherwise.
+    Select(usize, Box<FieldExprAst>, Box<FieldExprAst>),
+}
+
+impl FieldExprAst {
+    /// Value of the expression modulo the prime `modulus`, or `None` if a divisor is zero.
+    ///
+    /// Both branches of a `select` are evaluated, since the chip computes every division
+    /// regardless of the flags.
+    pub fn eval(&self, inputs: &[BigUint], flags: &[bool], modulus: &BigUint) -> Option<BigUint> {
+        let eval = |ast: &FieldExprAst| ast.eval(inputs, flags, modulus);
+        let value = match self {
+            FieldExprAst::Input(i) => inputs[*i].clone(),
+            FieldExprAst::Const(c) => BigUint::from(*c),
+            FieldExprAst::Add(a, b) => eval(a)? + eval(b)?,
+            FieldExprAst::Sub(a, b) => eval(a)? + modulus - eval(b)?,
+            FieldExprAst::Mul(a, b) => eval(a)? * eval(b)?,
+            FieldExprAst::Div(a, b) => {
+                let (a, b) = (eval(a)?, eval(b)?);
+                if b.is_zero() {
+                    return None;
+                }
+                a * b.modpow(&(modulus - 2u32), modulus)
+            }
+            FieldExprAst::Neg(a) => modulus - eval(a)?,
+            FieldExprAst::Pow(a, exp) => eval(a)?.modpow(&BigUint::from(*exp), modulus),
+            FieldExprAst::Select(flag, a, b) => {
+                let (a, b) = (eval(a)?, eval(b)?);
+                if flags[*flag] {
+                    a
+                } else {
+                    b
+                }
+            }
+        };
+        Some(value % modulus)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FieldExprParseError {
+    #[error("unexpected character {1:?} at offset {0}")]
+    UnexpectedChar(usize, char),
+    #[error("unexpected token {1:?} at offset {0}")]
+    UnexpectedToken(usize, String),
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+    #[error("integer literal {0} does not fit in a u64")]
+    IntegerOverflow(String),
+    #[error("unknown input {0}")]
+    UnknownInput(String),
+    #[error("unknown flag {0}")]
+    UnknownFlag(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Int(String),
+    Punct(char),
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match self {
+            Token::Ident(s) | Token::Int(s) => s.clone(),
+            Token::Punct(c) => c.to_string(),
+        }
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<(usize, Token)>, FieldExprParseError> {
+    fn take_while(
+        chars: &mut Peekable<CharIndices>,
+        src: &str,
+        start: usize,
+        f: impl Fn(char) -> bool,
+    ) -> String {
+        let mut end = src.len();
+        while let Some(&(i, c)) = chars.peek() {
+            if !f(c) {
+                end = i;
+                break;
+            }
+            chars.next();
+        }
+        src[start..end].to_string()
+    }
+
+    let mut tokens = vec![];
+    let mut chars = src.char_indices().peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let s = take_while(&mut chars, src, i, |c| c.is_ascii_digit() || c == '_');
+            tokens.push((i, Token::Int(s)));
+        } else if c.is_alphabetic() || c == '_' {
+            let s = take_while(&mut chars, src, i, |c| c.is_alphanumeric() || c == '_');
+            tokens.push((i, Token::Ident(s)));
+        } else if "+-*/(),".contains(c) {
+            chars.next();
+            tokens.push((i, Token::Punct(c)));
+        } else {
+            return Err(FieldExprParseError::UnexpectedChar(i, c));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    inputs: &'a [String],
+    flags: &'a [String],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn next(&mut self) -> Result<Token, FieldExprParseError> {
+        let (_, token) = self
+            .tokens
+            .get(self.pos)
+            .ok_or(FieldExprParseError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token.clone())
+    }
+
+    fn unexpected(&self) -> FieldExprParseError {
+        match self.tokens.get(self.pos) {
+            Some((offset, token)) => FieldExprParseError::UnexpectedToken(*offset, token.text()),
+            None => FieldExprParseError::UnexpectedEnd,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), FieldExprParseError> {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn expr(&mut self) -> Result<FieldExprAst, FieldExprParseError> {
+        let mut lhs = self.term()?;
+        while let Some(Token::Punct(c @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = Box::new(self.term()?);
+            lhs = if c == '+' {
+                FieldExprAst::Add(Box::new(lhs), rhs)
+            } else {
+                FieldExprAst::Sub(Box::new(lhs), rhs)
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<FieldExprAst, FieldExprParseError> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Punct(c @ ('*' | '/'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = Box::new(self.unary()?);
+            lhs = if c == '*' {
+                FieldExprAst::Mul(Box::new(lhs), rhs)
+            } else {
+                FieldExprAst::Div(Box::new(lhs), rhs)
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<FieldExprAst, FieldExprParseError> {
+        if self.peek() == Some(&Token::Punct('-')) {
+            self.pos += 1;
+            return Ok(FieldExprAst::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn integer(&mut self) -> Result<u64, FieldExprParseError> {
+        match self.peek() {
+            Some(Token::Int(s)) => {
+                let s = s.clone();
+                self.pos += 1;
+                s.replace('_', "")
+                    .parse()
+                    .map_err(|_| FieldExprParseError::IntegerOverflow(s))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn primary(&mut self) -> Result<FieldExprAst, FieldExprParseError> {
+        match self.peek() {
+            Some(Token::Int(_)) => Ok(FieldExprAst::Const(self.integer()?)),
+            Some(Token::Punct('(')) => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) if name == "pow" => {
+                self.pos += 1;
+                self.expect('(')?;
+                let base = self.expr()?;
+                self.expect(',')?;
+                let exp = self.integer()?;
+                self.expect(')')?;
+                Ok(FieldExprAst::Pow(Box::new(base), exp))
+            }
+            Some(Token::Ident(name)) if name == "select" => {
+                self.pos += 1;
+                self.expect('(')?;
+                let flag = match self.next()? {
+                    Token::Ident(flag) => self
+                        .flags
+                        .iter()
+                        .position(|f| *f == flag)
+                        .ok_or(FieldExprParseError::UnknownFlag(flag))?,
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.unexpected());
+                    }
+                };
+                self.expect(',')?;
+                let a = self.expr()?;
+                self.expect(',')?;
+                let b = self.expr()?;
+                self.expect(')')?;
+                Ok(FieldExprAst::Select(flag, Box::new(a), Box::new(b)))
+            }
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                self.inputs
+                    .iter()
+                    .position(|input| *input == name)
+                    .map(FieldExprAst::Input)
+                    .ok_or(FieldExprParseError::UnknownInput(name))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+/// Parses `src`, resolving identifiers against the names of the inputs and the flags.
+pub fn parse_field_expr(
+    src: &str,
+    inputs: &[String],
+    flags: &[String],
+) -> Result<FieldExprAst, FieldExprParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        pos: 0,
+        inputs,
+        flags,
+    };
+    let expr = parser.expr()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parser.unexpected());
+    }
+    Ok(expr)
+}
@@ -0,0 +1,243 @@
+use num_bigint::BigUint;
+use openvm_circuit::arch::testing::{VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_instructions::riscv::RV32_CELL_BITS;
+use openvm_mod_circuit_builder::test_utils::biguint_to_limbs;
+use openvm_pairing_guest::bn254::BN254_MODULUS;
+use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapAdapterChip};
+use openvm_stark_backend::p3_field::FieldAlgebra;
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, utils::create_seeded_rng};
+use rand::Rng;
+
+use super::{parse_field_expr, FieldExprAst, FieldExprChip, FieldExprParseError};
+use crate::FieldExprOp;
+
+const NUM_LIMBS: usize = 32;
+const LIMB_BITS: usize = 8;
+type F = BabyBear;
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+fn random_element(rng: &mut impl Rng, modulus: &BigUint) -> BigUint {
+    let digits: Vec<u32> = (0..NUM_LIMBS / 4).map(|_| rng.gen()).collect();
+    BigUint::new(digits) % modulus
+}
+
+fn to_limbs(x: &BigUint) -> [F; NUM_LIMBS] {
+    biguint_to_limbs::<NUM_LIMBS>(x.clone(), LIMB_BITS).map(F::from_canonical_u32)
+}
+
+/// Runs the setup of `op` with the 32-limb chip, then each `(local_opcode, inputs, expected)`
+/// of `calls`, checking that the first outputs are `expected`.
+fn run<const BLOCKS: usize>(op: FieldExprOp, calls: Vec<(usize, Vec<BigUint>, Vec<BigUint>)>) {
+    let mut tester: VmChipTestBuilder<F> = VmChipTestBuilder::default();
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let adapter = Rv32VecHeapAdapterChip::<F, 2, BLOCKS, BLOCKS, NUM_LIMBS, NUM_LIMBS>::new(
+        tester.execution_bus(),
+        tester.program_bus(),
+        tester.memory_bridge(),
+        tester.address_bits(),
+        bitwise_chip.clone(),
+    );
+    let offset = 0x800;
+    let mut chip = FieldExprChip::new(
+        adapter,
+        &op,
+        NUM_LIMBS,
+        offset,
+        tester.range_checker(),
+        tester.offline_memory_mutex_arc(),
+    );
+
+    let write = |tester: &mut VmChipTestBuilder<F>, local_opcode: usize, inputs: &[BigUint]| {
+        let mut inputs: Vec<_> = inputs.iter().map(to_limbs).collect();
+        inputs.resize(2 * BLOCKS, [F::ZERO; NUM_LIMBS]);
+        let rs2 = inputs.split_off(BLOCKS);
+        rv32_write_heap_default(tester, inputs, rs2, offset + local_opcode)
+    };
+
+    let mut setup_inputs = vec![BigUint::from(1u32); op.inputs.len()];
+    setup_inputs[0] = op.modulus.clone();
+    let setup_instruction = write(&mut tester, op.num_opcodes() - 1, &setup_inputs);
+    tester.execute(&mut chip, &setup_instruction);
+
+    for (local_opcode, inputs, expected) in calls {
+        let instruction = write(&mut tester, local_opcode, &inputs);
+        tester.execute(&mut chip, &instruction);
+        let expr = chip.0.core.expr();
+        let flags = (0..expr.builder.num_flags)
+            .map(|i| op.flags.is_empty() || i == local_opcode)
+            .collect();
+        let mut padded = inputs.clone();
+        padded.resize(2 * BLOCKS, BigUint::from(0u32));
+        let outputs = expr.execute_with_output(padded, flags);
+        assert_eq!(outputs.len(), BLOCKS);
+        assert_eq!(outputs[..expected.len()], expected);
+    }
+
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_mul_add() {
+    let mut rng = create_seeded_rng();
+    let modulus = BN254_MODULUS.clone();
+    let op = FieldExprOp::new(
+        "MulAdd".to_string(),
+        modulus.clone(),
+        names(&["a", "b", "c"]),
+        names(&["a * b + c"]),
+    );
+    assert_eq!(op.elements_per_pointer(), 2);
+    let a = random_element(&mut rng, &modulus);
+    let b = random_element(&mut rng, &(&modulus - 7u32));
+    let c = random_element(&mut rng, &(&modulus - 11u32));
+    let expected = (&a * &b + &c) % &modulus;
+    run::<2>(op, vec![(0, vec![a, b, c], vec![expected])]);
+}
+
+#[test]
+fn test_flags() {
+    let mut rng = create_seeded_rng();
+    let modulus = BN254_MODULUS.clone();
+    let op = FieldExprOp::new(
+        "AddSub".to_string(),
+        modulus.clone(),
+        names(&["x", "y"]),
+        names(&["select(is_add, x + y, x - y)"]),
+    )
+    .with_flags(names(&["is_add", "is_sub"]));
+    assert_eq!(op.num_opcodes(), 3);
+    let x = random_element(&mut rng, &modulus);
+    let y = random_element(&mut rng, &(&modulus - 3u32));
+    let sum = (&x + &y) % &modulus;
+    let diff = (&x + &modulus - &y) % &modulus;
+    run::<1>(
+        op,
+        vec![
+            (0, vec![x.clone(), y.clone()], vec![sum]),
+            (1, vec![x, y], vec![diff]),
+        ],
+    );
+}
+
+#[test]
+fn test_pow_div_neg() {
+    let mut rng = create_seeded_rng();
+    let modulus = BN254_MODULUS.clone();
+    let op = FieldExprOp::new(
+        "PowDiv".to_string(),
+        modulus.clone(),
+        names(&["x", "y"]),
+        names(&["-pow(x, 5) / y", "(x - 3) * 2"]),
+    );
+    assert_eq!(op.elements_per_pointer(), 2);
+    let x = random_element(&mut rng, &modulus);
+    let y = random_element(&mut rng, &(&modulus - 5u32)) + 1u32;
+    let y_inv = y.modpow(&(&modulus - 2u32), &modulus);
+    let first = (&modulus - x.modpow(&BigUint::from(5u32), &modulus)) * y_inv % &modulus;
+    let second = (&x + &modulus - 3u32) * 2u32 % &modulus;
+    run::<2>(op, vec![(0, vec![x, y], vec![first, second])]);
+}
+
+#[test]
+fn test_parse() {
+    let inputs = names(&["x", "y"]);
+    let flags = names(&["f"]);
+    let x = || Box::new(FieldExprAst::Input(0));
+    let y = || Box::new(FieldExprAst::Input(1));
+    assert_eq!(
+        parse_field_expr("x + y * 2", &inputs, &flags),
+        Ok(FieldExprAst::Add(
+            x(),
+            Box::new(FieldExprAst::Mul(y(), Box::new(FieldExprAst::Const(2))))
+        ))
+    );
+    assert_eq!(
+        parse_field_expr("-(x - y) / pow(x, 3)", &inputs, &flags),
+        Ok(FieldExprAst::Div(
+            Box::new(FieldExprAst::Neg(Box::new(FieldExprAst::Sub(x(), y())))),
+            Box::new(FieldExprAst::Pow(x(), 3)),
+        ))
+    );
+    assert_eq!(
+        parse_field_expr("select(f, x, y)", &inputs, &flags),
+        Ok(FieldExprAst::Select(0, x(), y()))
+    );
+    assert_eq!(
+        parse_field_expr("x + z", &inputs, &flags),
+        Err(FieldExprParseError::UnknownInput("z".to_string()))
+    );
+    assert_eq!(
+        parse_field_expr("select(g, x, y)", &inputs, &flags),
+        Err(FieldExprParseError::UnknownFlag("g".to_string()))
+    );
+    assert_eq!(
+        parse_field_expr("x y", &inputs, &flags),
+        Err(FieldExprParseError::UnexpectedToken(2, "y".to_string()))
+    );
+    assert_eq!(
+        parse_field_expr("x +", &inputs, &flags),
+        Err(FieldExprParseError::UnexpectedEnd)
+    );
+    assert_eq!(
+        parse_field_expr("x % y", &inputs, &flags),
+        Err(FieldExprParseError::UnexpectedChar(2, '%'))
+    );
+}
+
+#[test]
+#[should_panic(expected = "division is not supported for a composite modulus")]
+fn test_div_composite_modulus() {
+    let op = FieldExprOp::new(
+        "Div".to_string(),
+        BigUint::from(15u32),
+        names(&["x", "y"]),
+        names(&["x / y"]),
+    )
+    .with_composite_modulus();
+    run::<1>(op, vec![]);
+}
+
+#[test]
+#[should_panic(expected = "divides by zero at setup")]
+fn test_div_vanishing_at_setup() {
+    let op = FieldExprOp::new(
+        "Slope".to_string(),
+        BN254_MODULUS.clone(),
+        names(&["a", "b", "c"]),
+        names(&["a / (b - c)"]),
+    );
+    op.check_valid();
+}
+
+#[test]
+fn test_eval() {
+    let modulus = BigUint::from(13u32);
+    let inputs = names(&["x", "y"]);
+    let flags = names(&["f"]);
+    let eval = |expr: &str, values: [u32; 2], flag: bool| {
+        parse_field_expr(expr, &inputs, &flags).unwrap().eval(
+            &values.map(BigUint::from),
+            &[flag],
+            &modulus,
+        )
+    };
+    assert_eq!(eval("-x / y + 1", [3, 2], false), Some(BigUint::from(6u32)));
+    assert_eq!(
+        eval("select(f, pow(x, 2), y - x)", [3, 2], true),
+        Some(BigUint::from(9u32))
+    );
+    assert_eq!(
+        eval("select(f, pow(x, 2), y - x)", [3, 2], false),
+        Some(BigUint::from(12u32))
+    );
+    // divisions in both branches of a select are computed
+    assert_eq!(eval("select(f, x, x / (y - 2))", [3, 2], true), None);
+}
@@ -0,0 +1,238 @@
+use derive_more::derive::From;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use openvm_algebra_guest::FIELD_EXPR_MAX_KINDS;
+use openvm_algebra_transpiler::field_expr_opcode;
+use openvm_circuit::{
+    arch::{SystemPort, VmExtension, VmInventory, VmInventoryBuilder, VmInventoryError},
+    system::phantom::PhantomChip,
+};
+use openvm_circuit_derive::{AnyEnum, InstructionExecutor};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_instructions::opcode_names::OpcodeNames;
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::field_expr_chip::{parse_field_expr, FieldExprChip};
+
+/// Maximum number of field expression operations, limited by the size of `funct7`.
+pub const FIELD_EXPR_MAX_OPS: usize = 128 / FIELD_EXPR_MAX_KINDS as usize;
+
+/// Extension with one custom opcode per operation defined in the configuration, so that guest
+/// programs get a chip for an arithmetic formula without writing any circuit code.
+#[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
+pub struct FieldExprExtension {
+    /// The operation at index `i` is called with `op_idx = i` in the guest.
+    pub ops: Vec<FieldExprOp>,
+}
+
+/// An operation computing field expressions of its inputs modulo `modulus`.
+///
+/// Each output is an expression in the grammar of [crate::field_expr_chip::parse_field_expr],
+/// e.g. `a * b + c` or `select(is_double, x * x, x * y)`. Each flag gets its own opcode which
+/// sets this flag, so an operation with flags has one opcode per flag, and one opcode otherwise.
+///
+/// Every operation must be set up before use, with the modulus as first input and 1 as the other
+/// inputs, so divisors must not vanish at this point. [FieldExprOp::check_valid] rejects
+/// operations whose divisors do.
+#[serde_as]
+#[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
+pub struct FieldExprOp {
+    pub name: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub modulus: BigUint,
+    /// Names of the inputs, in memory order.
+    pub inputs: Vec<String>,
+    /// Expressions of the outputs, in memory order.
+    pub outputs: Vec<String>,
+    #[new(default)]
+    #[serde(default)]
+    pub flags: Vec<String>,
+    /// Division is not supported for composite moduli.
+    #[new(value = "true")]
+    #[serde(default = "default_is_prime")]
+    pub is_prime: bool,
+}
+
+fn default_is_prime() -> bool {
+    true
+}
+
+impl FieldExprOp {
+    pub fn with_flags(mut self, flags: Vec<String>) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn with_composite_modulus(mut self) -> Self {
+        self.is_prime = false;
+        self
+    }
+
+    /// Number of field elements read from each of `rs1` and `rs2`, and written to `rd`: the
+    /// first half of the inputs is read from `rs1` and the second half from `rs2`.
+    pub fn elements_per_pointer(&self) -> usize {
+        let elements = self.inputs.len().div_ceil(2).max(self.outputs.len());
+        elements.next_power_of_two()
+    }
+
+    /// Number of local opcodes, including setup.
+    pub fn num_opcodes(&self) -> usize {
+        self.flags.len().max(1) + 1
+    }
+
+    pub fn check_valid(&self) {
+        assert!(
+            !self.outputs.is_empty(),
+            "{}: at least one output is required",
+            self.name
+        );
+        assert!(
+            self.elements_per_pointer() <= 4,
+            "{}: at most 8 inputs and 4 outputs are supported",
+            self.name
+        );
+        assert!(
+            self.num_opcodes() <= FIELD_EXPR_MAX_KINDS as usize,
+            "{}: at most {} flags are supported",
+            self.name,
+            FIELD_EXPR_MAX_KINDS - 1
+        );
+        // The setup executes the expressions, including every division, with all flags unset.
+        let mut setup_inputs = vec![BigUint::one(); self.inputs.len()];
+        if let Some(first) = setup_inputs.first_mut() {
+            *first = BigUint::zero();
+        }
+        let setup_flags = vec![false; self.flags.len()];
+        for output in &self.outputs {
+            let ast = parse_field_expr(output, &self.inputs, &self.flags)
+                .unwrap_or_else(|e| panic!("{}: invalid output {output:?}: {e}", self.name));
+            if self.is_prime {
+                assert!(
+                    ast.eval(&setup_inputs, &setup_flags, &self.modulus).is_some(),
+                    "{}: output {output:?} divides by zero at setup, where the first input is the modulus and the other inputs are 1",
+                    self.name
+                );
+            }
+        }
+    }
+}
+
+#[derive(ChipUsageGetter, Chip, InstructionExecutor, AnyEnum, From)]
+pub enum FieldExprExtensionExecutor<F: PrimeField32> {
+    // 32 limbs, 1, 2 or 4 elements per pointer
+    FieldExpr1Rv32_32(FieldExprChip<F, 1, 32>),
+    FieldExpr2Rv32_32(FieldExprChip<F, 2, 32>),
+    FieldExpr4Rv32_32(FieldExprChip<F, 4, 32>),
+    // 48 limbs, 1, 2 or 4 elements per pointer
+    FieldExpr1Rv32_48(FieldExprChip<F, 3, 16>),
+    FieldExpr2Rv32_48(FieldExprChip<F, 6, 16>),
+    FieldExpr4Rv32_48(FieldExprChip<F, 12, 16>),
+}
+
+#[derive(ChipUsageGetter, Chip, AnyEnum, From)]
+pub enum FieldExprExtensionPeriphery<F: PrimeField32> {
+    BitwiseOperationLookup(SharedBitwiseOperationLookupChip<8>),
+    // We put this only to get the <F> generic to work
+    Phantom(PhantomChip<F>),
+}
+
+impl<F: PrimeField32> VmExtension<F> for FieldExprExtension {
+    type Executor = FieldExprExtensionExecutor<F>;
+    type Periphery = FieldExprExtensionPeriphery<F>;
+
+    fn build(
+        &self,
+        builder: &mut VmInventoryBuilder<F>,
+    ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError> {
+        assert!(
+            self.ops.len() <= FIELD_EXPR_MAX_OPS,
+            "at most {FIELD_EXPR_MAX_OPS} field expression operations are supported"
+        );
+        let mut inventory = VmInventory::new();
+        let SystemPort {
+            execution_bus,
+            program_bus,
+            memory_bridge,
+        } = builder.system_port();
+        let bitwise_lu_chip = if let Some(&chip) = builder
+            .find_chip::<SharedBitwiseOperationLookupChip<8>>()
+            .first()
+        {
+            chip.clone()
+        } else {
+            let bitwise_lu_bus = BitwiseOperationLookupBus::new(builder.new_bus_idx());
+            let chip = SharedBitwiseOperationLookupChip::new(bitwise_lu_bus);
+            inventory.add_periphery_chip(chip.clone());
+            chip
+        };
+        let offline_memory = builder.system_base().offline_memory();
+        let range_checker = builder.system_base().range_checker_chip.clone();
+        let address_bits = builder.system_config().memory_config.pointer_max_bits;
+
+        for (op_idx, op) in self.ops.iter().enumerate() {
+            op.check_valid();
+            // determine the number of bytes needed to represent a field element
+            let bytes = op.modulus.bits().div_ceil(8);
+            let start_offset = field_expr_opcode(op_idx, 0).as_usize();
+            let opcodes = (0..op.num_opcodes()).map(|i| field_expr_opcode(op_idx, i));
+            let adapter = Rv32VecHeapAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            );
+            macro_rules! add_chip {
+                ($variant:ident, $limbs:expr) => {
+                    inventory.add_executor(
+                        FieldExprExtensionExecutor::$variant(FieldExprChip::new(
+                            adapter,
+                            op,
+                            $limbs,
+                            start_offset,
+                            range_checker.clone(),
+                            offline_memory.clone(),
+                        )),
+                        opcodes,
+                    )?
+                };
+            }
+
+            match (bytes, op.elements_per_pointer()) {
+                (0..=32, 1) => add_chip!(FieldExpr1Rv32_32, 32),
+                (0..=32, 2) => add_chip!(FieldExpr2Rv32_32, 32),
+                (0..=32, _) => add_chip!(FieldExpr4Rv32_32, 32),
+                (33..=48, 1) => add_chip!(FieldExpr1Rv32_48, 48),
+                (33..=48, 2) => add_chip!(FieldExpr2Rv32_48, 48),
+                (33..=48, _) => add_chip!(FieldExpr4Rv32_48, 48),
+                _ => panic!("Modulus too large"),
+            }
+        }
+
+        Ok(inventory)
+    }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        let mut names = OpcodeNames::default();
+        for (op_idx, op) in self.ops.iter().enumerate() {
+            let prefix = format!("field_expr.{}", op.name);
+            if op.flags.is_empty() {
+                names.insert(field_expr_opcode(op_idx, 0), prefix.clone());
+            }
+            for (i, flag) in op.flags.iter().enumerate() {
+                names.insert(field_expr_opcode(op_idx, i), format!("{prefix}.{flag}"));
+            }
+            names.insert(
+                field_expr_opcode(op_idx, op.num_opcodes() - 1),
+                format!("{prefix}.SETUP"),
+            );
+        }
+        names
+    }
+}
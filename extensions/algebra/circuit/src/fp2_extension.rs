@@ -10,13 +10,13 @@ use openvm_circuit_primitives::bitwise_op_lookup::{
     BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
 };
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
-use openvm_instructions::{LocalOpcode, VmOpcode};
+use openvm_instructions::{opcode_names::OpcodeNames, LocalOpcode, VmOpcode};
 use openvm_mod_circuit_builder::ExprBuilderConfig;
 use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
 use openvm_stark_backend::p3_field::PrimeField32;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
-use strum::EnumCount;
+use strum::{EnumCount, IntoEnumIterator};
 
 use crate::fp2_chip::{Fp2AddSubChip, Fp2MulDivChip};
 
@@ -85,11 +85,13 @@ impl<F: PrimeField32> VmExtension<F> for Fp2Extension {
                 modulus: modulus.clone(),
                 num_limbs: 32,
                 limb_bits: 8,
+                is_prime: true,
             };
             let config48 = ExprBuilderConfig {
                 modulus: modulus.clone(),
                 num_limbs: 48,
                 limb_bits: 8,
+                is_prime: true,
             };
             let adapter_chip_32 = Rv32VecHeapAdapterChip::new(
                 execution_bus,
@@ -167,4 +169,14 @@ impl<F: PrimeField32> VmExtension<F> for Fp2Extension {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        (0..self.supported_modulus.len()).fold(OpcodeNames::default(), |names, i| {
+            names.with_opcodes_at(
+                &format!("fp2[{i}]"),
+                Fp2Opcode::CLASS_OFFSET + i * Fp2Opcode::COUNT,
+                Fp2Opcode::iter(),
+            )
+        })
+    }
 }
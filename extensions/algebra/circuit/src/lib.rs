@@ -1,3 +1,4 @@
+pub mod field_expr_chip;
 pub mod fp2_chip;
 pub mod modular_chip;
 
@@ -7,5 +8,7 @@ mod modular_extension;
 pub use modular_extension::*;
 mod fp2_extension;
 pub use fp2_extension::*;
+mod field_expr_extension;
+pub use field_expr_extension::*;
 mod config;
 pub use config::*;
@@ -93,3 +93,56 @@ impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
         Self(VmChipWrapper::new(adapter, core, offline_memory))
     }
 }
+
+/// Multiplication without division, for moduli that are not prime.
+pub fn mul_expr(config: ExprBuilderConfig, range_bus: VariableRangeCheckerBus) -> FieldExpr {
+    config.check_valid();
+    let builder = ExprBuilder::new(config, range_bus.range_max_bits);
+    let builder = Rc::new(RefCell::new(builder));
+    let x = ExprBuilder::new_input(builder.clone());
+    let y = ExprBuilder::new_input(builder.clone());
+    // When it's SETUP op, x = p == 0, y = 0, and z = x * y = 0 still satisfies the constraint.
+    let mut z = x * y;
+    z.save_output();
+
+    let builder = builder.borrow().clone();
+    FieldExpr::new(builder, range_bus, true)
+}
+
+/// Modular multiplication chip that supports composite moduli. It handles the `MUL` and
+/// `SETUP_MULDIV` opcodes, but not `DIV`.
+#[derive(Chip, ChipUsageGetter, InstructionExecutor)]
+pub struct ModularMulChip<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>(
+    pub  VmChipWrapper<
+        F,
+        Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        FieldExpressionCoreChip,
+    >,
+);
+
+impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    ModularMulChip<F, BLOCKS, BLOCK_SIZE>
+{
+    pub fn new(
+        adapter: Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        config: ExprBuilderConfig,
+        offset: usize,
+        range_checker: SharedVariableRangeCheckerChip,
+        offline_memory: Arc<Mutex<OfflineMemory<F>>>,
+    ) -> Self {
+        let expr = mul_expr(config, range_checker.bus());
+        let core = FieldExpressionCoreChip::new(
+            expr,
+            offset,
+            vec![
+                Rv32ModularArithmeticOpcode::MUL as usize,
+                Rv32ModularArithmeticOpcode::SETUP_MULDIV as usize,
+            ],
+            vec![],
+            range_checker,
+            "ModularMul",
+            false,
+        );
+        Self(VmChipWrapper::new(adapter, core, offline_memory))
+    }
+}
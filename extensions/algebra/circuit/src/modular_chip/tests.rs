@@ -28,7 +28,7 @@ use rand::Rng;
 
 use super::{
     ModularAddSubChip, ModularIsEqualChip, ModularIsEqualCoreAir, ModularIsEqualCoreChip,
-    ModularIsEqualCoreCols, ModularIsEqualCoreRecord, ModularMulDivChip,
+    ModularIsEqualCoreCols, ModularIsEqualCoreRecord, ModularMulChip, ModularMulDivChip,
 };
 
 const NUM_LIMBS: usize = 32;
@@ -59,6 +59,7 @@ fn test_addsub(opcode_offset: usize, modulus: BigUint) {
         modulus: modulus.clone(),
         num_limbs: NUM_LIMBS,
         limb_bits: LIMB_BITS,
+        is_prime: true,
     };
     let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
     let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
@@ -188,6 +189,7 @@ fn test_muldiv(opcode_offset: usize, modulus: BigUint) {
         modulus: modulus.clone(),
         num_limbs: NUM_LIMBS,
         limb_bits: LIMB_BITS,
+        is_prime: true,
     };
     let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
     let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
@@ -297,6 +299,102 @@ fn test_muldiv(opcode_offset: usize, modulus: BigUint) {
     tester.simple_test().expect("Verification failed");
 }
 
+#[test]
+fn test_composite_mul() {
+    // A 512-bit modulus with two 256-bit prime factors, like an RSA modulus.
+    const NUM_LIMBS: usize = 64;
+    const BLOCKS: usize = NUM_LIMBS / BLOCK_SIZE;
+    let modulus = secp256k1_coord_prime() * secp256k1_scalar_prime();
+
+    let mut tester: VmChipTestBuilder<F> = VmChipTestBuilder::default();
+    let config = ExprBuilderConfig {
+        modulus: modulus.clone(),
+        num_limbs: NUM_LIMBS,
+        limb_bits: LIMB_BITS,
+        is_prime: false,
+    };
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    // doing 2xBLOCK_SIZE reads and writes
+    let adapter = Rv32VecHeapAdapterChip::<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>::new(
+        tester.execution_bus(),
+        tester.program_bus(),
+        tester.memory_bridge(),
+        tester.address_bits(),
+        bitwise_chip.clone(),
+    );
+    let mut chip = ModularMulChip::new(
+        adapter,
+        config,
+        Rv32ModularArithmeticOpcode::CLASS_OFFSET,
+        tester.range_checker(),
+        tester.offline_memory_mutex_arc(),
+    );
+    let mut rng = create_seeded_rng();
+    let num_tests = 20;
+    let setup = Rv32ModularArithmeticOpcode::SETUP_MULDIV as usize;
+    let mut all_ops = vec![setup];
+    let mut all_a = vec![modulus.clone()];
+    let mut all_b = vec![BigUint::zero()];
+    for _ in 0..num_tests {
+        let a_digits: Vec<_> = (0..NUM_LIMBS / 4).map(|_| rng.gen::<u32>()).collect();
+        let b_digits: Vec<_> = (0..NUM_LIMBS / 4).map(|_| rng.gen::<u32>()).collect();
+        all_ops.push(MUL_LOCAL);
+        all_a.push(BigUint::new(a_digits) % &modulus);
+        all_b.push(BigUint::new(b_digits) % &modulus);
+    }
+
+    for ((op, a), b) in all_ops.into_iter().zip(all_a).zip(all_b) {
+        let expected_answer = if op == setup {
+            a.clone() % &modulus
+        } else {
+            (&a * &b) % &modulus
+        };
+
+        let ptr_as = 1;
+        let addr_ptr1 = 0;
+        let addr_ptr2 = 12;
+        let addr_ptr3 = 24;
+
+        let data_as = 2;
+        let address1 = 0;
+        let address2 = 128;
+        let address3 = 256;
+
+        write_ptr_reg(&mut tester, ptr_as, addr_ptr1, address1);
+        write_ptr_reg(&mut tester, ptr_as, addr_ptr2, address2);
+        write_ptr_reg(&mut tester, ptr_as, addr_ptr3, address3);
+
+        for (address, x) in [(address1, &a), (address2, &b)] {
+            let limbs = biguint_to_limbs::<NUM_LIMBS>(x.clone(), LIMB_BITS);
+            for (i, block) in limbs.chunks_exact(BLOCK_SIZE).enumerate() {
+                let block: [BabyBear; BLOCK_SIZE] =
+                    from_fn(|j| BabyBear::from_canonical_u32(block[j]));
+                tester.write(data_as, address as usize + i * BLOCK_SIZE, block);
+            }
+        }
+
+        let instruction = Instruction::from_isize(
+            VmOpcode::from_usize(chip.0.core.air.offset + op),
+            addr_ptr3 as isize,
+            addr_ptr1 as isize,
+            addr_ptr2 as isize,
+            ptr_as as isize,
+            data_as as isize,
+        );
+        tester.execute(&mut chip, &instruction);
+
+        let expected_limbs = biguint_to_limbs::<NUM_LIMBS>(expected_answer, LIMB_BITS);
+        for (i, expected) in expected_limbs.into_iter().enumerate() {
+            let read_val = tester.read_cell(data_as, address3 as usize + i);
+            assert_eq!(BabyBear::from_canonical_u32(expected), read_val);
+        }
+    }
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+
+    tester.simple_test().expect("Verification failed");
+}
+
 fn test_is_equal<const NUM_LANES: usize, const LANE_SIZE: usize, const TOTAL_LIMBS: usize>(
     opcode_offset: usize,
     modulus: BigUint,
@@ -380,6 +478,12 @@ fn test_modular_is_equal_3x16() {
     test_is_equal::<3, 16, 48>(17, BLS12_381_MODULUS.clone(), 100);
 }
 
+#[test]
+fn test_modular_is_equal_2x32() {
+    let modulus = secp256k1_coord_prime() * secp256k1_scalar_prime();
+    test_is_equal::<2, 32, 64>(17, modulus, 100);
+}
+
 // Wrapper chip for testing a bad setup row
 type BadModularIsEqualChip<
     F,
@@ -11,16 +11,17 @@ use openvm_circuit_primitives::bitwise_op_lookup::{
     BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
 };
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
-use openvm_instructions::{LocalOpcode, VmOpcode};
+use openvm_instructions::{opcode_names::OpcodeNames, LocalOpcode, VmOpcode};
 use openvm_mod_circuit_builder::ExprBuilderConfig;
 use openvm_rv32_adapters::{Rv32IsEqualModAdapterChip, Rv32VecHeapAdapterChip};
 use openvm_stark_backend::p3_field::PrimeField32;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
-use strum::EnumCount;
+use strum::{EnumCount, IntoEnumIterator};
 
 use crate::modular_chip::{
-    ModularAddSubChip, ModularIsEqualChip, ModularIsEqualCoreChip, ModularMulDivChip,
+    ModularAddSubChip, ModularIsEqualChip, ModularIsEqualCoreChip, ModularMulChip,
+    ModularMulDivChip,
 };
 
 #[serde_as]
@@ -28,18 +29,39 @@ use crate::modular_chip::{
 pub struct ModularExtension {
     #[serde_as(as = "Vec<DisplayFromStr>")]
     pub supported_modulus: Vec<BigUint>,
+    /// Moduli in `supported_modulus` that are not prime, e.g. RSA moduli. Division is not
+    /// supported for them, so they only get addition, subtraction, multiplication and equality.
+    #[new(default)]
+    #[serde(default)]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub composite_modulus: Vec<BigUint>,
+}
+
+impl ModularExtension {
+    /// Marks `modulus` as composite. It must also be in `supported_modulus`.
+    pub fn with_composite_modulus(mut self, modulus: BigUint) -> Self {
+        self.composite_modulus.push(modulus);
+        self
+    }
 }
 
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, AnyEnum, From)]
 pub enum ModularExtensionExecutor<F: PrimeField32> {
-    // 32 limbs prime
+    // 32 limbs
     ModularAddSubRv32_32(ModularAddSubChip<F, 1, 32>),
     ModularMulDivRv32_32(ModularMulDivChip<F, 1, 32>),
+    ModularMulRv32_32(ModularMulChip<F, 1, 32>),
     ModularIsEqualRv32_32(ModularIsEqualChip<F, 1, 32, 32>),
-    // 48 limbs prime
+    // 48 limbs
     ModularAddSubRv32_48(ModularAddSubChip<F, 3, 16>),
     ModularMulDivRv32_48(ModularMulDivChip<F, 3, 16>),
+    ModularMulRv32_48(ModularMulChip<F, 3, 16>),
     ModularIsEqualRv32_48(ModularIsEqualChip<F, 3, 16, 48>),
+    // 64 limbs
+    ModularAddSubRv32_64(ModularAddSubChip<F, 2, 32>),
+    ModularMulDivRv32_64(ModularMulDivChip<F, 2, 32>),
+    ModularMulRv32_64(ModularMulChip<F, 2, 32>),
+    ModularIsEqualRv32_64(ModularIsEqualChip<F, 2, 32, 64>),
 }
 
 #[derive(ChipUsageGetter, Chip, AnyEnum, From)]
@@ -82,12 +104,24 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
             ..=(Rv32ModularArithmeticOpcode::SETUP_ADDSUB as usize);
         let muldiv_opcodes = (Rv32ModularArithmeticOpcode::MUL as usize)
             ..=(Rv32ModularArithmeticOpcode::SETUP_MULDIV as usize);
+        let mul_opcodes = [
+            Rv32ModularArithmeticOpcode::MUL as usize,
+            Rv32ModularArithmeticOpcode::SETUP_MULDIV as usize,
+        ];
         let iseq_opcodes = (Rv32ModularArithmeticOpcode::IS_EQ as usize)
             ..=(Rv32ModularArithmeticOpcode::SETUP_ISEQ as usize);
 
+        for modulus in &self.composite_modulus {
+            assert!(
+                self.supported_modulus.contains(modulus),
+                "composite modulus {modulus} is not in supported_modulus"
+            );
+        }
+
         for (i, modulus) in self.supported_modulus.iter().enumerate() {
-            // determine the number of bytes needed to represent a prime field element
+            // determine the number of bytes needed to represent an element modulo `modulus`
             let bytes = modulus.bits().div_ceil(8);
+            let is_prime = !self.composite_modulus.contains(modulus);
             let start_offset =
                 Rv32ModularArithmeticOpcode::CLASS_OFFSET + i * Rv32ModularArithmeticOpcode::COUNT;
 
@@ -95,11 +129,19 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                 modulus: modulus.clone(),
                 num_limbs: 32,
                 limb_bits: 8,
+                is_prime,
             };
             let config48 = ExprBuilderConfig {
                 modulus: modulus.clone(),
                 num_limbs: 48,
                 limb_bits: 8,
+                is_prime,
+            };
+            let config64 = ExprBuilderConfig {
+                modulus: modulus.clone(),
+                num_limbs: 64,
+                limb_bits: 8,
+                is_prime,
             };
             let adapter_chip_32 = Rv32VecHeapAdapterChip::new(
                 execution_bus,
@@ -115,6 +157,13 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                 address_bits,
                 bitwise_lu_chip.clone(),
             );
+            let adapter_chip_64 = Rv32VecHeapAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            );
 
             if bytes <= 32 {
                 let addsub_chip = ModularAddSubChip::new(
@@ -130,19 +179,33 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                         .clone()
                         .map(|x| VmOpcode::from_usize(x + start_offset)),
                 )?;
-                let muldiv_chip = ModularMulDivChip::new(
-                    adapter_chip_32.clone(),
-                    config32.clone(),
-                    start_offset,
-                    range_checker.clone(),
-                    offline_memory.clone(),
-                );
-                inventory.add_executor(
-                    ModularExtensionExecutor::ModularMulDivRv32_32(muldiv_chip),
-                    muldiv_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + start_offset)),
-                )?;
+                if is_prime {
+                    let muldiv_chip = ModularMulDivChip::new(
+                        adapter_chip_32.clone(),
+                        config32.clone(),
+                        start_offset,
+                        range_checker.clone(),
+                        offline_memory.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::ModularMulDivRv32_32(muldiv_chip),
+                        muldiv_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + start_offset)),
+                    )?;
+                } else {
+                    let mul_chip = ModularMulChip::new(
+                        adapter_chip_32.clone(),
+                        config32.clone(),
+                        start_offset,
+                        range_checker.clone(),
+                        offline_memory.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::ModularMulRv32_32(mul_chip),
+                        mul_opcodes.map(|x| VmOpcode::from_usize(x + start_offset)),
+                    )?;
+                }
                 let isequal_chip = ModularIsEqualChip::new(
                     Rv32IsEqualModAdapterChip::new(
                         execution_bus,
@@ -178,19 +241,95 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                         .clone()
                         .map(|x| VmOpcode::from_usize(x + start_offset)),
                 )?;
-                let muldiv_chip = ModularMulDivChip::new(
-                    adapter_chip_48.clone(),
-                    config48.clone(),
+                if is_prime {
+                    let muldiv_chip = ModularMulDivChip::new(
+                        adapter_chip_48.clone(),
+                        config48.clone(),
+                        start_offset,
+                        range_checker.clone(),
+                        offline_memory.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::ModularMulDivRv32_48(muldiv_chip),
+                        muldiv_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + start_offset)),
+                    )?;
+                } else {
+                    let mul_chip = ModularMulChip::new(
+                        adapter_chip_48.clone(),
+                        config48.clone(),
+                        start_offset,
+                        range_checker.clone(),
+                        offline_memory.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::ModularMulRv32_48(mul_chip),
+                        mul_opcodes.map(|x| VmOpcode::from_usize(x + start_offset)),
+                    )?;
+                }
+                let isequal_chip = ModularIsEqualChip::new(
+                    Rv32IsEqualModAdapterChip::new(
+                        execution_bus,
+                        program_bus,
+                        memory_bridge,
+                        address_bits,
+                        bitwise_lu_chip.clone(),
+                    ),
+                    ModularIsEqualCoreChip::new(
+                        modulus.clone(),
+                        bitwise_lu_chip.clone(),
+                        start_offset,
+                    ),
+                    offline_memory.clone(),
+                );
+                inventory.add_executor(
+                    ModularExtensionExecutor::ModularIsEqualRv32_48(isequal_chip),
+                    iseq_opcodes
+                        .clone()
+                        .map(|x| VmOpcode::from_usize(x + start_offset)),
+                )?;
+            } else if bytes <= 64 {
+                let addsub_chip = ModularAddSubChip::new(
+                    adapter_chip_64.clone(),
+                    config64.clone(),
                     start_offset,
                     range_checker.clone(),
                     offline_memory.clone(),
                 );
                 inventory.add_executor(
-                    ModularExtensionExecutor::ModularMulDivRv32_48(muldiv_chip),
-                    muldiv_opcodes
+                    ModularExtensionExecutor::ModularAddSubRv32_64(addsub_chip),
+                    addsub_opcodes
                         .clone()
                         .map(|x| VmOpcode::from_usize(x + start_offset)),
                 )?;
+                if is_prime {
+                    let muldiv_chip = ModularMulDivChip::new(
+                        adapter_chip_64.clone(),
+                        config64.clone(),
+                        start_offset,
+                        range_checker.clone(),
+                        offline_memory.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::ModularMulDivRv32_64(muldiv_chip),
+                        muldiv_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + start_offset)),
+                    )?;
+                } else {
+                    let mul_chip = ModularMulChip::new(
+                        adapter_chip_64.clone(),
+                        config64.clone(),
+                        start_offset,
+                        range_checker.clone(),
+                        offline_memory.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::ModularMulRv32_64(mul_chip),
+                        mul_opcodes.map(|x| VmOpcode::from_usize(x + start_offset)),
+                    )?;
+                }
                 let isequal_chip = ModularIsEqualChip::new(
                     Rv32IsEqualModAdapterChip::new(
                         execution_bus,
@@ -207,7 +346,7 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                     offline_memory.clone(),
                 );
                 inventory.add_executor(
-                    ModularExtensionExecutor::ModularIsEqualRv32_48(isequal_chip),
+                    ModularExtensionExecutor::ModularIsEqualRv32_64(isequal_chip),
                     iseq_opcodes
                         .clone()
                         .map(|x| VmOpcode::from_usize(x + start_offset)),
@@ -219,4 +358,14 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        (0..self.supported_modulus.len()).fold(OpcodeNames::default(), |names, i| {
+            names.with_opcodes_at(
+                &format!("modular[{i}]"),
+                Rv32ModularArithmeticOpcode::CLASS_OFFSET + i * Rv32ModularArithmeticOpcode::COUNT,
+                Rv32ModularArithmeticOpcode::iter(),
+            )
+        })
+    }
 }
@@ -5,6 +5,7 @@ extern crate self as openvm_algebra_guest;
 pub const OPCODE: u8 = 0x2b;
 pub const MODULAR_ARITHMETIC_FUNCT3: u8 = 0b000;
 pub const COMPLEX_EXT_FIELD_FUNCT3: u8 = 0b010;
+pub const FIELD_EXPR_FUNCT3: u8 = 0b100;
 
 /// Modular arithmetic is configurable.
 /// The funct7 field equals `mod_idx * MODULAR_ARITHMETIC_MAX_KINDS + base_funct7`.
@@ -39,6 +40,11 @@ impl ComplexExtFieldBaseFunct7 {
     pub const COMPLEX_EXT_FIELD_MAX_KINDS: u8 = 8;
 }
 
+/// Field expression opcodes are configurable. The funct7 field equals
+/// `op_idx * FIELD_EXPR_MAX_KINDS + local_opcode`. The local opcodes of an operation are one per
+/// flag in order, or a single one if it has no flags, followed by its setup opcode.
+pub const FIELD_EXPR_MAX_KINDS: u8 = 8;
+
 /// Modular arithmetic traits for use with OpenVM intrinsics.
 extern crate alloc;
 
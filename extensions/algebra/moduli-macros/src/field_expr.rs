@@ -0,0 +1,294 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, BinOp, Expr, Ident, Lit, UnOp};
+
+/// One operation of `field_expr_declare!`.
+pub struct FieldExprOp {
+    pub name: Ident,
+    pub mod_type: Expr,
+    pub op_idx: usize,
+    pub inputs: Vec<Ident>,
+    pub outputs: Vec<(Expr, String)>,
+    pub flags: Vec<Ident>,
+}
+
+fn error<T>(tokens: impl Spanned, message: &str) -> syn::Result<T> {
+    Err(syn::Error::new(tokens.span(), message))
+}
+
+fn parse_ident(expr: &Expr) -> syn::Result<Ident> {
+    match expr {
+        Expr::Path(path) if path.path.get_ident().is_some() => {
+            Ok(path.path.get_ident().unwrap().clone())
+        }
+        _ => error(expr, "Expected an identifier"),
+    }
+}
+
+fn parse_array(expr: &Expr) -> syn::Result<Vec<&Expr>> {
+    match expr {
+        Expr::Array(array) => Ok(array.elems.iter().collect()),
+        _ => error(expr, "Expected an array"),
+    }
+}
+
+fn parse_u64(expr: &Expr) -> syn::Result<u64> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        _ => error(expr, "Expected an integer literal"),
+    }
+}
+
+impl FieldExprOp {
+    pub fn parse(item: openvm_macros_common::Item) -> syn::Result<Self> {
+        let mut mod_type = None;
+        let mut op_idx = None;
+        let mut inputs = None;
+        let mut outputs = None;
+        let mut flags = vec![];
+        for param in item.params {
+            match param.name.to_string().as_str() {
+                "mod_type" => mod_type = Some(param.value),
+                "op_idx" => op_idx = Some(parse_u64(&param.value)? as usize),
+                "inputs" => {
+                    inputs = Some(
+                        parse_array(&param.value)?
+                            .into_iter()
+                            .map(parse_ident)
+                            .collect::<syn::Result<Vec<_>>>()?,
+                    )
+                }
+                "outputs" => {
+                    let mut exprs = vec![];
+                    for output in parse_array(&param.value)? {
+                        let Expr::Lit(syn::ExprLit {
+                            lit: Lit::Str(s), ..
+                        }) = output
+                        else {
+                            return error(output, "Expected a string literal");
+                        };
+                        let expr = syn::parse_str::<Expr>(&s.value())
+                            .map_err(|e| syn::Error::new(s.span(), e))?;
+                        exprs.push((expr, s.value()));
+                    }
+                    outputs = Some(exprs);
+                }
+                "flags" => {
+                    flags = parse_array(&param.value)?
+                        .into_iter()
+                        .map(parse_ident)
+                        .collect::<syn::Result<Vec<_>>>()?
+                }
+                _ => return error(&param.name, "Unknown parameter"),
+            }
+        }
+        let name = item.name;
+        let missing = |param| syn::Error::new(name.span(), format!("{param} is required"));
+        Ok(Self {
+            mod_type: mod_type.ok_or_else(|| missing("mod_type"))?,
+            op_idx: op_idx.ok_or_else(|| missing("op_idx"))?,
+            inputs: inputs.ok_or_else(|| missing("inputs"))?,
+            outputs: outputs.ok_or_else(|| missing("outputs"))?,
+            flags,
+            name,
+        })
+    }
+
+    /// Number of field elements read from each of `rs1` and `rs2` and written to `rd`, which
+    /// must match `FieldExprOp::elements_per_pointer` in the circuit.
+    fn elements_per_pointer(&self) -> usize {
+        let elements = self.inputs.len().div_ceil(2).max(self.outputs.len());
+        elements.next_power_of_two()
+    }
+
+    /// Host implementation of an output, with the flag `flag` set.
+    fn eval_host(&self, expr: &Expr, flag: Option<&Ident>) -> syn::Result<TokenStream> {
+        let ty = &self.mod_type;
+        Ok(match expr {
+            Expr::Paren(paren) => self.eval_host(&paren.expr, flag)?,
+            Expr::Path(_) => {
+                let ident = parse_ident(expr)?;
+                if !self.inputs.contains(&ident) {
+                    return error(expr, "Unknown input");
+                }
+                quote! { #ident.clone() }
+            }
+            Expr::Lit(_) => {
+                let value = parse_u64(expr)?;
+                quote! { <#ty as openvm_algebra_guest::IntMod>::from_u64(#value) }
+            }
+            Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+                let a = self.eval_host(&unary.expr, flag)?;
+                quote! { -(#a) }
+            }
+            Expr::Binary(binary) => {
+                let a = self.eval_host(&binary.left, flag)?;
+                let b = self.eval_host(&binary.right, flag)?;
+                match binary.op {
+                    BinOp::Add(_) => quote! { (#a) + (#b) },
+                    BinOp::Sub(_) => quote! { (#a) - (#b) },
+                    BinOp::Mul(_) => quote! { (#a) * (#b) },
+                    BinOp::Div(_) => {
+                        quote! { openvm_algebra_guest::DivUnsafe::div_unsafe(#a, #b) }
+                    }
+                    _ => return error(expr, "Unsupported operator"),
+                }
+            }
+            Expr::Call(call) => {
+                let func = parse_ident(&call.func)?;
+                let args: Vec<_> = call.args.iter().collect();
+                match (func.to_string().as_str(), args.as_slice()) {
+                    ("pow", [base, exp]) => {
+                        let base = self.eval_host(base, flag)?;
+                        let exp = parse_u64(exp)?;
+                        quote! {{
+                            let base = #base;
+                            let mut acc = <#ty as openvm_algebra_guest::IntMod>::ONE;
+                            for i in (0..u64::BITS).rev() {
+                                acc = acc.clone() * &acc;
+                                if (#exp >> i) & 1 == 1 {
+                                    acc = acc * &base;
+                                }
+                            }
+                            acc
+                        }}
+                    }
+                    ("select", [selector, a, b]) => {
+                        let selector = parse_ident(selector)?;
+                        if !self.flags.contains(&selector) {
+                            return error(selector, "Unknown flag");
+                        }
+                        if flag == Some(&selector) {
+                            self.eval_host(a, flag)?
+                        } else {
+                            self.eval_host(b, flag)?
+                        }
+                    }
+                    _ => return error(expr, "Expected pow(x, n) or select(flag, a, b)"),
+                }
+            }
+            _ => return error(expr, "Unsupported expression"),
+        })
+    }
+
+    /// A function computing the outputs with the flag `flag` set, or the only function if the
+    /// operation has no flags.
+    fn function(
+        &self,
+        fn_name: &Ident,
+        flag: Option<&Ident>,
+        local_opcode: usize,
+    ) -> syn::Result<TokenStream> {
+        let ty = &self.mod_type;
+        let inputs = &self.inputs;
+        let num_outputs = self.outputs.len();
+        let elements = self.elements_per_pointer();
+        let op_idx = self.op_idx;
+        let host_outputs = self
+            .outputs
+            .iter()
+            .map(|(expr, _)| self.eval_host(expr, flag))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let input_indices = 0..inputs.len();
+        let outputs = (0..num_outputs).map(|i| format_ident!("output_{}", i));
+        let outputs_pattern = (0..elements).map(|i| {
+            if i < num_outputs {
+                let output = format_ident!("output_{}", i);
+                quote! { #output }
+            } else {
+                quote! { _ }
+            }
+        });
+        let doc = format!(
+            "Returns `[{}]`.",
+            self.outputs
+                .iter()
+                .map(|(_, s)| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Ok(quote! {
+            #[doc = #doc]
+            #[allow(unused_variables)]
+            pub fn #fn_name(#(#inputs: &#ty),*) -> [#ty; #num_outputs] {
+                #[cfg(not(target_os = "zkvm"))]
+                {
+                    [#(#host_outputs),*]
+                }
+                #[cfg(target_os = "zkvm")]
+                {
+                    // The first half of the inputs is read from rs1 and the second half from rs2.
+                    let mut buf: [#ty; 2 * #elements] =
+                        core::array::from_fn(|_| <#ty as openvm_algebra_guest::IntMod>::ZERO);
+                    #(buf[#input_indices] = #inputs.clone();)*
+                    let mut uninit: core::mem::MaybeUninit<[#ty; #elements]> =
+                        core::mem::MaybeUninit::uninit();
+                    openvm::platform::custom_insn_r!(
+                        opcode = ::openvm_algebra_guest::OPCODE,
+                        funct3 = ::openvm_algebra_guest::FIELD_EXPR_FUNCT3 as usize,
+                        funct7 = #op_idx * (::openvm_algebra_guest::FIELD_EXPR_MAX_KINDS as usize) + #local_opcode,
+                        rd = In uninit.as_mut_ptr(),
+                        rs1 = In buf.as_ptr(),
+                        rs2 = In buf[#elements..].as_ptr()
+                    );
+                    let [#(#outputs_pattern),*] = unsafe { uninit.assume_init() };
+                    [#(#outputs),*]
+                }
+            }
+        })
+    }
+
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let name = &self.name;
+        let ty = &self.mod_type;
+        let elements = self.elements_per_pointer();
+        let op_idx = self.op_idx;
+        let functions = if self.flags.is_empty() {
+            vec![self.function(&format_ident!("eval"), None, 0)?]
+        } else {
+            self.flags
+                .iter()
+                .enumerate()
+                .map(|(i, flag)| self.function(flag, Some(flag), i))
+                .collect::<syn::Result<Vec<_>>>()?
+        };
+        let setup_opcode = self.flags.len().max(1);
+        let setup_doc = format!(
+            "Sets up the chip of `{}`. Must be called before any other function of `{}`.",
+            name, name
+        );
+        Ok(quote! {
+            pub struct #name;
+
+            #[allow(non_snake_case)]
+            impl #name {
+                #(#functions)*
+
+                #[doc = #setup_doc]
+                pub fn setup() {
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        // The setup reads the modulus as first input, and 1 as the other inputs.
+                        let mut buf: [#ty; 2 * #elements] =
+                            core::array::from_fn(|_| <#ty as openvm_algebra_guest::IntMod>::ONE);
+                        buf[0] = <#ty as openvm_algebra_guest::IntMod>::from_repr(
+                            <#ty as openvm_algebra_guest::IntMod>::MODULUS,
+                        );
+                        let mut uninit: core::mem::MaybeUninit<[#ty; #elements]> =
+                            core::mem::MaybeUninit::uninit();
+                        openvm::platform::custom_insn_r!(
+                            opcode = ::openvm_algebra_guest::OPCODE,
+                            funct3 = ::openvm_algebra_guest::FIELD_EXPR_FUNCT3 as usize,
+                            funct7 = #op_idx * (::openvm_algebra_guest::FIELD_EXPR_MAX_KINDS as usize) + #setup_opcode,
+                            rd = In uninit.as_mut_ptr(),
+                            rs1 = In buf.as_ptr(),
+                            rs2 = In buf[#elements..].as_ptr()
+                        );
+                    }
+                }
+            }
+        })
+    }
+}
@@ -12,6 +12,8 @@ use syn::{
     parse_macro_input, LitStr, Token,
 };
 
+mod field_expr;
+
 static MOD_IDX: AtomicUsize = AtomicUsize::new(0);
 
 /// This macro generates the code to setup the modulus for a given prime. Also it places the moduli into a special static variable to be later extracted from the ELF and used by the VM.
@@ -23,6 +25,17 @@ static MOD_IDX: AtomicUsize = AtomicUsize::new(0);
 /// }
 /// ```
 /// This creates two structs, `Bls12381` and `Bn254`, each representing the modular arithmetic class (implementing `Add`, `Sub` and so on).
+///
+/// A modulus that is not prime, such as an RSA modulus, must be declared with `is_prime = false`:
+/// ```
+/// moduli_declare! {
+///     Rsa512 { modulus = "0xc2f16787881cf9e6ab9ec96d66663a1927e671a42098099abd6418a58542195c6763b3a1404db1c23757c6b21a1cdd2b7bb72b4fc7c3878027f715bc67026c8d", is_prime = false },
+/// }
+/// ```
+/// Division panics for such a struct, since the VM does not support it for composite moduli.
+///
+/// A modulus can have at most 64 bytes (512 bits), so larger moduli such as 2048-bit RSA moduli
+/// are not supported.
 #[proc_macro]
 pub fn moduli_declare(input: TokenStream) -> TokenStream {
     let MacroArgs { items } = parse_macro_input!(input as MacroArgs);
@@ -35,6 +48,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
         let struct_name = item.name.to_string();
         let struct_name = syn::Ident::new(&struct_name, span.into());
         let mut modulus: Option<String> = None;
+        let mut is_prime = true;
         for param in item.params {
             match param.name.to_string().as_str() {
                 "modulus" => {
@@ -50,6 +64,19 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                             .into();
                     }
                 }
+                "is_prime" => {
+                    if let syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Bool(value),
+                        ..
+                    }) = param.value
+                    {
+                        is_prime = value.value;
+                    } else {
+                        return syn::Error::new_spanned(param.value, "Expected a bool literal")
+                            .to_compile_error()
+                            .into();
+                    }
+                }
                 _ => {
                     panic!("Unknown parameter {}", param.name);
                 }
@@ -70,8 +97,10 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
         } else if limbs <= 48 {
             limbs = 48;
             block_size = 16;
+        } else if limbs <= 64 {
+            limbs = 64;
         } else {
-            panic!("limbs must be at most 48");
+            panic!("limbs must be at most 64");
         }
 
         let modulus_bytes = modulus_bytes
@@ -105,6 +134,70 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
 
         let module_name = format_ident!("algebra_impl_{}", mod_idx);
 
+        // Division is only supported by the VM for prime moduli. For a composite modulus we still
+        // implement the division traits, since `IntMod` requires them, but they always panic.
+        let (div_extern_decl, div_assign_unsafe_body, div_unsafe_refs_body) = if is_prime {
+            (
+                quote::quote! {
+                    fn #div_extern_func(rd: usize, rs1: usize, rs2: usize);
+                },
+                quote::quote! {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let modulus = Self::modulus_biguint();
+                        let inv = other.as_biguint().modinv(&modulus).unwrap();
+                        *self = Self::from_biguint((self.as_biguint() * inv) % modulus);
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        unsafe {
+                            #div_extern_func(
+                                self as *mut Self as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize,
+                            );
+                        }
+                    }
+                },
+                quote::quote! {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let modulus = Self::modulus_biguint();
+                        let inv = other.as_biguint().modinv(&modulus).unwrap();
+                        Self::from_biguint((self.as_biguint() * inv) % modulus)
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        let mut uninit: core::mem::MaybeUninit<#struct_name> = core::mem::MaybeUninit::uninit();
+                        unsafe {
+                            #div_extern_func(
+                                uninit.as_mut_ptr() as usize,
+                                self as *const #struct_name as usize,
+                                other as *const #struct_name as usize,
+                            );
+                        }
+                        unsafe { uninit.assume_init() }
+                    }
+                },
+            )
+        } else {
+            let message = format!(
+                "division is not supported for composite modulus {}",
+                struct_name
+            );
+            (
+                quote::quote! {},
+                quote::quote! {
+                    let _ = other;
+                    panic!(#message);
+                },
+                quote::quote! {
+                    let _ = other;
+                    panic!(#message);
+                },
+            )
+        };
+
         let result = TokenStream::from(quote::quote_spanned! { span.into() =>
             #[derive(Clone, Eq, serde::Serialize, serde::Deserialize)]
             #[repr(C, align(#block_size))]
@@ -114,7 +207,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 fn #add_extern_func(rd: usize, rs1: usize, rs2: usize);
                 fn #sub_extern_func(rd: usize, rs1: usize, rs2: usize);
                 fn #mul_extern_func(rd: usize, rs1: usize, rs2: usize);
-                fn #div_extern_func(rd: usize, rs1: usize, rs2: usize);
+                #div_extern_decl
                 fn #is_eq_extern_func(rs1: usize, rs2: usize) -> bool;
             }
 
@@ -193,22 +286,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
 
                 #[inline(always)]
                 fn div_assign_unsafe_impl(&mut self, other: &Self) {
-                    #[cfg(not(target_os = "zkvm"))]
-                    {
-                        let modulus = Self::modulus_biguint();
-                        let inv = other.as_biguint().modinv(&modulus).unwrap();
-                        *self = Self::from_biguint((self.as_biguint() * inv) % modulus);
-                    }
-                    #[cfg(target_os = "zkvm")]
-                    {
-                        unsafe {
-                            #div_extern_func(
-                                self as *mut Self as usize,
-                                self as *const Self as usize,
-                                other as *const Self as usize,
-                            );
-                        }
-                    }
+                    #div_assign_unsafe_body
                 }
 
                 /// SAFETY: `dst_ptr` must be a raw pointer to `&mut Self`.
@@ -285,24 +363,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
 
                 #[inline(always)]
                 fn div_unsafe_refs_impl(&self, other: &Self) -> Self {
-                    #[cfg(not(target_os = "zkvm"))]
-                    {
-                        let modulus = Self::modulus_biguint();
-                        let inv = other.as_biguint().modinv(&modulus).unwrap();
-                        Self::from_biguint((self.as_biguint() * inv) % modulus)
-                    }
-                    #[cfg(target_os = "zkvm")]
-                    {
-                        let mut uninit: core::mem::MaybeUninit<#struct_name> = core::mem::MaybeUninit::uninit();
-                        unsafe {
-                            #div_extern_func(
-                                uninit.as_mut_ptr() as usize,
-                                self as *const #struct_name as usize,
-                                other as *const #struct_name as usize,
-                            );
-                        }
-                        unsafe { uninit.assume_init() }
-                    }
+                    #div_unsafe_refs_body
                 }
 
                 #[inline(always)]
@@ -681,6 +742,43 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
     TokenStream::from_iter(output)
 }
 
+/// This macro generates the functions calling the operations of a `FieldExprExtension`. The
+/// parameters of each operation must match its configuration in the VM, and `op_idx` is its index
+/// there.
+/// Usage:
+/// ```
+/// field_expr_declare! {
+///     MulAdd {
+///         mod_type = Bn254Fp,
+///         op_idx = 0,
+///         inputs = [a, b, c],
+///         outputs = ["a * b + c"],
+///     },
+///     AddSub {
+///         mod_type = Bn254Fp,
+///         op_idx = 1,
+///         inputs = [x, y],
+///         outputs = ["select(is_add, x + y, x - y)"],
+///         flags = [is_add, is_sub],
+///     },
+/// }
+/// ```
+/// This creates a struct `MulAdd` with a function `MulAdd::eval(a, b, c) -> [Bn254Fp; 1]`, and a
+/// struct `AddSub` with one function per flag, `AddSub::is_add(x, y)` and `AddSub::is_sub(x, y)`.
+/// Each struct also gets a `setup` function, which must be called before the others.
+#[proc_macro]
+pub fn field_expr_declare(input: TokenStream) -> TokenStream {
+    let MacroArgs { items } = parse_macro_input!(input as MacroArgs);
+    let mut output = Vec::new();
+    for item in items {
+        match field_expr::FieldExprOp::parse(item).and_then(|op| op.expand()) {
+            Ok(tokens) => output.push(tokens),
+            Err(e) => return e.to_compile_error().into(),
+        }
+    }
+    TokenStream::from(quote::quote! { #(#output)* })
+}
+
 struct ModuliDefine {
     items: Vec<LitStr>,
 }
@@ -721,8 +819,10 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
             limbs = 32;
         } else if limbs <= 48 {
             limbs = 48;
+        } else if limbs <= 64 {
+            limbs = 64;
         } else {
-            panic!("limbs must be at most 48");
+            panic!("limbs must be at most 64");
         }
 
         let modulus_bytes = modulus_bytes
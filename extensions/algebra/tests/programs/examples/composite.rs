@@ -0,0 +1,50 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+use openvm_algebra_guest::IntMod;
+
+openvm::entry!(main);
+
+// A 512-bit RSA modulus, which needs 64 limbs.
+openvm_algebra_moduli_macros::moduli_declare! {
+    Rsa512 {
+        modulus = "0xc2f16787881cf9e6ab9ec96d66663a1927e671a42098099abd6418a58542195c6763b3a1404db1c23757c6b21a1cdd2b7bb72b4fc7c3878027f715bc67026c8d",
+        is_prime = false,
+    },
+}
+
+openvm_algebra_moduli_macros::moduli_init!(
+    "0xc2f16787881cf9e6ab9ec96d66663a1927e671a42098099abd6418a58542195c6763b3a1404db1c23757c6b21a1cdd2b7bb72b4fc7c3878027f715bc67026c8d"
+);
+
+pub fn main() {
+    setup_all_moduli();
+
+    let minus_one = Rsa512::ZERO - &Rsa512::ONE;
+    assert_eq!(minus_one.clone() * &minus_one, Rsa512::ONE);
+    assert_eq!(minus_one.clone() + &Rsa512::ONE, Rsa512::ZERO);
+
+    let a = Rsa512::from_u32(1 << 20);
+    let b = Rsa512::from_u32(3 << 20);
+    assert_eq!(a.clone() * &b, Rsa512::from_u32(3) * &a * &a);
+    assert_eq!(b.clone() - &a, a.double());
+
+    // s^65537 mod n
+    let s = Rsa512::from_be_bytes(&hex!(
+        "21c8d3d107c667e325eb2b0ebd5f73bbaf36131e0dce9f7072874bc3af8d2911"
+        "eb2a48a85fed58349712d4cedf89bfcf342d9f4c04b7d652d11d3d5f584eb0d4"
+    ));
+    s.assert_unique();
+    let mut m = s.clone();
+    for _ in 0..16 {
+        m.square_assign();
+    }
+    m *= &s;
+    let expected = Rsa512::from_be_bytes(&hex!(
+        "0001ffffffffffffffffffff003031300d060960864801650304020105000420"
+        "02408f199f0e97ee929b645fe66e6472be385262c2ad2a7f6d4a2e391033a27b"
+    ));
+    assert_eq!(m, expected);
+    assert_ne!(m, expected + &Rsa512::ONE);
+}
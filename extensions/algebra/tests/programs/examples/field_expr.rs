@@ -0,0 +1,42 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm_algebra_guest::{moduli_macros::*, IntMod};
+
+openvm::entry!(main);
+
+moduli_declare! {
+    Secp256k1Coord { modulus = "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE FFFFFC2F" },
+}
+
+moduli_init! {
+    "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE FFFFFC2F",
+}
+
+field_expr_declare! {
+    MulAdd { mod_type = Secp256k1Coord, op_idx = 0, inputs = [a, b, c], outputs = ["a * b + c"] },
+    AddSub {
+        mod_type = Secp256k1Coord,
+        op_idx = 1,
+        inputs = [x, y],
+        outputs = ["select(is_add, x + y, x - y)"],
+        flags = [is_add, is_sub],
+    },
+}
+
+pub fn main() {
+    setup_all_moduli();
+    MulAdd::setup();
+    AddSub::setup();
+
+    let a = Secp256k1Coord::from_u32(1234);
+    let b = Secp256k1Coord::ZERO - &Secp256k1Coord::from_u32(5678);
+    let c = Secp256k1Coord::from_u32(91011);
+    let [res] = MulAdd::eval(&a, &b, &c);
+    assert_eq!(res, a.clone() * &b + &c);
+
+    let [sum] = AddSub::is_add(&a, &b);
+    assert_eq!(sum, a.clone() + &b);
+    let [diff] = AddSub::is_sub(&a, &b);
+    assert_eq!(diff, a.clone() - &b);
+}
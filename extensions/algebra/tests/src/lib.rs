@@ -5,7 +5,8 @@ mod tests {
     use eyre::Result;
     use num_bigint::BigUint;
     use openvm_algebra_circuit::{
-        Fp2Extension, ModularExtension, Rv32ModularConfig, Rv32ModularWithFp2Config,
+        FieldExprExtension, FieldExprOp, Fp2Extension, ModularExtension, Rv32ModularConfig,
+        Rv32ModularWithFp2Config,
     };
     use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
     use openvm_circuit::{arch::SystemConfig, utils::air_test};
@@ -14,6 +15,7 @@ mod tests {
     use openvm_rv32im_transpiler::{
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
     };
+    use openvm_sdk::config::SdkVmConfig;
     use openvm_stark_sdk::p3_baby_bear::BabyBear;
     use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
     use openvm_transpiler::{transpiler::Transpiler, FromElf};
@@ -55,6 +57,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_composite_modulus() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "composite")?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        let modulus = BigUint::parse_bytes(b"c2f16787881cf9e6ab9ec96d66663a1927e671a42098099abd6418a58542195c6763b3a1404db1c23757c6b21a1cdd2b7bb72b4fc7c3878027f715bc67026c8d", 16).unwrap();
+        let mut config = Rv32ModularConfig::new(vec![modulus.clone()]);
+        config.modular = config.modular.with_composite_modulus(modulus);
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_field_expr() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "field_expr")?;
+        let modulus = SECP256K1_CONFIG.modulus.clone();
+        let names = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let ops = vec![
+            FieldExprOp::new(
+                "MulAdd".to_string(),
+                modulus.clone(),
+                names(&["a", "b", "c"]),
+                names(&["a * b + c"]),
+            ),
+            FieldExprOp::new(
+                "AddSub".to_string(),
+                modulus.clone(),
+                names(&["x", "y"]),
+                names(&["select(is_add, x + y, x - y)"]),
+            )
+            .with_flags(names(&["is_add", "is_sub"])),
+        ];
+        let config = SdkVmConfig::builder()
+            .system(SystemConfig::default().with_continuations().into())
+            .rv32i(Default::default())
+            .rv32m(Default::default())
+            .io(Default::default())
+            .modular(ModularExtension::new(vec![modulus]))
+            .field_expr(FieldExprExtension::new(ops))
+            .build();
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     fn test_complex_two_moduli() -> Result<()> {
         let elf = build_example_program_at_path(get_programs_dir!(), "complex-two-modulos")?;
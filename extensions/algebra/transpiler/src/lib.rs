@@ -1,6 +1,6 @@
 use openvm_algebra_guest::{
-    ComplexExtFieldBaseFunct7, ModArithBaseFunct7, COMPLEX_EXT_FIELD_FUNCT3,
-    MODULAR_ARITHMETIC_FUNCT3, OPCODE,
+    ComplexExtFieldBaseFunct7, ModArithBaseFunct7, COMPLEX_EXT_FIELD_FUNCT3, FIELD_EXPR_FUNCT3,
+    FIELD_EXPR_MAX_KINDS, MODULAR_ARITHMETIC_FUNCT3, OPCODE,
 };
 use openvm_instructions::{
     instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, LocalOpcode, VmOpcode,
@@ -43,12 +43,27 @@ pub enum Fp2Opcode {
     SETUP_MULDIV,
 }
 
+/// Global opcode of the first local opcode of the first field expression operation. Operation
+/// `op_idx` owns the opcodes starting at `FIELD_EXPR_OPCODE_OFFSET + op_idx * FIELD_EXPR_MAX_KINDS`.
+pub const FIELD_EXPR_OPCODE_OFFSET: usize = 0x800;
+
+/// Returns the global opcode of local opcode `local_opcode` of field expression operation `op_idx`.
+pub fn field_expr_opcode(op_idx: usize, local_opcode: usize) -> VmOpcode {
+    assert!(local_opcode < FIELD_EXPR_MAX_KINDS as usize);
+    VmOpcode::from_usize(
+        FIELD_EXPR_OPCODE_OFFSET + op_idx * FIELD_EXPR_MAX_KINDS as usize + local_opcode,
+    )
+}
+
 #[derive(Default)]
 pub struct ModularTranspilerExtension;
 
 #[derive(Default)]
 pub struct Fp2TranspilerExtension;
 
+#[derive(Default)]
+pub struct FieldExprTranspilerExtension;
+
 impl<F: PrimeField32> TranspilerExtension<F> for ModularTranspilerExtension {
     fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
         if instruction_stream.is_empty() {
@@ -205,3 +220,32 @@ impl<F: PrimeField32> TranspilerExtension<F> for Fp2TranspilerExtension {
         instruction.map(TranspilerOutput::one_to_one)
     }
 }
+
+impl<F: PrimeField32> TranspilerExtension<F> for FieldExprTranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        if instruction_stream.is_empty() {
+            return None;
+        }
+        let instruction_u32 = instruction_stream[0];
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+
+        if opcode != OPCODE {
+            return None;
+        }
+        if funct3 != FIELD_EXPR_FUNCT3 {
+            return None;
+        }
+
+        let dec_insn = RType::new(instruction_u32);
+        let op_idx = dec_insn.funct7 as usize / FIELD_EXPR_MAX_KINDS as usize;
+        let local_opcode = dec_insn.funct7 as usize % FIELD_EXPR_MAX_KINDS as usize;
+        let global_opcode = field_expr_opcode(op_idx, local_opcode).as_usize();
+        Some(TranspilerOutput::one_to_one(from_r_type(
+            global_opcode,
+            2,
+            &dec_insn,
+            true,
+        )))
+    }
+}
@@ -16,7 +16,7 @@ use openvm_circuit_primitives::{
     range_tuple::{RangeTupleCheckerBus, SharedRangeTupleCheckerChip},
 };
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
-use openvm_instructions::{program::DEFAULT_PC_STEP, LocalOpcode};
+use openvm_instructions::{opcode_names::OpcodeNames, program::DEFAULT_PC_STEP, LocalOpcode};
 use openvm_rv32im_circuit::{
     Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
     Rv32MExecutor, Rv32MPeriphery,
@@ -238,4 +238,38 @@ impl<F: PrimeField32> VmExtension<F> for Int256 {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default()
+            .with_opcodes_at(
+                "int256",
+                Rv32BaseAlu256Opcode::CLASS_OFFSET,
+                Rv32BaseAlu256Opcode::iter().map(|opcode| opcode.0),
+            )
+            .with_opcodes_at(
+                "int256",
+                Rv32LessThan256Opcode::CLASS_OFFSET,
+                Rv32LessThan256Opcode::iter().map(|opcode| opcode.0),
+            )
+            .with_opcodes_at(
+                "int256",
+                Rv32BranchEqual256Opcode::CLASS_OFFSET,
+                Rv32BranchEqual256Opcode::iter().map(|opcode| opcode.0),
+            )
+            .with_opcodes_at(
+                "int256",
+                Rv32BranchLessThan256Opcode::CLASS_OFFSET,
+                Rv32BranchLessThan256Opcode::iter().map(|opcode| opcode.0),
+            )
+            .with_opcodes_at(
+                "int256",
+                Rv32Mul256Opcode::CLASS_OFFSET,
+                Rv32Mul256Opcode::iter().map(|opcode| opcode.0),
+            )
+            .with_opcodes_at(
+                "int256",
+                Rv32Shift256Opcode::CLASS_OFFSET,
+                Rv32Shift256Opcode::iter().map(|opcode| opcode.0),
+            )
+    }
 }
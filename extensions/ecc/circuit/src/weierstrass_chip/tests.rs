@@ -84,6 +84,7 @@ fn test_add_ne() {
         modulus: secp256k1_coord_prime(),
         num_limbs: NUM_LIMBS,
         limb_bits: LIMB_BITS,
+        is_prime: true,
     };
     let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
     let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
@@ -156,6 +157,7 @@ fn test_double() {
         modulus: secp256k1_coord_prime(),
         num_limbs: NUM_LIMBS,
         limb_bits: LIMB_BITS,
+        is_prime: true,
     };
     let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
     let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
@@ -218,6 +220,7 @@ fn test_p256_double() {
         modulus: secp256r1_coord_prime(),
         num_limbs: NUM_LIMBS,
         limb_bits: LIMB_BITS,
+        is_prime: true,
     };
     let a = BigUint::from_str_radix(
         "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
@@ -17,13 +17,13 @@ use openvm_ecc_guest::{
     p256::{CURVE_A as P256_A, CURVE_B as P256_B, P256_MODULUS, P256_ORDER},
 };
 use openvm_ecc_transpiler::{EccPhantom, Rv32WeierstrassOpcode};
-use openvm_instructions::{LocalOpcode, PhantomDiscriminant, VmOpcode};
+use openvm_instructions::{opcode_names::OpcodeNames, LocalOpcode, PhantomDiscriminant, VmOpcode};
 use openvm_mod_circuit_builder::ExprBuilderConfig;
 use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
 use openvm_stark_backend::p3_field::PrimeField32;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
-use strum::EnumCount;
+use strum::{EnumCount, IntoEnumIterator};
 
 use super::{EcAddNeChip, EcDoubleChip};
 
@@ -120,11 +120,13 @@ impl<F: PrimeField32> VmExtension<F> for WeierstrassExtension {
                 modulus: curve.modulus.clone(),
                 num_limbs: 32,
                 limb_bits: 8,
+                is_prime: true,
             };
             let config48 = ExprBuilderConfig {
                 modulus: curve.modulus.clone(),
                 num_limbs: 48,
                 limb_bits: 8,
+                is_prime: true,
             };
             if bytes <= 32 {
                 let add_ne_chip = EcAddNeChip::new(
@@ -222,6 +224,16 @@ impl<F: PrimeField32> VmExtension<F> for WeierstrassExtension {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        (0..self.supported_curves.len()).fold(OpcodeNames::default(), |names, i| {
+            names.with_opcodes_at(
+                &format!("ecc[{i}]"),
+                Rv32WeierstrassOpcode::CLASS_OFFSET + i * Rv32WeierstrassOpcode::COUNT,
+                Rv32WeierstrassOpcode::iter(),
+            )
+        })
+    }
 }
 
 pub(crate) mod phantom {
@@ -104,4 +104,8 @@ impl<F: PrimeField32> VmExtension<F> for Keccak256 {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> opcode_names::OpcodeNames {
+        opcode_names::OpcodeNames::default().with_opcodes("", Rv32KeccakOpcode::iter())
+    }
 }
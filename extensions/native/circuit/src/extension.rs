@@ -13,7 +13,9 @@ use openvm_circuit::{
 };
 use openvm_circuit_derive::{AnyEnum, InstructionExecutor, VmConfig};
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
-use openvm_instructions::{program::DEFAULT_PC_STEP, LocalOpcode, PhantomDiscriminant};
+use openvm_instructions::{
+    opcode_names::OpcodeNames, program::DEFAULT_PC_STEP, LocalOpcode, PhantomDiscriminant,
+};
 use openvm_native_compiler::{
     CastfOpcode, FieldArithmeticOpcode, FieldExtensionOpcode, FriOpcode, NativeBranchEqualOpcode,
     NativeJalOpcode, NativeLoadStore4Opcode, NativeLoadStoreOpcode, NativePhantom,
@@ -244,6 +246,28 @@ impl<F: PrimeField32> VmExtension<F> for Native {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default()
+            .with_opcodes("native", NativeLoadStoreOpcode::iter())
+            .with_opcodes_at(
+                "native4",
+                NativeLoadStore4Opcode::CLASS_OFFSET,
+                NativeLoadStore4Opcode::iter().map(|opcode| opcode.0),
+            )
+            .with_opcodes_at(
+                "native",
+                NativeBranchEqualOpcode::CLASS_OFFSET,
+                NativeBranchEqualOpcode::iter().map(|opcode| opcode.0),
+            )
+            .with_opcodes("native", NativeJalOpcode::iter())
+            .with_opcodes("native", NativeRangeCheckOpcode::iter())
+            .with_opcodes("native", FieldArithmeticOpcode::iter())
+            .with_opcodes("native", FieldExtensionOpcode::iter())
+            .with_opcodes("native", FriOpcode::iter())
+            .with_opcodes("native", VerifyBatchOpcode::iter())
+            .with_opcodes("native", Poseidon2Opcode::iter())
+    }
 }
 
 pub(crate) mod phantom {
@@ -417,6 +441,10 @@ impl<F: PrimeField32> VmExtension<F> for CastFExtension {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default().with_opcodes("native", CastfOpcode::iter())
+    }
 }
 
 #[derive(Clone, Debug, VmConfig, derive_new::new, Serialize, Deserialize)]
@@ -1,17 +1,63 @@
 use openvm_circuit::arch::{Streams, SystemConfig, VmExecutor};
 use openvm_instructions::program::Program;
+use openvm_native_compiler::{
+    asm::AsmBuilder,
+    conversion::{CompilerOptions, AS},
+    interpreter::Interpreter,
+    optimizer::optimize,
+};
+use openvm_stark_backend::p3_field::{extension::BinomialExtensionField, FieldAlgebra};
 use openvm_stark_sdk::p3_baby_bear::BabyBear;
 
 use crate::{Native, NativeConfig};
 
 pub fn execute_program(program: Program<BabyBear>, input_stream: impl Into<Streams<BabyBear>>) {
+    test_executor().execute(program, input_stream).unwrap();
+}
+
+/// Executes the program of `builder` both on the DSL [Interpreter] and, compiled with `options`,
+/// on the native VM. Panics if either execution fails or if a memory cell written by the
+/// interpreter has a different final value in the VM.
+pub fn execute_program_differential(
+    builder: AsmBuilder<BabyBear, BinomialExtensionField<BabyBear, 4>>,
+    options: CompilerOptions,
+    input_stream: impl Into<Streams<BabyBear>>,
+) {
+    let input_stream = input_stream.into();
+    // Interpret the operations the VM executes, which the optimizer may have rewritten.
+    let operations = if options.enable_optimizations {
+        optimize(builder.operations.clone())
+    } else {
+        builder.operations.clone()
+    };
+    let mut interpreter = Interpreter::new(options).with_input_stream(input_stream.clone());
+    interpreter.run(&operations).unwrap();
+
+    let program = builder.compile_isa_with_options(options);
+    let final_memory = test_executor()
+        .execute(program, input_stream)
+        .unwrap()
+        .expect("final memory is set after execution");
+    let mismatches = interpreter.diff_memory(|address| {
+        final_memory
+            .get(&(AS::Native as u32, address))
+            .copied()
+            .unwrap_or(BabyBear::ZERO)
+    });
+    assert!(
+        mismatches.is_empty(),
+        "interpreter and VM disagree on {} memory cells, first: {:?}",
+        mismatches.len(),
+        mismatches[0]
+    );
+}
+
+fn test_executor() -> VmExecutor<BabyBear, NativeConfig> {
     let system_config = SystemConfig::default()
         .with_public_values(4)
         .with_max_segment_len((1 << 25) - 100);
     let config = NativeConfig::new(system_config, Native);
-    let executor = VmExecutor::<BabyBear, NativeConfig>::new(config);
-
-    executor.execute(program, input_stream).unwrap();
+    VmExecutor::<BabyBear, NativeConfig>::new(config)
 }
 
 pub(crate) const fn const_max(a: usize, b: usize) -> usize {
@@ -4,6 +4,7 @@ use openvm_stark_backend::p3_field::{ExtensionField, PrimeField32, TwoAdicField}
 use super::{config::AsmConfig, AsmCompiler};
 use crate::{
     conversion::{convert_program, CompilerOptions},
+    optimizer::optimize,
     prelude::Builder,
 };
 
@@ -16,8 +17,13 @@ impl<F: PrimeField32 + TwoAdicField, EF: ExtensionField<F> + TwoAdicField> AsmBu
     }
 
     pub fn compile_isa_with_options(self, options: CompilerOptions) -> Program<F> {
+        let operations = if options.enable_optimizations {
+            optimize(self.operations)
+        } else {
+            self.operations
+        };
         let mut compiler = AsmCompiler::new(options.word_size);
-        compiler.build(self.operations);
+        compiler.build(operations);
         let asm_code = compiler.code();
         convert_program(asm_code, options)
     }
@@ -33,10 +33,23 @@ pub(crate) const STACK_TOP: i32 = HEAP_START_ADDRESS - 64;
 pub struct AsmCompiler<F, EF> {
     basic_blocks: Vec<BasicBlock<F, EF>>,
     function_labels: BTreeMap<String, F>,
+    functions: BTreeMap<String, AsmFunction<F>>,
     trap_label: F,
     word_size: usize,
 }
 
+/// A function defined by [DslIr::FnDef].
+struct AsmFunction<F> {
+    /// Label of the first block of the body.
+    label: F,
+    /// Address where calls write their return address.
+    return_address: i32,
+    /// Block after the body, which jumps back to the caller.
+    return_block: F,
+    /// Labels of the blocks following each call.
+    return_labels: Vec<F>,
+}
+
 impl<F> Var<F> {
     /// Gets the frame pointer for a var.
     pub const fn fp(&self) -> i32 {
@@ -80,6 +93,7 @@ impl<F: PrimeField32 + TwoAdicField, EF: ExtensionField<F> + TwoAdicField> AsmCo
         Self {
             basic_blocks: vec![BasicBlock::new()],
             function_labels: BTreeMap::new(),
+            functions: BTreeMap::new(),
             trap_label: F::ONE,
             word_size,
         }
@@ -367,6 +381,44 @@ impl<F: PrimeField32 + TwoAdicField, EF: ExtensionField<F> + TwoAdicField> AsmCo
                     };
                     zip_for_compiler.for_each(move |_, builder| builder.build(block), debug_info);
                 }
+                DslIr::FnDef(name, return_address, block) => {
+                    // The body is emitted in place, so the code before it jumps over it.
+                    let def_label = self.block_label();
+                    self.basic_block();
+                    let label = self.block_label();
+                    self.function_labels.insert(name.clone(), label);
+                    self.build(block);
+                    self.basic_block();
+                    let return_block = self.block_label();
+                    self.basic_block();
+                    let after_label = self.block_label();
+                    self.push_to_block(def_label, AsmInstruction::j(after_label), debug_info);
+                    self.functions.insert(
+                        name,
+                        AsmFunction {
+                            label,
+                            return_address: return_address.fp(),
+                            return_block,
+                            return_labels: vec![],
+                        },
+                    );
+                }
+                DslIr::Call(name) => {
+                    let function = self
+                        .functions
+                        .get(&name)
+                        .unwrap_or_else(|| panic!("call to undefined function {name}"));
+                    let (label, return_address) = (function.label, function.return_address);
+                    // The call writes the pc of the next instruction, which starts a new block.
+                    self.push(AsmInstruction::Jump(return_address, label), debug_info);
+                    self.basic_block();
+                    let return_label = self.block_label();
+                    self.functions
+                        .get_mut(&name)
+                        .unwrap()
+                        .return_labels
+                        .push(return_label);
+                }
                 DslIr::AssertEqV(lhs, rhs) => {
                     // If lhs != rhs, execute TRAP
                     self.assert(lhs.fp(), ValueOrConst::Val(rhs.fp()), false, debug_info)
@@ -704,7 +756,25 @@ impl<F: PrimeField32 + TwoAdicField, EF: ExtensionField<F> + TwoAdicField> AsmCo
         if_compiler.then_label(trap_label, debug_info);
     }
 
-    pub fn code(self) -> AssemblyCode<F, EF> {
+    pub fn code(mut self) -> AssemblyCode<F, EF> {
+        // Now that all calls are known, emit the returns: compare the return address with the
+        // return label of each call.
+        for (name, function) in core::mem::take(&mut self.functions) {
+            let debug_info = Some(DebugInfo {
+                dsl_instruction: format!("Return({name})"),
+                trace: None,
+            });
+            if let Some((last, others)) = function.return_labels.split_last() {
+                for &label in others {
+                    self.push_to_block(
+                        function.return_block,
+                        AsmInstruction::BeqLabel(label, function.return_address),
+                        debug_info.clone(),
+                    );
+                }
+                self.push_to_block(function.return_block, AsmInstruction::j(*last), debug_info);
+            }
+        }
         let labels = self
             .function_labels
             .into_iter()
@@ -1057,10 +1127,18 @@ impl<F: PrimeField32 + TwoAdicField, EF: ExtensionField<F> + TwoAdicField> AsmCo
     ) {
         let rhs = rhs.as_base_slice();
 
-        self.push(
-            AsmInstruction::CopyF(dst.fp(), lhs.fp()),
-            debug_info.clone(),
-        );
+        // A zero base coefficient is a plain copy, which keeps existing programs unchanged.
+        if rhs[0] == F::ZERO {
+            self.push(
+                AsmInstruction::CopyF(dst.fp(), lhs.fp()),
+                debug_info.clone(),
+            );
+        } else {
+            self.push(
+                AsmInstruction::AddFI(dst.fp(), lhs.fp(), rhs[0]),
+                debug_info.clone(),
+            );
+        }
 
         for i in 1..EF::D {
             let j = i as i32;
@@ -90,6 +90,9 @@ pub enum AsmInstruction<F, EF> {
     /// Branch equal immediate.
     BeqI(F, i32, F),
 
+    /// Branch to label if mem[lhs] is the pc of label, as written by a `Jump` to return to label.
+    BeqLabel(F, i32),
+
     /// Branch not equal extension.
     BneE(F, i32, i32),
 
@@ -292,6 +295,14 @@ impl<F: PrimeField32, EF: ExtensionField<F>> AsmInstruction<F, EF> {
                     rhs
                 )
             }
+            AsmInstruction::BeqLabel(label, lhs) => {
+                write!(
+                    f,
+                    "beql {}, ({})fp",
+                    labels.get(label).unwrap_or(&format!(".L{}", label)),
+                    lhs
+                )
+            }
             AsmInstruction::BneE(label, lhs, rhs) => {
                 write!(
                     f,
@@ -0,0 +1,484 @@
+use std::sync::Arc;
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField as _;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    R1CSVar,
+};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use openvm_stark_backend::p3_field::{
+    extension::{BinomialExtensionField, BinomiallyExtendable},
+    Field, FieldAlgebra, FieldExtensionAlgebra, PrimeField32,
+};
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+pub(crate) const BABYBEAR_MAX_BITS: usize = 31;
+// bits reserved so that if we do lazy range checking, we still have a valid result
+// the first reserved bit is so that we can represent negative numbers
+// the second is to accommodate lazy range checking
+const RESERVED_HIGH_BITS: usize = 2;
+/// Largest `max_bits` of a [BabyBearVar], so that its value cannot overflow `Fr`.
+const MAX_BITS: usize = Fr::MODULUS_BIT_SIZE as usize - 1 - RESERVED_HIGH_BITS;
+
+#[derive(Clone, Debug)]
+pub struct BabyBearVar {
+    /// Logically `value` is a signed integer represented as `Fr`.
+    /// Invariants:
+    /// - `|value|` never overflows `Fr`
+    /// - `|value| < 2^max_bits` and `max_bits <= Fr::CAPACITY - RESERVED_HIGH_BITS`
+    ///
+    /// As in the halo2 compiler, `value` can go through additions and multiplications without
+    /// extra constraints as long as `max_bits` stays in bounds.
+    pub value: FpVar<Fr>,
+    /// The value is guaranteed to be less than 2^max_bits.
+    pub max_bits: usize,
+}
+
+impl BabyBearVar {
+    /// Fails with [SynthesisError::AssignmentMissing] when generating keys.
+    pub fn to_baby_bear(&self) -> Result<BabyBear, SynthesisError> {
+        let value = fr_to_bigint(&self.value.value()?).mod_floor(&BabyBear::ORDER_U32.into());
+        Ok(BabyBear::from_canonical_u32(value.try_into().unwrap()))
+    }
+}
+
+pub struct BabyBearChip {
+    pub cs: ConstraintSystemRef<Fr>,
+}
+
+impl BabyBearChip {
+    pub fn new(cs: ConstraintSystemRef<Fr>) -> Self {
+        BabyBearChip { cs }
+    }
+
+    pub fn load_witness(
+        &self,
+        value: impl FnOnce() -> Result<BabyBear, SynthesisError>,
+    ) -> Result<BabyBearVar, SynthesisError> {
+        let value = FpVar::new_witness(self.cs.clone(), || {
+            value().map(|value| Fr::from(value.as_canonical_u32()))
+        })?;
+        range_check(&self.cs, &value, BABYBEAR_MAX_BITS)?;
+        Ok(BabyBearVar {
+            value,
+            max_bits: BABYBEAR_MAX_BITS,
+        })
+    }
+
+    pub fn load_constant(&self, value: BabyBear) -> BabyBearVar {
+        let value = value.as_canonical_u32();
+        BabyBearVar {
+            value: FpVar::constant(Fr::from(value)),
+            max_bits: (u32::BITS - value.leading_zeros()) as usize,
+        }
+    }
+
+    pub fn reduce(&self, a: &BabyBearVar) -> Result<BabyBearVar, SynthesisError> {
+        let (_, r) = signed_div_mod(&self.cs, &a.value, a.max_bits)?;
+        Ok(BabyBearVar {
+            value: r,
+            max_bits: BABYBEAR_MAX_BITS,
+        })
+    }
+
+    /// Reduce max_bits if possible. This function doesn't guarantee that the actual value is within BabyBear.
+    pub fn reduce_max_bits(&self, a: BabyBearVar) -> Result<BabyBearVar, SynthesisError> {
+        if a.max_bits > BABYBEAR_MAX_BITS {
+            self.reduce(&a)
+        } else {
+            Ok(a)
+        }
+    }
+
+    /// Reduces `a`, and then `b` if that is not enough, until `fits(a.max_bits, b.max_bits)`.
+    fn reduce_operands(
+        &self,
+        a: &mut BabyBearVar,
+        b: &mut BabyBearVar,
+        fits: impl Fn(usize, usize) -> bool,
+    ) -> Result<(), SynthesisError> {
+        if !fits(a.max_bits, b.max_bits) {
+            *a = self.reduce(a)?;
+            if !fits(a.max_bits, b.max_bits) {
+                *b = self.reduce(b)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add(
+        &self,
+        mut a: BabyBearVar,
+        mut b: BabyBearVar,
+    ) -> Result<BabyBearVar, SynthesisError> {
+        self.reduce_operands(&mut a, &mut b, |a, b| a.max(b) + 1 <= MAX_BITS)?;
+        Ok(BabyBearVar {
+            value: &a.value + &b.value,
+            max_bits: a.max_bits.max(b.max_bits) + 1,
+        })
+    }
+
+    pub fn neg(&self, a: BabyBearVar) -> Result<BabyBearVar, SynthesisError> {
+        Ok(BabyBearVar {
+            value: a.value.negate()?,
+            max_bits: a.max_bits,
+        })
+    }
+
+    pub fn sub(
+        &self,
+        mut a: BabyBearVar,
+        mut b: BabyBearVar,
+    ) -> Result<BabyBearVar, SynthesisError> {
+        self.reduce_operands(&mut a, &mut b, |a, b| a.max(b) + 1 <= MAX_BITS)?;
+        Ok(BabyBearVar {
+            value: &a.value - &b.value,
+            max_bits: a.max_bits.max(b.max_bits) + 1,
+        })
+    }
+
+    pub fn mul(
+        &self,
+        mut a: BabyBearVar,
+        mut b: BabyBearVar,
+    ) -> Result<BabyBearVar, SynthesisError> {
+        if a.max_bits < b.max_bits {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.reduce_operands(&mut a, &mut b, |a, b| a + b <= MAX_BITS)?;
+        Ok(BabyBearVar {
+            value: &a.value * &b.value,
+            max_bits: a.max_bits + b.max_bits,
+        })
+    }
+
+    pub fn mul_add(
+        &self,
+        mut a: BabyBearVar,
+        mut b: BabyBearVar,
+        mut c: BabyBearVar,
+    ) -> Result<BabyBearVar, SynthesisError> {
+        if a.max_bits < b.max_bits {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.reduce_operands(&mut a, &mut b, |a, b| a + b + 1 <= MAX_BITS)?;
+        if c.max_bits + 1 > MAX_BITS {
+            c = self.reduce(&c)?;
+        }
+        Ok(BabyBearVar {
+            value: &a.value * &b.value + &c.value,
+            max_bits: c.max_bits.max(a.max_bits + b.max_bits) + 1,
+        })
+    }
+
+    pub fn div(
+        &self,
+        mut a: BabyBearVar,
+        mut b: BabyBearVar,
+    ) -> Result<BabyBearVar, SynthesisError> {
+        let mut c = self.load_witness(|| {
+            let b_inv = b
+                .to_baby_bear()?
+                .try_inverse()
+                .ok_or(SynthesisError::DivisionByZero)?;
+            Ok(a.to_baby_bear()? * b_inv)
+        })?;
+        // constraint a = b * c (mod p)
+        if a.max_bits > MAX_BITS {
+            a = self.reduce(&a)?;
+        }
+        self.reduce_operands(&mut b, &mut c, |b, c| b + c <= MAX_BITS)?;
+        let diff = BabyBearVar {
+            value: &a.value - &b.value * &c.value,
+            max_bits: a.max_bits.max(b.max_bits + c.max_bits) + 1,
+        };
+        self.assert_zero(diff)?;
+        Ok(c)
+    }
+
+    /// Returns `a` if `cond` is 1 and `b` if `cond` is 0. `cond` is assumed to be a bit.
+    pub fn select(
+        &self,
+        cond: &FpVar<Fr>,
+        a: BabyBearVar,
+        b: BabyBearVar,
+    ) -> Result<BabyBearVar, SynthesisError> {
+        Ok(BabyBearVar {
+            value: select(cond, &a.value, &b.value),
+            max_bits: a.max_bits.max(b.max_bits),
+        })
+    }
+
+    pub fn assert_zero(&self, a: BabyBearVar) -> Result<(), SynthesisError> {
+        // The proof of correctness of this function is listed in `signed_div_mod`.
+        assert!(a.max_bits <= MAX_BITS);
+        let b = BigUint::from(BabyBear::ORDER_U32);
+        let div = FpVar::new_witness(self.cs.clone(), || {
+            let a = fr_to_bigint(&a.value.value()?);
+            Ok(bigint_to_fr(&a.div_floor(&b.clone().into())))
+        })?;
+        (&div * Fr::from(BabyBear::ORDER_U32)).enforce_equal(&a.value)?;
+        // Constrain that `abs(div) <= 2 ** (2 ** a_num_bits / b).bits()`.
+        let bound = (BigUint::from(1u32) << a.max_bits) / &b;
+        let shifted_div = &div + Fr::from(bound.clone());
+        range_check(
+            &self.cs,
+            &shifted_div,
+            (bound * 2u32 + 1u32).bits() as usize,
+        )?;
+        Ok(())
+    }
+
+    pub fn assert_equal(&self, a: BabyBearVar, b: BabyBearVar) -> Result<(), SynthesisError> {
+        let diff = self.sub(a, b)?;
+        self.assert_zero(diff)
+    }
+}
+
+/// Returns `a` if `cond` is 1 and `b` if `cond` is 0. `cond` is assumed to be a bit.
+pub fn select(cond: &FpVar<Fr>, a: &FpVar<Fr>, b: &FpVar<Fr>) -> FpVar<Fr> {
+    cond * (a - b) + b
+}
+
+/// Constrains `0 <= a < 2^num_bits` and returns the bits of `a` in little-endian order.
+pub fn range_check(
+    cs: &ConstraintSystemRef<Fr>,
+    a: &FpVar<Fr>,
+    num_bits: usize,
+) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+    assert!(num_bits < Fr::MODULUS_BIT_SIZE as usize);
+    let value = a.value().ok().map(BigUint::from);
+    let bits = (0..num_bits)
+        .map(|i| {
+            Boolean::new_witness(cs.clone(), || {
+                value
+                    .as_ref()
+                    .map(|value| value.bit(i as u64))
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Boolean::le_bits_to_fp_var(&bits)?.enforce_equal(a)?;
+    Ok(bits)
+}
+
+/// Constrains and returns `(c, r)` such that `a = BabyBear::ORDER_U32 * c + r`.
+///
+/// * a: value to divide
+/// * a_num_bits: number of bits needed to represent the absolute value of `a`
+///
+/// See `signed_div_mod` in the halo2 compiler for the proof of correctness, which carries over
+/// since the constraints are the same.
+fn signed_div_mod(
+    cs: &ConstraintSystemRef<Fr>,
+    a: &FpVar<Fr>,
+    a_num_bits: usize,
+) -> Result<(FpVar<Fr>, FpVar<Fr>), SynthesisError> {
+    let b = BigUint::from(BabyBear::ORDER_U32);
+    let div_rem = || {
+        let a = fr_to_bigint(&a.value()?);
+        Ok(a.div_mod_floor(&b.clone().into()))
+    };
+    let div = FpVar::new_witness(cs.clone(), || div_rem().map(|(div, _)| bigint_to_fr(&div)))?;
+    let rem = FpVar::new_witness(cs.clone(), || div_rem().map(|(_, rem)| bigint_to_fr(&rem)))?;
+    (&div * Fr::from(BabyBear::ORDER_U32) + &rem).enforce_equal(a)?;
+    // Constrain that `abs(div) <= 2 ** (2 ** a_num_bits / b).bits()`.
+    let bound = (BigUint::from(1u32) << a_num_bits) / &b;
+    let shifted_div = &div + Fr::from(bound.clone());
+    range_check(cs, &shifted_div, (bound * 2u32 + 1u32).bits() as usize)?;
+    // `0 <= rem` and `rem <= b - 1` are both 31-bit range checks.
+    range_check(cs, &rem, BABYBEAR_MAX_BITS)?;
+    range_check(
+        cs,
+        &(FpVar::constant(Fr::from(BabyBear::ORDER_U32 - 1)) - &rem),
+        BABYBEAR_MAX_BITS,
+    )?;
+    Ok((div, rem))
+}
+
+/// Interprets `a` as a signed integer in `(-p/2, p/2]`.
+fn fr_to_bigint(a: &Fr) -> BigInt {
+    let a = BigUint::from(*a);
+    if a > BigUint::from(Fr::MODULUS_MINUS_ONE_DIV_TWO) {
+        BigInt::from(a) - BigInt::from(BigUint::from(Fr::MODULUS))
+    } else {
+        BigInt::from(a)
+    }
+}
+
+fn bigint_to_fr(a: &BigInt) -> Fr {
+    let value = Fr::from(a.magnitude().clone());
+    if a.sign() == Sign::Minus {
+        -value
+    } else {
+        value
+    }
+}
+
+// irred poly is x^4 - 11
+pub struct BabyBearExt4Chip {
+    pub base: Arc<BabyBearChip>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BabyBearExt4Var(pub [BabyBearVar; 4]);
+pub type BabyBearExt4 = BinomialExtensionField<BabyBear, 4>;
+
+impl BabyBearExt4Var {
+    /// Fails with [SynthesisError::AssignmentMissing] when generating keys.
+    pub fn to_extension_field(&self) -> Result<BabyBearExt4, SynthesisError> {
+        let b_val = self
+            .0
+            .iter()
+            .map(|x| x.to_baby_bear())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BabyBearExt4::from_base_slice(&b_val))
+    }
+
+    fn try_from_fn(
+        f: impl FnMut(usize) -> Result<BabyBearVar, SynthesisError>,
+    ) -> Result<Self, SynthesisError> {
+        let coeffs = (0..4).map(f).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(coeffs.try_into().unwrap()))
+    }
+}
+
+impl BabyBearExt4Chip {
+    pub fn new(base_chip: Arc<BabyBearChip>) -> Self {
+        BabyBearExt4Chip { base: base_chip }
+    }
+
+    pub fn load_witness(
+        &self,
+        value: impl FnOnce() -> Result<BabyBearExt4, SynthesisError>,
+    ) -> Result<BabyBearExt4Var, SynthesisError> {
+        let value = value();
+        BabyBearExt4Var::try_from_fn(|i| {
+            self.base
+                .load_witness(|| value.clone().map(|value| value.as_base_slice()[i]))
+        })
+    }
+
+    pub fn load_constant(&self, value: BabyBearExt4) -> BabyBearExt4Var {
+        BabyBearExt4Var(std::array::from_fn(|i| {
+            self.base.load_constant(value.as_base_slice()[i])
+        }))
+    }
+
+    pub fn add(
+        &self,
+        a: BabyBearExt4Var,
+        b: BabyBearExt4Var,
+    ) -> Result<BabyBearExt4Var, SynthesisError> {
+        BabyBearExt4Var::try_from_fn(|i| self.base.add(a.0[i].clone(), b.0[i].clone()))
+    }
+
+    pub fn neg(&self, a: BabyBearExt4Var) -> Result<BabyBearExt4Var, SynthesisError> {
+        BabyBearExt4Var::try_from_fn(|i| self.base.neg(a.0[i].clone()))
+    }
+
+    pub fn sub(
+        &self,
+        a: BabyBearExt4Var,
+        b: BabyBearExt4Var,
+    ) -> Result<BabyBearExt4Var, SynthesisError> {
+        BabyBearExt4Var::try_from_fn(|i| self.base.sub(a.0[i].clone(), b.0[i].clone()))
+    }
+
+    pub fn scalar_mul(
+        &self,
+        a: BabyBearExt4Var,
+        b: BabyBearVar,
+    ) -> Result<BabyBearExt4Var, SynthesisError> {
+        BabyBearExt4Var::try_from_fn(|i| self.base.mul(a.0[i].clone(), b.clone()))
+    }
+
+    pub fn select(
+        &self,
+        cond: &FpVar<Fr>,
+        a: BabyBearExt4Var,
+        b: BabyBearExt4Var,
+    ) -> Result<BabyBearExt4Var, SynthesisError> {
+        BabyBearExt4Var::try_from_fn(|i| self.base.select(cond, a.0[i].clone(), b.0[i].clone()))
+    }
+
+    pub fn assert_zero(&self, a: BabyBearExt4Var) -> Result<(), SynthesisError> {
+        for x in a.0 {
+            self.base.assert_zero(x)?;
+        }
+        Ok(())
+    }
+
+    pub fn assert_equal(
+        &self,
+        a: BabyBearExt4Var,
+        b: BabyBearExt4Var,
+    ) -> Result<(), SynthesisError> {
+        for (a, b) in a.0.into_iter().zip(b.0) {
+            self.base.assert_equal(a, b)?;
+        }
+        Ok(())
+    }
+
+    pub fn mul(
+        &self,
+        mut a: BabyBearExt4Var,
+        mut b: BabyBearExt4Var,
+    ) -> Result<BabyBearExt4Var, SynthesisError> {
+        // Each coefficient of the product sums 4 products of limbs and is then multiplied by W,
+        // so reduce the limbs up front rather than once per product.
+        let max_bits = |x: &BabyBearExt4Var| x.0.iter().map(|x| x.max_bits).max().unwrap();
+        if max_bits(&a) + max_bits(&b) + 8 > MAX_BITS {
+            a = self.reduce_max_bits(a)?;
+            b = self.reduce_max_bits(b)?;
+        }
+        let mut coeffs: Vec<Option<BabyBearVar>> = vec![None; 7];
+        for (i, a) in a.0.iter().enumerate() {
+            for (j, b) in b.0.iter().enumerate() {
+                let prod = self.base.mul(a.clone(), b.clone())?;
+                coeffs[i + j] = Some(match coeffs[i + j].take() {
+                    Some(sum) => self.base.add(sum, prod)?,
+                    None => prod,
+                });
+            }
+        }
+        let coeffs = coeffs.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+        let w = self
+            .base
+            .load_constant(<BabyBear as BinomiallyExtendable<4>>::W);
+        BabyBearExt4Var::try_from_fn(|i| match coeffs.get(i + 4) {
+            Some(high) => self
+                .base
+                .mul_add(high.clone(), w.clone(), coeffs[i].clone()),
+            None => Ok(coeffs[i].clone()),
+        })
+    }
+
+    pub fn div(
+        &self,
+        a: BabyBearExt4Var,
+        b: BabyBearExt4Var,
+    ) -> Result<BabyBearExt4Var, SynthesisError> {
+        let c = self.load_witness(|| {
+            let b_inv = b
+                .to_extension_field()?
+                .try_inverse()
+                .ok_or(SynthesisError::DivisionByZero)?;
+            Ok(a.to_extension_field()? * b_inv)
+        })?;
+        // constraint a = b * c
+        let prod = self.mul(b, c.clone())?;
+        self.assert_equal(a, prod)?;
+        Ok(c)
+    }
+
+    pub fn reduce_max_bits(&self, a: BabyBearExt4Var) -> Result<BabyBearExt4Var, SynthesisError> {
+        BabyBearExt4Var::try_from_fn(|i| self.base.reduce_max_bits(a.0[i].clone()))
+    }
+}
@@ -0,0 +1,543 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    marker::PhantomData,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{Arc, LazyLock},
+};
+
+use ark_bn254::Fr;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    R1CSVar, ToBitsGadget,
+};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use itertools::Itertools;
+#[cfg(feature = "bench-metrics")]
+use openvm_circuit::metrics::cycle_tracker::CycleTracker;
+use openvm_stark_backend::p3_field::{Field, FieldAlgebra, PrimeField};
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, p3_bn254_fr::Bn254Fr};
+
+use crate::{
+    constraints::groth16::{
+        baby_bear::{
+            range_check, select, BabyBearChip, BabyBearExt4, BabyBearExt4Chip, BabyBearExt4Var,
+            BabyBearVar,
+        },
+        poseidon2_perm::{Poseidon2Params, Poseidon2State},
+    },
+    ir::{Config, DslIr, TracedVec, Witness},
+};
+
+const POSEIDON2_T: usize = 3;
+static POSEIDON2_PARAMS: LazyLock<Poseidon2Params<Fr, POSEIDON2_T>> = LazyLock::new(|| {
+    use ark_ff::PrimeField as _;
+    use zkhash::{
+        ark_ff::{BigInteger, PrimeField as _},
+        fields::bn256::FpBN256 as ark_FpBN256,
+        poseidon2::poseidon2_instance_bn256::{MAT_DIAG3_M_1, RC3},
+    };
+
+    fn convert_fr(input: ark_FpBN256) -> Fr {
+        Fr::from_le_bytes_mod_order(&input.into_bigint().to_bytes_le())
+    }
+    const T: usize = 3;
+    let rounds_f = 8;
+    let rounds_p = 56;
+    let mut round_constants: Vec<[Fr; T]> = RC3
+        .iter()
+        .map(|vec| {
+            vec.iter()
+                .cloned()
+                .map(convert_fr)
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap()
+        })
+        .collect();
+
+    let rounds_f_beginning = rounds_f / 2;
+    let p_end = rounds_f_beginning + rounds_p;
+    let internal_round_constants = round_constants
+        .drain(rounds_f_beginning..p_end)
+        .map(|vec| vec[0])
+        .collect::<Vec<_>>();
+    let external_round_constants = round_constants;
+    Poseidon2Params {
+        rounds_f,
+        rounds_p,
+        mat_internal_diag_m_1: MAT_DIAG3_M_1
+            .iter()
+            .copied()
+            .map(convert_fr)
+            .collect_vec()
+            .try_into()
+            .unwrap(),
+        external_rc: external_round_constants,
+        internal_rc: internal_round_constants,
+    }
+});
+
+/// The backend for the Groth16 constraint compiler. It compiles the same operations as the halo2
+/// constraint compiler, to R1CS over BN254.
+#[derive(Debug, Clone)]
+pub struct Groth16ConstraintCompiler<C: Config> {
+    pub num_public_values: usize,
+    #[allow(unused_variables)]
+    pub profiling: bool,
+    pub phantom: PhantomData<C>,
+}
+
+/// Witness values of the operations, provided by the prover outside of the constraint compiler.
+/// Empty when generating keys.
+#[derive(Debug, Clone, Default)]
+pub struct Groth16State<C: Config> {
+    pub vars: HashMap<u32, Fr>,
+    pub felts: HashMap<u32, C::F>,
+    pub exts: HashMap<u32, C::EF>,
+}
+
+impl<C: Config> Groth16State<C> {
+    pub fn load_witness(&mut self, witness: Witness<C>) {
+        for (i, x) in witness.vars.iter().enumerate() {
+            self.vars.insert(i as u32, convert_fr(x));
+        }
+        for (i, x) in witness.felts.into_iter().enumerate() {
+            self.felts.insert(i as u32, x);
+        }
+        for (i, x) in witness.exts.into_iter().enumerate() {
+            self.exts.insert(i as u32, x);
+        }
+    }
+}
+
+impl<C: Config + Debug> Groth16ConstraintCompiler<C> {
+    pub fn new(num_public_values: usize) -> Self {
+        Self {
+            num_public_values,
+            profiling: false,
+            phantom: PhantomData,
+        }
+    }
+    pub fn with_profiling(mut self) -> Self {
+        self.profiling = true;
+        self
+    }
+    /// Create R1CS constraints from a list of operations in the DSL. The public values are
+    /// allocated as the instance variables of `cs`, in order.
+    ///
+    /// Witness values are only read when `cs` is not in setup mode.
+    pub fn constrain_r1cs(
+        &self,
+        cs: ConstraintSystemRef<Fr>,
+        state: &Groth16State<C>,
+        operations: TracedVec<DslIr<C>>,
+    ) -> Result<(), SynthesisError>
+    where
+        C: Config<N = Bn254Fr, F = BabyBear, EF = BabyBearExt4>,
+    {
+        #[cfg(feature = "bench-metrics")]
+        let mut cell_tracker = CycleTracker::new();
+        let f_chip = Arc::new(BabyBearChip::new(cs.clone()));
+        let ext_chip = BabyBearExt4Chip::new(Arc::clone(&f_chip));
+        let mut public_values = vec![FpVar::zero(); self.num_public_values];
+
+        // Local variables for referencing during the course of constraint building
+        let mut vars = HashMap::<u32, FpVar<Fr>>::new();
+        let mut felts = HashMap::<u32, BabyBearVar>::new();
+        let mut exts = HashMap::<u32, BabyBearExt4Var>::new();
+
+        for (instruction, backtrace) in operations {
+            #[cfg(feature = "bench-metrics")]
+            let old_num_constraints = cs.num_constraints();
+            let res = catch_unwind(AssertUnwindSafe(|| -> Result<(), SynthesisError> {
+                match instruction {
+                    DslIr::ImmV(a, b) => {
+                        vars.insert(a.0, FpVar::constant(convert_fr(&b)));
+                    }
+                    DslIr::ImmF(a, b) => {
+                        let x = f_chip.load_constant(b);
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::ImmE(a, b) => {
+                        let x = ext_chip.load_constant(b);
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::AddV(a, b, c) => {
+                        let x = &vars[&b.0] + &vars[&c.0];
+                        vars.insert(a.0, x);
+                    }
+                    DslIr::AddVI(a, b, c) => {
+                        let x = &vars[&b.0] + convert_fr(&c);
+                        vars.insert(a.0, x);
+                    }
+                    DslIr::AddF(a, b, c) => {
+                        let x = f_chip.add(felts[&b.0].clone(), felts[&c.0].clone())?;
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::AddFI(a, b, c) => {
+                        let x = if c.is_zero() {
+                            felts[&b.0].clone()
+                        } else {
+                            f_chip.add(felts[&b.0].clone(), f_chip.load_constant(c))?
+                        };
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::AddE(a, b, c) => {
+                        let x = ext_chip.add(exts[&b.0].clone(), exts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::AddEF(a, b, c) => {
+                        let mut x = exts[&b.0].clone();
+                        x.0[0] = f_chip.add(x.0[0].clone(), felts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::AddEFI(a, b, c) => {
+                        let mut x = exts[&b.0].clone();
+                        if !c.is_zero() {
+                            x.0[0] = f_chip.add(x.0[0].clone(), f_chip.load_constant(c))?;
+                        }
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::AddEI(a, b, c) => {
+                        let x = if c.is_zero() {
+                            exts[&b.0].clone()
+                        } else {
+                            ext_chip.add(exts[&b.0].clone(), ext_chip.load_constant(c))?
+                        };
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::AddEFFI(a, b, c) => {
+                        let mut x = ext_chip.load_constant(c);
+                        x.0[0] = f_chip.add(x.0[0].clone(), felts[&b.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::SubV(a, b, c) => {
+                        let x = &vars[&b.0] - &vars[&c.0];
+                        vars.insert(a.0, x);
+                    }
+                    DslIr::SubF(a, b, c) => {
+                        let x = f_chip.sub(felts[&b.0].clone(), felts[&c.0].clone())?;
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::SubE(a, b, c) => {
+                        let x = ext_chip.sub(exts[&b.0].clone(), exts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::SubEF(a, b, c) => {
+                        let mut x = exts[&b.0].clone();
+                        x.0[0] = f_chip.sub(x.0[0].clone(), felts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::SubEI(a, b, c) => {
+                        let x = if c.is_zero() {
+                            exts[&b.0].clone()
+                        } else {
+                            ext_chip.sub(exts[&b.0].clone(), ext_chip.load_constant(c))?
+                        };
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::SubVIN(a, b, c) => {
+                        let x = FpVar::constant(convert_fr(&b)) - &vars[&c.0];
+                        vars.insert(a.0, x);
+                    }
+                    DslIr::SubEIN(a, b, c) => {
+                        let x = ext_chip.sub(ext_chip.load_constant(b), exts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::SubEFI(a, b, c) => {
+                        let mut x = exts[&b.0].clone();
+                        if !c.is_zero() {
+                            x.0[0] = f_chip.sub(x.0[0].clone(), f_chip.load_constant(c))?;
+                        }
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::MulV(a, b, c) => {
+                        let x = &vars[&b.0] * &vars[&c.0];
+                        vars.insert(a.0, x);
+                    }
+                    DslIr::MulVI(a, b, c) => {
+                        let x = &vars[&b.0] * convert_fr(&c);
+                        vars.insert(a.0, x);
+                    }
+                    DslIr::MulF(a, b, c) => {
+                        let x = f_chip.mul(felts[&b.0].clone(), felts[&c.0].clone())?;
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::MulFI(a, b, c) => {
+                        let x = if c.is_one() {
+                            felts[&b.0].clone()
+                        } else if c.is_zero() {
+                            f_chip.load_constant(BabyBear::ZERO)
+                        } else {
+                            f_chip.mul(felts[&b.0].clone(), f_chip.load_constant(c))?
+                        };
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::MulE(a, b, c) => {
+                        let x = ext_chip.mul(exts[&b.0].clone(), exts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::MulEI(a, b, c) => {
+                        let x = if c.is_one() {
+                            exts[&b.0].clone()
+                        } else if c.is_zero() {
+                            ext_chip.load_constant(BabyBearExt4::ZERO)
+                        } else {
+                            ext_chip.mul(exts[&b.0].clone(), ext_chip.load_constant(c))?
+                        };
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::MulEF(a, b, c) => {
+                        let x = ext_chip.scalar_mul(exts[&b.0].clone(), felts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::MulEFI(a, b, c) => {
+                        let x = if c.is_one() {
+                            exts[&b.0].clone()
+                        } else if c.is_zero() {
+                            ext_chip.load_constant(BabyBearExt4::ZERO)
+                        } else {
+                            ext_chip.scalar_mul(exts[&b.0].clone(), f_chip.load_constant(c))?
+                        };
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::DivF(a, b, c) => {
+                        let x = f_chip.div(felts[&b.0].clone(), felts[&c.0].clone())?;
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::DivFIN(a, b, c) => {
+                        // a = b / c
+                        let tmp = f_chip.load_constant(b);
+                        let x = if b.is_zero() {
+                            tmp
+                        } else {
+                            f_chip.div(tmp, felts[&c.0].clone())?
+                        };
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::DivE(a, b, c) => {
+                        let x = ext_chip.div(exts[&b.0].clone(), exts[&c.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::DivEIN(a, b, c) => {
+                        let tmp = ext_chip.load_constant(b);
+                        let x = if b.is_zero() {
+                            tmp
+                        } else {
+                            ext_chip.div(tmp, exts[&c.0].clone())?
+                        };
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::NegE(a, b) => {
+                        let x = ext_chip.neg(exts[&b.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::CastFV(a, b) => {
+                        let reduced_felt = f_chip.reduce(&felts[&b.0])?;
+                        vars.insert(a.0, reduced_felt.value);
+                    }
+                    DslIr::CircuitNum2BitsF(value, output) => {
+                        let val = f_chip.reduce(&felts[&value.0])?;
+                        let x = range_check(&cs, &val.value, 32)?; // C::F::bits());
+                        assert!(output.len() <= x.len());
+                        for (o, x) in output.into_iter().zip(x) {
+                            vars.insert(o.0, FpVar::from(x));
+                        }
+                    }
+                    DslIr::CircuitVarTo64BitsF(value, output) => {
+                        let limbs = var_to_u64_limbs(&vars[&value.0])?;
+                        for (o, l) in output.into_iter().zip(limbs) {
+                            felts.insert(o.0, l);
+                        }
+                    }
+                    DslIr::CircuitPoseidon2Permute(state_vars) => {
+                        let mut state = Poseidon2State::<Fr, POSEIDON2_T>::new(
+                            state_vars.map(|x| vars[&x.0].clone()),
+                        );
+                        state.permutation(&POSEIDON2_PARAMS)?;
+                        for (x, s) in state_vars.iter().zip(state.s) {
+                            vars.insert(x.0, s);
+                        }
+                    }
+                    DslIr::CircuitSelectV(cond, a, b, out) => {
+                        let x = select(&vars[&cond.0], &vars[&a.0], &vars[&b.0]);
+                        vars.insert(out.0, x);
+                    }
+                    DslIr::CircuitSelectF(cond, a, b, out) => {
+                        let x = f_chip.select(
+                            &vars[&cond.0],
+                            felts[&a.0].clone(),
+                            felts[&b.0].clone(),
+                        )?;
+                        felts.insert(out.0, x);
+                    }
+                    DslIr::CircuitSelectE(cond, a, b, out) => {
+                        let x = ext_chip.select(
+                            &vars[&cond.0],
+                            exts[&a.0].clone(),
+                            exts[&b.0].clone(),
+                        )?;
+                        exts.insert(out.0, x);
+                    }
+                    DslIr::CircuitExt2Felt(a, b) => {
+                        for (i, x) in a.iter().enumerate() {
+                            felts.insert(x.0, exts[&b.0].0[i].clone());
+                        }
+                    }
+                    DslIr::AssertEqV(a, b) => {
+                        vars[&a.0].enforce_equal(&vars[&b.0])?;
+                    }
+                    DslIr::AssertEqVI(a, b) => {
+                        vars[&a.0].enforce_equal(&FpVar::constant(convert_fr(&b)))?;
+                    }
+                    DslIr::AssertEqF(a, b) => {
+                        f_chip.assert_equal(felts[&a.0].clone(), felts[&b.0].clone())?;
+                    }
+                    DslIr::AssertEqFI(a, b) => {
+                        if b.is_zero() {
+                            f_chip.assert_zero(felts[&a.0].clone())?;
+                        } else {
+                            f_chip.assert_equal(felts[&a.0].clone(), f_chip.load_constant(b))?;
+                        }
+                    }
+                    DslIr::AssertEqE(a, b) => {
+                        ext_chip.assert_equal(exts[&a.0].clone(), exts[&b.0].clone())?;
+                    }
+                    DslIr::AssertEqEI(a, b) => {
+                        if b.is_zero() {
+                            ext_chip.assert_zero(exts[&a.0].clone())?;
+                        } else {
+                            ext_chip.assert_equal(exts[&a.0].clone(), ext_chip.load_constant(b))?;
+                        }
+                    }
+                    // Values are unknown when generating keys, so nothing is printed then.
+                    DslIr::PrintV(a) => {
+                        if let Ok(x) = vars[&a.0].value() {
+                            println!("PrintV: {}", x);
+                        }
+                    }
+                    DslIr::PrintF(a) => {
+                        if let Ok(x) = felts[&a.0].to_baby_bear() {
+                            println!("PrintF: {:?}", x);
+                        }
+                    }
+                    DslIr::PrintE(a) => {
+                        if let Ok(x) = exts[&a.0].to_extension_field() {
+                            println!("PrintE: {:?}", x);
+                        }
+                    }
+                    DslIr::WitnessVar(a, b) => {
+                        let x = FpVar::new_witness(cs.clone(), || {
+                            state
+                                .vars
+                                .get(&b)
+                                .copied()
+                                .ok_or(SynthesisError::AssignmentMissing)
+                        })?;
+                        vars.insert(a.0, x);
+                    }
+                    DslIr::WitnessFelt(a, b) => {
+                        let x = f_chip.load_witness(|| {
+                            state
+                                .felts
+                                .get(&b)
+                                .copied()
+                                .ok_or(SynthesisError::AssignmentMissing)
+                        })?;
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::WitnessExt(a, b) => {
+                        let x = ext_chip.load_witness(|| {
+                            state
+                                .exts
+                                .get(&b)
+                                .copied()
+                                .ok_or(SynthesisError::AssignmentMissing)
+                        })?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::CircuitFelts2Ext(a, b) => {
+                        let x = BabyBearExt4Var(a.map(|a| felts[&a.0].clone()));
+                        exts.insert(b.0, x);
+                    }
+                    DslIr::CircuitFeltReduce(a) => {
+                        let x = f_chip.reduce_max_bits(felts[&a.0].clone())?;
+                        felts.insert(a.0, x);
+                    }
+                    DslIr::CircuitExtReduce(a) => {
+                        let x = ext_chip.reduce_max_bits(exts[&a.0].clone())?;
+                        exts.insert(a.0, x);
+                    }
+                    DslIr::CircuitLessThan(a, b) => {
+                        let bits = C::F::bits();
+                        range_check(&cs, &vars[&a.0], bits)?;
+                        range_check(&cs, &vars[&b.0], bits)?;
+                        // a < b iff b - a - 1 doesn't wrap around
+                        let diff = &vars[&b.0] - &vars[&a.0] - Fr::from(1u32);
+                        range_check(&cs, &diff, bits)?;
+                    }
+                    DslIr::CycleTrackerStart(_name) => {
+                        #[cfg(feature = "bench-metrics")]
+                        cell_tracker.start(_name);
+                    }
+                    DslIr::CycleTrackerEnd(_name) => {
+                        #[cfg(feature = "bench-metrics")]
+                        cell_tracker.end(_name);
+                    }
+                    DslIr::CircuitPublish(val, index) => {
+                        public_values[index] = vars[&val.0].clone();
+                    }
+                    _ => panic!("unsupported {:?}", instruction),
+                }
+                Ok(())
+            }));
+            if !matches!(res, Ok(Ok(()))) {
+                if let Some(mut backtrace) = backtrace {
+                    backtrace.resolve();
+                    eprintln!("openvm circuit failure; backtrace:\n{:?}", backtrace);
+                }
+            }
+            match res {
+                Ok(res) => res?,
+                Err(panic) => resume_unwind(panic),
+            }
+            #[cfg(feature = "bench-metrics")]
+            if self.profiling {
+                let labels = [("cell_tracker_span", cell_tracker.get_full_name())];
+                metrics::counter!("r1cs_constraints", &labels)
+                    .increment((cs.num_constraints() - old_num_constraints) as u64);
+            }
+        }
+
+        for public_value in public_values {
+            let instance = FpVar::new_input(cs.clone(), || public_value.value())?;
+            instance.enforce_equal(&public_value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Assumes F is Bn254 Fr and converts to arkworks Fr type
+pub fn convert_fr<F: PrimeField>(a: &F) -> Fr {
+    Fr::from(a.as_canonical_biguint())
+}
+
+/// Decomposes `x` into 64-bit limbs, the last of which has the remaining 62 bits. The bits are
+/// constrained to be the canonical representation of `x`, so the decomposition can't overflow.
+fn var_to_u64_limbs(x: &FpVar<Fr>) -> Result<[BabyBearVar; 4], SynthesisError> {
+    let bits = x.to_bits_le()?;
+    let limbs = bits
+        .chunks(64)
+        .map(|bits| {
+            Ok(BabyBearVar {
+                value: Boolean::le_bits_to_fp_var(bits)?,
+                max_bits: bits.len(),
+            })
+        })
+        .collect::<Result<Vec<_>, SynthesisError>>()?;
+    Ok(limbs.try_into().unwrap())
+}
@@ -0,0 +1,116 @@
+//! R1CS implementation of poseidon2 perm for Bn254Fr
+//! sbox degree 5
+
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::{fp::FpVar, FieldVar};
+use ark_relations::r1cs::SynthesisError;
+
+#[derive(Clone, Debug)]
+pub struct Poseidon2State<F: PrimeField, const T: usize> {
+    pub s: [FpVar<F>; T],
+}
+
+#[derive(Debug, Clone)]
+pub struct Poseidon2Params<F: PrimeField, const T: usize> {
+    /// Number of full rounds
+    pub rounds_f: usize,
+    pub rounds_p: usize,
+    pub mat_internal_diag_m_1: [F; T],
+    pub external_rc: Vec<[F; T]>,
+    pub internal_rc: Vec<F>,
+}
+
+impl<F: PrimeField, const T: usize> Poseidon2Params<F, T> {
+    pub fn new(
+        rounds_f: usize,
+        rounds_p: usize,
+        mat_internal_diag_m_1: [F; T],
+        external_rc: Vec<[F; T]>,
+        internal_rc: Vec<F>,
+    ) -> Self {
+        Self {
+            rounds_f,
+            rounds_p,
+            mat_internal_diag_m_1,
+            external_rc,
+            internal_rc,
+        }
+    }
+}
+
+impl<F: PrimeField, const T: usize> Poseidon2State<F, T> {
+    pub fn new(state: [FpVar<F>; T]) -> Self {
+        Self { s: state }
+    }
+
+    /// Perform permutation on this state.
+    ///
+    /// Linear layers and round constants are folded into linear combinations, so only the sboxes
+    /// add constraints.
+    pub fn permutation(&mut self, params: &Poseidon2Params<F, T>) -> Result<(), SynthesisError> {
+        let rounds_f_beginning = params.rounds_f / 2;
+
+        // First half of the full round
+        self.matmul_external();
+        for r in 0..rounds_f_beginning {
+            self.add_rc(params.external_rc[r]);
+            self.sbox()?;
+            self.matmul_external();
+        }
+
+        for r in 0..params.rounds_p {
+            self.s[0] = &self.s[0] + params.internal_rc[r];
+            self.s[0] = Self::x_power5(&self.s[0])?;
+            self.matmul_internal(params.mat_internal_diag_m_1);
+        }
+
+        for r in rounds_f_beginning..params.rounds_f {
+            self.add_rc(params.external_rc[r]);
+            self.sbox()?;
+            self.matmul_external();
+        }
+        Ok(())
+    }
+
+    fn x_power5(x: &FpVar<F>) -> Result<FpVar<F>, SynthesisError> {
+        let x2 = x.square()?;
+        let x4 = x2.square()?;
+        Ok(x4 * x)
+    }
+
+    fn sbox(&mut self) -> Result<(), SynthesisError> {
+        for x in self.s.iter_mut() {
+            *x = Self::x_power5(x)?;
+        }
+        Ok(())
+    }
+
+    fn matmul_external(&mut self) {
+        // Only doing T = 3 case
+        assert_eq!(T, 3);
+
+        // Matrix is circ(2, 1, 1)
+        let sum = self.sum();
+        for x in self.s.iter_mut() {
+            *x = &*x + &sum;
+        }
+    }
+
+    fn add_rc(&mut self, round_constants: [F; T]) {
+        for (x, rc) in self.s.iter_mut().zip(round_constants) {
+            *x = &*x + rc;
+        }
+    }
+
+    fn matmul_internal(&mut self, mat_internal_diag_m_1: [F; T]) {
+        assert_eq!(T, 3);
+        let sum = self.sum();
+        for (x, diag) in self.s.iter_mut().zip(mat_internal_diag_m_1) {
+            *x = &*x * diag + &sum;
+        }
+    }
+
+    fn sum(&self) -> FpVar<F> {
+        self.s.iter().fold(FpVar::zero(), |acc, x| acc + x)
+    }
+}
@@ -20,6 +20,9 @@ pub struct CompilerOptions {
     // The compiler will ensure that the heap pointer is aligned to be a multiple of `word_size`.
     pub word_size: usize,
     pub enable_cycle_tracker: bool,
+    /// Run the [optimization passes](crate::optimizer) before lowering to assembly.
+    #[serde(default)]
+    pub enable_optimizations: bool,
 }
 
 impl Default for CompilerOptions {
@@ -27,6 +30,7 @@ impl Default for CompilerOptions {
         CompilerOptions {
             word_size: 8,
             enable_cycle_tracker: false,
+            enable_optimizations: false,
         }
     }
 }
@@ -40,6 +44,10 @@ impl CompilerOptions {
         self.enable_cycle_tracker = true;
         self
     }
+    pub fn with_optimizations(mut self) -> Self {
+        self.enable_optimizations = true;
+        self
+    }
 }
 
 fn inst<F: PrimeField64>(opcode: VmOpcode, a: F, b: F, c: F, d: AS, e: AS) -> Instruction<F> {
@@ -213,6 +221,17 @@ fn convert_instruction<F: PrimeField32, EF: ExtensionField<F>>(
                 AS::Immediate,
             ),
         ],
+        AsmInstruction::BeqLabel(label, lhs) => vec![
+            // if mem[lhs] == labels[label], pc <- labels[label]
+            inst(
+                options.opcode_with_offset(NativeBranchEqualOpcode(BranchEqualOpcode::BEQ)),
+                i32_f(lhs),
+                labels(label),
+                labels(label) - pc,
+                AS::Native,
+                AS::Immediate,
+            ),
+        ],
         AsmInstruction::BneE(label, lhs, rhs) => (0..EF::D)
             .map(|i|
             // if mem[lhs + i] != mem[rhs +i] for i = 0..4, pc <- labels[label]
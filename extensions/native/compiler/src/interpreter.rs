@@ -0,0 +1,757 @@
+//! A reference interpreter executing [DslIr] programs of the native [AsmConfig] directly.
+//!
+//! Executing a program on the interpreter is much faster than compiling it and executing it on the
+//! native VM, and failures are reported at the DSL instruction that caused them, together with the
+//! backtrace recorded when the instruction was pushed (see [TracedVec::trace_push]).
+//!
+//! Variables and the heap live at the addresses the [AsmCompiler](crate::asm::AsmCompiler)
+//! assigns them, so the memory of the interpreter can be compared with the final memory of the VM
+//! executing the compiled program, see [Interpreter::diff_memory].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::ControlFlow,
+};
+
+use backtrace::Backtrace;
+use openvm_circuit::arch::Streams;
+use openvm_poseidon2_air::{Poseidon2Config, Poseidon2SubChip};
+use openvm_stark_backend::p3_field::{
+    ExtensionField, Field, FieldAlgebra, FieldExtensionAlgebra, PrimeField32, TwoAdicField,
+};
+use thiserror::Error;
+
+use crate::{
+    asm::{AsmConfig, HEAP_PTR, HEAP_START_ADDRESS, MEMORY_BITS},
+    conversion::CompilerOptions,
+    ir::{
+        Array, Config, DslIr, Ext, Felt, MemIndex, Ptr, RVar, TracedVec, Usize, Var, Witness,
+        DIGEST_SIZE, PERMUTATION_WIDTH,
+    },
+};
+
+/// A failed DSL instruction.
+#[derive(Error, Debug)]
+#[error("{kind} at {instruction}")]
+pub struct InterpreterError {
+    pub kind: InterpreterErrorKind,
+    /// Name of the failed instruction.
+    pub instruction: String,
+    /// Resolved backtrace of where the instruction was pushed, if it was recorded.
+    pub backtrace: Option<Backtrace>,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InterpreterErrorKind {
+    #[error("assertion failed: {lhs} != {rhs}")]
+    AssertionFailed { lhs: String, rhs: String },
+    #[error("assertion failed: value is zero")]
+    AssertNonZeroFailed,
+    #[error("error instruction executed")]
+    Error,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("range check failed: {value} does not fit in {bits} bits")]
+    RangeCheckFailed { value: u32, bits: usize },
+    #[error("input stream is empty")]
+    EndOfInputStream,
+    #[error("hint stream is empty")]
+    EndOfHintStream,
+    #[error("expected a hint of length {expected}, got {actual}")]
+    UnexpectedHintLength { expected: usize, actual: usize },
+    #[error("hint space has no entry {id}")]
+    HintSpaceOutOfBounds { id: usize },
+    #[error("witness has no entry {index}")]
+    WitnessOutOfBounds { index: u32 },
+    #[error("public value {index} is already set")]
+    PublicValueAlreadySet { index: usize },
+    #[error("batch opening does not match the commitment")]
+    VerifyBatchFailed,
+    #[error("call to undefined function {0}")]
+    UndefinedFunction(String),
+    #[error("instruction is not supported by the native VM")]
+    Unsupported,
+}
+
+impl InterpreterError {
+    fn new<C: Config>(
+        kind: InterpreterErrorKind,
+        op: &DslIr<C>,
+        trace: &Option<Backtrace>,
+    ) -> Self {
+        let backtrace = trace.clone().map(|mut backtrace| {
+            backtrace.resolve();
+            backtrace
+        });
+        Self {
+            kind,
+            instruction: op.to_string(),
+            backtrace,
+        }
+    }
+}
+
+/// A memory cell whose value differs between the interpreter and another execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryMismatch<F> {
+    pub address: u32,
+    /// Value written by the interpreter.
+    pub expected: F,
+    /// Value in the other execution.
+    pub actual: F,
+}
+
+/// Functions defined so far, by name.
+type Functions<'a, F, EF> = HashMap<&'a str, &'a TracedVec<DslIr<AsmConfig<F, EF>>>>;
+
+/// Executes [DslIr] programs of the native [AsmConfig] against a [Witness] and input [Streams].
+pub struct Interpreter<F: PrimeField32 + TwoAdicField, EF: ExtensionField<F> + TwoAdicField> {
+    /// Memory of the native address space, by address. Missing cells are zero.
+    memory: HashMap<u32, F>,
+    streams: Streams<F>,
+    witness: Witness<AsmConfig<F, EF>>,
+    public_values: BTreeMap<usize, F>,
+    word_size: usize,
+    poseidon2: Poseidon2SubChip<F, 0>,
+}
+
+impl<F: PrimeField32 + TwoAdicField, EF: ExtensionField<F> + TwoAdicField> Interpreter<F, EF> {
+    /// Creates an interpreter allocating the heap like a program compiled with `options`.
+    pub fn new(options: CompilerOptions) -> Self {
+        let heap_start = F::from_canonical_u32(HEAP_START_ADDRESS as u32);
+        Self {
+            memory: HashMap::from([(HEAP_PTR as u32, heap_start)]),
+            streams: Streams::default(),
+            witness: Witness::default(),
+            public_values: BTreeMap::new(),
+            word_size: options.word_size,
+            poseidon2: Poseidon2SubChip::new(Poseidon2Config::default().constants),
+        }
+    }
+
+    pub fn with_input_stream(mut self, streams: impl Into<Streams<F>>) -> Self {
+        self.streams = streams.into();
+        self
+    }
+
+    /// Sets the witness read by `Witness*` instructions.
+    pub fn with_witness(mut self, witness: Witness<AsmConfig<F, EF>>) -> Self {
+        self.witness = witness;
+        self
+    }
+
+    /// Executes `operations` until the end or until a `Halt` instruction.
+    pub fn run(
+        &mut self,
+        operations: &TracedVec<DslIr<AsmConfig<F, EF>>>,
+    ) -> Result<(), InterpreterError> {
+        self.block(operations, &mut HashMap::new())?;
+        Ok(())
+    }
+
+    pub fn var(&self, var: Var<F>) -> F {
+        self.read(var.fp() as u32)
+    }
+
+    pub fn felt(&self, felt: Felt<F>) -> F {
+        self.read(felt.fp() as u32)
+    }
+
+    pub fn ext(&self, ext: Ext<F, EF>) -> EF {
+        self.read_ext(ext.fp() as u32)
+    }
+
+    /// Memory cells written so far, by address in the native address space.
+    pub fn memory(&self) -> &HashMap<u32, F> {
+        &self.memory
+    }
+
+    pub fn streams(&self) -> &Streams<F> {
+        &self.streams
+    }
+
+    pub fn public_values(&self) -> &BTreeMap<usize, F> {
+        &self.public_values
+    }
+
+    /// Compares every cell written by the interpreter with the value `read` returns for its
+    /// address in the native address space, e.g. from the final memory of the VM executing the
+    /// same operations. The VM also writes scratch cells the interpreter does not use, so those
+    /// are not compared. Mismatches are sorted by address.
+    pub fn diff_memory(&self, read: impl Fn(u32) -> F) -> Vec<MemoryMismatch<F>> {
+        let mut mismatches: Vec<_> = self
+            .memory
+            .iter()
+            .filter_map(|(&address, &expected)| {
+                let actual = read(address);
+                (actual != expected).then_some(MemoryMismatch {
+                    address,
+                    expected,
+                    actual,
+                })
+            })
+            .collect();
+        mismatches.sort_by_key(|mismatch| mismatch.address);
+        mismatches
+    }
+
+    fn block<'a>(
+        &mut self,
+        operations: &'a TracedVec<DslIr<AsmConfig<F, EF>>>,
+        functions: &mut Functions<'a, F, EF>,
+    ) -> Result<ControlFlow<()>, InterpreterError> {
+        for (op, trace) in operations.vec.iter().zip(&operations.traces) {
+            if self.instruction(op, trace, functions)?.is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn instruction<'a>(
+        &mut self,
+        op: &'a DslIr<AsmConfig<F, EF>>,
+        trace: &Option<Backtrace>,
+        functions: &mut Functions<'a, F, EF>,
+    ) -> Result<ControlFlow<()>, InterpreterError> {
+        let fail = |kind| InterpreterError::new(kind, op, trace);
+        match op {
+            DslIr::ImmV(dst, src) => self.set_var(*dst, *src),
+            DslIr::ImmF(dst, src) => self.set_felt(*dst, *src),
+            DslIr::ImmE(dst, src) => self.set_ext(*dst, *src),
+            DslIr::AddV(dst, lhs, rhs) => self.set_var(*dst, self.var(*lhs) + self.var(*rhs)),
+            DslIr::AddVI(dst, lhs, rhs) => self.set_var(*dst, self.var(*lhs) + *rhs),
+            DslIr::AddF(dst, lhs, rhs) => self.set_felt(*dst, self.felt(*lhs) + self.felt(*rhs)),
+            DslIr::AddFI(dst, lhs, rhs) => self.set_felt(*dst, self.felt(*lhs) + *rhs),
+            DslIr::AddE(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) + self.ext(*rhs)),
+            DslIr::AddEI(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) + *rhs),
+            DslIr::AddEF(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) + self.felt(*rhs)),
+            DslIr::AddEFI(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) + *rhs),
+            DslIr::AddEFFI(dst, lhs, rhs) => self.set_ext(*dst, *rhs + self.felt(*lhs)),
+            DslIr::SubV(dst, lhs, rhs) => self.set_var(*dst, self.var(*lhs) - self.var(*rhs)),
+            DslIr::SubVI(dst, lhs, rhs) => self.set_var(*dst, self.var(*lhs) - *rhs),
+            DslIr::SubVIN(dst, lhs, rhs) => self.set_var(*dst, *lhs - self.var(*rhs)),
+            DslIr::SubF(dst, lhs, rhs) => self.set_felt(*dst, self.felt(*lhs) - self.felt(*rhs)),
+            DslIr::SubFI(dst, lhs, rhs) => self.set_felt(*dst, self.felt(*lhs) - *rhs),
+            DslIr::SubFIN(dst, lhs, rhs) => self.set_felt(*dst, *lhs - self.felt(*rhs)),
+            DslIr::SubE(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) - self.ext(*rhs)),
+            DslIr::SubEI(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) - *rhs),
+            DslIr::SubEIN(dst, lhs, rhs) => self.set_ext(*dst, *lhs - self.ext(*rhs)),
+            DslIr::SubEFI(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) - *rhs),
+            DslIr::SubEF(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) - self.felt(*rhs)),
+            DslIr::MulV(dst, lhs, rhs) => self.set_var(*dst, self.var(*lhs) * self.var(*rhs)),
+            DslIr::MulVI(dst, lhs, rhs) => self.set_var(*dst, self.var(*lhs) * *rhs),
+            DslIr::MulF(dst, lhs, rhs) => self.set_felt(*dst, self.felt(*lhs) * self.felt(*rhs)),
+            DslIr::MulFI(dst, lhs, rhs) => self.set_felt(*dst, self.felt(*lhs) * *rhs),
+            DslIr::MulE(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) * self.ext(*rhs)),
+            DslIr::MulEI(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) * *rhs),
+            DslIr::MulEFI(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) * *rhs),
+            DslIr::MulEF(dst, lhs, rhs) => self.set_ext(*dst, self.ext(*lhs) * self.felt(*rhs)),
+            DslIr::DivF(dst, lhs, rhs) => {
+                let value = div(self.felt(*lhs), self.felt(*rhs)).map_err(fail)?;
+                self.set_felt(*dst, value);
+            }
+            DslIr::DivFI(dst, lhs, rhs) => {
+                let value = div(self.felt(*lhs), *rhs).map_err(fail)?;
+                self.set_felt(*dst, value);
+            }
+            DslIr::DivFIN(dst, lhs, rhs) => {
+                let value = div(*lhs, self.felt(*rhs)).map_err(fail)?;
+                self.set_felt(*dst, value);
+            }
+            DslIr::DivE(dst, lhs, rhs) => {
+                let value = div(self.ext(*lhs), self.ext(*rhs)).map_err(fail)?;
+                self.set_ext(*dst, value);
+            }
+            DslIr::DivEI(dst, lhs, rhs) => {
+                let value = div(self.ext(*lhs), *rhs).map_err(fail)?;
+                self.set_ext(*dst, value);
+            }
+            DslIr::DivEIN(dst, lhs, rhs) => {
+                let value = div(*lhs, self.ext(*rhs)).map_err(fail)?;
+                self.set_ext(*dst, value);
+            }
+            DslIr::DivEFI(dst, lhs, rhs) => {
+                let value = div(self.ext(*lhs), EF::from_base(*rhs)).map_err(fail)?;
+                self.set_ext(*dst, value);
+            }
+            DslIr::DivEF(dst, lhs, rhs) => {
+                let value = div(self.ext(*lhs), EF::from_base(self.felt(*rhs))).map_err(fail)?;
+                self.set_ext(*dst, value);
+            }
+            DslIr::NegV(dst, src) => self.set_var(*dst, -self.var(*src)),
+            DslIr::NegF(dst, src) => self.set_felt(*dst, -self.felt(*src)),
+            DslIr::NegE(dst, src) => self.set_ext(*dst, -self.ext(*src)),
+            DslIr::CastFV(dst, src) => self.set_var(*dst, self.felt(*src)),
+            DslIr::UnsafeCastVF(dst, src) => self.set_felt(*dst, self.var(*src)),
+            DslIr::ZipFor(starts, end0, step_sizes, loop_vars, block) => {
+                for (start, loop_var) in starts.iter().zip(loop_vars) {
+                    self.set_var(*loop_var, self.rvar(*start));
+                }
+                // Like the compiled loop, the loop variables are compared after each step, so a
+                // start beyond the end never terminates.
+                while self.var(loop_vars[0]) != self.rvar(*end0) {
+                    if self.block(block, functions)?.is_break() {
+                        return Ok(ControlFlow::Break(()));
+                    }
+                    for (loop_var, step_size) in loop_vars.iter().zip(step_sizes) {
+                        self.set_var(*loop_var, self.var(*loop_var) + *step_size);
+                    }
+                }
+            }
+            DslIr::IfEq(lhs, rhs, then_block, else_block) => {
+                let is_eq = self.var(*lhs) == self.var(*rhs);
+                return self.block(if is_eq { then_block } else { else_block }, functions);
+            }
+            DslIr::IfNe(lhs, rhs, then_block, else_block) => {
+                let is_eq = self.var(*lhs) == self.var(*rhs);
+                return self.block(if is_eq { else_block } else { then_block }, functions);
+            }
+            DslIr::IfEqI(lhs, rhs, then_block, else_block) => {
+                let is_eq = self.var(*lhs) == *rhs;
+                return self.block(if is_eq { then_block } else { else_block }, functions);
+            }
+            DslIr::IfNeI(lhs, rhs, then_block, else_block) => {
+                let is_eq = self.var(*lhs) == *rhs;
+                return self.block(if is_eq { else_block } else { then_block }, functions);
+            }
+            // The return address is not written: it is a pc of the compiled program.
+            DslIr::FnDef(name, _, block) => {
+                functions.insert(name, block);
+            }
+            DslIr::Call(name) => {
+                let block = *functions
+                    .get(name.as_str())
+                    .ok_or_else(|| fail(InterpreterErrorKind::UndefinedFunction(name.clone())))?;
+                return self.block(block, functions);
+            }
+            DslIr::AssertEqV(lhs, rhs) => {
+                assert_eq(self.var(*lhs), self.var(*rhs)).map_err(fail)?;
+            }
+            DslIr::AssertEqF(lhs, rhs) => {
+                assert_eq(self.felt(*lhs), self.felt(*rhs)).map_err(fail)?;
+            }
+            DslIr::AssertEqE(lhs, rhs) => {
+                assert_eq(self.ext(*lhs), self.ext(*rhs)).map_err(fail)?;
+            }
+            DslIr::AssertEqVI(lhs, rhs) => assert_eq(self.var(*lhs), *rhs).map_err(fail)?,
+            DslIr::AssertEqFI(lhs, rhs) => assert_eq(self.felt(*lhs), *rhs).map_err(fail)?,
+            DslIr::AssertEqEI(lhs, rhs) => assert_eq(self.ext(*lhs), *rhs).map_err(fail)?,
+            DslIr::AssertNonZero(u) => {
+                let value = match u {
+                    Usize::Const(c) => *c.borrow(),
+                    Usize::Var(v) => self.var(*v),
+                };
+                if value.is_zero() {
+                    return Err(fail(InterpreterErrorKind::AssertNonZeroFailed));
+                }
+            }
+            DslIr::Alloc(ptr, len, size) => self.alloc(*ptr, *len, *size).map_err(fail)?,
+            DslIr::LoadV(var, ptr, index) => {
+                let value = self.read(self.heap_address(*ptr, *index));
+                self.set_var(*var, value);
+            }
+            DslIr::LoadF(felt, ptr, index) => {
+                let value = self.read(self.heap_address(*ptr, *index));
+                self.set_felt(*felt, value);
+            }
+            DslIr::LoadE(ext, ptr, index) => {
+                let value = self.read_ext(self.heap_address(*ptr, *index));
+                self.set_ext(*ext, value);
+            }
+            DslIr::LoadHeapPtr(ptr) => self.set_var(ptr.address, self.read(HEAP_PTR as u32)),
+            DslIr::StoreV(var, ptr, index) => {
+                self.write(self.heap_address(*ptr, *index), self.var(*var));
+            }
+            DslIr::StoreF(felt, ptr, index) => {
+                self.write(self.heap_address(*ptr, *index), self.felt(*felt));
+            }
+            DslIr::StoreE(ext, ptr, index) => {
+                self.write_ext(self.heap_address(*ptr, *index), self.ext(*ext));
+            }
+            DslIr::StoreHeapPtr(ptr) => self.write(HEAP_PTR as u32, self.var(ptr.address)),
+            DslIr::Poseidon2PermuteBabyBear(dst, src) => {
+                let (Array::Dyn(dst, _), Array::Dyn(src, _)) = (dst, src) else {
+                    return Err(fail(InterpreterErrorKind::Unsupported));
+                };
+                let src = self.pointer(*src);
+                let state = std::array::from_fn(|i| self.read(src + i as u32));
+                let state = self.poseidon2.permute(state);
+                let dst = self.pointer(*dst);
+                for (i, value) in state.into_iter().enumerate() {
+                    self.write(dst + i as u32, value);
+                }
+            }
+            DslIr::Poseidon2CompressBabyBear(result, left, right) => {
+                let (Array::Dyn(result, _), Array::Dyn(left, _), Array::Dyn(right, _)) =
+                    (result, left, right)
+                else {
+                    return Err(fail(InterpreterErrorKind::Unsupported));
+                };
+                let left = self.read_digest(self.pointer(*left));
+                let right = self.read_digest(self.pointer(*right));
+                let digest = self.compress(left, right);
+                let result = self.pointer(*result);
+                for (i, value) in digest.into_iter().enumerate() {
+                    self.write(result + i as u32, value);
+                }
+            }
+            DslIr::PrintV(var) => println!("{}", self.var(*var)),
+            DslIr::PrintF(felt) => println!("{}", self.felt(*felt)),
+            DslIr::PrintE(ext) => {
+                for value in self.ext(*ext).as_base_slice() {
+                    println!("{}", value);
+                }
+            }
+            DslIr::Error() => return Err(fail(InterpreterErrorKind::Error)),
+            DslIr::HintInputVec() => {
+                let hint = self.next_input().map_err(fail)?;
+                self.streams.hint_stream = [F::from_canonical_usize(hint.len())].into();
+                self.streams.hint_stream.extend(hint);
+            }
+            DslIr::HintFelt() => {
+                let hint = self.next_input().map_err(fail)?;
+                if hint.len() != 1 {
+                    return Err(fail(InterpreterErrorKind::UnexpectedHintLength {
+                        expected: 1,
+                        actual: hint.len(),
+                    }));
+                }
+                self.streams.hint_stream = hint.into();
+            }
+            DslIr::HintBitsF(felt, len) => {
+                let value = self.felt(*felt).as_canonical_u32();
+                self.streams.hint_stream = (0..*len)
+                    .map(|i| F::from_bool(i < u32::BITS && (value >> i) & 1 == 1))
+                    .collect();
+            }
+            DslIr::StoreHintWord(ptr, index) => {
+                let value = self
+                    .streams
+                    .hint_stream
+                    .pop_front()
+                    .ok_or_else(|| fail(InterpreterErrorKind::EndOfHintStream))?;
+                self.write(self.heap_address(*ptr, *index), value);
+            }
+            DslIr::HintLoad() => {
+                let payload = self.next_input().map_err(fail)?;
+                let id = self.streams.hint_space.len();
+                self.streams.hint_space.push(payload);
+                self.streams.hint_stream = [F::from_canonical_usize(id)].into();
+            }
+            DslIr::WitnessVar(var, index) => {
+                let value = witness(&self.witness.vars, *index).map_err(fail)?;
+                self.set_var(*var, value);
+            }
+            DslIr::WitnessFelt(felt, index) => {
+                let value = witness(&self.witness.felts, *index).map_err(fail)?;
+                self.set_felt(*felt, value);
+            }
+            DslIr::WitnessExt(ext, index) => {
+                let value = witness(&self.witness.exts, *index).map_err(fail)?;
+                self.set_ext(*ext, value);
+            }
+            DslIr::Publish(felt, index) => {
+                let index = self.var(*index).as_canonical_u32() as usize;
+                if self.public_values.contains_key(&index) {
+                    return Err(fail(InterpreterErrorKind::PublicValueAlreadySet { index }));
+                }
+                self.public_values.insert(index, self.felt(*felt));
+            }
+            DslIr::Halt => return Ok(ControlFlow::Break(())),
+            DslIr::FriReducedOpening(alpha, hint_id, is_init, at_x_array, at_z_array, result) => {
+                let value = self
+                    .fri_reduced_opening(
+                        self.ext(*alpha),
+                        self.var(*hint_id),
+                        self.var(*is_init),
+                        self.pointer(at_x_array.ptr()),
+                        self.pointer(at_z_array.ptr()),
+                        self.var(at_z_array.len().get_var()),
+                    )
+                    .map_err(fail)?;
+                self.set_ext(*result, value);
+            }
+            DslIr::VerifyBatchFelt(dim, opened, proof_id, index, commit) => self
+                .verify_batch(
+                    self.pointer(dim.ptr()),
+                    self.pointer(opened.ptr()),
+                    self.var(opened.len().get_var()),
+                    self.var(*proof_id),
+                    self.pointer(index.ptr()),
+                    self.pointer(commit.ptr()),
+                    1,
+                )
+                .map_err(fail)?,
+            DslIr::VerifyBatchExt(dim, opened, proof_id, index, commit) => self
+                .verify_batch(
+                    self.pointer(dim.ptr()),
+                    self.pointer(opened.ptr()),
+                    self.var(opened.len().get_var()),
+                    self.var(*proof_id),
+                    self.pointer(index.ptr()),
+                    self.pointer(commit.ptr()),
+                    EF::D,
+                )
+                .map_err(fail)?,
+            DslIr::RangeCheckV(var, bits) => range_check(self.var(*var), *bits).map_err(fail)?,
+            DslIr::CycleTrackerStart(_) | DslIr::CycleTrackerEnd(_) => {}
+            DslIr::CircuitNum2BitsF(..)
+            | DslIr::CircuitVarTo64BitsF(..)
+            | DslIr::CircuitPoseidon2Permute(..)
+            | DslIr::CircuitPublish(..)
+            | DslIr::CircuitSelectV(..)
+            | DslIr::CircuitSelectF(..)
+            | DslIr::CircuitSelectE(..)
+            | DslIr::CircuitExt2Felt(..)
+            | DslIr::CircuitFelts2Ext(..)
+            | DslIr::CircuitFeltReduce(..)
+            | DslIr::CircuitExtReduce(..)
+            | DslIr::CircuitLessThan(..) => return Err(fail(InterpreterErrorKind::Unsupported)),
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn read(&self, address: u32) -> F {
+        self.memory.get(&address).copied().unwrap_or(F::ZERO)
+    }
+
+    fn write(&mut self, address: u32, value: F) {
+        self.memory.insert(address, value);
+    }
+
+    fn read_ext(&self, address: u32) -> EF {
+        let values: Vec<_> = (0..EF::D as u32).map(|i| self.read(address + i)).collect();
+        EF::from_base_slice(&values)
+    }
+
+    fn write_ext(&mut self, address: u32, value: EF) {
+        for (i, &value) in value.as_base_slice().iter().enumerate() {
+            self.write(address + i as u32, value);
+        }
+    }
+
+    fn read_digest(&self, address: u32) -> [F; DIGEST_SIZE] {
+        std::array::from_fn(|i| self.read(address + i as u32))
+    }
+
+    fn set_var(&mut self, var: Var<F>, value: F) {
+        self.write(var.fp() as u32, value);
+    }
+
+    fn set_felt(&mut self, felt: Felt<F>, value: F) {
+        self.write(felt.fp() as u32, value);
+    }
+
+    fn set_ext(&mut self, ext: Ext<F, EF>, value: EF) {
+        self.write_ext(ext.fp() as u32, value);
+    }
+
+    fn rvar(&self, rvar: RVar<F>) -> F {
+        match rvar {
+            RVar::Const(c) => c,
+            RVar::Val(var) => self.var(var),
+        }
+    }
+
+    fn pointer(&self, ptr: Ptr<F>) -> u32 {
+        self.var(ptr.address).as_canonical_u32()
+    }
+
+    fn heap_address(&self, ptr: Ptr<F>, index: MemIndex<F>) -> u32 {
+        let address = self.var(ptr.address)
+            + self.rvar(index.index) * F::from_canonical_usize(index.size)
+            + F::from_canonical_usize(index.offset);
+        address.as_canonical_u32()
+    }
+
+    fn next_input(&mut self) -> Result<Vec<F>, InterpreterErrorKind> {
+        self.streams
+            .input_stream
+            .pop_front()
+            .ok_or(InterpreterErrorKind::EndOfInputStream)
+    }
+
+    /// Allocates like [AsmCompiler::alloc](crate::asm::AsmCompiler::alloc), so that the heap
+    /// layout matches the compiled program.
+    fn alloc(
+        &mut self,
+        ptr: Ptr<F>,
+        len: RVar<F>,
+        size: usize,
+    ) -> Result<(), InterpreterErrorKind> {
+        let word_size = self.word_size;
+        let align = |x: usize| x.div_ceil(word_size) * word_size;
+        let heap_ptr = self.read(HEAP_PTR as u32);
+        self.set_var(ptr.address, heap_ptr);
+        let inc = match len {
+            RVar::Const(len) => {
+                F::from_canonical_usize(align(len.as_canonical_u32() as usize * size))
+            }
+            RVar::Val(len) => {
+                let size = align(size);
+                let size_bit = (usize::BITS - size.leading_zeros()) as usize;
+                let len = self.var(len);
+                range_check(len, MEMORY_BITS - size_bit)?;
+                len * F::from_canonical_usize(size)
+            }
+        };
+        let heap_ptr = heap_ptr + inc;
+        self.write(HEAP_PTR as u32, heap_ptr);
+        range_check(heap_ptr, MEMORY_BITS)
+    }
+
+    fn compress(&self, left: [F; DIGEST_SIZE], right: [F; DIGEST_SIZE]) -> [F; DIGEST_SIZE] {
+        let state: [F; PERMUTATION_WIDTH] = std::array::from_fn(|i| {
+            if i < DIGEST_SIZE {
+                left[i]
+            } else {
+                right[i - DIGEST_SIZE]
+            }
+        });
+        let state = self.poseidon2.permute(state);
+        std::array::from_fn(|i| state[i])
+    }
+
+    /// Same as the `FRI_REDUCED_OPENING` instruction: unless `is_init` is set, the values at `x`
+    /// are first taken from the hint space.
+    fn fri_reduced_opening(
+        &mut self,
+        alpha: EF,
+        hint_id: F,
+        is_init: F,
+        at_x: u32,
+        at_z: u32,
+        len: F,
+    ) -> Result<EF, InterpreterErrorKind> {
+        let len = len.as_canonical_u32();
+        if is_init.is_zero() {
+            let id = hint_id.as_canonical_u32() as usize;
+            let hint = self
+                .streams
+                .hint_space
+                .get_mut(id)
+                .ok_or(InterpreterErrorKind::HintSpaceOutOfBounds { id })?;
+            if hint.len() < len as usize {
+                return Err(InterpreterErrorKind::EndOfHintStream);
+            }
+            let values: Vec<_> = hint.drain(..len as usize).collect();
+            for (i, value) in values.into_iter().enumerate() {
+                self.write(at_x + i as u32, value);
+            }
+        }
+        let mut result = EF::ZERO;
+        for i in (0..len).rev() {
+            let x = self.read(at_x + i);
+            let z = self.read_ext(at_z + i * EF::D as u32);
+            result = result * alpha + (z - x);
+        }
+        Ok(result)
+    }
+
+    /// Same as the `VERIFY_BATCH` instruction: hashes the opened rows of each height into the
+    /// Merkle path given by the proof in the hint space, and checks the root against the
+    /// commitment.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_batch(
+        &self,
+        dim: u32,
+        opened: u32,
+        opened_len: F,
+        proof_id: F,
+        index: u32,
+        commit: u32,
+        element_size: usize,
+    ) -> Result<(), InterpreterErrorKind> {
+        let opened_len = opened_len.as_canonical_u32();
+        let id = proof_id.as_canonical_u32() as usize;
+        let proof = self
+            .streams
+            .hint_space
+            .get(id)
+            .ok_or(InterpreterErrorKind::HintSpaceOutOfBounds { id })?;
+        let sibling = |i: usize| -> Result<[F; DIGEST_SIZE], InterpreterErrorKind> {
+            proof
+                .get(i * DIGEST_SIZE..(i + 1) * DIGEST_SIZE)
+                .map(|sibling| sibling.try_into().unwrap())
+                .ok_or(InterpreterErrorKind::EndOfHintStream)
+        };
+
+        let initial_log_height = self.read(dim).as_canonical_u32();
+        let mut root = [F::ZERO; DIGEST_SIZE];
+        let mut opened_index = 0;
+        for (sibling_index, log_height) in (0..=initial_log_height).rev().enumerate() {
+            let log_height_f = F::from_canonical_u32(log_height);
+            if opened_index < opened_len && self.read(dim + opened_index) == log_height_f {
+                // Absorb the rows of this height into a sponge which overwrites its rate.
+                let mut state = [F::ZERO; PERMUTATION_WIDTH];
+                let mut absorbed = 0;
+                while opened_index < opened_len && self.read(dim + opened_index) == log_height_f {
+                    let row = self.read(opened + 2 * opened_index).as_canonical_u32();
+                    let row_len = self.read(opened + 2 * opened_index + 1).as_canonical_u32();
+                    for i in 0..row_len * element_size as u32 {
+                        state[absorbed] = self.read(row + i);
+                        absorbed += 1;
+                        if absorbed == DIGEST_SIZE {
+                            state = self.poseidon2.permute(state);
+                            absorbed = 0;
+                        }
+                    }
+                    opened_index += 1;
+                }
+                if absorbed > 0 {
+                    state = self.poseidon2.permute(state);
+                }
+                let hash = std::array::from_fn(|i| state[i]);
+                root = if log_height == initial_log_height {
+                    hash
+                } else {
+                    self.compress(root, hash)
+                };
+            }
+            if log_height > 0 {
+                let sibling = sibling(sibling_index)?;
+                root = if self.read(index + sibling_index as u32) == F::ONE {
+                    self.compress(sibling, root)
+                } else {
+                    self.compress(root, sibling)
+                };
+            }
+        }
+        if self.read_digest(commit) != root {
+            return Err(InterpreterErrorKind::VerifyBatchFailed);
+        }
+        Ok(())
+    }
+}
+
+fn div<T: Field>(lhs: T, rhs: T) -> Result<T, InterpreterErrorKind> {
+    rhs.try_inverse()
+        .map(|inv| lhs * inv)
+        .ok_or(InterpreterErrorKind::DivisionByZero)
+}
+
+fn assert_eq<T: PartialEq + std::fmt::Display>(lhs: T, rhs: T) -> Result<(), InterpreterErrorKind> {
+    if lhs != rhs {
+        return Err(InterpreterErrorKind::AssertionFailed {
+            lhs: lhs.to_string(),
+            rhs: rhs.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn range_check<F: PrimeField32>(value: F, bits: usize) -> Result<(), InterpreterErrorKind> {
+    let value = value.as_canonical_u32();
+    if bits < u32::BITS as usize && value >> bits != 0 {
+        return Err(InterpreterErrorKind::RangeCheckFailed { value, bits });
+    }
+    Ok(())
+}
+
+fn witness<T: Copy>(values: &[T], index: u32) -> Result<T, InterpreterErrorKind> {
+    values
+        .get(index as usize)
+        .copied()
+        .ok_or(InterpreterErrorKind::WitnessOutOfBounds { index })
+}
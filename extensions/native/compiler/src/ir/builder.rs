@@ -1,4 +1,8 @@
-use std::{iter::Zip, vec::IntoIter};
+use std::{
+    iter::Zip,
+    sync::{atomic::AtomicU32, Arc},
+    vec::IntoIter,
+};
 
 use backtrace::Backtrace;
 use itertools::izip;
@@ -107,6 +111,11 @@ pub struct Builder<C: Config> {
     pub(crate) witness_space: Vec<Vec<WitnessRef>>,
     pub flags: BuilderFlags,
     pub is_sub_builder: bool,
+    /// Number of functions defined so far, shared with all sub-builders to give functions
+    /// unique names.
+    pub(crate) fn_count: Arc<AtomicU32>,
+    /// Names of the functions that can be called from this builder.
+    pub(crate) fns_in_scope: Vec<String>,
 }
 
 impl<C: Config> Builder<C> {
@@ -126,6 +135,8 @@ impl<C: Config> Builder<C> {
             nb_public_values: self.nb_public_values,
             flags: self.flags,
             is_sub_builder: true,
+            fn_count: self.fn_count.clone(),
+            fns_in_scope: self.fns_in_scope.clone(),
         }
     }
 
@@ -0,0 +1,106 @@
+use std::sync::atomic::Ordering;
+
+use super::{Builder, Config, DslIr, Var, Variable};
+
+/// A function defined with [Builder::define_fn], taking arguments of type `A` and returning a
+/// result of type `R`.
+pub struct DslFn<'a, C: Config, A, R> {
+    name: String,
+    kind: DslFnKind<'a, C, A, R>,
+}
+
+enum DslFnKind<'a, C: Config, A, R> {
+    /// The body is compiled once. Arguments and result are passed through the given variables.
+    Compiled { args: A, result: R },
+    /// The body is inlined at every call, which is the only option in static mode.
+    Inlined(Box<dyn Fn(&mut Builder<C>, A) -> R + 'a>),
+}
+
+impl<C: Config, A, R> DslFn<'_, C, A, R> {
+    /// The unique name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<C: Config> Builder<C> {
+    /// Defines a function whose body is built by `f`. The body is compiled once and executed by
+    /// every [call](Builder::call), which is cheaper in program size than inlining the body when
+    /// it is large and called from several places.
+    ///
+    /// Functions use dedicated slots of the stack: the arguments, the locals of the body and the
+    /// return address are allocated once at definition, and the slots are never reused by the
+    /// caller. So a call does not need to save a frame, but a function can not call itself, which
+    /// is enforced by the function not being in scope in its own body. A call is a `JAL` to the
+    /// body which writes the return address, and the body returns by comparing it to the address
+    /// following each call. A return therefore costs one comparison per call site, so a function
+    /// only saves cycles over inlining when this is small next to the cost of its body.
+    ///
+    /// The function can only be called from this builder and its sub-builders. The body may read
+    /// and assign variables of the enclosing scope. In static mode, the body is inlined instead.
+    pub fn define_fn<'a, A, R>(
+        &mut self,
+        name: &str,
+        f: impl Fn(&mut Builder<C>, A) -> R + 'a,
+    ) -> DslFn<'a, C, A, R>
+    where
+        A: Variable<C>,
+        R: Variable<C>,
+    {
+        let name = format!("{name}_{}", self.fn_count.fetch_add(1, Ordering::Relaxed));
+        if self.flags.static_only {
+            return DslFn {
+                name,
+                kind: DslFnKind::Inlined(Box::new(f)),
+            };
+        }
+
+        let args = A::uninit(self);
+        let return_address: Var<_> = self.uninit();
+        let mut body_builder = self.create_sub_builder();
+        let result = f(&mut body_builder, args.clone());
+
+        // The slots of the function can not be reused by the caller.
+        self.var_count = body_builder.var_count;
+        self.felt_count = body_builder.felt_count;
+        self.ext_count = body_builder.ext_count;
+
+        self.trace_push(DslIr::FnDef(
+            name.clone(),
+            return_address,
+            body_builder.operations,
+        ));
+        self.fns_in_scope.push(name.clone());
+        DslFn {
+            name,
+            kind: DslFnKind::Compiled { args, result },
+        }
+    }
+
+    /// Calls a function defined with [Builder::define_fn] and returns a copy of its result.
+    pub fn call<A, R>(&mut self, f: &DslFn<'_, C, A, R>, args: A) -> R
+    where
+        A: Variable<C>,
+        R: Variable<C>,
+    {
+        match &f.kind {
+            DslFnKind::Compiled {
+                args: params,
+                result,
+            } => {
+                assert!(
+                    self.fns_in_scope.contains(&f.name),
+                    "function {} called out of scope",
+                    f.name
+                );
+                params.assign(args.into(), self);
+                self.trace_push(DslIr::Call(f.name.clone()));
+                // Copy the result out, so that it is not overwritten by the next call.
+                let ret: R = self.uninit();
+                ret.assign(result.clone().into(), self);
+                ret
+            }
+            DslFnKind::Inlined(body) => body(self, args),
+        }
+    }
+}
@@ -140,6 +140,12 @@ pub enum DslIr<C: Config> {
     IfEqI(Var<C::N>, C::N, TracedVec<DslIr<C>>, TracedVec<DslIr<C>>),
     /// Executes a not equal conditional branch with the parameters (lhs var, rhs imm, then body, else body).
     IfNeI(Var<C::N>, C::N, TracedVec<DslIr<C>>, TracedVec<DslIr<C>>),
+    /// Defines a function with the parameters (unique name, return address var, body). The body
+    /// is only executed by a `Call`. ASM only.
+    FnDef(String, Var<C::N>, TracedVec<DslIr<C>>),
+    /// Calls the function with the given name. Arguments and results are passed in the slots of
+    /// the function's frame. ASM only.
+    Call(String),
 
     // Assertions.
     /// Assert that two variables are equal (var == var).
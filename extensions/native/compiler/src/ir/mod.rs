@@ -1,5 +1,6 @@
 pub use builder::*;
 pub use collections::*;
+pub use functions::*;
 pub use instructions::*;
 use openvm_stark_backend::p3_field::{ExtensionField, PrimeField, PrimeField32, TwoAdicField};
 pub use poseidon::{DIGEST_SIZE, PERMUTATION_WIDTH};
@@ -14,6 +15,7 @@ mod bits;
 mod builder;
 mod collections;
 mod fri;
+mod functions;
 mod instructions;
 mod poseidon;
 mod ptr;
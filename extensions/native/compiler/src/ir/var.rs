@@ -68,6 +68,40 @@ impl<C: Config, T: Variable<C>, const N: usize> Variable<C> for [T; N] {
     }
 }
 
+/// Tuples of variables are variables, e.g. to pass several arguments to a [DslFn](super::DslFn).
+macro_rules! impl_variable_for_tuple {
+    ($($T:ident $i:tt),*) => {
+        impl<C: Config, $($T: Variable<C>),*> Variable<C> for ($($T,)*) {
+            type Expression = Self;
+
+            #[allow(unused_variables)]
+            fn uninit(builder: &mut Builder<C>) -> Self {
+                ($($T::uninit(builder),)*)
+            }
+
+            #[allow(unused_variables)]
+            fn assign(&self, src: Self::Expression, builder: &mut Builder<C>) {
+                $(self.$i.assign(src.$i.into(), builder);)*
+            }
+
+            #[allow(unused_variables)]
+            fn assert_eq(
+                lhs: impl Into<Self::Expression>,
+                rhs: impl Into<Self::Expression>,
+                builder: &mut Builder<C>,
+            ) {
+                let (lhs, rhs) = (lhs.into(), rhs.into());
+                $($T::assert_eq(lhs.$i, rhs.$i, builder);)*
+            }
+        }
+    };
+}
+
+impl_variable_for_tuple!();
+impl_variable_for_tuple!(T0 0, T1 1);
+impl_variable_for_tuple!(T0 0, T1 1, T2 2);
+impl_variable_for_tuple!(T0 0, T1 1, T2 2, T3 3);
+
 impl<C: Config, T: MemVariable<C>, const N: usize> MemVariable<C> for [T; N] {
     fn size_of() -> usize {
         N * T::size_of()
@@ -13,7 +13,9 @@ extern crate core;
 pub mod asm;
 pub mod constraints;
 pub mod conversion;
+pub mod interpreter;
 pub mod ir;
+pub mod optimizer;
 
 pub mod prelude {
     pub use openvm_native_compiler_derive::DslVariable;
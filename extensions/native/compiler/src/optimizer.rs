@@ -0,0 +1,1037 @@
+//! Optimization passes over [DslIr], run before lowering to assembly when
+//! [CompilerOptions::enable_optimizations](crate::conversion::CompilerOptions) is set.
+//!
+//! DSL variables are mutable: they are reassigned inside loops and branches and written by
+//! instructions with side effects. The passes therefore only rewrite or remove arithmetic
+//! instructions, track facts about each assignment of a variable separately, and treat every
+//! other instruction as possibly writing all variables it mentions, where a function call
+//! mentions all variables of the called function. Rewritten instructions keep the backtrace of
+//! the instruction they replace.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    mem::{discriminant, Discriminant},
+};
+
+use backtrace::Backtrace;
+use openvm_stark_backend::p3_field::{Field, FieldAlgebra, FieldExtensionAlgebra};
+
+use crate::ir::{Array, Config, DslIr, Ext, Felt, MemIndex, Ptr, RVar, TracedVec, Usize, Var};
+
+/// Runs all passes over `operations`.
+pub fn optimize<C: Config>(operations: TracedVec<DslIr<C>>) -> TracedVec<DslIr<C>> {
+    eliminate_dead_code(propagate(operations))
+}
+
+/// Constant folding, copy propagation and common subexpression elimination.
+///
+/// Operands of arithmetic instructions are replaced with the constants or variables they are
+/// known to equal, and an instruction is replaced with a cheaper one computing the same value,
+/// e.g. an immediate if all operands are constant or a copy of a variable that already holds the
+/// same expression. Divisions by zero are never folded so that they still fail at runtime.
+pub fn propagate<C: Config>(operations: TracedVec<DslIr<C>>) -> TracedVec<DslIr<C>> {
+    let functions = summarize_functions(&operations.vec);
+    block(operations, &functions, &mut Facts::default())
+}
+
+/// Removes arithmetic instructions whose result is never read. Divisions are kept because they
+/// fail on a zero divisor.
+pub fn eliminate_dead_code<C: Config>(operations: TracedVec<DslIr<C>>) -> TracedVec<DslIr<C>> {
+    let functions = summarize_functions(&operations.vec);
+    dead_code_block(operations, &functions, &mut HashSet::new())
+}
+
+/// A DSL variable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Slot {
+    Var(u32),
+    Felt(u32),
+    Ext(u32),
+}
+
+/// The variables mentioned by each function, including by the functions it calls. A call may
+/// read and assign all of them.
+type Functions = HashMap<String, HashSet<Slot>>;
+
+fn summarize_functions<C: Config>(operations: &[DslIr<C>]) -> Functions {
+    let mut functions = Functions::new();
+    summarize_block(operations, &mut functions);
+    functions
+}
+
+/// Adds the functions defined in `operations` to `functions`. Functions are defined before they
+/// are called, so a forward pass sees the callees of each function first.
+fn summarize_block<C: Config>(operations: &[DslIr<C>], functions: &mut Functions) {
+    for op in operations {
+        match op {
+            DslIr::FnDef(name, return_address, body) => {
+                summarize_block(&body.vec, functions);
+                let mut slots = HashSet::new();
+                return_address.collect_slots(&mut slots);
+                body.vec
+                    .iter()
+                    .for_each(|op| collect_slots(op, functions, &mut slots));
+                functions.insert(name.clone(), slots);
+            }
+            DslIr::ZipFor(_, _, _, _, body) => summarize_block(&body.vec, functions),
+            DslIr::IfEq(_, _, then_block, else_block)
+            | DslIr::IfNe(_, _, then_block, else_block)
+            | DslIr::IfEqI(_, _, then_block, else_block)
+            | DslIr::IfNeI(_, _, then_block, else_block) => {
+                summarize_block(&then_block.vec, functions);
+                summarize_block(&else_block.vec, functions);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// An operand of an arithmetic instruction. Constants have the type of the result: `N` for vars,
+/// `F` for felts and `EF` for exts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Operand<N, F, EF> {
+    Slot(Slot),
+    N(N),
+    F(F),
+    EF(EF),
+}
+
+type OperandOf<C> = Operand<<C as Config>::N, <C as Config>::F, <C as Config>::EF>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum OpKind {
+    Copy,
+    Neg,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// An arithmetic instruction `dst = lhs op rhs`, or `dst = op lhs` for unary operations.
+#[derive(Clone, Copy, Debug)]
+struct Arith<N, F, EF> {
+    kind: OpKind,
+    dst: Slot,
+    lhs: Operand<N, F, EF>,
+    rhs: Option<Operand<N, F, EF>>,
+}
+
+type ArithOf<C> = Arith<<C as Config>::N, <C as Config>::F, <C as Config>::EF>;
+
+/// Returns `op` as an [Arith] if it is an arithmetic instruction. Felt constants of ext
+/// instructions are converted to `EF`.
+fn to_arith<C: Config>(op: &DslIr<C>) -> Option<ArithOf<C>> {
+    use OpKind::*;
+    use Operand::{EF, F, N};
+
+    let v = |v: &Var<C::N>| Operand::Slot(Slot::Var(v.0));
+    let f = |f: &Felt<C::F>| Operand::Slot(Slot::Felt(f.0));
+    let e = |e: &Ext<C::F, C::EF>| Operand::Slot(Slot::Ext(e.0));
+    let fi = |c: &C::F| EF(C::EF::from_base(*c));
+    let (kind, dst, lhs, rhs) = match op {
+        DslIr::ImmV(d, c) => (Copy, v(d), N(*c), None),
+        DslIr::ImmF(d, c) => (Copy, f(d), F(*c), None),
+        DslIr::ImmE(d, c) => (Copy, e(d), EF(*c), None),
+        DslIr::AddV(d, l, r) => (Add, v(d), v(l), Some(v(r))),
+        DslIr::AddVI(d, l, r) => (Add, v(d), v(l), Some(N(*r))),
+        DslIr::AddF(d, l, r) => (Add, f(d), f(l), Some(f(r))),
+        DslIr::AddFI(d, l, r) => (Add, f(d), f(l), Some(F(*r))),
+        DslIr::AddE(d, l, r) => (Add, e(d), e(l), Some(e(r))),
+        DslIr::AddEI(d, l, r) => (Add, e(d), e(l), Some(EF(*r))),
+        DslIr::AddEF(d, l, r) => (Add, e(d), e(l), Some(f(r))),
+        DslIr::AddEFI(d, l, r) => (Add, e(d), e(l), Some(fi(r))),
+        DslIr::AddEFFI(d, l, r) => (Add, e(d), f(l), Some(EF(*r))),
+        DslIr::SubV(d, l, r) => (Sub, v(d), v(l), Some(v(r))),
+        DslIr::SubVI(d, l, r) => (Sub, v(d), v(l), Some(N(*r))),
+        DslIr::SubVIN(d, l, r) => (Sub, v(d), N(*l), Some(v(r))),
+        DslIr::SubF(d, l, r) => (Sub, f(d), f(l), Some(f(r))),
+        DslIr::SubFI(d, l, r) => (Sub, f(d), f(l), Some(F(*r))),
+        DslIr::SubFIN(d, l, r) => (Sub, f(d), F(*l), Some(f(r))),
+        DslIr::SubE(d, l, r) => (Sub, e(d), e(l), Some(e(r))),
+        DslIr::SubEI(d, l, r) => (Sub, e(d), e(l), Some(EF(*r))),
+        DslIr::SubEIN(d, l, r) => (Sub, e(d), EF(*l), Some(e(r))),
+        DslIr::SubEFI(d, l, r) => (Sub, e(d), e(l), Some(fi(r))),
+        DslIr::SubEF(d, l, r) => (Sub, e(d), e(l), Some(f(r))),
+        DslIr::MulV(d, l, r) => (Mul, v(d), v(l), Some(v(r))),
+        DslIr::MulVI(d, l, r) => (Mul, v(d), v(l), Some(N(*r))),
+        DslIr::MulF(d, l, r) => (Mul, f(d), f(l), Some(f(r))),
+        DslIr::MulFI(d, l, r) => (Mul, f(d), f(l), Some(F(*r))),
+        DslIr::MulE(d, l, r) => (Mul, e(d), e(l), Some(e(r))),
+        DslIr::MulEI(d, l, r) => (Mul, e(d), e(l), Some(EF(*r))),
+        DslIr::MulEFI(d, l, r) => (Mul, e(d), e(l), Some(fi(r))),
+        DslIr::MulEF(d, l, r) => (Mul, e(d), e(l), Some(f(r))),
+        DslIr::DivF(d, l, r) => (Div, f(d), f(l), Some(f(r))),
+        DslIr::DivFI(d, l, r) => (Div, f(d), f(l), Some(F(*r))),
+        DslIr::DivFIN(d, l, r) => (Div, f(d), F(*l), Some(f(r))),
+        DslIr::DivE(d, l, r) => (Div, e(d), e(l), Some(e(r))),
+        DslIr::DivEI(d, l, r) => (Div, e(d), e(l), Some(EF(*r))),
+        DslIr::DivEIN(d, l, r) => (Div, e(d), EF(*l), Some(e(r))),
+        DslIr::DivEFI(d, l, r) => (Div, e(d), e(l), Some(fi(r))),
+        DslIr::DivEF(d, l, r) => (Div, e(d), e(l), Some(f(r))),
+        DslIr::NegV(d, s) => (Neg, v(d), v(s), None),
+        DslIr::NegF(d, s) => (Neg, f(d), f(s), None),
+        DslIr::NegE(d, s) => (Neg, e(d), e(s), None),
+        _ => return None,
+    };
+    let Operand::Slot(dst) = dst else {
+        unreachable!()
+    };
+    Some(Arith {
+        kind,
+        dst,
+        lhs,
+        rhs,
+    })
+}
+
+impl<N: Field, F: Field, EF: Field> Arith<N, F, EF> {
+    fn copy(dst: Slot, src: Operand<N, F, EF>) -> Self {
+        Self {
+            kind: OpKind::Copy,
+            dst,
+            lhs: src,
+            rhs: None,
+        }
+    }
+
+    fn operands(&self) -> impl Iterator<Item = Operand<N, F, EF>> {
+        std::iter::once(self.lhs).chain(self.rhs)
+    }
+
+    /// Folds constant operands and applies the identities `x + 0 = x - 0 = x * 1 = x / 1 = x` and
+    /// `x * 0 = 0`.
+    fn simplify(self) -> Self {
+        let folded = match (self.lhs, self.rhs) {
+            (Operand::N(l), None) => fold(self.kind, l, None).map(Operand::N),
+            (Operand::N(l), Some(Operand::N(r))) => fold(self.kind, l, Some(r)).map(Operand::N),
+            (Operand::F(l), None) => fold(self.kind, l, None).map(Operand::F),
+            (Operand::F(l), Some(Operand::F(r))) => fold(self.kind, l, Some(r)).map(Operand::F),
+            (Operand::EF(l), None) => fold(self.kind, l, None).map(Operand::EF),
+            (Operand::EF(l), Some(Operand::EF(r))) => fold(self.kind, l, Some(r)).map(Operand::EF),
+            _ => None,
+        };
+        if let Some(value) = folded {
+            return Self::copy(self.dst, value);
+        }
+
+        let Some(rhs) = self.rhs else {
+            return self;
+        };
+        let lhs = self.lhs;
+        match self.kind {
+            OpKind::Add | OpKind::Sub if rhs.is_zero() => Self::copy(self.dst, lhs),
+            OpKind::Add if lhs.is_zero() => Self::copy(self.dst, rhs),
+            OpKind::Mul if rhs.is_zero() => Self::copy(self.dst, rhs),
+            OpKind::Mul if lhs.is_zero() => Self::copy(self.dst, lhs),
+            OpKind::Mul | OpKind::Div if rhs.is_one() => Self::copy(self.dst, lhs),
+            OpKind::Mul if lhs.is_one() => Self::copy(self.dst, rhs),
+            _ => self,
+        }
+    }
+}
+
+impl<N: Field, F: Field, EF: Field> Operand<N, F, EF> {
+    fn is_zero(&self) -> bool {
+        match self {
+            Self::N(c) => c.is_zero(),
+            Self::F(c) => c.is_zero(),
+            Self::EF(c) => c.is_zero(),
+            Self::Slot(_) => false,
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        match self {
+            Self::N(c) => c.is_one(),
+            Self::F(c) => c.is_one(),
+            Self::EF(c) => c.is_one(),
+            Self::Slot(_) => false,
+        }
+    }
+}
+
+/// Evaluates an operation on constants. Returns `None` for copies and divisions by zero.
+fn fold<T: Field>(kind: OpKind, lhs: T, rhs: Option<T>) -> Option<T> {
+    match (kind, rhs) {
+        (OpKind::Neg, None) => Some(-lhs),
+        (OpKind::Add, Some(rhs)) => Some(lhs + rhs),
+        (OpKind::Sub, Some(rhs)) => Some(lhs - rhs),
+        (OpKind::Mul, Some(rhs)) => Some(lhs * rhs),
+        (OpKind::Div, Some(rhs)) => rhs.try_inverse().map(|inv| lhs * inv),
+        _ => None,
+    }
+}
+
+/// Returns the cheapest instruction computing `arith`, if there is one.
+fn from_arith<C: Config>(arith: ArithOf<C>) -> Option<DslIr<C>> {
+    use OpKind::*;
+    use Operand::{Slot as S, EF, F, N};
+    use Slot::{Ext as E, Felt as Fe, Var as V};
+
+    let var = Var::<C::N>::new;
+    let felt = Felt::<C::F>::new;
+    let ext = Ext::<C::F, C::EF>::new;
+    let base = |c: C::EF| {
+        let coeffs = c.as_base_slice();
+        coeffs[1..].iter().all(|c| c.is_zero()).then(|| coeffs[0])
+    };
+    let op = match (arith.dst, arith.kind, arith.lhs, arith.rhs) {
+        (V(d), Copy, N(c), None) => DslIr::ImmV(var(d), c),
+        (V(d), Copy, S(V(s)), None) => DslIr::AddVI(var(d), var(s), C::N::ZERO),
+        (V(d), Neg, S(V(s)), None) => DslIr::NegV(var(d), var(s)),
+        (V(d), Add, S(V(l)), Some(S(V(r)))) => DslIr::AddV(var(d), var(l), var(r)),
+        (V(d), Add, S(V(l)), Some(N(r))) | (V(d), Add, N(r), Some(S(V(l)))) => {
+            DslIr::AddVI(var(d), var(l), r)
+        }
+        (V(d), Sub, S(V(l)), Some(S(V(r)))) => DslIr::SubV(var(d), var(l), var(r)),
+        (V(d), Sub, S(V(l)), Some(N(r))) => DslIr::SubVI(var(d), var(l), r),
+        (V(d), Sub, N(l), Some(S(V(r)))) => DslIr::SubVIN(var(d), l, var(r)),
+        (V(d), Mul, S(V(l)), Some(S(V(r)))) => DslIr::MulV(var(d), var(l), var(r)),
+        (V(d), Mul, S(V(l)), Some(N(r))) | (V(d), Mul, N(r), Some(S(V(l)))) => {
+            DslIr::MulVI(var(d), var(l), r)
+        }
+
+        (Fe(d), Copy, F(c), None) => DslIr::ImmF(felt(d), c),
+        (Fe(d), Copy, S(Fe(s)), None) => DslIr::AddFI(felt(d), felt(s), C::F::ZERO),
+        (Fe(d), Neg, S(Fe(s)), None) => DslIr::NegF(felt(d), felt(s)),
+        (Fe(d), Add, S(Fe(l)), Some(S(Fe(r)))) => DslIr::AddF(felt(d), felt(l), felt(r)),
+        (Fe(d), Add, S(Fe(l)), Some(F(r))) | (Fe(d), Add, F(r), Some(S(Fe(l)))) => {
+            DslIr::AddFI(felt(d), felt(l), r)
+        }
+        (Fe(d), Sub, S(Fe(l)), Some(S(Fe(r)))) => DslIr::SubF(felt(d), felt(l), felt(r)),
+        (Fe(d), Sub, S(Fe(l)), Some(F(r))) => DslIr::SubFI(felt(d), felt(l), r),
+        (Fe(d), Sub, F(l), Some(S(Fe(r)))) => DslIr::SubFIN(felt(d), l, felt(r)),
+        (Fe(d), Mul, S(Fe(l)), Some(S(Fe(r)))) => DslIr::MulF(felt(d), felt(l), felt(r)),
+        (Fe(d), Mul, S(Fe(l)), Some(F(r))) | (Fe(d), Mul, F(r), Some(S(Fe(l)))) => {
+            DslIr::MulFI(felt(d), felt(l), r)
+        }
+        (Fe(d), Div, S(Fe(l)), Some(S(Fe(r)))) => DslIr::DivF(felt(d), felt(l), felt(r)),
+        (Fe(d), Div, S(Fe(l)), Some(F(r))) => DslIr::DivFI(felt(d), felt(l), r),
+        (Fe(d), Div, F(l), Some(S(Fe(r)))) => DslIr::DivFIN(felt(d), l, felt(r)),
+
+        (E(d), Copy, EF(c), None) => DslIr::ImmE(ext(d), c),
+        (E(d), Copy, S(E(s)), None) => DslIr::AddEI(ext(d), ext(s), C::EF::ZERO),
+        (E(d), Copy, S(Fe(s)), None) => DslIr::AddEFFI(ext(d), felt(s), C::EF::ZERO),
+        (E(d), Neg, S(E(s)), None) => DslIr::NegE(ext(d), ext(s)),
+        (E(d), Add, S(E(l)), Some(S(E(r)))) => DslIr::AddE(ext(d), ext(l), ext(r)),
+        (E(d), Add, S(E(l)), Some(EF(r))) | (E(d), Add, EF(r), Some(S(E(l)))) => match base(r) {
+            Some(r) => DslIr::AddEFI(ext(d), ext(l), r),
+            None => DslIr::AddEI(ext(d), ext(l), r),
+        },
+        (E(d), Add, S(E(l)), Some(S(Fe(r)))) | (E(d), Add, S(Fe(r)), Some(S(E(l)))) => {
+            DslIr::AddEF(ext(d), ext(l), felt(r))
+        }
+        (E(d), Add, S(Fe(l)), Some(EF(r))) | (E(d), Add, EF(r), Some(S(Fe(l)))) => {
+            DslIr::AddEFFI(ext(d), felt(l), r)
+        }
+        (E(d), Sub, S(E(l)), Some(S(E(r)))) => DslIr::SubE(ext(d), ext(l), ext(r)),
+        (E(d), Sub, S(E(l)), Some(EF(r))) => match base(r) {
+            Some(r) => DslIr::SubEFI(ext(d), ext(l), r),
+            None => DslIr::SubEI(ext(d), ext(l), r),
+        },
+        (E(d), Sub, EF(l), Some(S(E(r)))) => DslIr::SubEIN(ext(d), l, ext(r)),
+        (E(d), Sub, S(E(l)), Some(S(Fe(r)))) => DslIr::SubEF(ext(d), ext(l), felt(r)),
+        (E(d), Mul, S(E(l)), Some(S(E(r)))) => DslIr::MulE(ext(d), ext(l), ext(r)),
+        (E(d), Mul, S(E(l)), Some(EF(r))) | (E(d), Mul, EF(r), Some(S(E(l)))) => match base(r) {
+            Some(r) => DslIr::MulEFI(ext(d), ext(l), r),
+            None => DslIr::MulEI(ext(d), ext(l), r),
+        },
+        (E(d), Mul, S(E(l)), Some(S(Fe(r)))) | (E(d), Mul, S(Fe(r)), Some(S(E(l)))) => {
+            DslIr::MulEF(ext(d), ext(l), felt(r))
+        }
+        (E(d), Div, S(E(l)), Some(S(E(r)))) => DslIr::DivE(ext(d), ext(l), ext(r)),
+        // `DivEFI` is lowered with the inverse of the divisor, which must not be zero.
+        (E(d), Div, S(E(l)), Some(EF(r))) => match base(r).filter(|r| !r.is_zero()) {
+            Some(r) => DslIr::DivEFI(ext(d), ext(l), r),
+            None => DslIr::DivEI(ext(d), ext(l), r),
+        },
+        (E(d), Div, EF(l), Some(S(E(r)))) => DslIr::DivEIN(ext(d), l, ext(r)),
+        (E(d), Div, S(E(l)), Some(S(Fe(r)))) => DslIr::DivEF(ext(d), ext(l), felt(r)),
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// Number of native instructions an arithmetic instruction is lowered to by the assembly
+/// compiler.
+fn cost<C: Config>(op: &DslIr<C>) -> usize {
+    let d = <C::EF as FieldExtensionAlgebra<C::F>>::D;
+    match op {
+        DslIr::AddE(..) | DslIr::SubE(..) | DslIr::MulE(..) | DslIr::DivE(..) => 1,
+        DslIr::MulEI(..) | DslIr::DivEI(..) | DslIr::DivEIN(..) => d + 1,
+        DslIr::ImmE(..)
+        | DslIr::AddEI(..)
+        | DslIr::AddEF(..)
+        | DslIr::AddEFI(..)
+        | DslIr::AddEFFI(..)
+        | DslIr::SubEI(..)
+        | DslIr::SubEIN(..)
+        | DslIr::SubEFI(..)
+        | DslIr::SubEF(..)
+        | DslIr::MulEFI(..)
+        | DslIr::MulEF(..)
+        | DslIr::DivEFI(..)
+        | DslIr::DivEF(..)
+        | DslIr::NegE(..) => d,
+        _ => 1,
+    }
+}
+
+/// Key of an expression for common subexpression elimination: the type of the result, the
+/// operation and the operands, with variables paired with the version of their value.
+type ExprKey<C> = (Discriminant<Slot>, OpKind, [Option<(OperandOf<C>, u32)>; 2]);
+
+/// Facts known to hold at a point of the program.
+///
+/// Every assignment of a variable gets a new version, so that facts about an older value are
+/// invalidated without having to look for them. Facts learned inside a nested block are undone
+/// when leaving it.
+#[derive(Default)]
+struct Facts<C: Config> {
+    next_version: u32,
+    versions: HashMap<Slot, u32>,
+    constants: HashMap<Slot, OperandOf<C>>,
+    /// Variables that hold a copy of another variable at a given version.
+    copies: HashMap<Slot, (Slot, u32)>,
+    /// Variables at a given version that hold the value of an expression.
+    exprs: HashMap<ExprKey<C>, (Slot, u32)>,
+    /// Previous values of the facts changed since entering the innermost nested block.
+    journal: Vec<Undo<C>>,
+    depth: usize,
+}
+
+enum Undo<C: Config> {
+    Version(Slot, Option<u32>),
+    Constant(Slot, Option<OperandOf<C>>),
+    Copy(Slot, Option<(Slot, u32)>),
+    Expr(ExprKey<C>, Option<(Slot, u32)>),
+}
+
+impl<C: Config> Facts<C> {
+    fn version(&self, slot: Slot) -> u32 {
+        self.versions.get(&slot).copied().unwrap_or_default()
+    }
+
+    /// Returns the constant or the variable holding the same value as `slot`. Felt constants
+    /// are converted to `EF` if `in_ext` is set.
+    fn resolve(&self, slot: Slot, in_ext: bool) -> OperandOf<C> {
+        let resolved = match self.constants.get(&slot) {
+            Some(constant) => *constant,
+            None => match self.copies.get(&slot) {
+                Some(&(src, version)) if self.version(src) == version => Operand::Slot(src),
+                _ => Operand::Slot(slot),
+            },
+        };
+        match resolved {
+            Operand::F(c) if in_ext => Operand::EF(C::EF::from_base(c)),
+            resolved => resolved,
+        }
+    }
+
+    fn key(&self, arith: &ArithOf<C>) -> ExprKey<C> {
+        let versioned = |operand: OperandOf<C>| match operand {
+            Operand::Slot(slot) => (operand, self.version(slot)),
+            constant => (constant, 0),
+        };
+        (
+            discriminant(&arith.dst),
+            arith.kind,
+            [Some(versioned(arith.lhs)), arith.rhs.map(versioned)],
+        )
+    }
+
+    /// Records that `slot` was assigned a new value.
+    fn assign(&mut self, slot: Slot) {
+        self.next_version += 1;
+        let old = self.versions.insert(slot, self.next_version);
+        self.record(Undo::Version(slot, old));
+        let old = self.constants.remove(&slot);
+        self.record(Undo::Constant(slot, old));
+        let old = self.copies.remove(&slot);
+        self.record(Undo::Copy(slot, old));
+    }
+
+    fn insert_constant(&mut self, slot: Slot, constant: OperandOf<C>) {
+        let old = self.constants.insert(slot, constant);
+        self.record(Undo::Constant(slot, old));
+    }
+
+    fn insert_copy(&mut self, slot: Slot, src: Slot) {
+        let old = self.copies.insert(slot, (src, self.version(src)));
+        self.record(Undo::Copy(slot, old));
+    }
+
+    fn insert_expr(&mut self, key: ExprKey<C>, slot: Slot) {
+        let old = self.exprs.insert(key, (slot, self.version(slot)));
+        self.record(Undo::Expr(key, old));
+    }
+
+    fn record(&mut self, undo: Undo<C>) {
+        if self.depth > 0 {
+            self.journal.push(undo);
+        }
+    }
+
+    /// Enters a nested block. Returns the checkpoint to pass to [Self::leave].
+    fn enter(&mut self) -> usize {
+        self.depth += 1;
+        self.journal.len()
+    }
+
+    /// Leaves a nested block, undoing all facts learned since `checkpoint`.
+    fn leave(&mut self, checkpoint: usize) {
+        for undo in self.journal.drain(checkpoint..).rev() {
+            match undo {
+                Undo::Version(slot, old) => restore(&mut self.versions, slot, old),
+                Undo::Constant(slot, old) => restore(&mut self.constants, slot, old),
+                Undo::Copy(slot, old) => restore(&mut self.copies, slot, old),
+                Undo::Expr(key, old) => restore(&mut self.exprs, key, old),
+            }
+        }
+        self.depth -= 1;
+    }
+}
+
+fn restore<K: Eq + Hash, V>(map: &mut HashMap<K, V>, key: K, old: Option<V>) {
+    match old {
+        Some(value) => map.insert(key, value),
+        None => map.remove(&key),
+    };
+}
+
+fn block<C: Config>(
+    operations: TracedVec<DslIr<C>>,
+    functions: &Functions,
+    facts: &mut Facts<C>,
+) -> TracedVec<DslIr<C>> {
+    let mut optimized = TracedVec::new();
+    for (op, trace) in operations {
+        if let Some(op) = instruction(op, functions, facts) {
+            optimized.extend([(op, trace)]);
+        }
+    }
+    optimized
+}
+
+/// Optimizes `op` and updates `facts` to hold after it. Returns `None` if `op` has no effect.
+fn instruction<C: Config>(
+    op: DslIr<C>,
+    functions: &Functions,
+    facts: &mut Facts<C>,
+) -> Option<DslIr<C>> {
+    if let Some(arith) = to_arith(&op) {
+        return arithmetic(op, arith, facts);
+    }
+    let op = match op {
+        DslIr::IfEq(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) = branches(then_block, else_block, functions, facts);
+            DslIr::IfEq(lhs, rhs, then_block, else_block)
+        }
+        DslIr::IfNe(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) = branches(then_block, else_block, functions, facts);
+            DslIr::IfNe(lhs, rhs, then_block, else_block)
+        }
+        DslIr::IfEqI(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) = branches(then_block, else_block, functions, facts);
+            DslIr::IfEqI(lhs, rhs, then_block, else_block)
+        }
+        DslIr::IfNeI(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) = branches(then_block, else_block, functions, facts);
+            DslIr::IfNeI(lhs, rhs, then_block, else_block)
+        }
+        DslIr::ZipFor(starts, end0, step_sizes, loop_vars, body) => {
+            // Only facts about variables that are not assigned in the loop hold at the start of
+            // every iteration.
+            let mut assigned = HashSet::new();
+            loop_vars.collect_slots(&mut assigned);
+            body.vec
+                .iter()
+                .for_each(|op| may_assign(op, functions, &mut assigned));
+            assigned.into_iter().for_each(|slot| facts.assign(slot));
+            let checkpoint = facts.enter();
+            let body = block(body, functions, facts);
+            facts.leave(checkpoint);
+            DslIr::ZipFor(starts, end0, step_sizes, loop_vars, body)
+        }
+        // Nothing is known about the variables when the function is called.
+        DslIr::FnDef(name, return_address, body) => {
+            let body = block(body, functions, &mut Facts::default());
+            DslIr::FnDef(name, return_address, body)
+        }
+        op => {
+            let mut assigned = HashSet::new();
+            may_assign(&op, functions, &mut assigned);
+            assigned.into_iter().for_each(|slot| facts.assign(slot));
+            op
+        }
+    };
+    Some(op)
+}
+
+fn branches<C: Config>(
+    then_block: TracedVec<DslIr<C>>,
+    else_block: TracedVec<DslIr<C>>,
+    functions: &Functions,
+    facts: &mut Facts<C>,
+) -> (TracedVec<DslIr<C>>, TracedVec<DslIr<C>>) {
+    let mut assigned = HashSet::new();
+    for op in then_block.vec.iter().chain(&else_block.vec) {
+        may_assign(op, functions, &mut assigned);
+    }
+    let checkpoint = facts.enter();
+    let then_block = block(then_block, functions, facts);
+    facts.leave(checkpoint);
+    let checkpoint = facts.enter();
+    let else_block = block(else_block, functions, facts);
+    facts.leave(checkpoint);
+    assigned.into_iter().for_each(|slot| facts.assign(slot));
+    (then_block, else_block)
+}
+
+fn arithmetic<C: Config>(
+    op: DslIr<C>,
+    arith: ArithOf<C>,
+    facts: &mut Facts<C>,
+) -> Option<DslIr<C>> {
+    let in_ext = matches!(arith.dst, Slot::Ext(_));
+    let resolve = |operand: OperandOf<C>| match operand {
+        Operand::Slot(slot) => facts.resolve(slot, in_ext),
+        constant => constant,
+    };
+    let mut simplified = Arith {
+        lhs: resolve(arith.lhs),
+        rhs: arith.rhs.map(resolve),
+        ..arith
+    }
+    .simplify();
+    let key = (simplified.kind != OpKind::Copy).then(|| facts.key(&simplified));
+    if let Some(&(slot, version)) = key.as_ref().and_then(|key| facts.exprs.get(key)) {
+        if facts.version(slot) == version {
+            simplified = Arith::copy(arith.dst, Operand::Slot(slot));
+        }
+    }
+    if simplified.kind == OpKind::Copy && simplified.lhs == Operand::Slot(arith.dst) {
+        return None;
+    }
+
+    let reads_dst = simplified
+        .operands()
+        .any(|operand| operand == Operand::Slot(arith.dst));
+    facts.assign(arith.dst);
+    match (simplified.kind, simplified.lhs) {
+        (OpKind::Copy, Operand::Slot(src)) => facts.insert_copy(arith.dst, src),
+        (OpKind::Copy, constant) => facts.insert_constant(arith.dst, constant),
+        _ if !reads_dst => facts.insert_expr(key.unwrap(), arith.dst),
+        _ => {}
+    }
+
+    let optimized = from_arith(simplified).filter(|new| cost(new) <= cost(&op));
+    Some(optimized.unwrap_or(op))
+}
+
+/// Adds the variables that `op` may assign to `assigned`. Instructions other than arithmetic,
+/// loads and control flow may assign any variable they mention.
+fn may_assign<C: Config>(op: &DslIr<C>, functions: &Functions, assigned: &mut HashSet<Slot>) {
+    if let Some(arith) = to_arith(op) {
+        assigned.insert(arith.dst);
+        return;
+    }
+    match op {
+        DslIr::ZipFor(_, _, _, loop_vars, block) => {
+            loop_vars.collect_slots(assigned);
+            block
+                .vec
+                .iter()
+                .for_each(|op| may_assign(op, functions, assigned));
+        }
+        DslIr::IfEq(_, _, then_block, else_block)
+        | DslIr::IfNe(_, _, then_block, else_block)
+        | DslIr::IfEqI(_, _, then_block, else_block)
+        | DslIr::IfNeI(_, _, then_block, else_block) => {
+            for op in then_block.vec.iter().chain(&else_block.vec) {
+                may_assign(op, functions, assigned);
+            }
+        }
+        _ => {
+            if let Some(dst) = assigned_slot(op) {
+                assigned.insert(dst);
+            } else if !is_read_only(op) {
+                collect_slots(op, functions, assigned);
+            }
+        }
+    }
+}
+
+/// Returns the variable assigned by a non-arithmetic instruction that assigns exactly one
+/// variable.
+fn assigned_slot<C: Config>(op: &DslIr<C>) -> Option<Slot> {
+    match op {
+        DslIr::CastFV(dst, _) => Some(Slot::Var(dst.0)),
+        DslIr::UnsafeCastVF(dst, _) => Some(Slot::Felt(dst.0)),
+        DslIr::LoadV(dst, ..) => Some(Slot::Var(dst.0)),
+        DslIr::LoadF(dst, ..) => Some(Slot::Felt(dst.0)),
+        DslIr::LoadE(dst, ..) => Some(Slot::Ext(dst.0)),
+        DslIr::Alloc(ptr, ..) | DslIr::LoadHeapPtr(ptr) => Some(Slot::Var(ptr.address.0)),
+        _ => None,
+    }
+}
+
+/// Returns whether `op` reads the variables it mentions without assigning any of them.
+fn is_read_only<C: Config>(op: &DslIr<C>) -> bool {
+    matches!(
+        op,
+        DslIr::AssertEqV(..)
+            | DslIr::AssertEqF(..)
+            | DslIr::AssertEqE(..)
+            | DslIr::AssertEqVI(..)
+            | DslIr::AssertEqFI(..)
+            | DslIr::AssertEqEI(..)
+            | DslIr::AssertNonZero(..)
+            | DslIr::StoreV(..)
+            | DslIr::StoreF(..)
+            | DslIr::StoreE(..)
+            | DslIr::StoreHeapPtr(..)
+            | DslIr::StoreHintWord(..)
+            | DslIr::PrintV(..)
+            | DslIr::PrintF(..)
+            | DslIr::PrintE(..)
+            | DslIr::Error()
+            | DslIr::HintInputVec()
+            | DslIr::HintFelt()
+            | DslIr::HintBitsF(..)
+            | DslIr::HintLoad()
+            | DslIr::Publish(..)
+            | DslIr::Halt
+            | DslIr::CircuitPublish(..)
+            | DslIr::RangeCheckV(..)
+            | DslIr::CycleTrackerStart(..)
+            | DslIr::CycleTrackerEnd(..)
+    )
+}
+
+/// Adds all variables mentioned by `op`, including in nested blocks and by called functions, to
+/// `slots`. The body of a function definition is only mentioned by its calls.
+fn collect_slots<C: Config>(op: &DslIr<C>, functions: &Functions, slots: &mut HashSet<Slot>) {
+    if let Some(arith) = to_arith(op) {
+        slots.insert(arith.dst);
+        for operand in arith.operands() {
+            if let Operand::Slot(slot) = operand {
+                slots.insert(slot);
+            }
+        }
+        return;
+    }
+    match op {
+        DslIr::ZipFor(starts, end0, _, loop_vars, block) => {
+            starts.collect_slots(slots);
+            end0.collect_slots(slots);
+            loop_vars.collect_slots(slots);
+            block
+                .vec
+                .iter()
+                .for_each(|op| collect_slots(op, functions, slots));
+        }
+        DslIr::IfEq(lhs, rhs, then_block, else_block)
+        | DslIr::IfNe(lhs, rhs, then_block, else_block) => {
+            lhs.collect_slots(slots);
+            rhs.collect_slots(slots);
+            for op in then_block.vec.iter().chain(&else_block.vec) {
+                collect_slots(op, functions, slots);
+            }
+        }
+        DslIr::IfEqI(lhs, _, then_block, else_block)
+        | DslIr::IfNeI(lhs, _, then_block, else_block) => {
+            lhs.collect_slots(slots);
+            for op in then_block.vec.iter().chain(&else_block.vec) {
+                collect_slots(op, functions, slots);
+            }
+        }
+        DslIr::FnDef(..) => {}
+        DslIr::Call(name) => slots.extend(&functions[name]),
+        DslIr::CastFV(a, b) => (a, b).collect_slots(slots),
+        DslIr::UnsafeCastVF(a, b) => (a, b).collect_slots(slots),
+        DslIr::AssertEqV(a, b) => (a, b).collect_slots(slots),
+        DslIr::AssertEqF(a, b) => (a, b).collect_slots(slots),
+        DslIr::AssertEqE(a, b) => (a, b).collect_slots(slots),
+        DslIr::AssertEqVI(a, _) => a.collect_slots(slots),
+        DslIr::AssertEqFI(a, _) => a.collect_slots(slots),
+        DslIr::AssertEqEI(a, _) => a.collect_slots(slots),
+        DslIr::AssertNonZero(a) => a.collect_slots(slots),
+        DslIr::Alloc(ptr, len, _) => (ptr, len).collect_slots(slots),
+        DslIr::LoadV(a, ptr, index) | DslIr::StoreV(a, ptr, index) => {
+            (a, (ptr, index)).collect_slots(slots)
+        }
+        DslIr::LoadF(a, ptr, index) | DslIr::StoreF(a, ptr, index) => {
+            (a, (ptr, index)).collect_slots(slots)
+        }
+        DslIr::LoadE(a, ptr, index) | DslIr::StoreE(a, ptr, index) => {
+            (a, (ptr, index)).collect_slots(slots)
+        }
+        DslIr::LoadHeapPtr(ptr) | DslIr::StoreHeapPtr(ptr) => ptr.collect_slots(slots),
+        DslIr::CircuitNum2BitsF(a, bits) => (a, bits).collect_slots(slots),
+        DslIr::CircuitVarTo64BitsF(a, limbs) => (a, limbs).collect_slots(slots),
+        DslIr::Poseidon2PermuteBabyBear(a, b) => (a, b).collect_slots(slots),
+        DslIr::Poseidon2CompressBabyBear(a, b, c) => (a, (b, c)).collect_slots(slots),
+        DslIr::CircuitPoseidon2Permute(a) => a.collect_slots(slots),
+        DslIr::PrintV(a) => a.collect_slots(slots),
+        DslIr::PrintF(a) => a.collect_slots(slots),
+        DslIr::PrintE(a) => a.collect_slots(slots),
+        DslIr::HintBitsF(a, _) => a.collect_slots(slots),
+        DslIr::StoreHintWord(ptr, index) => (ptr, index).collect_slots(slots),
+        DslIr::WitnessVar(a, _) => a.collect_slots(slots),
+        DslIr::WitnessFelt(a, _) => a.collect_slots(slots),
+        DslIr::WitnessExt(a, _) => a.collect_slots(slots),
+        DslIr::Publish(a, b) => (a, b).collect_slots(slots),
+        DslIr::CircuitPublish(a, _) => a.collect_slots(slots),
+        DslIr::CircuitSelectV(a, b, c, d) => ((a, b), (c, d)).collect_slots(slots),
+        DslIr::CircuitSelectF(a, b, c, d) => ((a, b), (c, d)).collect_slots(slots),
+        DslIr::CircuitSelectE(a, b, c, d) => ((a, b), (c, d)).collect_slots(slots),
+        DslIr::CircuitExt2Felt(a, b) | DslIr::CircuitFelts2Ext(a, b) => (a, b).collect_slots(slots),
+        DslIr::CircuitFeltReduce(a) => a.collect_slots(slots),
+        DslIr::CircuitExtReduce(a) => a.collect_slots(slots),
+        DslIr::CircuitLessThan(a, b) => (a, b).collect_slots(slots),
+        DslIr::FriReducedOpening(a, b, c, d, e, f) => ((a, b), (c, d), (e, f)).collect_slots(slots),
+        DslIr::VerifyBatchFelt(a, b, c, d, e) => ((a, b), (c, d), e).collect_slots(slots),
+        DslIr::VerifyBatchExt(a, b, c, d, e) => ((a, b), (c, d), e).collect_slots(slots),
+        DslIr::RangeCheckV(a, _) => a.collect_slots(slots),
+        DslIr::Error()
+        | DslIr::HintInputVec()
+        | DslIr::HintFelt()
+        | DslIr::HintLoad()
+        | DslIr::Halt
+        | DslIr::CycleTrackerStart(_)
+        | DslIr::CycleTrackerEnd(_) => {}
+        _ => unreachable!("arithmetic instructions are handled above"),
+    }
+}
+
+/// Types that mention DSL variables.
+trait CollectSlots {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>);
+}
+
+impl<N> CollectSlots for Var<N> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        slots.insert(Slot::Var(self.0));
+    }
+}
+
+impl<F> CollectSlots for Felt<F> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        slots.insert(Slot::Felt(self.0));
+    }
+}
+
+impl<F, EF> CollectSlots for Ext<F, EF> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        slots.insert(Slot::Ext(self.0));
+    }
+}
+
+impl<N> CollectSlots for RVar<N> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        if let RVar::Val(var) = self {
+            var.collect_slots(slots);
+        }
+    }
+}
+
+impl<N> CollectSlots for Usize<N> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        if let Usize::Var(var) = self {
+            var.collect_slots(slots);
+        }
+    }
+}
+
+impl<N> CollectSlots for Ptr<N> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        self.address.collect_slots(slots);
+    }
+}
+
+impl<N> CollectSlots for MemIndex<N> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        self.index.collect_slots(slots);
+    }
+}
+
+impl<C: Config, T: CollectSlots> CollectSlots for Array<C, T> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        match self {
+            Array::Fixed(values) => values.borrow().iter().flatten().for_each(|value| {
+                value.collect_slots(slots);
+            }),
+            Array::Dyn(ptr, len) => (ptr, len).collect_slots(slots),
+        }
+    }
+}
+
+impl<T: CollectSlots> CollectSlots for [T] {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        self.iter().for_each(|value| value.collect_slots(slots));
+    }
+}
+
+impl<T: CollectSlots, const N: usize> CollectSlots for [T; N] {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        self.as_slice().collect_slots(slots);
+    }
+}
+
+impl<T: CollectSlots> CollectSlots for Vec<T> {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        self.as_slice().collect_slots(slots);
+    }
+}
+
+impl<T: CollectSlots + ?Sized> CollectSlots for &T {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        (**self).collect_slots(slots);
+    }
+}
+
+impl<A: CollectSlots, B: CollectSlots> CollectSlots for (A, B) {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        self.0.collect_slots(slots);
+        self.1.collect_slots(slots);
+    }
+}
+
+impl<A: CollectSlots, B: CollectSlots, D: CollectSlots> CollectSlots for (A, B, D) {
+    fn collect_slots(&self, slots: &mut HashSet<Slot>) {
+        self.0.collect_slots(slots);
+        self.1.collect_slots(slots);
+        self.2.collect_slots(slots);
+    }
+}
+
+/// Removes dead arithmetic instructions from `operations`. `live` holds the variables read
+/// after `operations` on entry and before them on exit.
+fn dead_code_block<C: Config>(
+    operations: TracedVec<DslIr<C>>,
+    functions: &Functions,
+    live: &mut HashSet<Slot>,
+) -> TracedVec<DslIr<C>> {
+    let mut kept: Vec<(DslIr<C>, Option<Backtrace>)> = Vec::with_capacity(operations.vec.len());
+    for (op, trace) in operations.vec.into_iter().zip(operations.traces).rev() {
+        if let Some(op) = dead_code_instruction(op, functions, live) {
+            kept.push((op, trace));
+        }
+    }
+    let mut optimized = TracedVec::new();
+    optimized.extend(kept.into_iter().rev());
+    optimized
+}
+
+fn dead_code_instruction<C: Config>(
+    op: DslIr<C>,
+    functions: &Functions,
+    live: &mut HashSet<Slot>,
+) -> Option<DslIr<C>> {
+    if let Some(arith) = to_arith(&op) {
+        if arith.kind != OpKind::Div && !live.contains(&arith.dst) {
+            return None;
+        }
+        live.remove(&arith.dst);
+        for operand in arith.operands() {
+            if let Operand::Slot(slot) = operand {
+                live.insert(slot);
+            }
+        }
+        return Some(op);
+    }
+    let op = match op {
+        DslIr::IfEq(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) =
+                dead_code_branches(then_block, else_block, functions, live);
+            (lhs, rhs).collect_slots(live);
+            DslIr::IfEq(lhs, rhs, then_block, else_block)
+        }
+        DslIr::IfNe(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) =
+                dead_code_branches(then_block, else_block, functions, live);
+            (lhs, rhs).collect_slots(live);
+            DslIr::IfNe(lhs, rhs, then_block, else_block)
+        }
+        DslIr::IfEqI(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) =
+                dead_code_branches(then_block, else_block, functions, live);
+            lhs.collect_slots(live);
+            DslIr::IfEqI(lhs, rhs, then_block, else_block)
+        }
+        DslIr::IfNeI(lhs, rhs, then_block, else_block) => {
+            let (then_block, else_block) =
+                dead_code_branches(then_block, else_block, functions, live);
+            lhs.collect_slots(live);
+            DslIr::IfNeI(lhs, rhs, then_block, else_block)
+        }
+        DslIr::ZipFor(starts, end0, step_sizes, loop_vars, block) => {
+            // Everything the loop reads is live at the end of its body, for the next iteration.
+            (&starts, &end0, &loop_vars).collect_slots(live);
+            block
+                .vec
+                .iter()
+                .for_each(|op| collect_slots(op, functions, live));
+            let block = dead_code_block(block, functions, &mut live.clone());
+            DslIr::ZipFor(starts, end0, step_sizes, loop_vars, block)
+        }
+        // Any variable of the function may be read after it returns.
+        DslIr::FnDef(name, return_address, body) => {
+            let body = dead_code_block(body, functions, &mut functions[&name].clone());
+            DslIr::FnDef(name, return_address, body)
+        }
+        DslIr::CastFV(dst, src) => {
+            if !live.remove(&Slot::Var(dst.0)) {
+                return None;
+            }
+            src.collect_slots(live);
+            DslIr::CastFV(dst, src)
+        }
+        DslIr::UnsafeCastVF(dst, src) => {
+            if !live.remove(&Slot::Felt(dst.0)) {
+                return None;
+            }
+            src.collect_slots(live);
+            DslIr::UnsafeCastVF(dst, src)
+        }
+        DslIr::LoadV(dst, ptr, index) => {
+            live.remove(&Slot::Var(dst.0));
+            (ptr, index).collect_slots(live);
+            DslIr::LoadV(dst, ptr, index)
+        }
+        DslIr::LoadF(dst, ptr, index) => {
+            live.remove(&Slot::Felt(dst.0));
+            (ptr, index).collect_slots(live);
+            DslIr::LoadF(dst, ptr, index)
+        }
+        DslIr::LoadE(dst, ptr, index) => {
+            live.remove(&Slot::Ext(dst.0));
+            (ptr, index).collect_slots(live);
+            DslIr::LoadE(dst, ptr, index)
+        }
+        // Over-approximates the variables read by `op`, which keeps them alive.
+        op => {
+            collect_slots(&op, functions, live);
+            op
+        }
+    };
+    Some(op)
+}
+
+fn dead_code_branches<C: Config>(
+    then_block: TracedVec<DslIr<C>>,
+    else_block: TracedVec<DslIr<C>>,
+    functions: &Functions,
+    live: &mut HashSet<Slot>,
+) -> (TracedVec<DslIr<C>>, TracedVec<DslIr<C>>) {
+    let mut then_live = live.clone();
+    let then_block = dead_code_block(then_block, functions, &mut then_live);
+    let mut else_live = live.clone();
+    let else_block = dead_code_block(else_block, functions, &mut else_live);
+    *live = then_live;
+    live.extend(else_live);
+    (then_block, else_block)
+}
@@ -1,7 +1,7 @@
 use openvm_native_circuit::execute_program;
 use openvm_native_compiler::{
     asm::AsmBuilder,
-    ir::{Ext, Felt},
+    ir::{Ext, ExtConst, Felt},
 };
 use openvm_stark_backend::p3_field::{
     extension::BinomialExtensionField, FieldAlgebra, FieldExtensionAlgebra,
@@ -62,3 +62,31 @@ fn test_ext_from_base_slice() {
     println!("{}", program);
     execute_program(program, vec![]);
 }
+
+#[test]
+fn test_felt_plus_ext_const() {
+    const D: usize = 4;
+    type F = BabyBear;
+    type EF = BinomialExtensionField<BabyBear, D>;
+
+    let mut builder = AsmBuilder::<F, EF>::default();
+
+    let base_slice = [
+        F::from_canonical_usize(123),
+        F::from_canonical_usize(234),
+        F::from_canonical_usize(345),
+        F::from_canonical_usize(456),
+    ];
+    let val = EF::from_base_slice(&base_slice);
+
+    let felt: Felt<_> = builder.constant(F::from_canonical_usize(7));
+    // Lowered to AddEFFI, whose constant has a nonzero base coefficient.
+    let actual: Ext<_, _> = builder.eval(val.cons() + felt);
+    let expected: Ext<_, _> = builder.constant(val + EF::from_canonical_usize(7));
+    builder.assert_ext_eq(actual, expected);
+
+    builder.halt();
+
+    let program = builder.compile_isa();
+    execute_program(program, vec![]);
+}
@@ -0,0 +1,157 @@
+use openvm_native_circuit::execute_program;
+use openvm_native_compiler::{
+    asm::{AsmBuilder, AsmConfig},
+    conversion::CompilerOptions,
+    ir::{Builder, DslIr, Ext, ExtConst, Felt, Var},
+};
+use openvm_stark_backend::p3_field::{extension::BinomialExtensionField, FieldAlgebra};
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+type F = BabyBear;
+type EF = BinomialExtensionField<BabyBear, 4>;
+
+#[test]
+fn test_compiler_functions() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+
+    let count: Var<_> = builder.eval(F::ZERO);
+    let mul_add = builder.define_fn(
+        "mul_add",
+        |builder, (a, b, c): (Felt<F>, Felt<F>, Felt<F>)| {
+            // Functions can assign variables of the enclosing scope.
+            builder.assign(&count, count + F::ONE);
+            let ret: Felt<_> = builder.eval(a * b + c);
+            ret
+        },
+    );
+    let square_plus_one = builder.define_fn("square_plus_one", |builder, x: Ext<F, EF>| {
+        let ret: Ext<_, _> = builder.eval(x * x + EF::ONE.cons());
+        ret
+    });
+    // Functions can call previously defined functions.
+    let cube_plus_two = builder.define_fn("cube_plus_two", |builder, x: Felt<F>| {
+        let zero: Felt<_> = builder.eval(F::ZERO);
+        let two: Felt<_> = builder.eval(F::TWO);
+        let square = builder.call(&mul_add, (x, x, zero));
+        builder.call(&mul_add, (square, x, two))
+    });
+
+    let two: Felt<_> = builder.eval(F::TWO);
+    let three: Felt<_> = builder.eval(F::from_canonical_u32(3));
+    let seven = builder.call(&mul_add, (two, two, three));
+    let twenty_four = builder.call(&mul_add, (seven, three, three));
+    builder.assert_felt_eq(seven, F::from_canonical_u32(7));
+    builder.assert_felt_eq(twenty_four, F::from_canonical_u32(24));
+
+    let x: Ext<_, _> = builder.eval(EF::TWO.cons());
+    let five = builder.call(&square_plus_one, x);
+    let twenty_six = builder.call(&square_plus_one, five);
+    builder.assert_ext_eq(five, EF::from_canonical_u32(5).cons());
+    builder.assert_ext_eq(twenty_six, EF::from_canonical_u32(26).cons());
+
+    // Calls from loops and branches.
+    let acc: Felt<_> = builder.eval(F::ZERO);
+    let n: Var<_> = builder.eval(F::from_canonical_u32(5));
+    builder.range(0, n).for_each(|_, builder| {
+        let cube = builder.call(&cube_plus_two, two);
+        builder.assign(&acc, acc + cube);
+    });
+    builder.assert_felt_eq(acc, F::from_canonical_u32(50));
+    let zero: Var<_> = builder.eval(F::ZERO);
+    builder.if_ne(zero, n).then_or_else(
+        |builder| {
+            let value = builder.call(&cube_plus_two, three);
+            builder.assert_felt_eq(value, F::from_canonical_u32(29));
+        },
+        |builder| {
+            let value = builder.call(&cube_plus_two, two);
+            builder.assert_felt_eq(value, F::ZERO);
+        },
+    );
+
+    // 2 direct calls, 2 per call of `cube_plus_two`.
+    builder.assert_var_eq(count, F::from_canonical_u32(2 + 2 * 6));
+
+    builder.halt();
+
+    let optimized = builder
+        .clone()
+        .compile_isa_with_options(CompilerOptions::default().with_optimizations());
+    execute_program(optimized, vec![]);
+    let program = builder.compile_isa();
+    execute_program(program, vec![]);
+}
+
+/// Calling a function instead of inlining its body emits the body once.
+#[test]
+fn test_compiler_functions_program_size() {
+    fn body(builder: &mut Builder<AsmConfig<F, EF>>, x: Ext<F, EF>) -> Ext<F, EF> {
+        let acc: Ext<_, _> = builder.eval(x);
+        for i in 0..20 {
+            builder.assign(&acc, acc * x + EF::from_canonical_u32(i).cons());
+        }
+        acc
+    }
+
+    let build = |use_fn: bool| {
+        let mut builder = AsmBuilder::<F, EF>::default();
+        let f = builder.define_fn("body", body);
+        let x: Ext<_, _> = builder.eval(EF::TWO.cons());
+        let first = body(&mut builder, x);
+        for _ in 0..10 {
+            let result = if use_fn {
+                builder.call(&f, x)
+            } else {
+                body(&mut builder, x)
+            };
+            builder.assert_ext_eq(result, first);
+        }
+        builder.halt();
+        builder.compile_isa()
+    };
+
+    let inlined = build(false);
+    let called = build(true);
+    assert!(
+        2 * called.num_defined_instructions() < inlined.num_defined_instructions(),
+        "{} vs {}",
+        called.num_defined_instructions(),
+        inlined.num_defined_instructions()
+    );
+    execute_program(inlined, vec![]);
+    execute_program(called, vec![]);
+}
+
+#[test]
+fn test_compiler_functions_static_mode_inlines() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+    builder.flags.static_only = true;
+
+    let double = builder.define_fn("double", |builder, x: Felt<F>| {
+        let ret: Felt<_> = builder.eval(x + x);
+        ret
+    });
+    let two: Felt<_> = builder.eval(F::TWO);
+    let four = builder.call(&double, two);
+    builder.assert_felt_eq(four, F::from_canonical_u32(4));
+
+    assert!(!builder
+        .operations
+        .vec
+        .iter()
+        .any(|op| matches!(op, DslIr::FnDef(..) | DslIr::Call(..))));
+}
+
+#[test]
+#[should_panic = "called out of scope"]
+fn test_compiler_functions_out_of_scope() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+
+    let zero: Var<_> = builder.eval(F::ZERO);
+    let mut f = None;
+    builder.if_eq(zero, zero).then(|builder| {
+        f = Some(builder.define_fn("identity", |_, x: Felt<F>| x));
+    });
+    let one: Felt<_> = builder.eval(F::ONE);
+    builder.call(f.as_ref().unwrap(), one);
+}
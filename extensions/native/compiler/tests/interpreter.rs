@@ -0,0 +1,161 @@
+use openvm_native_circuit::execute_program_differential;
+use openvm_native_compiler::{
+    asm::AsmBuilder,
+    conversion::CompilerOptions,
+    interpreter::{Interpreter, InterpreterErrorKind},
+    ir::{Array, Ext, ExtConst, Felt, Var},
+};
+use openvm_stark_backend::p3_field::{extension::BinomialExtensionField, FieldAlgebra};
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+type F = BabyBear;
+type EF = BinomialExtensionField<BabyBear, 4>;
+
+fn build_program() -> AsmBuilder<F, EF> {
+    let mut builder = AsmBuilder::<F, EF>::default();
+
+    // Arithmetic on every kind of value.
+    let a: Felt<_> = builder.eval(F::from_canonical_u32(7));
+    let b: Felt<_> = builder.eval(a * a - F::ONE);
+    let c: Felt<_> = builder.eval(b / a);
+    builder.assert_felt_eq(c * a, b);
+    let x: Ext<_, _> = builder.eval(EF::from_canonical_u32(3).cons() + a);
+    builder.assert_ext_eq(x, EF::from_canonical_u32(10).cons());
+    let y: Ext<_, _> = builder.eval(x * x / (x - a));
+    builder.assert_ext_eq(
+        y,
+        EF::from_canonical_u32(100).cons() / EF::from_canonical_u32(3).cons(),
+    );
+
+    // Arrays, loops and branches.
+    let n: Var<_> = builder.eval(F::from_canonical_u32(5));
+    let squares: Array<_, Var<_>> = builder.dyn_array(n);
+    builder.range(0, n).for_each(|i_vec, builder| {
+        let i = i_vec[0];
+        builder.set(&squares, i, i * i);
+    });
+    let sum: Var<_> = builder.eval(F::ZERO);
+    builder.range(0, n).for_each(|i_vec, builder| {
+        let square = builder.get(&squares, i_vec[0]);
+        builder
+            .if_ne(square, F::from_canonical_u32(9))
+            .then_or_else(
+                |builder| builder.assign(&sum, sum + square),
+                |builder| builder.assign(&sum, sum + F::ONE),
+            );
+    });
+    builder.assert_var_eq(sum, F::from_canonical_u32(22));
+
+    // Hints and bit decomposition.
+    let hinted = builder.hint_felts();
+    builder.assert_var_eq(hinted.len(), F::TWO);
+    let first = builder.get(&hinted, 0);
+    let bits = builder.num2bits_f(first, 8);
+    let bit = builder.get(&bits, 1);
+    builder.assert_var_eq(bit, F::ONE);
+
+    // Poseidon2.
+    let state: Array<_, Felt<_>> = builder.dyn_array(16);
+    builder.range(0, 16).for_each(|i_vec, builder| {
+        let value: Felt<_> = builder.eval(F::ZERO);
+        builder.set(&state, i_vec[0], value);
+    });
+    builder.set(&state, 0, first);
+    let permuted = builder.poseidon2_permute(&state);
+    let _ = builder.get(&permuted, 0);
+
+    // Functions.
+    let mul_add = builder.define_fn(
+        "mul_add",
+        |builder, (a, b, c): (Felt<F>, Felt<F>, Felt<F>)| {
+            let ret: Felt<_> = builder.eval(a * b + c);
+            ret
+        },
+    );
+    let result = builder.call(&mul_add, (a, b, c));
+    builder.assert_felt_eq(
+        result,
+        F::from_canonical_u32(7 * 48) + F::from_canonical_u32(48) / F::from_canonical_u32(7),
+    );
+
+    // FRI reduced opening of hinted values.
+    let alpha: Ext<_, _> = builder.eval(EF::from_canonical_u32(2).cons());
+    let ps_at_z: Array<_, Ext<_, _>> = builder.dyn_array(3);
+    builder.range(0, 3).for_each(|i_vec, builder| {
+        builder.set(&ps_at_z, i_vec[0], x);
+    });
+    let hint_id = builder.hint_load();
+    let ps_at_x = builder.dyn_array(3);
+    let is_init = builder.constant(F::ZERO);
+    let _ = builder.fri_single_reduced_opening_eval(alpha, hint_id, is_init, &ps_at_x, &ps_at_z);
+
+    builder.halt();
+    builder
+}
+
+fn input_stream() -> Vec<Vec<F>> {
+    vec![
+        vec![F::TWO, F::from_canonical_u32(5)],
+        [1, 2, 3].map(F::from_canonical_u32).to_vec(),
+    ]
+}
+
+#[test]
+fn test_interpreter_matches_vm() {
+    execute_program_differential(build_program(), CompilerOptions::default(), input_stream());
+}
+
+#[test]
+fn test_interpreter_matches_vm_optimized() {
+    execute_program_differential(
+        build_program(),
+        CompilerOptions::default().with_optimizations(),
+        input_stream(),
+    );
+}
+
+#[test]
+fn test_interpreter_reads_values() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+    let a: Felt<_> = builder.eval(F::from_canonical_u32(6));
+    let b: Felt<_> = builder.eval(a * F::from_canonical_u32(7));
+    let x: Ext<_, _> = builder.eval(EF::ONE.cons() + b);
+    builder.halt();
+
+    let mut interpreter = Interpreter::<F, EF>::new(CompilerOptions::default());
+    interpreter.run(&builder.operations).unwrap();
+    assert_eq!(interpreter.felt(b), F::from_canonical_u32(42));
+    assert_eq!(interpreter.ext(x), EF::from_canonical_u32(43));
+}
+
+#[test]
+fn test_interpreter_assertion_failure() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+    let a: Felt<_> = builder.eval(F::ONE);
+    builder.assert_felt_eq(a, F::TWO);
+    builder.halt();
+
+    let mut interpreter = Interpreter::<F, EF>::new(CompilerOptions::default());
+    let err = interpreter.run(&builder.operations).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        InterpreterErrorKind::AssertionFailed { .. }
+    ));
+    assert_eq!(err.instruction, "AssertEqFI");
+    // Backtraces are only recorded when RUST_BACKTRACE is set.
+    assert_eq!(
+        err.backtrace.is_some(),
+        std::env::var_os("RUST_BACKTRACE").is_some()
+    );
+}
+
+#[test]
+fn test_interpreter_end_of_input_stream() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+    let _ = builder.hint_felt();
+    builder.halt();
+
+    let mut interpreter = Interpreter::<F, EF>::new(CompilerOptions::default());
+    let err = interpreter.run(&builder.operations).unwrap_err();
+    assert!(matches!(err.kind, InterpreterErrorKind::EndOfInputStream));
+}
@@ -0,0 +1,103 @@
+use openvm_circuit::arch::VmExecutor;
+use openvm_native_circuit::{execute_program, NativeConfig};
+use openvm_native_compiler::{
+    asm::{AsmBuilder, AsmConfig},
+    conversion::CompilerOptions,
+    ir::{Array, Builder, Ext, ExtConst, Felt, Var},
+};
+use openvm_stark_backend::p3_field::{extension::BinomialExtensionField, FieldAlgebra};
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+type F = BabyBear;
+type EF = BinomialExtensionField<BabyBear, 4>;
+
+/// Compiles `builder` with and without optimizations, executes both programs and returns their
+/// numbers of instructions.
+fn compile_and_execute(builder: Builder<AsmConfig<F, EF>>) -> (usize, usize) {
+    let unoptimized = builder.clone().compile_isa();
+    let optimized =
+        builder.compile_isa_with_options(CompilerOptions::default().with_optimizations());
+    let lens = (
+        unoptimized.num_defined_instructions(),
+        optimized.num_defined_instructions(),
+    );
+    execute_program(unoptimized, vec![]);
+    execute_program(optimized, vec![]);
+    lens
+}
+
+#[test]
+fn test_optimizer_straight_line() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+
+    let two: Felt<_> = builder.eval(F::TWO);
+    let three: Felt<_> = builder.eval(F::from_canonical_u32(3));
+    let six: Felt<_> = builder.eval(two * three);
+    let unused: Felt<_> = builder.eval(six * six + two);
+    builder.assign(&unused, unused + six);
+    builder.assert_felt_eq(six, F::from_canonical_u32(6));
+
+    let x: Felt<_> = builder.eval(two / three);
+    let y: Felt<_> = builder.eval(two / three);
+    builder.assert_felt_eq(x, y);
+    let sum: Felt<_> = builder.eval(x + F::ZERO);
+    builder.assert_felt_eq(sum * F::ONE, x);
+
+    let a: Ext<_, _> = builder.eval(EF::from_canonical_u32(5).cons());
+    let b: Ext<_, _> = builder.eval(a * a + a);
+    builder.assert_ext_eq(b, EF::from_canonical_u32(30).cons());
+
+    builder.halt();
+
+    let (unoptimized, optimized) = compile_and_execute(builder);
+    assert!(optimized < unoptimized, "{optimized} >= {unoptimized}");
+}
+
+#[test]
+fn test_optimizer_control_flow() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+
+    let zero: Var<_> = builder.eval(F::ZERO);
+    let n: Var<_> = builder.eval(F::from_canonical_u32(10));
+    let one: Felt<_> = builder.eval(F::ONE);
+
+    // Variables assigned in a loop are not constant inside or after it.
+    let acc: Felt<_> = builder.eval(F::ZERO);
+    let array: Array<_, Felt<_>> = builder.dyn_array(n);
+    builder.range(zero, n).for_each(|i_vec, builder| {
+        let i = i_vec[0];
+        let doubled: Felt<_> = builder.eval(one + one);
+        builder.assign(&acc, acc + doubled);
+        builder.set(&array, i, acc);
+    });
+    builder.assert_felt_eq(acc, F::from_canonical_u32(20));
+    let last = builder.get(&array, 9);
+    builder.assert_felt_eq(last, acc);
+
+    // Variables assigned in a branch are not constant after it.
+    let c: Var<_> = builder.eval(F::ONE);
+    let copy: Var<_> = builder.eval(c);
+    builder.if_eq(n, zero).then_or_else(
+        |builder| builder.assign(&c, F::TWO),
+        |builder| builder.assign(&c, c + c + copy),
+    );
+    builder.assert_var_eq(c, F::from_canonical_u32(3));
+    builder.assert_var_eq(copy, F::ONE);
+
+    builder.halt();
+
+    compile_and_execute(builder);
+}
+
+#[test]
+fn test_optimizer_keeps_division_by_zero() {
+    let mut builder = AsmBuilder::<F, EF>::default();
+
+    let zero: Felt<_> = builder.eval(F::ZERO);
+    let _unused: Felt<_> = builder.eval(F::ONE / zero);
+    builder.halt();
+
+    let program = builder.compile_isa_with_options(CompilerOptions::default().with_optimizations());
+    let executor = VmExecutor::<BabyBear, NativeConfig>::new(NativeConfig::aggregation(4, 3));
+    assert!(executor.execute(program, vec![]).is_err());
+}
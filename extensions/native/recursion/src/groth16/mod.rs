@@ -0,0 +1,258 @@
+pub mod solidity;
+#[cfg(test)]
+mod tests;
+pub mod verifier;
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, OnceLock},
+};
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInt, BigInteger, PrimeField};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError,
+};
+use ark_snark::SNARK;
+use openvm_native_compiler::{
+    constraints::groth16::compiler::{Groth16ConstraintCompiler, Groth16State},
+    ir::{Config, Witness},
+};
+use openvm_stark_backend::p3_field::extension::BinomialExtensionField;
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, p3_bn254_fr::Bn254Fr};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::halo2::DslOperations;
+
+/// A 256-bit EVM word, big-endian.
+pub type EvmWord = [u8; 32];
+
+/// A prover that can generate Groth16 proofs over BN254 of DSL operations, as an alternative to
+/// the halo2 KZG prover.
+#[derive(Debug, Clone)]
+pub struct Groth16Prover;
+
+/// A Groth16 proof in the format expected by the Solidity verifier of
+/// [solidity::generate_solidity_verifier].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Groth16EvmProof {
+    /// Public values of the circuit.
+    pub instances: Vec<EvmWord>,
+    /// `[A.x, A.y, B.x.c1, B.x.c0, B.y.c1, B.y.c0, C.x, C.y]`. `B` is in the order of the EVM
+    /// pairing precompile, which puts the imaginary part of each coordinate first.
+    pub proof: [EvmWord; 8],
+}
+
+/// R1CS circuit of DSL operations, with the witness values when it is used for proving.
+pub struct Groth16Circuit<C: Config> {
+    pub dsl_operations: DslOperations<C>,
+    pub state: Groth16State<C>,
+    pub profiling: bool,
+    /// Set to the public values of the circuit once its constraints are generated, unless it is
+    /// in setup mode.
+    public_values: Arc<OnceLock<Vec<Fr>>>,
+}
+
+impl<C: Config> Groth16Circuit<C> {
+    pub fn new(dsl_operations: DslOperations<C>, witness: Witness<C>, profiling: bool) -> Self
+    where
+        C: Default,
+    {
+        let mut state = Groth16State::default();
+        state.load_witness(witness);
+        Self {
+            dsl_operations,
+            state,
+            profiling,
+            public_values: Default::default(),
+        }
+    }
+}
+
+impl<C> ConstraintSynthesizer<Fr> for Groth16Circuit<C>
+where
+    C: Config<N = Bn254Fr, F = BabyBear, EF = BinomialExtensionField<BabyBear, 4>> + Debug,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let backend = Groth16ConstraintCompiler::<C>::new(self.dsl_operations.num_public_values);
+        let backend = if self.profiling {
+            backend.with_profiling()
+        } else {
+            backend
+        };
+        backend.constrain_r1cs(cs.clone(), &self.state, self.dsl_operations.operations)?;
+        if !cs.is_in_setup_mode() {
+            let cs = cs.borrow().unwrap();
+            // The first instance variable is the constant 1.
+            let _ = self.public_values.set(cs.instance_assignment[1..].to_vec());
+        }
+        Ok(())
+    }
+}
+
+impl Groth16Prover {
+    /// Checks the constraints with a witness, without generating keys or a proof.
+    ///
+    /// Returns the public values.
+    pub fn mock<
+        C: Config<N = Bn254Fr, F = BabyBear, EF = BinomialExtensionField<BabyBear, 4>>
+            + Debug
+            + Default,
+    >(
+        dsl_operations: DslOperations<C>,
+        witness: Witness<C>,
+    ) -> Vec<Fr> {
+        let cs = ConstraintSystem::new_ref();
+        Groth16Circuit::new(dsl_operations, witness, false)
+            .generate_constraints(cs.clone())
+            .unwrap();
+        println!("Number of constraints: {}", cs.num_constraints());
+        assert!(
+            cs.is_satisfied().unwrap(),
+            "R1CS constraints are not satisfied"
+        );
+        let cs = cs.borrow().unwrap();
+        cs.instance_assignment[1..].to_vec()
+    }
+
+    /// Generates Groth16 keys for DSL operations with a trusted setup run on this machine.
+    ///
+    /// ATTENTION: this is only for testing. Anyone who knows the randomness drawn from `rng` can
+    /// forge proofs, so keys used in production must come from a setup ceremony.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn keygen_with_local_setup<
+        C: Config<N = Bn254Fr, F = BabyBear, EF = BinomialExtensionField<BabyBear, 4>>
+            + Debug
+            + Default,
+    >(
+        dsl_operations: DslOperations<C>,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> ProvingKey<Bn254> {
+        #[cfg(feature = "bench-metrics")]
+        let start = std::time::Instant::now();
+        let circuit = Groth16Circuit::new(dsl_operations, Witness::default(), false);
+        let (pk, _) = Groth16::<Bn254>::circuit_specific_setup(circuit, rng).unwrap();
+        #[cfg(feature = "bench-metrics")]
+        metrics::gauge!("groth16_keygen_time_ms").set(start.elapsed().as_millis() as f64);
+        pk
+    }
+
+    pub fn prove<
+        C: Config<N = Bn254Fr, F = BabyBear, EF = BinomialExtensionField<BabyBear, 4>>
+            + Debug
+            + Default,
+    >(
+        pk: &ProvingKey<Bn254>,
+        dsl_operations: DslOperations<C>,
+        witness: Witness<C>,
+        profiling: bool,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Groth16EvmProof {
+        #[cfg(feature = "bench-metrics")]
+        let start = std::time::Instant::now();
+        let circuit = Groth16Circuit::new(dsl_operations, witness, profiling);
+        let public_values = circuit.public_values.clone();
+        let proof = Groth16::<Bn254>::prove(pk, circuit, rng).unwrap();
+        #[cfg(feature = "bench-metrics")]
+        metrics::gauge!("total_proof_time_ms").set(start.elapsed().as_millis() as f64);
+        Groth16EvmProof::new(&proof, public_values.get().unwrap())
+    }
+
+    /// Verifies a proof natively. Returns `false` if the proof is invalid or malformed.
+    pub fn verify(vk: &VerifyingKey<Bn254>, evm_proof: &Groth16EvmProof) -> bool {
+        let Some((proof, public_values)) = evm_proof.to_ark() else {
+            return false;
+        };
+        if public_values.len() + 1 != vk.gamma_abc_g1.len() {
+            return false;
+        }
+        Groth16::<Bn254>::verify(vk, &public_values, &proof).unwrap_or(false)
+    }
+}
+
+impl Groth16EvmProof {
+    pub fn new(proof: &Proof<Bn254>, public_values: &[Fr]) -> Self {
+        Self {
+            instances: public_values.iter().map(to_word).collect(),
+            proof: [
+                to_word(&proof.a.x),
+                to_word(&proof.a.y),
+                to_word(&proof.b.x.c1),
+                to_word(&proof.b.x.c0),
+                to_word(&proof.b.y.c1),
+                to_word(&proof.b.y.c0),
+                to_word(&proof.c.x),
+                to_word(&proof.c.y),
+            ],
+        }
+    }
+
+    /// Returns the proof and the public values, or `None` if some word is not a canonical field
+    /// element or some point is not in its group.
+    pub fn to_ark(&self) -> Option<(Proof<Bn254>, Vec<Fr>)> {
+        let [a_x, a_y, b_x_c1, b_x_c0, b_y_c1, b_y_c0, c_x, c_y] =
+            self.proof.each_ref().map(from_word::<Fq>);
+        let a = to_g1(a_x?, a_y?)?;
+        let b = to_g2(Fq2::new(b_x_c0?, b_x_c1?), Fq2::new(b_y_c0?, b_y_c1?))?;
+        let c = to_g1(c_x?, c_y?)?;
+        let public_values = self
+            .instances
+            .iter()
+            .map(from_word::<Fr>)
+            .collect::<Option<Vec<_>>>()?;
+        Some((Proof { a, b, c }, public_values))
+    }
+
+    /// Calldata of a `verifyProof(uint256[8],uint256[N])` call to the Solidity verifier, where
+    /// `N` is the number of public values.
+    pub fn calldata(&self) -> Vec<u8> {
+        let signature = format!(
+            "{}(uint256[8],uint256[{}])",
+            solidity::VERIFY_FUNCTION,
+            self.instances.len()
+        );
+        let mut selector = [0u8; 32];
+        let mut keccak = Keccak::v256();
+        keccak.update(signature.as_bytes());
+        keccak.finalize(&mut selector);
+        let mut calldata = selector[..4].to_vec();
+        for word in self.proof.iter().chain(&self.instances) {
+            calldata.extend_from_slice(word);
+        }
+        calldata
+    }
+}
+
+/// EVM points at infinity are encoded as `(0, 0)`, which matches the coordinates of the
+/// arkworks identity.
+fn to_g1(x: Fq, y: Fq) -> Option<G1Affine> {
+    if x == Fq::from(0u32) && y == Fq::from(0u32) {
+        return Some(G1Affine::zero());
+    }
+    let point = G1Affine::new_unchecked(x, y);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+fn to_g2(x: Fq2, y: Fq2) -> Option<G2Affine> {
+    if x == Fq2::from(0u32) && y == Fq2::from(0u32) {
+        return Some(G2Affine::zero());
+    }
+    let point = G2Affine::new_unchecked(x, y);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+pub(crate) fn to_word<F: PrimeField<BigInt = BigInt<4>>>(x: &F) -> EvmWord {
+    x.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+/// Returns `None` if `word` is not less than the modulus of `F`.
+pub(crate) fn from_word<F: PrimeField<BigInt = BigInt<4>>>(word: &EvmWord) -> Option<F> {
+    let limbs = std::array::from_fn(|i| {
+        u64::from_be_bytes(word[24 - 8 * i..32 - 8 * i].try_into().unwrap())
+    });
+    F::from_bigint(BigInt::new(limbs))
+}
@@ -0,0 +1,205 @@
+//! Solidity verifier of Groth16 proofs over BN254, using the EVM precompiles for BN254 point
+//! addition (0x06), scalar multiplication (0x07) and pairing check (0x08).
+
+use std::fmt::Write;
+
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInt, BigInteger, PrimeField};
+use ark_groth16::VerifyingKey;
+
+/// Name of the verification function of the generated contract.
+pub const VERIFY_FUNCTION: &str = "verifyProof";
+
+fn uint256<F: PrimeField<BigInt = BigInt<4>>>(x: &F) -> String {
+    format!("0x{}", hex_word(&super::to_word(x)))
+}
+
+fn modulus<F: PrimeField>() -> String {
+    format!(
+        "0x{}",
+        hex_word(&F::MODULUS.to_bytes_be().try_into().unwrap())
+    )
+}
+
+fn hex_word(word: &[u8; 32]) -> String {
+    word.iter().fold(String::with_capacity(64), |mut s, b| {
+        write!(s, "{:02x}", b).unwrap();
+        s
+    })
+}
+
+fn g1_constants(out: &mut String, name: &str, p: &G1Affine) {
+    writeln!(out, "    uint256 constant {name}_X = {};", uint256(&p.x)).unwrap();
+    writeln!(out, "    uint256 constant {name}_Y = {};", uint256(&p.y)).unwrap();
+}
+
+/// G2 coordinates are written with the imaginary part first, as expected by the pairing
+/// precompile.
+fn g2_constants(out: &mut String, name: &str, p: &G2Affine) {
+    writeln!(
+        out,
+        "    uint256 constant {name}_X_C1 = {};",
+        uint256(&p.x.c1)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    uint256 constant {name}_X_C0 = {};",
+        uint256(&p.x.c0)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    uint256 constant {name}_Y_C1 = {};",
+        uint256(&p.y.c1)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    uint256 constant {name}_Y_C0 = {};",
+        uint256(&p.y.c0)
+    )
+    .unwrap();
+}
+
+/// Generate the Solidity source of a `Groth16Verifier` contract for `vk`.
+///
+/// The contract exposes `verifyProof(uint256[8] proof, uint256[N] publicValues)`, which reverts
+/// unless the proof is valid. See
+/// [super::Groth16EvmProof] for the layout of `proof`.
+pub fn generate_solidity_verifier(vk: &VerifyingKey<Bn254>) -> String {
+    let num_public_values = vk.gamma_abc_g1.len() - 1;
+    assert!(
+        num_public_values > 0,
+        "Groth16 verifier needs at least one public value"
+    );
+
+    let mut out = String::new();
+    out.push_str("// SPDX-License-Identifier: MIT\n");
+    out.push_str("pragma solidity ^0.8.19;\n\n");
+    out.push_str("/// @notice Verifies Groth16 proofs over BN254. Generated by OpenVM.\n");
+    out.push_str("contract Groth16Verifier {\n");
+    writeln!(out, "    // Base field modulus").unwrap();
+    writeln!(out, "    uint256 constant P = {};", modulus::<Fq>()).unwrap();
+    writeln!(out, "    // Scalar field modulus").unwrap();
+    writeln!(out, "    uint256 constant R = {};", modulus::<Fr>()).unwrap();
+    out.push('\n');
+    g1_constants(&mut out, "ALPHA", &vk.alpha_g1);
+    g2_constants(&mut out, "BETA", &vk.beta_g2);
+    g2_constants(&mut out, "GAMMA", &vk.gamma_g2);
+    g2_constants(&mut out, "DELTA", &vk.delta_g2);
+    out.push('\n');
+    for (i, ic) in vk.gamma_abc_g1.iter().enumerate() {
+        g1_constants(&mut out, &format!("IC{i}"), ic);
+    }
+    out.push_str(
+        r#"
+    function _ecAdd(uint256 ax, uint256 ay, uint256 bx, uint256 by)
+        internal
+        view
+        returns (uint256 x, uint256 y)
+    {
+        bool success;
+        assembly {
+            let m := mload(0x40)
+            mstore(m, ax)
+            mstore(add(m, 0x20), ay)
+            mstore(add(m, 0x40), bx)
+            mstore(add(m, 0x60), by)
+            success := staticcall(gas(), 0x06, m, 0x80, m, 0x40)
+            x := mload(m)
+            y := mload(add(m, 0x20))
+        }
+        require(success, "ecAdd failed");
+    }
+
+    function _ecMul(uint256 ax, uint256 ay, uint256 s) internal view returns (uint256 x, uint256 y) {
+        bool success;
+        assembly {
+            let m := mload(0x40)
+            mstore(m, ax)
+            mstore(add(m, 0x20), ay)
+            mstore(add(m, 0x40), s)
+            success := staticcall(gas(), 0x07, m, 0x60, m, 0x40)
+            x := mload(m)
+            y := mload(add(m, 0x20))
+        }
+        require(success, "ecMul failed");
+    }
+
+"#,
+    );
+
+    writeln!(
+        out,
+        "    function {VERIFY_FUNCTION}(uint256[8] calldata proof, uint256[{num_public_values}] calldata publicValues)"
+    )
+    .unwrap();
+    out.push_str(
+        r#"        external
+        view
+    {
+        for (uint256 i = 0; i < 8; i++) {
+            require(proof[i] < P, "proof is not canonical");
+        }
+        for (uint256 i = 0; i < publicValues.length; i++) {
+            require(publicValues[i] < R, "public value is not canonical");
+        }
+
+        // vkX = IC0 + sum_i publicValues[i] * IC{i + 1}
+        uint256 x = IC0_X;
+        uint256 y = IC0_Y;
+        uint256 tx;
+        uint256 ty;
+"#,
+    );
+    for i in 0..num_public_values {
+        let j = i + 1;
+        writeln!(
+            out,
+            "        (tx, ty) = _ecMul(IC{j}_X, IC{j}_Y, publicValues[{i}]);"
+        )
+        .unwrap();
+        writeln!(out, "        (x, y) = _ecAdd(x, y, tx, ty);").unwrap();
+    }
+    out.push_str(
+        r#"
+        // e(-A, B) * e(alpha, beta) * e(vkX, gamma) * e(C, delta) == 1
+        uint256[24] memory input;
+        input[0] = proof[0];
+        input[1] = proof[1] == 0 ? 0 : P - proof[1];
+        input[2] = proof[2];
+        input[3] = proof[3];
+        input[4] = proof[4];
+        input[5] = proof[5];
+        input[6] = ALPHA_X;
+        input[7] = ALPHA_Y;
+        input[8] = BETA_X_C1;
+        input[9] = BETA_X_C0;
+        input[10] = BETA_Y_C1;
+        input[11] = BETA_Y_C0;
+        input[12] = x;
+        input[13] = y;
+        input[14] = GAMMA_X_C1;
+        input[15] = GAMMA_X_C0;
+        input[16] = GAMMA_Y_C1;
+        input[17] = GAMMA_Y_C0;
+        input[18] = proof[6];
+        input[19] = proof[7];
+        input[20] = DELTA_X_C1;
+        input[21] = DELTA_X_C0;
+        input[22] = DELTA_Y_C1;
+        input[23] = DELTA_Y_C0;
+
+        bool success;
+        uint256[1] memory result;
+        assembly {
+            success := staticcall(gas(), 0x08, input, 0x300, result, 0x20)
+        }
+        require(success && result[0] == 1, "invalid proof");
+    }
+}
+"#,
+    );
+    out
+}
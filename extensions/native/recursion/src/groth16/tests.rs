@@ -0,0 +1,162 @@
+use ark_bn254::Fr;
+use openvm_native_compiler::{
+    constraints::groth16::compiler::convert_fr,
+    ir::{Builder, Config, Ext, ExtConst, Felt, Var, Witness},
+};
+use openvm_stark_backend::p3_field::{
+    reduce_32 as reduce_32_gt, split_32 as split_32_gt, FieldAlgebra, FieldExtensionAlgebra,
+};
+use openvm_stark_sdk::{
+    config::baby_bear_poseidon2_root::root_perm, p3_baby_bear::BabyBear, p3_bn254_fr::Bn254Fr,
+};
+use p3_symmetric::Permutation;
+use rand::{rngs::StdRng, SeedableRng};
+use snark_verifier_sdk::snark_verifier::loader::evm::{compile_solidity, deploy_and_call};
+
+use crate::{
+    config::outer::OuterConfig,
+    groth16::{verifier::Groth16VerifierProvingKey, Groth16Prover},
+    halo2::DslOperations,
+    utils::{reduce_32, split_32},
+};
+
+fn mock(builder: Builder<OuterConfig>, num_public_values: usize) -> Vec<Fr> {
+    Groth16Prover::mock::<OuterConfig>(
+        DslOperations {
+            operations: builder.operations,
+            num_public_values,
+        },
+        Witness::default(),
+    )
+}
+
+fn publish_builder(value: Bn254Fr) -> Builder<OuterConfig> {
+    let mut builder = Builder::<OuterConfig>::default();
+    builder.flags.static_only = true;
+    let value = builder.eval(value);
+    builder.static_commit_public_value(0, value);
+    builder
+}
+
+#[test]
+fn test_publish() {
+    let value_fr = Bn254Fr::from_canonical_u32(1345237507);
+    let pis = mock(publish_builder(value_fr), 1);
+    assert_eq!(pis, vec![convert_fr(&value_fr)]);
+}
+
+#[test]
+fn test_felt_ext_arithmetic() {
+    let mut builder = Builder::<OuterConfig>::default();
+    builder.flags.static_only = true;
+    let a = BabyBear::from_canonical_u32(2013265920);
+    let b = BabyBear::from_canonical_u32(1234567);
+    let a_felt: Felt<_> = builder.eval(a);
+    let b_felt: Felt<_> = builder.eval(b);
+    let c_felt: Felt<_> = builder.eval(a_felt * b_felt - a_felt / b_felt);
+    builder.assert_felt_eq(c_felt, a * b - a / b);
+
+    type EF = <OuterConfig as Config>::EF;
+    let x = EF::from_base_slice(&[1, 2, 3, 2013265920].map(BabyBear::from_canonical_u32));
+    let y = EF::from_base_slice(&[5, 1, 0, 7].map(BabyBear::from_canonical_u32));
+    let x_ext: Ext<_, _> = builder.eval(x.cons());
+    let y_ext: Ext<_, _> = builder.eval(y.cons());
+    let z_ext: Ext<_, _> = builder.eval(x_ext * y_ext + x_ext / y_ext - y_ext);
+    builder.assert_ext_eq(z_ext, (x * y + x / y - y).cons());
+
+    mock(builder, 0);
+}
+
+#[test]
+fn test_p2_permute_mut() {
+    let input = [0, 1, 2].map(Bn254Fr::from_canonical_u32);
+    let mut output = input;
+    root_perm().permute_mut(&mut output);
+
+    let mut builder = Builder::<OuterConfig>::default();
+    let vars: [Var<_>; 3] = input.map(|x| builder.eval(x));
+    builder.p2_permute_mut(vars);
+    for (var, expected) in vars.into_iter().zip(output) {
+        builder.assert_var_eq(var, expected);
+    }
+
+    mock(builder, 0);
+}
+
+#[test]
+fn test_reduce_32() {
+    let value_1 = BabyBear::from_canonical_u32(1345237507);
+    let value_2 = BabyBear::from_canonical_u32(1000001);
+    let gt: Bn254Fr = reduce_32_gt(&[value_1, value_2]);
+
+    let mut builder = Builder::<OuterConfig>::default();
+    builder.flags.static_only = true;
+    let value_1 = builder.eval(value_1);
+    let value_2 = builder.eval(value_2);
+    let result = reduce_32(&mut builder, &[value_1, value_2]);
+    builder.assert_var_eq(result, gt);
+
+    mock(builder, 0);
+}
+
+#[test]
+fn test_split_32() {
+    let f = |value| {
+        let gt: Vec<BabyBear> = split_32_gt(value, 3);
+
+        let mut builder = Builder::<OuterConfig>::default();
+        builder.flags.static_only = true;
+        let value = builder.eval(value);
+        let result = split_32(&mut builder, value, 3);
+        for (x, expected) in result.into_iter().zip(gt) {
+            builder.assert_felt_eq(x, expected);
+        }
+
+        mock(builder, 0);
+    };
+    f(Bn254Fr::from_canonical_u32(1345237507));
+    f(Bn254Fr::ZERO);
+    f(Bn254Fr::ZERO - Bn254Fr::ONE);
+}
+
+#[test]
+#[should_panic(expected = "R1CS constraints are not satisfied")]
+fn test_unsatisfied() {
+    let mut builder = Builder::<OuterConfig>::default();
+    builder.flags.static_only = true;
+    let a: Felt<_> = builder.eval(BabyBear::ONE);
+    builder.assert_felt_eq(a, BabyBear::TWO);
+
+    mock(builder, 0);
+}
+
+#[test]
+fn test_local_setup_prove_verify() {
+    let value_fr = Bn254Fr::from_canonical_u32(1345237507);
+    let dsl_ops = DslOperations {
+        operations: publish_builder(value_fr).operations,
+        num_public_values: 1,
+    };
+    let mut rng = StdRng::seed_from_u64(0);
+    let pk = Groth16VerifierProvingKey::keygen_with_local_setup(dsl_ops, &mut rng);
+    let proof = pk.prove(Witness::default(), false, &mut rng);
+    assert_eq!(
+        proof.to_ark().unwrap().1,
+        vec![convert_fr(&value_fr)],
+        "unexpected public values"
+    );
+    assert!(pk.verify(&proof));
+
+    let serialized = bitcode::serialize(&pk).unwrap();
+    let pk: Groth16VerifierProvingKey = bitcode::deserialize(&serialized).unwrap();
+    assert!(pk.verify(&proof));
+
+    let mut tampered = proof.clone();
+    tampered.instances[0][31] ^= 1;
+    assert!(!pk.verify(&tampered));
+
+    let deployment_code = compile_solidity(&pk.generate_solidity_verifier());
+    let gas_cost = deploy_and_call(deployment_code.clone(), proof.calldata()).unwrap();
+    println!("Groth16 verifier gas cost: {gas_cost}");
+    assert!(deploy_and_call(deployment_code, tampered.calldata()).is_err());
+}
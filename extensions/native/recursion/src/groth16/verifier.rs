@@ -0,0 +1,92 @@
+use ark_bn254::Bn254;
+use ark_groth16::ProvingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use openvm_native_compiler::ir::Witness;
+use rand::{CryptoRng, RngCore};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    config::outer::OuterConfig,
+    groth16::{solidity::generate_solidity_verifier, Groth16EvmProof, Groth16Prover},
+    halo2::DslOperations,
+};
+
+/// Groth16 proving key of the static verifier circuit.
+///
+/// Attention: Groth16 needs a trusted setup per circuit. Keys generated by
+/// [Groth16VerifierProvingKey::keygen_with_local_setup] must not be used in production.
+#[derive(Clone, Debug)]
+pub struct Groth16VerifierProvingKey {
+    pub pk: ProvingKey<Bn254>,
+    pub dsl_ops: DslOperations<OuterConfig>,
+}
+
+impl Groth16VerifierProvingKey {
+    /// Generate the proving key of the circuit of `dsl_ops` with a setup run on this machine.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn keygen_with_local_setup(
+        dsl_ops: DslOperations<OuterConfig>,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Self {
+        Self {
+            pk: Groth16Prover::keygen_with_local_setup(dsl_ops.clone(), rng),
+            dsl_ops,
+        }
+    }
+
+    pub fn prove(
+        &self,
+        witness: Witness<OuterConfig>,
+        profiling: bool,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Groth16EvmProof {
+        Groth16Prover::prove(&self.pk, self.dsl_ops.clone(), witness, profiling, rng)
+    }
+
+    pub fn verify(&self, proof: &Groth16EvmProof) -> bool {
+        Groth16Prover::verify(&self.pk.vk, proof)
+    }
+
+    /// Solidity source of a contract which verifies proofs of this circuit.
+    pub fn generate_solidity_verifier(&self) -> String {
+        generate_solidity_verifier(&self.pk.vk)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedGroth16VerifierProvingKey {
+    pk_bytes: Vec<u8>,
+    dsl_ops: DslOperations<OuterConfig>,
+}
+
+impl Serialize for Groth16VerifierProvingKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut pk_bytes = Vec::new();
+        self.pk
+            .serialize_uncompressed(&mut pk_bytes)
+            .map_err(serde::ser::Error::custom)?;
+        let serialized = SerializedGroth16VerifierProvingKey {
+            pk_bytes,
+            dsl_ops: self.dsl_ops.clone(),
+        };
+        serialized.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Groth16VerifierProvingKey {
+    fn deserialize<D>(deserializer: D) -> Result<Groth16VerifierProvingKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let SerializedGroth16VerifierProvingKey { pk_bytes, dsl_ops } =
+            SerializedGroth16VerifierProvingKey::deserialize(deserializer)?;
+
+        let pk = ProvingKey::<Bn254>::deserialize_uncompressed(&pk_bytes[..])
+            .map_err(|e| de::Error::custom(format!("invalid bytes for proving key: {}", e)))?;
+
+        Ok(Groth16VerifierProvingKey { pk, dsl_ops })
+    }
+}
@@ -48,6 +48,43 @@ pub struct EvmProof {
     pub proof: Vec<u8>,
 }
 
+impl EvmProof {
+    /// Calldata of the EVM verifier of the wrapper circuit: each instance as a big-endian word,
+    /// followed by the proof.
+    pub fn to_calldata(&self) -> Vec<u8> {
+        let mut calldata = Vec::new();
+        for x in self.instances.iter().flatten() {
+            let mut bytes = x.to_bytes();
+            bytes.reverse();
+            calldata.extend_from_slice(&bytes);
+        }
+        calldata.extend_from_slice(&self.proof);
+        calldata
+    }
+
+    /// Inverse of [Self::to_calldata] for a circuit with a single instance column of
+    /// `num_instances` instances. Returns `None` if `calldata` is too short or an instance is not
+    /// a canonical field element.
+    pub fn from_calldata(calldata: &[u8], num_instances: usize) -> Option<Self> {
+        if calldata.len() < num_instances * 32 {
+            return None;
+        }
+        let (instances, proof) = calldata.split_at(num_instances * 32);
+        let instances = instances
+            .chunks_exact(32)
+            .map(|word| {
+                let mut bytes: [u8; 32] = word.try_into().unwrap();
+                bytes.reverse();
+                Option::from(Fr::from_bytes(&bytes))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            instances: vec![instances],
+            proof: proof.to_vec(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DslOperations<C: Config> {
     pub operations: TracedVec<DslIr<C>>,
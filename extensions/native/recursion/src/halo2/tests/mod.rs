@@ -24,7 +24,7 @@ use crate::{
     config::outer::OuterConfig,
     halo2::{
         utils::gen_kzg_params, wrapper::Halo2WrapperProvingKey, CircuitBuilderStage::Prover,
-        DslOperations, Halo2Prover, Halo2ProvingMetadata, Halo2ProvingPinning,
+        DslOperations, EvmProof, Halo2Prover, Halo2ProvingMetadata, Halo2ProvingPinning,
     },
     utils::{reduce_32, split_32},
 };
@@ -174,3 +174,22 @@ fn test_pinning_serde() {
     build_dummy_circuit(&mut builder, DUMMY_N);
     gen_snark_shplonk(&params, &pinning.pk, builder, None::<&str>);
 }
+
+#[test]
+fn test_evm_proof_calldata() {
+    let proof = EvmProof {
+        instances: vec![vec![Fr::from(1u64), -Fr::from(1u64), Fr::from(1u64 << 40)]],
+        proof: vec![1, 2, 3, 4],
+    };
+    let calldata = proof.to_calldata();
+    assert_eq!(calldata.len(), 3 * 32 + 4);
+    assert_eq!(calldata[31], 1);
+    let decoded = EvmProof::from_calldata(&calldata, 3).unwrap();
+    assert_eq!(decoded.instances, proof.instances);
+    assert_eq!(decoded.proof, proof.proof);
+
+    assert!(EvmProof::from_calldata(&calldata[..64], 3).is_none());
+    let mut non_canonical = calldata;
+    non_canonical[..32].fill(0xff);
+    assert!(EvmProof::from_calldata(&non_canonical, 3).is_none());
+}
@@ -2,7 +2,7 @@ use itertools::Itertools;
 use openvm_stark_backend::p3_util::log2_ceil_usize;
 use serde::{Deserialize, Serialize};
 use snark_verifier_sdk::{
-    evm::{evm_verify, gen_evm_proof_shplonk, gen_evm_verifier_shplonk},
+    evm::{evm_verify, gen_evm_proof_shplonk, gen_evm_verifier_shplonk, gen_evm_verifier_sol_code},
     halo2::aggregation::{AggregationCircuit, AggregationConfigParams, VerifierUniversality},
     snark_verifier::halo2_base::{
         gates::circuit::{
@@ -99,6 +99,20 @@ impl Halo2WrapperProvingKey {
             None,
         ))
     }
+    /// Return the Solidity source of the EVM verifier of [Self::generate_evm_verifier]. Compiling
+    /// it with `solc` gives the same deployment code.
+    pub fn generate_evm_verifier_sol_code(&self, params: &Halo2Params) -> String {
+        assert_eq!(
+            self.pinning.metadata.config_params.k as u32,
+            params.k(),
+            "Provided params don't match circuit config"
+        );
+        gen_evm_verifier_sol_code::<AggregationCircuit, SHPLONK>(
+            params,
+            self.pinning.pk.get_vk(),
+            self.pinning.metadata.num_pvs.clone(),
+        )
+    }
     pub fn prove_for_evm(&self, params: &Halo2Params, snark_to_verify: Snark) -> EvmProof {
         #[cfg(feature = "bench-metrics")]
         let start = std::time::Instant::now();
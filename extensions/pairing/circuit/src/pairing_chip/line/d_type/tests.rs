@@ -54,6 +54,7 @@ fn test_mul_013_by_013() {
             modulus: BN254_MODULUS.clone(),
             num_limbs: NUM_LIMBS,
             limb_bits: LIMB_BITS,
+            is_prime: true,
         },
         BN254_XI_ISIZE,
         PairingOpcode::CLASS_OFFSET,
@@ -149,6 +150,7 @@ fn test_mul_by_01234() {
             modulus: BN254_MODULUS.clone(),
             num_limbs: NUM_LIMBS,
             limb_bits: LIMB_BITS,
+            is_prime: true,
         },
         BN254_XI_ISIZE,
         PairingOpcode::CLASS_OFFSET,
@@ -226,6 +228,7 @@ fn test_evaluate_line() {
         modulus: BN254_MODULUS.clone(),
         limb_bits: BN254_LIMB_BITS,
         num_limbs: BN254_NUM_LIMBS,
+        is_prime: true,
     };
     let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
     let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
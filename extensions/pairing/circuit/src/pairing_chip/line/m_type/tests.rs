@@ -48,6 +48,7 @@ fn test_mul_023_by_023() {
             modulus: BLS12_381_MODULUS.clone(),
             num_limbs: BLS12_381_NUM_LIMBS,
             limb_bits: BLS12_381_LIMB_BITS,
+            is_prime: true,
         },
         BLS12_381_XI_ISIZE,
         PairingOpcode::CLASS_OFFSET,
@@ -147,6 +148,7 @@ fn test_mul_by_02345() {
             modulus: BLS12_381_MODULUS.clone(),
             num_limbs: BLS12_381_NUM_LIMBS,
             limb_bits: BLS12_381_LIMB_BITS,
+            is_prime: true,
         },
         BLS12_381_XI_ISIZE,
         PairingOpcode::CLASS_OFFSET,
@@ -148,6 +148,7 @@ mod tests {
                 modulus: BN254_MODULUS.clone(),
                 limb_bits: LIMB_BITS,
                 num_limbs: NUM_LIMBS,
+                is_prime: true,
             },
             PairingOpcode::CLASS_OFFSET,
             tester.range_checker(),
@@ -130,6 +130,7 @@ mod tests {
             modulus: BN254_MODULUS.clone(),
             limb_bits: BN254_LIMB_BITS,
             num_limbs: BN254_NUM_LIMBS,
+            is_prime: true,
         };
         let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
         let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
@@ -197,6 +198,7 @@ mod tests {
             modulus: BLS12_381_MODULUS.clone(),
             limb_bits: BLS12_381_LIMB_BITS,
             num_limbs: BLS12_381_NUM_LIMBS,
+            is_prime: true,
         };
         let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
         let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
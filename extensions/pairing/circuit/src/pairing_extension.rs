@@ -9,7 +9,7 @@ use openvm_circuit_derive::{AnyEnum, InstructionExecutor};
 use openvm_circuit_primitives::bitwise_op_lookup::SharedBitwiseOperationLookupChip;
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
 use openvm_ecc_circuit::CurveConfig;
-use openvm_instructions::PhantomDiscriminant;
+use openvm_instructions::{opcode_names::OpcodeNames, PhantomDiscriminant};
 use openvm_pairing_guest::{
     bls12_381::{BLS12_381_MODULUS, BLS12_381_ORDER, BLS12_381_XI_ISIZE},
     bn254::{BN254_MODULUS, BN254_ORDER, BN254_XI_ISIZE},
@@ -93,6 +93,12 @@ impl<F: PrimeField32> VmExtension<F> for PairingExtension {
 
         Ok(inventory)
     }
+
+    /// The extension only adds a phantom sub-executor, and phantom instructions are named by the
+    /// system.
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default()
+    }
 }
 
 pub(crate) mod phantom {
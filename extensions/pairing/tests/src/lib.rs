@@ -7,7 +7,11 @@ mod bn254 {
     use eyre::Result;
     use openvm_algebra_circuit::{Fp2Extension, ModularExtension};
     use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
-    use openvm_circuit::{arch::SystemConfig, utils::air_test_with_min_segments};
+    use openvm_assembler::{assemble, disassemble};
+    use openvm_circuit::{
+        arch::{SystemConfig, VmConfig},
+        utils::air_test_with_min_segments,
+    };
     use openvm_ecc_circuit::WeierstrassExtension;
     use openvm_ecc_guest::{
         algebra::field::FieldExtension,
@@ -292,6 +296,51 @@ mod bn254 {
         Ok(())
     }
 
+    #[test]
+    fn test_bn254_disassemble_roundtrip() -> Result<()> {
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "pairing_check",
+            ["bn254"],
+        )?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(PairingTranspilerExtension)
+                .with_extension(ModularTranspilerExtension)
+                .with_extension(Fp2TranspilerExtension),
+        )?;
+
+        let names = VmConfig::<F>::opcode_names(&get_testing_config());
+        let instructions = openvm_exe
+            .program
+            .enumerate_by_pc()
+            .into_iter()
+            .map(|(pc, instruction, _)| (pc, instruction))
+            .collect::<Vec<_>>();
+        for (pc, instruction) in &instructions {
+            assert!(
+                names.name(instruction.opcode).is_some(),
+                "opcode {} at pc {pc} has no name",
+                instruction.opcode
+            );
+        }
+
+        let listing = disassemble(&openvm_exe, &names);
+        let reassembled = assemble::<F>(&listing, &names)?;
+        let reassembled_instructions = reassembled
+            .program
+            .enumerate_by_pc()
+            .into_iter()
+            .map(|(pc, instruction, _)| (pc, instruction))
+            .collect::<Vec<_>>();
+        assert_eq!(reassembled_instructions, instructions);
+        Ok(())
+    }
+
     #[test]
     fn test_bn254_pairing_check_fallback() -> Result<()> {
         let elf = build_example_program_at_path_with_features(
@@ -60,6 +60,11 @@ pub fn read_rv32_register<F: PrimeField32>(
     (record.0, val)
 }
 
+/// Byte offset of the return address `x1` in the register address space.
+pub const RV32_RA_PTR: u32 = RV32_REGISTER_NUM_LIMBS as u32;
+/// Byte offset of the stack pointer `x2` in the register address space.
+pub const RV32_SP_PTR: u32 = 2 * RV32_REGISTER_NUM_LIMBS as u32;
+
 /// Peeks at the value of a register without updating the memory state or incrementing the timestamp.
 pub fn unsafe_read_rv32_register<F: PrimeField32>(memory: &MemoryController<F>, pointer: F) -> u32 {
     let data = memory.unsafe_read::<RV32_REGISTER_NUM_LIMBS>(F::ONE, pointer);
@@ -1,8 +1,8 @@
 use derive_more::derive::From;
 use openvm_circuit::{
     arch::{
-        SystemConfig, SystemExecutor, SystemPeriphery, SystemPort, VmChipComplex, VmConfig,
-        VmExtension, VmInventory, VmInventoryBuilder, VmInventoryError,
+        GuestRegisters, SystemConfig, SystemExecutor, SystemPeriphery, SystemPort, VmChipComplex,
+        VmConfig, VmExtension, VmInventory, VmInventoryBuilder, VmInventoryError,
     },
     system::phantom::PhantomChip,
 };
@@ -12,7 +12,9 @@ use openvm_circuit_primitives::{
     range_tuple::{RangeTupleCheckerBus, SharedRangeTupleCheckerChip},
 };
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
-use openvm_instructions::{program::DEFAULT_PC_STEP, LocalOpcode, PhantomDiscriminant};
+use openvm_instructions::{
+    opcode_names::OpcodeNames, program::DEFAULT_PC_STEP, LocalOpcode, PhantomDiscriminant,
+};
 use openvm_rv32im_transpiler::{
     BaseAluOpcode, BranchEqualOpcode, BranchLessThanOpcode, DivRemOpcode, LessThanOpcode,
     MulHOpcode, MulOpcode, Rv32AuipcOpcode, Rv32HintStoreOpcode, Rv32JalLuiOpcode, Rv32JalrOpcode,
@@ -353,9 +355,37 @@ impl<F: PrimeField32> VmExtension<F> for Rv32I {
             phantom::Rv32PrintStrSubEx,
             PhantomDiscriminant(Rv32Phantom::PrintStr as u16),
         )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32HintLoadByKeySubEx,
+            PhantomDiscriminant(Rv32Phantom::HintLoadByKey as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32PanicInfoSubEx,
+            PhantomDiscriminant(Rv32Phantom::PanicInfo as u16),
+        )?;
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default()
+            .with_opcodes("", BaseAluOpcode::iter())
+            .with_opcodes("", LessThanOpcode::iter())
+            .with_opcodes("", ShiftOpcode::iter())
+            .with_opcodes("", Rv32LoadStoreOpcode::iter())
+            .with_opcodes("", BranchEqualOpcode::iter())
+            .with_opcodes("", BranchLessThanOpcode::iter())
+            .with_opcodes("", Rv32JalLuiOpcode::iter())
+            .with_opcodes("", Rv32JalrOpcode::iter())
+            .with_opcodes("", Rv32AuipcOpcode::iter())
+    }
+
+    fn guest_registers(&self) -> Option<GuestRegisters<F>> {
+        Some(GuestRegisters {
+            read_ra: |memory| unsafe_read_rv32_register(memory, F::from_canonical_u32(RV32_RA_PTR)),
+            read_sp: |memory| unsafe_read_rv32_register(memory, F::from_canonical_u32(RV32_SP_PTR)),
+        })
+    }
 }
 
 impl<F: PrimeField32> VmExtension<F> for Rv32M {
@@ -432,6 +462,13 @@ impl<F: PrimeField32> VmExtension<F> for Rv32M {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default()
+            .with_opcodes("", MulOpcode::iter())
+            .with_opcodes("", MulHOpcode::iter())
+            .with_opcodes("", DivRemOpcode::iter())
+    }
 }
 
 impl<F: PrimeField32> VmExtension<F> for Rv32Io {
@@ -480,13 +517,17 @@ impl<F: PrimeField32> VmExtension<F> for Rv32Io {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> OpcodeNames {
+        OpcodeNames::default().with_opcodes("", Rv32HintStoreOpcode::iter())
+    }
 }
 
 /// Phantom sub-executors
 mod phantom {
     use eyre::bail;
     use openvm_circuit::{
-        arch::{PhantomSubExecutor, Streams},
+        arch::{GuestPanicInfo, PhantomSubExecutor, Streams},
         system::memory::MemoryController,
     };
     use openvm_instructions::PhantomDiscriminant;
@@ -505,6 +546,8 @@ mod phantom {
         }
     }
     pub struct Rv32PrintStrSubEx;
+    pub struct Rv32HintLoadByKeySubEx;
+    pub struct Rv32PanicInfoSubEx;
 
     impl<F: Field> PhantomSubExecutor<F> for Rv32HintInputSubEx {
         fn phantom_execute(
@@ -516,27 +559,63 @@ mod phantom {
             _: F,
             _: u16,
         ) -> eyre::Result<()> {
-            let mut hint = match streams.input_stream.pop_front() {
+            let hint = match streams.input_stream.pop_front() {
                 Some(hint) => hint,
                 None => {
                     bail!("EndOfInputStream");
                 }
             };
-            streams.hint_stream.clear();
-            streams.hint_stream.extend(
-                (hint.len() as u32)
-                    .to_le_bytes()
-                    .iter()
-                    .map(|b| F::from_canonical_u8(*b)),
-            );
-            // Extend by 0 for 4 byte alignment
-            let capacity = hint.len().div_ceil(4) * 4;
-            hint.resize(capacity, F::ZERO);
-            streams.hint_stream.extend(hint);
+            reset_hint_stream_with_len_prefix(streams, hint);
             Ok(())
         }
     }
 
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32HintLoadByKeySubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let key_ptr = unsafe_read_rv32_register(memory, a);
+            let key_len = unsafe_read_rv32_register(memory, b);
+            let key = (0..key_len)
+                .map(|i| -> eyre::Result<u8> {
+                    let val = memory.unsafe_read_cell(F::TWO, F::from_canonical_u32(key_ptr + i));
+                    let byte: u8 = val.as_canonical_u32().try_into()?;
+                    Ok(byte)
+                })
+                .collect::<eyre::Result<Vec<u8>>>()?;
+            let hint = match streams.kv_store.get(&key) {
+                Some(hint) => hint.clone(),
+                None => {
+                    bail!("KeyNotFound: {:?}", key);
+                }
+            };
+            reset_hint_stream_with_len_prefix(streams, hint);
+            Ok(())
+        }
+    }
+
+    /// Replace the hint stream with the 4-byte decomposition of the length of `hint`, followed by
+    /// `hint` padded with zeros to 4 byte alignment.
+    fn reset_hint_stream_with_len_prefix<F: Field>(streams: &mut Streams<F>, mut hint: Vec<F>) {
+        streams.hint_stream.clear();
+        streams.hint_stream.extend(
+            (hint.len() as u32)
+                .to_le_bytes()
+                .iter()
+                .map(|b| F::from_canonical_u8(*b)),
+        );
+        // Extend by 0 for 4 byte alignment
+        let capacity = hint.len().div_ceil(4) * 4;
+        hint.resize(capacity, F::ZERO);
+        streams.hint_stream.extend(hint);
+    }
+
     impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32HintRandomSubEx {
         fn phantom_execute(
             &mut self,
@@ -580,4 +659,41 @@ mod phantom {
             Ok(())
         }
     }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32PanicInfoSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let ptr = unsafe_read_rv32_register(memory, a);
+            let len = unsafe_read_rv32_register(memory, b);
+            let bytes = (0..len)
+                .map(|i| -> eyre::Result<u8> {
+                    let val = memory.unsafe_read_cell(F::TWO, F::from_canonical_u32(ptr + i));
+                    let byte: u8 = val.as_canonical_u32().try_into()?;
+                    Ok(byte)
+                })
+                .collect::<eyre::Result<Vec<u8>>>()?;
+            // Payload layout: [line: u32 LE][file_len: u32 LE][file][message]
+            if bytes.len() < 8 {
+                bail!("PanicInfo payload too short: {} bytes", bytes.len());
+            }
+            let line = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+            let file_len = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+            let Some(file) = bytes[8..].get(..file_len) else {
+                bail!("PanicInfo file length {file_len} exceeds payload");
+            };
+            streams.guest_panic = Some(GuestPanicInfo {
+                message: String::from_utf8_lossy(&bytes[8 + file_len..]).into_owned(),
+                file: String::from_utf8_lossy(file).into_owned(),
+                line,
+            });
+            Ok(())
+        }
+    }
 }
@@ -55,6 +55,19 @@ pub fn hint_random(len: usize) {
     );
 }
 
+/// Reset the hint stream with the hint stored under the key `[key_ptr..key_ptr + key_len]`, in
+/// the same format as [hint_input].
+#[inline(always)]
+pub fn hint_load_by_key(key_ptr: *const u8, key_len: usize) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In key_ptr,
+        rs1 = In key_len,
+        imm = Const PhantomImm::HintLoadByKey as u16
+    );
+}
+
 /// Store rs1 to [[rd] + imm]_3.
 #[macro_export]
 macro_rules! reveal {
@@ -85,3 +98,16 @@ pub fn raw_print_str_from_bytes(msg_ptr: *const u8, len: usize) {
         imm = Const PhantomImm::PrintStr as u16
     );
 }
+
+/// Report the panic payload `[msg_ptr..msg_ptr + len]` to the host so that it can be surfaced
+/// in the execution error. The payload is laid out as `[line: u32 LE][file_len: u32 LE][file][message]`.
+#[inline(always)]
+pub fn raw_report_panic(msg_ptr: *const u8, len: usize) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In msg_ptr,
+        rs1 = In len,
+        imm = Const PhantomImm::PanicInfo as u16
+    );
+}
@@ -28,4 +28,6 @@ pub enum PhantomImm {
     HintInput = 0,
     PrintStr,
     HintRandom,
+    HintLoadByKey,
+    PanicInfo,
 }
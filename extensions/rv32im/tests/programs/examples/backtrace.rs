@@ -0,0 +1,23 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::hint::black_box;
+
+openvm::entry!(main);
+
+#[inline(never)]
+fn inner(depth: u32) -> u32 {
+    if black_box(depth) == 3 {
+        panic!("reached depth 3");
+    }
+    depth
+}
+
+#[inline(never)]
+fn outer(depth: u32) -> u32 {
+    black_box(inner(depth)) + 1
+}
+
+pub fn main() {
+    black_box(outer(black_box(3)));
+}
@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm::io::read;
+extern crate openvm;
+
+openvm::entry!(main);
+
+fn main() {
+    let code: u8 = read();
+    if code == 0 {
+        panic!("exit code must be non-zero");
+    }
+    openvm::process::exit(code);
+}
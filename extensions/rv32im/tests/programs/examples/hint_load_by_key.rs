@@ -0,0 +1,23 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::{hint_load_by_key, read_vec};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let first = hint_load_by_key(b"key");
+    // Loading a key does not consume its hint.
+    let second = hint_load_by_key(b"key");
+    if first != [0, 1, 2, 3, 4] || second != first {
+        openvm::process::panic();
+    }
+    // Keyed hints do not interfere with the input stream.
+    let input = read_vec();
+    if input != [5, 6] {
+        openvm::process::panic();
+    }
+    let other = hint_load_by_key(b"other key");
+    if other.len() != 1000 || other.iter().any(|&b| b != 7) {
+        openvm::process::panic();
+    }
+}
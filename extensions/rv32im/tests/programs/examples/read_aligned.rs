@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::{read_aligned, ARCHIVE_ALIGN};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // The lengths are not multiples of 4, so the second buffer must not start right after the
+    // first one.
+    for len in [5, 3] {
+        let bytes = read_aligned();
+        if bytes.len() != len || bytes.as_ptr() as usize % ARCHIVE_ALIGN != 0 {
+            openvm::process::panic();
+        }
+        for (i, &byte) in bytes.iter().enumerate() {
+            if byte != i as u8 {
+                openvm::process::panic();
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::hint::black_box;
+
+use openvm::io::read;
+extern crate openvm;
+
+openvm::entry!(main);
+
+/// Recursion with a 1 KiB stack frame, deep enough to exhaust the default 2 MiB stack.
+#[inline(never)]
+fn recurse(depth: u32) -> u32 {
+    let frame = black_box([depth; 256]);
+    if depth == 0 {
+        frame[0]
+    } else {
+        recurse(depth - 1).wrapping_add(frame[255])
+    }
+}
+
+fn main() {
+    let depth: u32 = read();
+    black_box(recurse(depth));
+}
@@ -0,0 +1,29 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use std::io::BufRead;
+
+openvm::entry!(main);
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    assert_eq!(args, ["vfs", "--name", "openvm"]);
+    assert_eq!(openvm::pal_abi::sys_argc(), 3);
+    let mut word = 0u32;
+    // An argument past the last one has length 0 instead of aborting the guest.
+    assert_eq!(unsafe { openvm::pal_abi::sys_argv(&mut word, 1, 3) }, 0);
+    assert_eq!(unsafe { openvm::pal_abi::sys_argv(&mut word, 1, 1) }, 6);
+    assert_eq!(std::env::var("GREETING").as_deref(), Ok("hello"));
+    assert!(std::env::var("MISSING").is_err());
+    assert_eq!(
+        openvm::fs::read_to_string("input.txt").as_deref(),
+        Some("42")
+    );
+    assert!(!openvm::fs::exists("missing.txt"));
+    let lines: Vec<String> = std::io::stdin()
+        .lock()
+        .lines()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(lines, ["line 1", "line 2"]);
+}
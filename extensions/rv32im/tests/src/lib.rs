@@ -1,12 +1,19 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use eyre::Result;
+    use openvm::fs::{file_key, stdin_chunk_key, VfsIndex, VFS_INDEX_KEY};
     use openvm_circuit::{
-        arch::{hasher::poseidon2::vm_poseidon2_hasher, ExecutionError, VmExecutor},
-        system::memory::tree::public_values::UserPublicValuesProof,
+        arch::{
+            hasher::poseidon2::vm_poseidon2_hasher, ExecutionError, ExecutionSegment, Streams,
+            Symbolizer, VmExecutor,
+        },
+        system::memory::{paged_vec::AddressMap, tree::public_values::UserPublicValuesProof},
         utils::{air_test, air_test_with_min_segments},
     };
     use openvm_instructions::exe::VmExe;
+    use openvm_platform::memory::GUEST_MIN_MEM;
     use openvm_rv32im_circuit::{Rv32IConfig, Rv32ImConfig};
     use openvm_rv32im_transpiler::{
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
@@ -89,6 +96,96 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_aligned() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "read_aligned")?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let config = Rv32IConfig::default();
+        let input = vec![
+            (0..5).map(F::from_canonical_u8).collect(),
+            (0..3).map(F::from_canonical_u8).collect(),
+        ];
+        air_test_with_min_segments(config, exe, input, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vfs() -> Result<()> {
+        let elf = build_example_program_at_path_with_features(get_programs_dir!(), "vfs", ["std"])?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        let to_field =
+            |bytes: &[u8]| -> Vec<F> { bytes.iter().map(|&b| F::from_canonical_u8(b)).collect() };
+        let stdin = b"line 1\nline 2\n";
+        let index = VfsIndex {
+            args: vec!["vfs".into(), "--name".into(), "openvm".into()],
+            env: vec![("GREETING".into(), "hello".into())],
+            files: vec!["input.txt".into()],
+            stdin_len: stdin.len() as u32,
+        };
+        let index_bytes: Vec<u8> = openvm::serde::to_vec(&index)
+            .unwrap()
+            .into_iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect();
+        let kv_store = HashMap::from([
+            (VFS_INDEX_KEY.to_vec(), to_field(&index_bytes)),
+            (file_key("input.txt"), to_field(b"42")),
+            (stdin_chunk_key(0), to_field(stdin)),
+        ]);
+
+        let executor = VmExecutor::<F, _>::new(Rv32ImConfig::default());
+        executor.execute(exe, Streams::new(vec![]).with_kv_store(kv_store))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_hint_load_by_key() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "hint_load_by_key")?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let input = vec![[5, 6].map(F::from_canonical_u8).to_vec()];
+        let kv_store = HashMap::from([
+            (b"key".to_vec(), (0..5).map(F::from_canonical_u8).collect()),
+            (b"other key".to_vec(), vec![F::from_canonical_u8(7); 1000]),
+        ]);
+
+        let executor = VmExecutor::<F, _>::new(Rv32ImConfig::default());
+        executor.execute(
+            exe.clone(),
+            Streams::new(input.clone()).with_kv_store(kv_store.clone()),
+        )?;
+
+        // A missing key is an execution error.
+        let mut missing = kv_store;
+        missing.remove(b"other key".as_slice());
+        let result = executor.execute(exe, Streams::new(input).with_kv_store(missing));
+        match result {
+            Err(ExecutionError::Phantom { inner, .. }) => {
+                assert!(inner.to_string().contains("KeyNotFound"));
+            }
+            _ => panic!("expected a phantom error, got {:?}", result.map(|_| ())),
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_read() -> Result<()> {
         let elf = build_example_program_at_path(get_programs_dir!(), "read")?;
@@ -186,6 +283,136 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stack_overflow() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "stack_overflow")?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let mut config = Rv32ImConfig::default();
+        config.rv32i.system = config.rv32i.system.with_stack_limit(GUEST_MIN_MEM as u32);
+        let executor = VmExecutor::<F, _>::new(config);
+        let input = |depth: u32| {
+            let words = openvm::serde::to_vec(&depth).unwrap();
+            vec![words
+                .into_iter()
+                .flat_map(|w| w.to_le_bytes())
+                .map(F::from_canonical_u8)
+                .collect::<Vec<_>>()]
+        };
+        executor.execute(exe.clone(), input(16))?;
+        match executor.execute(exe, input(1 << 12)) {
+            Err(ExecutionError::StackOverflow { .. }) => Ok(()),
+            Err(_) => panic!("should fail with `StackOverflow`"),
+            Ok(_) => panic!("should fail"),
+        }
+    }
+
+    #[test]
+    fn test_exit_code_and_guest_panic() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "exit_code")?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let config = Rv32ImConfig::default();
+        let executor = VmExecutor::<F, _>::new(config);
+        let input = |code: u8| {
+            let words = openvm::serde::to_vec(&code).unwrap();
+            vec![words
+                .into_iter()
+                .flat_map(|w| w.to_le_bytes())
+                .map(F::from_canonical_u8)
+                .collect::<Vec<_>>()]
+        };
+        match executor.execute(exe.clone(), input(200)) {
+            Err(ExecutionError::FailedWithExitCode(200)) => {}
+            Err(e) => panic!("should fail with exit code 200, got {e}"),
+            Ok(_) => panic!("should fail"),
+        }
+        match executor.execute(exe, input(0)) {
+            Err(ExecutionError::GuestPanic {
+                message,
+                file,
+                line,
+                ..
+            }) => {
+                assert_eq!(message, "exit code must be non-zero");
+                assert!(file.ends_with("exit_code.rs"));
+                assert_eq!(line, 12);
+                Ok(())
+            }
+            Err(e) => panic!("should fail with `GuestPanic`, got {e}"),
+            Ok(_) => panic!("should fail"),
+        }
+    }
+
+    #[test]
+    fn test_guest_backtrace() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "backtrace")?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let mut config = Rv32ImConfig::default();
+        let mem_config = config.rv32i.system.memory_config;
+        let memory = AddressMap::from_iter(
+            mem_config.as_offset,
+            1 << mem_config.as_height,
+            1 << mem_config.pointer_max_bits,
+            exe.init_memory.clone(),
+        );
+
+        // The call stack is only tracked when profiling.
+        let segment = ExecutionSegment::<F, _>::new(
+            &config,
+            exe.program.clone(),
+            Streams::default(),
+            Some(memory.clone()),
+            exe.fn_bounds.clone(),
+        );
+        assert!(segment.call_stack.is_none());
+
+        config.rv32i.system = config.rv32i.system.with_profiling();
+        let mut segment = ExecutionSegment::<F, _>::new(
+            &config,
+            exe.program.clone(),
+            Streams::default(),
+            Some(memory),
+            exe.fn_bounds.clone(),
+        );
+        // The guest panics, which terminates it with exit code 1.
+        let _ = segment.execute_from_pc(exe.pc_start);
+
+        let symbolizer = Symbolizer::new(&exe.fn_bounds, &exe.debug_sections);
+        let backtrace = symbolizer.backtrace(segment.call_stack.as_ref().unwrap());
+        let functions: Vec<_> = backtrace
+            .0
+            .iter()
+            .filter_map(|(_, function, _)| function.as_deref())
+            .collect();
+        // The panic handler is called from `inner`, called from `outer`, called from `main`.
+        let position = |name: &str| {
+            functions
+                .iter()
+                .position(|function| function.ends_with(name))
+                .unwrap_or_else(|| panic!("`{name}` not in backtrace:\n{backtrace}"))
+        };
+        assert!(position("::inner") < position("::outer"));
+        assert!(position("::outer") < position("::main"));
+        Ok(())
+    }
+
     #[test]
     fn test_hashmap() -> Result<()> {
         let elf =
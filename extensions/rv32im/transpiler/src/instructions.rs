@@ -278,4 +278,9 @@ pub enum Rv32Phantom {
     PrintStr,
     /// Prepare given amount of random numbers for hinting.
     HintRandom,
+    /// Prepare the hint stored under the key in memory for hinting, in the same format as
+    /// `HintInput`.
+    HintLoadByKey,
+    /// Peek the panic payload from memory and record it for the host.
+    PanicInfo,
 }
@@ -99,4 +99,8 @@ impl<F: PrimeField32> VmExtension<F> for Sha256 {
 
         Ok(inventory)
     }
+
+    fn opcode_names(&self) -> opcode_names::OpcodeNames {
+        opcode_names::OpcodeNames::default().with_opcodes("", Rv32Sha256Opcode::iter())
+    }
 }